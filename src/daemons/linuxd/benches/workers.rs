@@ -0,0 +1,249 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//! Tail latency of fast requests that share a connection with slow ones.
+//!
+//! A guest process issues reads that block on a slow device, modeled by a FIFO that the host feeds
+//! one byte at a time after a fixed delay. Meanwhile, other processes of the same guest issue small
+//! `pread()` calls on a regular file. The latency of those is measured with the worker pool
+//! disabled and enabled. Run with `cargo bench --bench workers`.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use ::anyhow::Result;
+use ::linuxd::{
+    fcntl,
+    unistd::message::{
+        ReadRequest,
+        ReadResponse,
+    },
+    LinuxDaemonMessage,
+    LinuxDaemonMessageHeader,
+};
+use ::linuxd_client::{
+    Client,
+    Daemon,
+};
+use ::nvx::{
+    ipc::Message,
+    pm::ProcessIdentifier,
+};
+use ::std::{
+    ffi::CString,
+    fs,
+    io,
+    os::unix::ffi::OsStrExt,
+    path::Path,
+    sync::mpsc::{
+        self,
+        Receiver,
+        Sender,
+    },
+    thread,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+use ::tempfile::TempDir;
+
+//==================================================================================================
+// Constants
+//==================================================================================================
+
+/// Process that reads from the slow device.
+const SLOW_PID: u32 = 1;
+
+/// Processes that read from the regular file.
+const FAST_PIDS: [u32; 3] = [2, 3, 4];
+
+/// Time that the slow device takes to produce a byte.
+const SLOW_READ_DELAY: Duration = Duration::from_millis(2);
+
+/// Number of slow reads.
+const ROUNDS: usize = 500;
+
+/// Number of fast reads issued while each slow read is in flight.
+const FAST_READS_PER_ROUND: usize = 8;
+
+/// Size of the regular file.
+const FILE_SIZE: usize = 1 << 20;
+
+/// Size of fast reads.
+const FAST_READ_SIZE: usize = 16;
+
+/// Sizes of the worker pool that are compared.
+const WORKERS: [usize; 2] = [0, 4];
+
+//==================================================================================================
+// Structures
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Latencies of fast reads, in microseconds, in ascending order.
+///
+struct Latencies(Vec<u64>);
+
+//==================================================================================================
+// Implementations
+//==================================================================================================
+
+impl Latencies {
+    fn percentile(&self, percentile: f64) -> u64 {
+        let index: usize = ((self.0.len() - 1) as f64 * percentile / 100.0).round() as usize;
+        self.0[index]
+    }
+
+    fn max(&self) -> u64 {
+        self.0.last().copied().unwrap_or(0)
+    }
+}
+
+//==================================================================================================
+// Standalone Functions
+//==================================================================================================
+
+fn main() -> Result<()> {
+    println!(
+        "{} slow reads of {:?}, {} concurrent preads of {} bytes each",
+        ROUNDS, SLOW_READ_DELAY, FAST_READS_PER_ROUND, FAST_READ_SIZE
+    );
+    println!(
+        "{:>8} {:>10} {:>10} {:>10} {:>10} {:>10}",
+        "workers", "p50 (us)", "p90 (us)", "p99 (us)", "p99.9 (us)", "max (us)"
+    );
+
+    for workers in WORKERS {
+        let latencies: Latencies = run(workers)?;
+        println!(
+            "{:>8} {:>10} {:>10} {:>10} {:>10} {:>10}",
+            workers,
+            latencies.percentile(50.0),
+            latencies.percentile(90.0),
+            latencies.percentile(99.0),
+            latencies.percentile(99.9),
+            latencies.max()
+        );
+    }
+
+    Ok(())
+}
+
+///
+/// # Description
+///
+/// Runs the mixed workload against a daemon with a given number of workers.
+///
+/// # Returns
+///
+/// Upon success, the latencies of fast reads are returned. Upon failure, an error is returned
+/// instead.
+///
+fn run(workers: usize) -> Result<Latencies> {
+    let root: TempDir = TempDir::new()?;
+    fs::write(root.path().join("file"), vec![0xa5; FILE_SIZE])?;
+    let feed: Sender<()> = spawn_device(&root.path().join("device"))?;
+
+    let daemon: Daemon = Daemon::spawn(
+        Path::new(env!("CARGO_BIN_EXE_linuxd")),
+        root.path(),
+        &["-no-auth", "-workers", &workers.to_string()],
+    )?;
+    let mut client: Client = daemon.connect(ProcessIdentifier::from(SLOW_PID), None)?;
+
+    let device: i32 = client.openat(fcntl::AT_FDCWD, "device", fcntl::O_RDWR, 0)?;
+    let file: i32 = client.openat(fcntl::AT_FDCWD, "file", fcntl::O_RDONLY, 0)?;
+    if device < 0 || file < 0 {
+        anyhow::bail!("openat() failed (device={}, file={})", device, file);
+    }
+
+    let mut latencies: Vec<u64> = Vec::with_capacity(ROUNDS * FAST_READS_PER_ROUND);
+    let mut buffer: [u8; FAST_READ_SIZE] = [0; FAST_READ_SIZE];
+    for round in 0..ROUNDS {
+        // The slow read is left in flight, and its response is collected at the end of the round.
+        client.set_pid(ProcessIdentifier::from(SLOW_PID));
+        client.send(ReadRequest::build(client.pid(), device, 1))?;
+        feed.send(())?;
+
+        for i in 0..FAST_READS_PER_ROUND {
+            client.set_pid(ProcessIdentifier::from(FAST_PIDS[i % FAST_PIDS.len()]));
+            let offset: i64 = (((round * FAST_READS_PER_ROUND + i) * 4099) % FILE_SIZE) as i64;
+
+            let started: Instant = Instant::now();
+            let count: isize = client.pread(file, &mut buffer, offset)? as isize;
+            latencies.push(started.elapsed().as_micros() as u64);
+
+            if count < 0 {
+                anyhow::bail!("pread() failed (count={})", count);
+            }
+        }
+
+        client.set_pid(ProcessIdentifier::from(SLOW_PID));
+        check_slow_read(client.recv()?)?;
+    }
+
+    latencies.sort_unstable();
+    Ok(Latencies(latencies))
+}
+
+///
+/// # Description
+///
+/// Creates a slow device, which produces a byte some time after each request.
+///
+/// # Returns
+///
+/// Upon success, the channel through which bytes are requested is returned. Upon failure, an error
+/// is returned instead.
+///
+fn spawn_device(path: &Path) -> Result<Sender<()>> {
+    let path: CString = CString::new(path.as_os_str().as_bytes())?;
+    if unsafe { libc::mkfifo(path.as_ptr(), 0o600) } != 0 {
+        anyhow::bail!("failed to create device: {}", io::Error::last_os_error());
+    }
+
+    // Opening both ends does not block, regardless of whether the daemon opened the device yet.
+    let fd: i32 = unsafe { libc::open(path.as_ptr(), libc::O_RDWR) };
+    if fd < 0 {
+        anyhow::bail!("failed to open device: {}", io::Error::last_os_error());
+    }
+
+    let (feed, requests): (Sender<()>, Receiver<()>) = mpsc::channel();
+    thread::spawn(move || {
+        for () in requests {
+            thread::sleep(SLOW_READ_DELAY);
+            let byte: u8 = 0;
+            unsafe { libc::write(fd, &byte as *const u8 as *const libc::c_void, 1) };
+        }
+        unsafe { libc::close(fd) };
+    });
+
+    Ok(feed)
+}
+
+///
+/// # Description
+///
+/// Checks the response to a slow read.
+///
+fn check_slow_read(response: Message) -> Result<()> {
+    if response.status != 0 {
+        anyhow::bail!("read() failed (status={})", { response.status });
+    }
+
+    let message: LinuxDaemonMessage = LinuxDaemonMessage::try_from_bytes(response.payload)
+        .map_err(|e| anyhow::anyhow!("invalid response: {:?}", e))?;
+    if { message.header } != LinuxDaemonMessageHeader::ReadResponse {
+        anyhow::bail!("unexpected response (header={:?})", { message.header });
+    }
+    let count: i32 = ReadResponse::from_bytes(message.payload).count;
+    if count != 1 {
+        anyhow::bail!("short read (count={})", count);
+    }
+
+    Ok(())
+}
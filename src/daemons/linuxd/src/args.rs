@@ -5,7 +5,19 @@
 // Imports
 //==================================================================================================
 
+use crate::{
+    backend::BackendKind,
+    resource::Resource,
+    stdio::{
+        StreamTarget,
+        STDIO_STREAMS,
+    },
+};
 use ::anyhow::Result;
+use ::std::path::{
+    Path,
+    PathBuf,
+};
 
 //==================================================================================================
 // Structures
@@ -19,6 +31,30 @@ use ::anyhow::Result;
 pub struct Args {
     /// Server socket address.
     server_sockaddr: String,
+    /// Allowed extended attribute namespaces, optionally for a specific environment.
+    xattr_namespaces: Vec<(Option<u32>, Vec<String>)>,
+    /// Standard stream mappings, optionally for a specific environment.
+    stdio: Vec<(Option<u32>, i32, StreamTarget)>,
+    /// Filesystem backends, optionally for a specific environment.
+    backends: Vec<(Option<u32>, BackendKind)>,
+    /// Resource limits, optionally for a specific environment.
+    limits: Vec<(Option<u32>, Resource, u64)>,
+    /// Access policy file.
+    policy: Option<PathBuf>,
+    /// Authentication key file.
+    auth_key: Option<PathBuf>,
+    /// Audit log file.
+    audit_log: Option<PathBuf>,
+    /// Prefix of capture files.
+    capture: Option<PathBuf>,
+    /// File that metrics are dumped to.
+    metrics: Option<PathBuf>,
+    /// Local port that metrics are served on.
+    metrics_port: Option<u16>,
+    /// Number of workers that run blocking operations.
+    workers: usize,
+    /// File that the port on which connections are accepted is written to.
+    port_file: Option<PathBuf>,
 }
 
 //==================================================================================================
@@ -29,6 +65,34 @@ impl Args {
     /// Command-line option for printing the help message.
     const OPT_HELP: &'static str = "-help";
     const OPT_SERVER_SOCKADDR: &'static str = "-server";
+    /// Command-line option for restricting extended attribute namespaces.
+    const OPT_XATTR_NAMESPACES: &'static str = "-xattr-namespaces";
+    /// Command-line option for mapping standard streams.
+    const OPT_STDIO: &'static str = "-stdio";
+    /// Command-line option for selecting the filesystem backend.
+    const OPT_BACKEND: &'static str = "-backend";
+    /// Command-line option for limiting resources.
+    const OPT_LIMIT: &'static str = "-limit";
+    /// Command-line option for loading an access policy.
+    const OPT_POLICY: &'static str = "-policy";
+    /// Command-line option for requiring connections to authenticate.
+    const OPT_AUTH_KEY: &'static str = "-auth-key";
+    /// Command-line option for accepting connections that do not authenticate.
+    const OPT_NO_AUTH: &'static str = "-no-auth";
+    /// Command-line option for recording requests to an audit log.
+    const OPT_AUDIT_LOG: &'static str = "-audit-log";
+    /// Command-line option for capturing connections.
+    const OPT_CAPTURE: &'static str = "-capture";
+    /// Command-line option for dumping metrics.
+    const OPT_METRICS: &'static str = "-metrics";
+    /// Command-line option for serving metrics.
+    const OPT_METRICS_PORT: &'static str = "-metrics-port";
+    /// Command-line option for sizing the worker pool.
+    const OPT_WORKERS: &'static str = "-workers";
+    /// Default number of workers.
+    const DEFAULT_WORKERS: usize = 4;
+    /// Command-line option for writing the port on which connections are accepted to a file.
+    const OPT_PORT_FILE: &'static str = "-port-file";
 
     ///
     /// # Description
@@ -48,6 +112,19 @@ impl Args {
         trace!("parse(): parsing command-line arguments...");
 
         let mut server_sockaddr: String = String::new();
+        let mut xattr_namespaces: Vec<(Option<u32>, Vec<String>)> = Vec::new();
+        let mut stdio: Vec<(Option<u32>, i32, StreamTarget)> = Vec::new();
+        let mut backends: Vec<(Option<u32>, BackendKind)> = Vec::new();
+        let mut limits: Vec<(Option<u32>, Resource, u64)> = Vec::new();
+        let mut policy: Option<PathBuf> = None;
+        let mut auth_key: Option<PathBuf> = None;
+        let mut no_auth: bool = false;
+        let mut audit_log: Option<PathBuf> = None;
+        let mut capture: Option<PathBuf> = None;
+        let mut metrics: Option<PathBuf> = None;
+        let mut metrics_port: Option<u16> = None;
+        let mut workers: usize = Self::DEFAULT_WORKERS;
+        let mut port_file: Option<PathBuf> = None;
 
         let mut i: usize = 1;
         while i < args.len() {
@@ -60,6 +137,99 @@ impl Args {
                     i += 1;
                     server_sockaddr = args[i].clone();
                 },
+                Self::OPT_XATTR_NAMESPACES => {
+                    i += 1;
+                    let spec: &str = args
+                        .get(i)
+                        .ok_or_else(|| anyhow::anyhow!("missing xattr namespaces"))?;
+                    xattr_namespaces.push(Self::parse_xattr_namespaces(spec)?);
+                },
+                Self::OPT_STDIO => {
+                    i += 1;
+                    let spec: &str = args
+                        .get(i)
+                        .ok_or_else(|| anyhow::anyhow!("missing stdio mapping"))?;
+                    stdio.push(Self::parse_stdio(spec)?);
+                },
+                Self::OPT_BACKEND => {
+                    i += 1;
+                    let spec: &str = args
+                        .get(i)
+                        .ok_or_else(|| anyhow::anyhow!("missing filesystem backend"))?;
+                    backends.push(Self::parse_backend(spec)?);
+                },
+                Self::OPT_LIMIT => {
+                    i += 1;
+                    let spec: &str = args
+                        .get(i)
+                        .ok_or_else(|| anyhow::anyhow!("missing resource limit"))?;
+                    limits.push(Self::parse_limit(spec)?);
+                },
+                Self::OPT_POLICY => {
+                    i += 1;
+                    let path: &str = args
+                        .get(i)
+                        .ok_or_else(|| anyhow::anyhow!("missing access policy file"))?;
+                    policy = Some(PathBuf::from(path));
+                },
+                Self::OPT_AUTH_KEY => {
+                    i += 1;
+                    let path: &str = args
+                        .get(i)
+                        .ok_or_else(|| anyhow::anyhow!("missing authentication key file"))?;
+                    auth_key = Some(PathBuf::from(path));
+                },
+                Self::OPT_NO_AUTH => {
+                    no_auth = true;
+                },
+                Self::OPT_AUDIT_LOG => {
+                    i += 1;
+                    let path: &str = args
+                        .get(i)
+                        .ok_or_else(|| anyhow::anyhow!("missing audit log file"))?;
+                    audit_log = Some(PathBuf::from(path));
+                },
+                Self::OPT_CAPTURE => {
+                    i += 1;
+                    let prefix: &str = args
+                        .get(i)
+                        .ok_or_else(|| anyhow::anyhow!("missing capture file prefix"))?;
+                    capture = Some(PathBuf::from(prefix));
+                },
+                Self::OPT_METRICS => {
+                    i += 1;
+                    let path: &str = args
+                        .get(i)
+                        .ok_or_else(|| anyhow::anyhow!("missing metrics file"))?;
+                    metrics = Some(PathBuf::from(path));
+                },
+                Self::OPT_METRICS_PORT => {
+                    i += 1;
+                    let value: &str = args
+                        .get(i)
+                        .ok_or_else(|| anyhow::anyhow!("missing metrics port"))?;
+                    metrics_port = Some(
+                        value
+                            .parse::<u16>()
+                            .map_err(|_| anyhow::anyhow!("invalid metrics port: {}", value))?,
+                    );
+                },
+                Self::OPT_WORKERS => {
+                    i += 1;
+                    let value: &str = args
+                        .get(i)
+                        .ok_or_else(|| anyhow::anyhow!("missing number of workers"))?;
+                    workers = value
+                        .parse::<usize>()
+                        .map_err(|_| anyhow::anyhow!("invalid number of workers: {}", value))?;
+                },
+                Self::OPT_PORT_FILE => {
+                    i += 1;
+                    let path: &str = args
+                        .get(i)
+                        .ok_or_else(|| anyhow::anyhow!("missing port file"))?;
+                    port_file = Some(PathBuf::from(path));
+                },
                 _ => {
                     return Err(anyhow::anyhow!("invalid argument"));
                 },
@@ -68,7 +238,39 @@ impl Args {
             i += 1;
         }
 
-        Ok(Self { server_sockaddr })
+        // Connections authenticate, unless told explicitly otherwise.
+        match (&auth_key, no_auth) {
+            (None, false) => anyhow::bail!(
+                "missing authentication key ({} <key-file>, or {} to accept unauthenticated \
+                 connections)",
+                Self::OPT_AUTH_KEY,
+                Self::OPT_NO_AUTH
+            ),
+            (Some(_), true) => {
+                anyhow::bail!(
+                    "conflicting options: {} and {}",
+                    Self::OPT_AUTH_KEY,
+                    Self::OPT_NO_AUTH
+                )
+            },
+            _ => {},
+        }
+
+        Ok(Self {
+            server_sockaddr,
+            xattr_namespaces,
+            stdio,
+            backends,
+            limits,
+            policy,
+            auth_key,
+            audit_log,
+            capture,
+            metrics,
+            metrics_port,
+            workers,
+            port_file,
+        })
     }
 
     ///
@@ -81,7 +283,29 @@ impl Args {
     /// - `program_name`: Name of the program.
     ///
     pub fn usage(program_name: &str) {
-        println!("Usage: {} {} <server-sockaddr>", program_name, Self::OPT_SERVER_SOCKADDR,);
+        println!(
+            "Usage: {} {} <server-sockaddr> [{} [<env>:]<namespace>[,<namespace>...]]... [{} \
+             [<env>:]<stdin|stdout|stderr>=<console|capture|file:<path>>]... [{} \
+             [<env>:]<host|memory[:<snapshot>]|overlay:<lower>:<upper>>]... [{} \
+             [<env>:]<nofile|wbytes|dbytes|fsize|nreqs>=<value>]... [{} \
+             <policy.toml|policy.json>] <{} <key-file>|{}> [{} <audit-log>] [{} \
+             <capture-prefix>] [{} <metrics.json>] [{} <port>] [{} <count>] [{} <port-file>]",
+            program_name,
+            Self::OPT_SERVER_SOCKADDR,
+            Self::OPT_XATTR_NAMESPACES,
+            Self::OPT_STDIO,
+            Self::OPT_BACKEND,
+            Self::OPT_LIMIT,
+            Self::OPT_POLICY,
+            Self::OPT_AUTH_KEY,
+            Self::OPT_NO_AUTH,
+            Self::OPT_AUDIT_LOG,
+            Self::OPT_CAPTURE,
+            Self::OPT_METRICS,
+            Self::OPT_METRICS_PORT,
+            Self::OPT_WORKERS,
+            Self::OPT_PORT_FILE,
+        );
     }
 
     ///
@@ -96,4 +320,314 @@ impl Args {
     pub fn server_sockaddr(&self) -> String {
         self.server_sockaddr.to_string()
     }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the allowed extended attribute namespaces.
+    ///
+    /// # Returns
+    ///
+    /// The allowed extended attribute namespaces, optionally for a specific environment.
+    ///
+    pub fn xattr_namespaces(&self) -> &[(Option<u32>, Vec<String>)] {
+        &self.xattr_namespaces
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the standard stream mappings.
+    ///
+    /// # Returns
+    ///
+    /// The standard stream mappings, optionally for a specific environment.
+    ///
+    pub fn stdio(&self) -> &[(Option<u32>, i32, StreamTarget)] {
+        &self.stdio
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the filesystem backends.
+    ///
+    /// # Returns
+    ///
+    /// The filesystem backends, optionally for a specific environment.
+    ///
+    pub fn backends(&self) -> &[(Option<u32>, BackendKind)] {
+        &self.backends
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the resource limits.
+    ///
+    /// # Returns
+    ///
+    /// The resource limits, optionally for a specific environment.
+    ///
+    pub fn limits(&self) -> &[(Option<u32>, Resource, u64)] {
+        &self.limits
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the access policy file.
+    ///
+    /// # Returns
+    ///
+    /// The path to the access policy file, if any.
+    ///
+    pub fn policy(&self) -> Option<&Path> {
+        self.policy.as_deref()
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the authentication key file.
+    ///
+    /// # Returns
+    ///
+    /// The path to the file that holds the key shared with clients, unless connections were
+    /// explicitly allowed not to authenticate.
+    ///
+    pub fn auth_key(&self) -> Option<&Path> {
+        self.auth_key.as_deref()
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the audit log file.
+    ///
+    /// # Returns
+    ///
+    /// The path to the file that completed requests are recorded to, if any.
+    ///
+    pub fn audit_log(&self) -> Option<&Path> {
+        self.audit_log.as_deref()
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the prefix of capture files.
+    ///
+    /// # Returns
+    ///
+    /// The prefix of the files that connections are captured to, if any.
+    ///
+    pub fn capture(&self) -> Option<&Path> {
+        self.capture.as_deref()
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the metrics file.
+    ///
+    /// # Returns
+    ///
+    /// The path to the file that metrics are dumped to on `SIGUSR1` and at shutdown, if any.
+    ///
+    pub fn metrics(&self) -> Option<&Path> {
+        self.metrics.as_deref()
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the metrics port.
+    ///
+    /// # Returns
+    ///
+    /// The local port that metrics are served on in the Prometheus text format, if any.
+    ///
+    pub fn metrics_port(&self) -> Option<u16> {
+        self.metrics_port
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the number of workers.
+    ///
+    /// # Returns
+    ///
+    /// The number of workers that run blocking operations of connections. Zero runs all operations
+    /// inline.
+    ///
+    pub fn workers(&self) -> usize {
+        self.workers
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the port file.
+    ///
+    /// # Returns
+    ///
+    /// The path to the file that the port on which connections are accepted is written to, if
+    /// any.
+    ///
+    pub fn port_file(&self) -> Option<&Path> {
+        self.port_file.as_deref()
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Parses a specification of allowed extended attribute namespaces.
+    ///
+    /// # Parameters
+    ///
+    /// - `spec`: Specification in the form `[<env>:]<namespace>[,<namespace>...]`.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the function returns the target environment, if any, and the allowed
+    /// namespaces. Upon failure, the function returns an error.
+    ///
+    fn parse_xattr_namespaces(spec: &str) -> Result<(Option<u32>, Vec<String>)> {
+        let (env, namespaces): (Option<u32>, &str) = match spec.split_once(':') {
+            Some((env, namespaces)) => {
+                let env: u32 = env
+                    .parse()
+                    .map_err(|_| anyhow::anyhow!("invalid environment identifier: {}", env))?;
+                (Some(env), namespaces)
+            },
+            None => (None, spec),
+        };
+
+        let mut allowed: Vec<String> = Vec::new();
+        for namespace in namespaces.split(',').filter(|ns| !ns.is_empty()) {
+            if !crate::xattr::XATTR_NAMESPACES.contains(&namespace) {
+                return Err(anyhow::anyhow!("invalid xattr namespace: {}", namespace));
+            }
+            allowed.push(namespace.to_string());
+        }
+
+        Ok((env, allowed))
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Parses a standard stream mapping.
+    ///
+    /// # Parameters
+    ///
+    /// - `spec`: Specification in the form `[<env>:]<stream>=<target>`.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the function returns the target environment, if any, the file descriptor of
+    /// the stream and where it is mapped to. Upon failure, the function returns an error.
+    ///
+    fn parse_stdio(spec: &str) -> Result<(Option<u32>, i32, StreamTarget)> {
+        let (stream, target): (&str, &str) = spec
+            .split_once('=')
+            .ok_or_else(|| anyhow::anyhow!("invalid stdio mapping: {}", spec))?;
+
+        let (env, stream): (Option<u32>, &str) = match stream.split_once(':') {
+            Some((env, stream)) => {
+                let env: u32 = env
+                    .parse()
+                    .map_err(|_| anyhow::anyhow!("invalid environment identifier: {}", env))?;
+                (Some(env), stream)
+            },
+            None => (None, stream),
+        };
+
+        let fd: i32 = STDIO_STREAMS
+            .iter()
+            .position(|s| *s == stream)
+            .ok_or_else(|| anyhow::anyhow!("invalid stream: {}", stream))?
+            as i32;
+
+        let target: StreamTarget = StreamTarget::parse(target)
+            .ok_or_else(|| anyhow::anyhow!("invalid stream target: {}", target))?;
+
+        // Reading from the console would stall the daemon.
+        if fd == libc::STDIN_FILENO && target == StreamTarget::Console {
+            return Err(anyhow::anyhow!("stdin cannot be mapped to the console"));
+        }
+
+        Ok((env, fd, target))
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Parses a filesystem backend selection.
+    ///
+    /// # Parameters
+    ///
+    /// - `spec`: Specification in the form `[<env>:]<backend>`.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the function returns the target environment, if any, and the kind of
+    /// backend. Upon failure, the function returns an error.
+    ///
+    fn parse_backend(spec: &str) -> Result<(Option<u32>, BackendKind)> {
+        // Backend specifications may contain colons themselves, so only a numeric prefix is
+        // taken as an environment identifier.
+        let (env, backend): (Option<u32>, &str) = match spec.split_once(':') {
+            Some((env, backend)) => match env.parse::<u32>() {
+                Ok(env) => (Some(env), backend),
+                Err(_) => (None, spec),
+            },
+            None => (None, spec),
+        };
+
+        let kind: BackendKind = BackendKind::parse(backend)
+            .ok_or_else(|| anyhow::anyhow!("invalid filesystem backend: {}", backend))?;
+
+        Ok((env, kind))
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Parses a resource limit.
+    ///
+    /// # Parameters
+    ///
+    /// - `spec`: Specification in the form `[<env>:]<resource>=<value>`.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the function returns the target environment, if any, the limited resource
+    /// and its limit. Upon failure, the function returns an error.
+    ///
+    fn parse_limit(spec: &str) -> Result<(Option<u32>, Resource, u64)> {
+        let (resource, value): (&str, &str) = spec
+            .split_once('=')
+            .ok_or_else(|| anyhow::anyhow!("invalid resource limit: {}", spec))?;
+
+        let (env, resource): (Option<u32>, &str) = match resource.split_once(':') {
+            Some((env, resource)) => {
+                let env: u32 = env
+                    .parse()
+                    .map_err(|_| anyhow::anyhow!("invalid environment identifier: {}", env))?;
+                (Some(env), resource)
+            },
+            None => (None, resource),
+        };
+
+        let resource: Resource = Resource::parse(resource)
+            .ok_or_else(|| anyhow::anyhow!("invalid resource: {}", resource))?;
+        let value: u64 = value
+            .parse()
+            .map_err(|_| anyhow::anyhow!("invalid resource limit value: {}", value))?;
+
+        Ok((env, resource, value))
+    }
 }
@@ -0,0 +1,493 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::policy::AccessRequest;
+use ::anyhow::Result;
+use ::linuxd::{
+    fcntl::message::{
+        FileAdvisoryInformationRequest,
+        FileSpaceControlRequest,
+        OpenAtResponse,
+        RecordLockRequest,
+    },
+    sys::{
+        file::message::FileLockRequest,
+        inotify::message::InotifyRemoveWatchRequest,
+        sendfile::message::{
+            SendFileRequest,
+            SendFileResponse,
+        },
+        stat::message::FileStatRequest,
+    },
+    unistd::message::{
+        CloseRequest,
+        CopyFileRangeRequest,
+        CopyFileRangeResponse,
+        FileDataSyncRequest,
+        FileSyncRequest,
+        FileTruncateRequest,
+        PartialReadRequest,
+        PartialReadResponse,
+        PartialWriteRequest,
+        PartialWriteResponse,
+        ReadRequest,
+        ReadResponse,
+        SeekRequest,
+        SeekResponse,
+        WriteRequest,
+        WriteResponse,
+    },
+    venv::{
+        message::JoinEnvResponse,
+        VirtualEnvironmentIdentifier,
+    },
+    LinuxDaemonMessage,
+    LinuxDaemonMessageHeader,
+};
+use ::nvx::{
+    ipc::Message,
+    pm::ProcessIdentifier,
+};
+use ::serde::Serialize;
+use ::std::{
+    collections::BTreeMap,
+    fs::{
+        File,
+        OpenOptions,
+    },
+    io::{
+        LineWriter,
+        Write,
+    },
+    path::Path,
+    time::{
+        Duration,
+        Instant,
+        SystemTime,
+        UNIX_EPOCH,
+    },
+};
+
+//==================================================================================================
+// Structures
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Arguments of a request that are recorded. Contents of buffers are never recorded.
+///
+#[derive(Debug, Default, Serialize)]
+struct Arguments {
+    /// Named paths.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    paths: Vec<String>,
+    /// File descriptors.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    fds: Vec<i32>,
+    /// Number of bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    count: Option<i64>,
+    /// Offset into a file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    offset: Option<i64>,
+}
+
+///
+/// # Description
+///
+/// Request that was received but not answered yet.
+///
+struct InflightRequest {
+    /// Type of the request, or of its parts, if it is understood.
+    header: Option<LinuxDaemonMessageHeader>,
+    /// Wall-clock time at which the request was received.
+    timestamp: SystemTime,
+    /// Instant at which the request was received.
+    started: Instant,
+    /// Environment of the process, if any.
+    env: Option<VirtualEnvironmentIdentifier>,
+    /// Name of the operation.
+    operation: &'static str,
+    /// Recorded arguments.
+    arguments: Arguments,
+}
+
+///
+/// # Description
+///
+/// Entry of the audit log.
+///
+#[derive(Serialize)]
+struct Entry<'a> {
+    /// Microseconds since the Unix epoch at which the request was received.
+    timestamp_us: u64,
+    /// Connection that the request arrived on.
+    connection: u64,
+    /// Environment of the process, if any.
+    venv: Option<u32>,
+    /// Process that issued the request.
+    pid: u32,
+    /// Name of the operation.
+    operation: &'static str,
+    /// Recorded arguments.
+    arguments: &'a Arguments,
+    /// Value that the operation returned, for operations that return one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<i64>,
+    /// Error number that the operation failed with, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    errno: Option<i32>,
+    /// Microseconds between receiving the request and answering it.
+    latency_us: u64,
+}
+
+///
+/// # Description
+///
+/// Audit log that records one JSON line per request that is answered, so that what guests did to
+/// the host can be investigated after the fact. A disabled audit log records nothing.
+///
+pub struct AuditLog {
+    /// File that entries are appended to, if the audit log is enabled.
+    sink: Option<LineWriter<File>>,
+    /// Sequence number of the connection.
+    connection: u64,
+    /// Requests that were received but not answered yet, by process.
+    inflight: BTreeMap<ProcessIdentifier, InflightRequest>,
+}
+
+//==================================================================================================
+// Implementations
+//==================================================================================================
+
+impl AuditLog {
+    ///
+    /// # Description
+    ///
+    /// Opens an audit log for a connection.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: File that entries are appended to, or `None` to disable the audit log.
+    /// - `connection`: Sequence number of the connection.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the audit log is returned. Upon failure, an error is returned instead.
+    ///
+    pub fn open(path: Option<&Path>, connection: u64) -> Result<Self> {
+        let sink: Option<LineWriter<File>> = match path {
+            Some(path) => {
+                let file: File = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .map_err(|e| anyhow::anyhow!("failed to open audit log {:?}: {}", path, e))?;
+                Some(LineWriter::new(file))
+            },
+            None => None,
+        };
+
+        Ok(Self {
+            sink,
+            connection,
+            inflight: BTreeMap::new(),
+        })
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Records that a message was received. Parts of a multipart request are recorded once, as of
+    /// the first part.
+    ///
+    /// # Parameters
+    ///
+    /// - `source`: Process that sent the message.
+    /// - `env`: Environment of the process, if any.
+    /// - `message`: Message, or `None` if it is not understood.
+    ///
+    pub fn begin(
+        &mut self,
+        source: ProcessIdentifier,
+        env: Option<VirtualEnvironmentIdentifier>,
+        message: Option<&LinuxDaemonMessage>,
+    ) {
+        if self.sink.is_none() {
+            return;
+        }
+
+        let header: Option<LinuxDaemonMessageHeader> = message.map(|message| message.header);
+        if let (Some(header), Some(inflight)) = (header, self.inflight.get(&source)) {
+            if header.is_part() && inflight.header == Some(header) {
+                return;
+            }
+        }
+
+        let (operation, arguments): (&'static str, Arguments) = match message {
+            Some(message) => {
+                (message.header.operation().unwrap_or("unknown"), Arguments::from_message(message))
+            },
+            None => ("unknown", Arguments::default()),
+        };
+
+        self.inflight.insert(
+            source,
+            InflightRequest {
+                header,
+                timestamp: SystemTime::now(),
+                started: Instant::now(),
+                env,
+                operation,
+                arguments,
+            },
+        );
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Records the paths of a request once it is described to the access policy.
+    ///
+    /// # Parameters
+    ///
+    /// - `source`: Process that sent the request.
+    /// - `request`: Description of the request.
+    ///
+    pub fn describe(&mut self, source: ProcessIdentifier, request: &AccessRequest) {
+        if let Some(inflight) = self.inflight.get_mut(&source) {
+            inflight.operation = request.operation();
+            inflight.arguments.paths = request.paths().map(str::to_string).collect();
+        }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Records that a message is sent. Responses complete the request of the process they are
+    /// addressed to, and all other messages are ignored.
+    ///
+    /// # Parameters
+    ///
+    /// - `message`: Message.
+    ///
+    pub fn complete(&mut self, message: &Message) {
+        if self.sink.is_none() {
+            return;
+        }
+
+        let response: Option<LinuxDaemonMessage> = if message.status == 0 {
+            match LinuxDaemonMessage::try_from_bytes(message.payload) {
+                Ok(response)
+                    if matches!(
+                        { response.header },
+                        LinuxDaemonMessageHeader::InotifyEventPart
+                    ) =>
+                {
+                    return
+                },
+                Ok(response) => Some(response),
+                Err(_) => None,
+            }
+        } else {
+            None
+        };
+
+        let destination: ProcessIdentifier = message.destination;
+        let inflight: InflightRequest = match self.inflight.remove(&destination) {
+            Some(inflight) => inflight,
+            None => return,
+        };
+
+        let entry: Entry = Entry {
+            timestamp_us: micros(
+                inflight
+                    .timestamp
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default(),
+            ),
+            connection: self.connection,
+            venv: inflight.env.map(|env| env.id()),
+            pid: u32::from(destination),
+            operation: inflight.operation,
+            arguments: &inflight.arguments,
+            result: response.as_ref().and_then(result),
+            errno: if message.status != 0 {
+                Some(-message.status)
+            } else {
+                None
+            },
+            latency_us: micros(inflight.started.elapsed()),
+        };
+
+        self.write(&entry);
+    }
+
+    // Append an entry to the audit log. Failures are reported but do not stop the daemon.
+    fn write(&mut self, entry: &Entry) {
+        if let Some(sink) = self.sink.as_mut() {
+            let mut line: Vec<u8> = match serde_json::to_vec(entry) {
+                Ok(line) => line,
+                Err(e) => {
+                    error!("failed to serialize audit entry (error={:?})", e);
+                    return;
+                },
+            };
+            line.push(b'\n');
+            if let Err(e) = sink.write_all(&line) {
+                error!("failed to write audit entry (error={:?})", e);
+            }
+        }
+    }
+}
+
+impl Arguments {
+    ///
+    /// # Description
+    ///
+    /// Records the arguments of a request that fits in a single message. Paths are recorded once
+    /// the request is described to the access policy.
+    ///
+    fn from_message(message: &LinuxDaemonMessage) -> Self {
+        let (fds, count, offset): (Vec<i32>, Option<i64>, Option<i64>) = match message.header {
+            LinuxDaemonMessageHeader::CloseRequest => {
+                let request: CloseRequest = CloseRequest::from_bytes(message.payload);
+                (vec![request.fd], None, None)
+            },
+            LinuxDaemonMessageHeader::FileDataSyncRequest => {
+                let request: FileDataSyncRequest = FileDataSyncRequest::from_bytes(message.payload);
+                (vec![request.fd], None, None)
+            },
+            LinuxDaemonMessageHeader::FileSyncRequest => {
+                let request: FileSyncRequest = FileSyncRequest::from_bytes(message.payload);
+                (vec![request.fd], None, None)
+            },
+            LinuxDaemonMessageHeader::SeekRequest => {
+                let request: SeekRequest = SeekRequest::from_bytes(message.payload);
+                (vec![request.fd], None, Some(request.offset))
+            },
+            LinuxDaemonMessageHeader::FileSpaceControlRequest => {
+                let request: FileSpaceControlRequest =
+                    FileSpaceControlRequest::from_bytes(message.payload);
+                (vec![request.fd], Some(request.len), Some(request.offset))
+            },
+            LinuxDaemonMessageHeader::FileTruncateRequest => {
+                let request: FileTruncateRequest = FileTruncateRequest::from_bytes(message.payload);
+                (vec![request.fd], Some(request.length), None)
+            },
+            LinuxDaemonMessageHeader::FileAdvisoryInformationRequest => {
+                let request: FileAdvisoryInformationRequest =
+                    FileAdvisoryInformationRequest::from_bytes(message.payload);
+                (vec![request.fd], Some(request.len), Some(request.offset))
+            },
+            LinuxDaemonMessageHeader::FileStatRequest => {
+                let request: FileStatRequest = FileStatRequest::from_bytes(message.payload);
+                (vec![request.fd], None, None)
+            },
+            LinuxDaemonMessageHeader::WriteRequest => {
+                let request: WriteRequest = WriteRequest::from_bytes(message.payload);
+                (vec![request.fd], Some(request.count as i64), None)
+            },
+            LinuxDaemonMessageHeader::ReadRequest => {
+                let request: ReadRequest = ReadRequest::from_bytes(message.payload);
+                (vec![request.fd], Some(request.count as i64), None)
+            },
+            LinuxDaemonMessageHeader::PartialWriteRequest => {
+                let request: PartialWriteRequest = PartialWriteRequest::from_bytes(message.payload);
+                (vec![request.fd], Some(request.count as i64), Some(request.offset))
+            },
+            LinuxDaemonMessageHeader::PartialReadRequest => {
+                let request: PartialReadRequest = PartialReadRequest::from_bytes(message.payload);
+                (vec![request.fd], Some(request.count as i64), Some(request.offset))
+            },
+            LinuxDaemonMessageHeader::FileLockRequest => {
+                let request: FileLockRequest = FileLockRequest::from_bytes(message.payload);
+                (vec![request.fd], None, None)
+            },
+            LinuxDaemonMessageHeader::RecordLockRequest => {
+                let request: RecordLockRequest = RecordLockRequest::from_bytes(message.payload);
+                (vec![request.fd], Some(request.l_len), Some(request.l_start))
+            },
+            LinuxDaemonMessageHeader::CopyFileRangeRequest => {
+                let request: CopyFileRangeRequest =
+                    CopyFileRangeRequest::from_bytes(message.payload);
+                (vec![request.fd_in, request.fd_out], Some(request.len as i64), None)
+            },
+            LinuxDaemonMessageHeader::SendFileRequest => {
+                let request: SendFileRequest = SendFileRequest::from_bytes(message.payload);
+                (vec![request.in_fd, request.out_fd], Some(request.count as i64), None)
+            },
+            LinuxDaemonMessageHeader::InotifyRemoveWatchRequest => {
+                let request: InotifyRemoveWatchRequest =
+                    InotifyRemoveWatchRequest::from_bytes(message.payload);
+                (vec![request.wd], None, None)
+            },
+            _ => (Vec::new(), None, None),
+        };
+
+        Self {
+            paths: Vec::new(),
+            fds,
+            count,
+            offset,
+        }
+    }
+}
+
+//==================================================================================================
+// Standalone Functions
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Returns the value that a successful response carries, for operations that return one.
+///
+fn result(response: &LinuxDaemonMessage) -> Option<i64> {
+    match response.header {
+        LinuxDaemonMessageHeader::OpenAtResponse => {
+            Some(OpenAtResponse::from_bytes(response.payload).ret as i64)
+        },
+        LinuxDaemonMessageHeader::SeekResponse => {
+            Some(SeekResponse::from_bytes(response.payload).offset)
+        },
+        LinuxDaemonMessageHeader::WriteResponse => {
+            Some(WriteResponse::from_bytes(response.payload).count as i64)
+        },
+        LinuxDaemonMessageHeader::ReadResponse => {
+            Some(ReadResponse::from_bytes(response.payload).count as i64)
+        },
+        LinuxDaemonMessageHeader::PartialWriteResponse => {
+            Some(PartialWriteResponse::from_bytes(response.payload).count as i64)
+        },
+        LinuxDaemonMessageHeader::PartialReadResponse => {
+            Some(PartialReadResponse::from_bytes(response.payload).count as i64)
+        },
+        LinuxDaemonMessageHeader::CopyFileRangeResponse => {
+            Some(CopyFileRangeResponse::from_bytes(response.payload).count as i64)
+        },
+        LinuxDaemonMessageHeader::SendFileResponse => {
+            Some(SendFileResponse::from_bytes(response.payload).count as i64)
+        },
+        LinuxDaemonMessageHeader::JoinEnvResponse => {
+            let env: VirtualEnvironmentIdentifier =
+                JoinEnvResponse::from_bytes(response.payload).env;
+            Some(env.id() as i64)
+        },
+        _ => None,
+    }
+}
+
+///
+/// # Description
+///
+/// Converts a duration into microseconds, saturating on overflow.
+///
+fn micros(duration: Duration) -> u64 {
+    u64::try_from(duration.as_micros()).unwrap_or(u64::MAX)
+}
@@ -0,0 +1,127 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use ::anyhow::Result;
+use ::linuxd::handshake::{
+    Challenge,
+    ChallengeResponse,
+    HANDSHAKE_ACCEPTED,
+    HANDSHAKE_REFUSED,
+    NONCE_SIZE,
+};
+use ::std::{
+    fs::{
+        self,
+        File,
+    },
+    io::{
+        Read,
+        Write,
+    },
+    net::TcpStream,
+    path::Path,
+    time::Duration,
+};
+
+//==================================================================================================
+// Constants
+//==================================================================================================
+
+/// Time that clients have to answer a challenge.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+//==================================================================================================
+// Structures
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Authenticates connections through a challenge-response handshake with a pre-shared key.
+///
+pub struct Authenticator {
+    /// Pre-shared key.
+    key: Vec<u8>,
+}
+
+//==================================================================================================
+// Implementations
+//==================================================================================================
+
+impl Authenticator {
+    ///
+    /// # Description
+    ///
+    /// Loads the pre-shared key from a file. Keys are kept out of the command line so that they
+    /// do not show up in the process list. A trailing newline is not part of the key.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: Path to the key file.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the authenticator is returned. Upon failure, an error is returned instead.
+    ///
+    pub fn load(path: &Path) -> Result<Self> {
+        let mut key: Vec<u8> = fs::read(path)?;
+        while key.last().is_some_and(|b| *b == b'\n' || *b == b'\r') {
+            key.pop();
+        }
+
+        if key.is_empty() {
+            anyhow::bail!("empty authentication key: {:?}", path);
+        }
+
+        info!("loaded authentication key (path={:?})", path);
+
+        Ok(Self { key })
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Runs the handshake on a newly established connection.
+    ///
+    /// # Parameters
+    ///
+    /// - `stream`: Connection to authenticate.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, empty is returned and the connection may be used. Upon failure, an error is
+    /// returned instead and the connection must be dropped.
+    ///
+    pub fn authenticate(&self, stream: &mut TcpStream) -> Result<()> {
+        // Clients that never answer must not stall the daemon.
+        stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+
+        let challenge: Challenge = Challenge::new(Self::nonce()?);
+        stream.write_all(&challenge.to_bytes())?;
+
+        let mut bytes: [u8; ChallengeResponse::SIZE] = [0; ChallengeResponse::SIZE];
+        stream.read_exact(&mut bytes)?;
+        let response: ChallengeResponse = ChallengeResponse::from_bytes(bytes);
+
+        if !response.verify(&self.key, &challenge) {
+            // The peer may already be gone, and it is refused either way.
+            let _ = stream.write_all(&[HANDSHAKE_REFUSED]);
+            anyhow::bail!("wrong challenge response");
+        }
+
+        stream.write_all(&[HANDSHAKE_ACCEPTED])?;
+        stream.set_read_timeout(None)?;
+
+        Ok(())
+    }
+
+    fn nonce() -> Result<[u8; NONCE_SIZE]> {
+        let mut nonce: [u8; NONCE_SIZE] = [0; NONCE_SIZE];
+        File::open("/dev/urandom")?.read_exact(&mut nonce)?;
+        Ok(nonce)
+    }
+}
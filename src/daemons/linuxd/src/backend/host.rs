@@ -0,0 +1,672 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::{
+    backend::FileSystem,
+    stdlib,
+};
+use ::alloc::ffi::CString;
+use ::core::{
+    ffi,
+    mem,
+    ptr,
+};
+use ::linuxd::{
+    fcntl,
+    sys::{
+        stat::stat,
+        types::{
+            mode_t,
+            off_t,
+        },
+    },
+    time::timespec,
+    unistd,
+};
+use ::std::path::{
+    Path,
+    PathBuf,
+};
+
+//==================================================================================================
+// Structures
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Filesystem backend that forwards operations to the host, through the C library. File
+/// descriptors handed out to guests are host file descriptors.
+///
+#[derive(Debug)]
+pub struct HostFileSystem {
+    /// Canonical host path of the root of the virtual environment.
+    root: PathBuf,
+}
+
+//==================================================================================================
+// Implementations
+//==================================================================================================
+
+impl HostFileSystem {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+}
+
+impl FileSystem for HostFileSystem {
+    fn open_at(&mut self, dirfd: i32, path: &str, flags: i32, mode: mode_t) -> Result<i32, i32> {
+        let dirfd: LibcAtFlags = LibcAtFlags::from(dirfd);
+        let path: CString = to_cstring(path)?;
+        let flags: LibcFileFlags = LibcFileFlags::try_from(flags)?;
+        let mode: LibcFileMode = LibcFileMode::try_from(mode)?;
+
+        debug!(
+            "libc::openat(): dirfd={:?}, path={:?}, flags={:?}, mode={:?}",
+            dirfd.inner(),
+            path,
+            flags.inner(),
+            mode.inner()
+        );
+        match unsafe { libc::openat(dirfd.inner(), path.as_ptr(), flags.inner(), mode.inner()) } {
+            fd if fd >= 0 => {
+                debug!("libc::openat(): fd={:?}", fd);
+                Ok(fd)
+            },
+            _ => Err(last_errno("libc::openat()")),
+        }
+    }
+
+    fn close(&mut self, fd: i32) -> Result<(), i32> {
+        debug!("libc::close(): fd={:?}", fd);
+        match unsafe { libc::close(fd) } {
+            0 => Ok(()),
+            _ => Err(last_errno("libc::close()")),
+        }
+    }
+
+    fn read(&mut self, fd: i32, buffer: &mut [u8]) -> Result<usize, i32> {
+        debug!("libc::read(): fd={:?}, count={:?}", fd, buffer.len());
+        match unsafe { libc::read(fd, buffer.as_mut_ptr() as *mut _, buffer.len()) } {
+            ret if ret >= 0 => Ok(ret as usize),
+            _ => Err(last_errno("libc::read()")),
+        }
+    }
+
+    fn write(&mut self, fd: i32, buffer: &[u8]) -> Result<usize, i32> {
+        debug!("libc::write(): fd={:?}, buffer={:?}", fd, buffer);
+        match unsafe { libc::write(fd, buffer.as_ptr() as *const _, buffer.len()) } {
+            ret if ret >= 0 => Ok(ret as usize),
+            _ => Err(last_errno("libc::write()")),
+        }
+    }
+
+    fn pread(&mut self, fd: i32, buffer: &mut [u8], offset: off_t) -> Result<usize, i32> {
+        debug!("libc::pread(): fd={:?}, count={:?}, offset={:?}", fd, buffer.len(), offset);
+        match unsafe { libc::pread(fd, buffer.as_mut_ptr() as *mut _, buffer.len(), offset) } {
+            ret if ret >= 0 => Ok(ret as usize),
+            _ => Err(last_errno("libc::pread()")),
+        }
+    }
+
+    fn pwrite(&mut self, fd: i32, buffer: &[u8], offset: off_t) -> Result<usize, i32> {
+        debug!("libc::pwrite(): fd={:?}, offset={:?}, buffer={:?}", fd, offset, buffer);
+        match unsafe { libc::pwrite(fd, buffer.as_ptr() as *const _, buffer.len(), offset) } {
+            ret if ret >= 0 => Ok(ret as usize),
+            _ => Err(last_errno("libc::pwrite()")),
+        }
+    }
+
+    fn lseek(&mut self, fd: i32, offset: off_t, whence: i32) -> Result<off_t, i32> {
+        let whence: LibcSeek = LibcSeek::try_from(whence)?;
+
+        debug!("libc::lseek(): fd={:?}, offset={:?}, whence={:?}", fd, offset, whence.inner());
+        match unsafe { libc::lseek(fd, offset, whence.inner()) } {
+            ret if ret >= 0 => Ok(ret),
+            _ => Err(last_errno("libc::lseek()")),
+        }
+    }
+
+    fn fsync(&mut self, fd: i32) -> Result<(), i32> {
+        debug!("libc::fsync(): fd={:?}", fd);
+        match unsafe { libc::fsync(fd) } {
+            0 => Ok(()),
+            _ => Err(last_errno("libc::fsync()")),
+        }
+    }
+
+    fn fdatasync(&mut self, fd: i32) -> Result<(), i32> {
+        debug!("libc::fdatasync(): fd={:?}", fd);
+        match unsafe { libc::fdatasync(fd) } {
+            0 => Ok(()),
+            _ => Err(last_errno("libc::fdatasync()")),
+        }
+    }
+
+    fn ftruncate(&mut self, fd: i32, length: off_t) -> Result<(), i32> {
+        debug!("libc::ftruncate(): fd={:?}, length={:?}", fd, length);
+        match unsafe { libc::ftruncate(fd, length) } {
+            0 => Ok(()),
+            _ => Err(last_errno("libc::ftruncate()")),
+        }
+    }
+
+    fn truncate(&mut self, path: &str, length: off_t) -> Result<(), i32> {
+        let path: CString = to_cstring(path)?;
+
+        debug!("libc::truncate(): path={:?}, length={:?}", path, length);
+        match unsafe { libc::truncate(path.as_ptr(), length) } {
+            0 => Ok(()),
+            _ => Err(last_errno("libc::truncate()")),
+        }
+    }
+
+    fn fallocate(&mut self, fd: i32, offset: off_t, len: off_t) -> Result<(), i32> {
+        debug!("libc::posix_fallocate(): fd={:?}, offset={:?}, len={:?}", fd, offset, len);
+        // This function returns the error number instead of setting it.
+        match unsafe { libc::posix_fallocate(fd, offset, len) } {
+            0 => Ok(()),
+            errno => {
+                debug!("libc::posix_fallocate(): errno={:?}", errno);
+                Err(errno)
+            },
+        }
+    }
+
+    fn fadvise(&mut self, fd: i32, offset: off_t, len: off_t, advice: i32) -> Result<(), i32> {
+        let advice: LibcFileAdvice = LibcFileAdvice::try_from(advice)?;
+
+        debug!(
+            "libc::posix_fadvise(): fd={:?}, offset={:?}, len={:?}, advice={:?}",
+            fd,
+            offset,
+            len,
+            advice.inner()
+        );
+        // This function returns the error number instead of setting it.
+        match unsafe { libc::posix_fadvise(fd, offset, len, advice.inner()) } {
+            0 => Ok(()),
+            errno => {
+                debug!("libc::posix_fadvise(): errno={:?}", errno);
+                Err(errno)
+            },
+        }
+    }
+
+    fn fstat(&mut self, fd: i32) -> Result<stat, i32> {
+        let mut st: libc::stat = unsafe { mem::zeroed() };
+
+        debug!("libc::fstat(): fd={:?}", fd);
+        match unsafe { libc::fstat(fd, &mut st) } {
+            0 => Ok(to_stat(&st)),
+            _ => Err(last_errno("libc::fstat()")),
+        }
+    }
+
+    fn fstat_at(&mut self, dirfd: i32, path: &str, flag: i32) -> Result<stat, i32> {
+        let dirfd: LibcAtFlags = LibcAtFlags::from(dirfd);
+        let path: CString = to_cstring(path)?;
+        let mut st: libc::stat = unsafe { mem::zeroed() };
+
+        debug!("libc::fstatat(): dirfd={:?}, path={:?}, flag={:?}", dirfd.inner(), path, flag);
+        match unsafe { libc::fstatat(dirfd.inner(), path.as_ptr(), &mut st, flag) } {
+            0 => Ok(to_stat(&st)),
+            _ => Err(last_errno("libc::fstatat()")),
+        }
+    }
+
+    fn unlink_at(&mut self, dirfd: i32, path: &str, flags: i32) -> Result<(), i32> {
+        let dirfd: LibcAtFlags = LibcAtFlags::from(dirfd);
+        let path: CString = to_cstring(path)?;
+        let flags: LibcUnlinkFlags = LibcUnlinkFlags::try_from(flags)?;
+
+        debug!(
+            "libc::unlinkat(): dirfd={:?}, path={:?}, flags={:?}",
+            dirfd.inner(),
+            path,
+            flags.inner()
+        );
+        match unsafe { libc::unlinkat(dirfd.inner(), path.as_ptr(), flags.inner()) } {
+            0 => Ok(()),
+            _ => Err(last_errno("libc::unlinkat()")),
+        }
+    }
+
+    fn rename_at(
+        &mut self,
+        olddirfd: i32,
+        oldpath: &str,
+        newdirfd: i32,
+        newpath: &str,
+        flags: u32,
+    ) -> Result<(), i32> {
+        let olddirfd: LibcAtFlags = LibcAtFlags::from(olddirfd);
+        let oldpath: CString = to_cstring(oldpath)?;
+        let newdirfd: LibcAtFlags = LibcAtFlags::from(newdirfd);
+        let newpath: CString = to_cstring(newpath)?;
+        let flags: LibcRenameFlags = LibcRenameFlags::try_from(flags)?;
+
+        debug!(
+            "libc::renameat2(): olddirfd={:?}, oldpath={:?}, newdirfd={:?}, newpath={:?}, \
+             flags={:?}",
+            olddirfd.inner(),
+            oldpath,
+            newdirfd.inner(),
+            newpath,
+            flags.inner()
+        );
+        match unsafe {
+            libc::renameat2(
+                olddirfd.inner(),
+                oldpath.as_ptr(),
+                newdirfd.inner(),
+                newpath.as_ptr(),
+                flags.inner(),
+            )
+        } {
+            0 => Ok(()),
+            _ => Err(last_errno("libc::renameat2()")),
+        }
+    }
+
+    fn link_at(
+        &mut self,
+        olddirfd: i32,
+        oldpath: &str,
+        newdirfd: i32,
+        newpath: &str,
+        flags: i32,
+    ) -> Result<(), i32> {
+        let olddirfd: LibcAtFlags = LibcAtFlags::from(olddirfd);
+        let oldpath: CString = to_cstring(oldpath)?;
+        let newdirfd: LibcAtFlags = LibcAtFlags::from(newdirfd);
+        let newpath: CString = to_cstring(newpath)?;
+
+        debug!(
+            "libc::linkat(): olddirfd={:?}, oldpath={:?}, newdirfd={:?}, newpath={:?}, flags={:?}",
+            olddirfd.inner(),
+            oldpath,
+            newdirfd.inner(),
+            newpath,
+            flags
+        );
+        match unsafe {
+            libc::linkat(
+                olddirfd.inner(),
+                oldpath.as_ptr(),
+                newdirfd.inner(),
+                newpath.as_ptr(),
+                flags,
+            )
+        } {
+            0 => Ok(()),
+            _ => Err(last_errno("libc::linkat()")),
+        }
+    }
+
+    fn symlink_at(&mut self, target: &str, dirfd: i32, linkpath: &str) -> Result<(), i32> {
+        let target: CString = to_cstring(target)?;
+        let dirfd: LibcAtFlags = LibcAtFlags::from(dirfd);
+        let linkpath: CString = to_cstring(linkpath)?;
+
+        debug!(
+            "libc::symlinkat(): target={:?}, dirfd={:?}, linkpath={:?}",
+            target,
+            dirfd.inner(),
+            linkpath
+        );
+        match unsafe { libc::symlinkat(target.as_ptr(), dirfd.inner(), linkpath.as_ptr()) } {
+            0 => Ok(()),
+            _ => Err(last_errno("libc::symlinkat()")),
+        }
+    }
+
+    fn readlink_at(&mut self, dirfd: i32, path: &str, buffer: &mut [u8]) -> Result<usize, i32> {
+        let dirfd: LibcAtFlags = LibcAtFlags::from(dirfd);
+        let path: CString = to_cstring(path)?;
+
+        debug!(
+            "libc::readlinkat(): dirfd={:?}, path={:?}, capacity={:?}",
+            dirfd.inner(),
+            path,
+            buffer.len()
+        );
+        match unsafe {
+            libc::readlinkat(
+                dirfd.inner(),
+                path.as_ptr(),
+                buffer.as_mut_ptr() as *mut ffi::c_char,
+                buffer.len(),
+            )
+        } {
+            len if len >= 0 => Ok(len as usize),
+            _ => Err(last_errno("libc::readlinkat()")),
+        }
+    }
+
+    fn copy_file_range(
+        &mut self,
+        fd_in: i32,
+        off_in: Option<&mut off_t>,
+        fd_out: i32,
+        off_out: Option<&mut off_t>,
+        len: usize,
+        flags: u32,
+    ) -> Result<usize, i32> {
+        debug!(
+            "libc::copy_file_range(): fd_in={:?}, off_in={:?}, fd_out={:?}, off_out={:?}, \
+             len={:?}, flags={:?}",
+            fd_in, off_in, fd_out, off_out, len, flags
+        );
+        let off_in: *mut off_t = off_in.map_or(ptr::null_mut(), |off_in| off_in as *mut off_t);
+        let off_out: *mut off_t = off_out.map_or(ptr::null_mut(), |off_out| off_out as *mut off_t);
+        match unsafe { libc::copy_file_range(fd_in, off_in, fd_out, off_out, len, flags) } {
+            ret if ret >= 0 => Ok(ret as usize),
+            _ => Err(last_errno("libc::copy_file_range()")),
+        }
+    }
+
+    fn sendfile(
+        &mut self,
+        out_fd: i32,
+        in_fd: i32,
+        offset: Option<&mut off_t>,
+        count: usize,
+    ) -> Result<usize, i32> {
+        // Only file-to-file transfers are supported, because guests have no access to host sockets.
+        check_regular_file(out_fd)?;
+
+        debug!(
+            "libc::sendfile(): out_fd={:?}, in_fd={:?}, offset={:?}, count={:?}",
+            out_fd, in_fd, offset, count
+        );
+        let offset: *mut off_t = offset.map_or(ptr::null_mut(), |offset| offset as *mut off_t);
+        match unsafe { libc::sendfile(out_fd, in_fd, offset, count) } {
+            ret if ret >= 0 => Ok(ret as usize),
+            _ => Err(last_errno("libc::sendfile()")),
+        }
+    }
+
+    fn access_mode(&mut self, fd: i32) -> Result<i32, i32> {
+        let flags: i32 = match unsafe { libc::fcntl(fd, libc::F_GETFL) } {
+            flags if flags >= 0 => flags,
+            _ => return Err(last_errno("libc::fcntl()")),
+        };
+
+        match flags & libc::O_ACCMODE {
+            libc::O_WRONLY => Ok(fcntl::O_WRONLY),
+            libc::O_RDWR => Ok(fcntl::O_RDWR),
+            _ => Ok(fcntl::O_RDONLY),
+        }
+    }
+
+    fn realpath(&mut self, path: &str) -> Result<String, i32> {
+        let canonical: PathBuf = stdlib::canonicalize(path, &self.root)?;
+
+        let relative: &Path = canonical
+            .strip_prefix(&self.root)
+            .map_err(|_| libc::EACCES)?;
+
+        Path::new("/")
+            .join(relative)
+            .into_os_string()
+            .into_string()
+            .map_err(|_| libc::EINVAL)
+    }
+}
+
+//==================================================================================================
+
+struct LibcFileFlags(libc::c_int);
+
+impl LibcFileFlags {
+    fn inner(&self) -> libc::c_int {
+        self.0
+    }
+
+    fn try_from(flags: ffi::c_int) -> Result<LibcFileFlags, i32> {
+        let flag_mappings: [(ffi::c_int, i32); 7] = [
+            (fcntl::O_APPEND, libc::O_APPEND),
+            (fcntl::O_CREAT, libc::O_CREAT),
+            (fcntl::O_EXCL, libc::O_EXCL),
+            (fcntl::O_RDONLY, libc::O_RDONLY),
+            (fcntl::O_RDWR, libc::O_RDWR),
+            (fcntl::O_TRUNC, libc::O_TRUNC),
+            (fcntl::O_WRONLY, libc::O_WRONLY),
+        ];
+
+        // TODO: check for unsupported flags.
+
+        let mut libc_flags: libc::c_int = 0;
+        for (nanvix_flag, f) in flag_mappings.iter() {
+            if (flags & nanvix_flag) == *nanvix_flag {
+                libc_flags |= *f;
+            }
+        }
+
+        Ok(LibcFileFlags(libc_flags))
+    }
+}
+
+struct LibcFileMode(libc::mode_t);
+
+impl LibcFileMode {
+    fn inner(&self) -> libc::mode_t {
+        self.0
+    }
+
+    fn try_from(mode: mode_t) -> Result<LibcFileMode, i32> {
+        let mode_mappings: [(mode_t, u32); 12] = [
+            (fcntl::S_IRWXU, libc::S_IRWXU),
+            (fcntl::S_IRUSR, libc::S_IRUSR),
+            (fcntl::S_IWUSR, libc::S_IWUSR),
+            (fcntl::S_IXUSR, libc::S_IXUSR),
+            (fcntl::S_IRWXG, libc::S_IRWXG),
+            (fcntl::S_IRGRP, libc::S_IRGRP),
+            (fcntl::S_IWGRP, libc::S_IWGRP),
+            (fcntl::S_IXGRP, libc::S_IXGRP),
+            (fcntl::S_IRWXO, libc::S_IRWXO),
+            (fcntl::S_IROTH, libc::S_IROTH),
+            (fcntl::S_IWOTH, libc::S_IWOTH),
+            (fcntl::S_IXOTH, libc::S_IXOTH),
+        ];
+
+        // TODO: check for unsupported flags.
+
+        let mut libc_mode: libc::mode_t = 0;
+        for (nanvix_mode, m) in mode_mappings.iter() {
+            if (mode & nanvix_mode) == *nanvix_mode {
+                libc_mode |= *m;
+            }
+        }
+
+        Ok(LibcFileMode(libc_mode))
+    }
+}
+
+struct LibcAtFlags(libc::c_int);
+
+impl LibcAtFlags {
+    fn inner(&self) -> libc::c_int {
+        self.0
+    }
+
+    fn from(flags: ffi::c_int) -> LibcAtFlags {
+        let libc_flags: libc::c_int = match flags {
+            fcntl::AT_FDCWD => libc::AT_FDCWD,
+            fcntl::AT_REMOVEDIR => libc::AT_REMOVEDIR,
+            _ => flags,
+        };
+
+        LibcAtFlags(libc_flags)
+    }
+}
+
+struct LibcRenameFlags(libc::c_uint);
+
+impl LibcRenameFlags {
+    fn inner(&self) -> libc::c_uint {
+        self.0
+    }
+
+    fn try_from(flags: u32) -> Result<LibcRenameFlags, i32> {
+        // Check for unsupported flags.
+        if (flags & !(fcntl::RENAME_NOREPLACE | fcntl::RENAME_EXCHANGE)) != 0 {
+            return Err(libc::EINVAL);
+        }
+
+        // An exchange requires the new path to exist, so it cannot be combined with no-replace.
+        if (flags & fcntl::RENAME_NOREPLACE) != 0 && (flags & fcntl::RENAME_EXCHANGE) != 0 {
+            return Err(libc::EINVAL);
+        }
+
+        let mut libc_flags: libc::c_uint = 0;
+        if (flags & fcntl::RENAME_NOREPLACE) != 0 {
+            libc_flags |= libc::RENAME_NOREPLACE;
+        }
+        if (flags & fcntl::RENAME_EXCHANGE) != 0 {
+            libc_flags |= libc::RENAME_EXCHANGE;
+        }
+
+        Ok(LibcRenameFlags(libc_flags))
+    }
+}
+
+struct LibcUnlinkFlags(libc::c_int);
+
+impl LibcUnlinkFlags {
+    fn inner(&self) -> libc::c_int {
+        self.0
+    }
+
+    fn try_from(flags: i32) -> Result<LibcUnlinkFlags, i32> {
+        // Check for unsupported flags.
+        if (flags & !fcntl::AT_REMOVEDIR) != 0 {
+            return Err(libc::EINVAL);
+        }
+
+        let mut libc_flags: libc::c_int = 0;
+        if (flags & fcntl::AT_REMOVEDIR) != 0 {
+            libc_flags |= libc::AT_REMOVEDIR;
+        }
+
+        Ok(LibcUnlinkFlags(libc_flags))
+    }
+}
+
+struct LibcFileAdvice(libc::c_int);
+
+impl LibcFileAdvice {
+    fn inner(&self) -> libc::c_int {
+        self.0
+    }
+
+    fn try_from(advice: i32) -> Result<LibcFileAdvice, i32> {
+        let libc_advice: libc::c_int = match advice {
+            fcntl::POSIX_FADV_NORMAL => libc::POSIX_FADV_NORMAL,
+            fcntl::POSIX_FADV_RANDOM => libc::POSIX_FADV_RANDOM,
+            fcntl::POSIX_FADV_SEQUENTIAL => libc::POSIX_FADV_SEQUENTIAL,
+            fcntl::POSIX_FADV_WILLNEED => libc::POSIX_FADV_WILLNEED,
+            fcntl::POSIX_FADV_DONTNEED => libc::POSIX_FADV_DONTNEED,
+            fcntl::POSIX_FADV_NOREUSE => libc::POSIX_FADV_NOREUSE,
+            _ => return Err(libc::EINVAL),
+        };
+
+        Ok(LibcFileAdvice(libc_advice))
+    }
+}
+
+struct LibcSeek(ffi::c_int);
+
+impl LibcSeek {
+    fn inner(&self) -> ffi::c_int {
+        self.0
+    }
+
+    fn try_from(whence: i32) -> Result<LibcSeek, i32> {
+        match whence {
+            unistd::SEEK_CUR => Ok(LibcSeek(libc::SEEK_CUR)),
+            unistd::SEEK_END => Ok(LibcSeek(libc::SEEK_END)),
+            unistd::SEEK_SET => Ok(LibcSeek(libc::SEEK_SET)),
+            unistd::SEEK_HOLE => Ok(LibcSeek(libc::SEEK_HOLE)),
+            unistd::SEEK_DATA => Ok(LibcSeek(libc::SEEK_DATA)),
+            _ => Err(libc::EINVAL),
+        }
+    }
+}
+
+//==================================================================================================
+// Standalone Functions
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Retrieves and logs the error number of the last failed call to the C library.
+///
+fn last_errno(function: &str) -> i32 {
+    let errno: i32 = unsafe { *libc::__errno_location() };
+    debug!("{}: errno={:?}", function, errno);
+    errno
+}
+
+fn to_cstring(path: &str) -> Result<CString, i32> {
+    CString::new(path).map_err(|_| libc::EINVAL)
+}
+
+fn to_stat(st: &libc::stat) -> stat {
+    stat {
+        st_dev: st.st_dev,
+        st_ino: st.st_ino,
+        st_mode: st.st_mode,
+        st_nlink: st.st_nlink,
+        st_uid: st.st_uid,
+        st_gid: st.st_gid,
+        st_rdev: st.st_rdev,
+        st_size: st.st_size,
+        st_atim: timespec {
+            tv_sec: st.st_atime,
+            tv_nsec: st.st_atime_nsec,
+        },
+        st_mtim: timespec {
+            tv_sec: st.st_mtime,
+            tv_nsec: st.st_mtime_nsec,
+        },
+        st_ctim: timespec {
+            tv_sec: st.st_ctime,
+            tv_nsec: st.st_ctime_nsec,
+        },
+        st_blksize: st.st_blksize,
+        st_blocks: st.st_blocks,
+    }
+}
+
+///
+/// # Description
+///
+/// Checks whether a file descriptor refers to a regular file.
+///
+/// # Parameters
+///
+/// - `fd`: File descriptor.
+///
+/// # Returns
+///
+/// Upon success, empty is returned. Upon failure, a host error number is returned instead.
+///
+fn check_regular_file(fd: i32) -> Result<(), i32> {
+    let mut st: libc::stat = unsafe { mem::zeroed() };
+    if unsafe { libc::fstat(fd, &mut st) } != 0 {
+        return Err(unsafe { *libc::__errno_location() });
+    }
+
+    if (st.st_mode & libc::S_IFMT) != libc::S_IFREG {
+        return Err(libc::EINVAL);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,1315 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::backend::FileSystem;
+use ::anyhow::Result;
+use ::core::cmp;
+use ::linuxd::{
+    fcntl,
+    limits,
+    sys::{
+        stat::stat,
+        types::{
+            dev_t,
+            ino_t,
+            mode_t,
+            off_t,
+        },
+    },
+    time::timespec,
+    unistd,
+};
+use ::std::{
+    collections::{
+        BTreeMap,
+        HashMap,
+    },
+    fs,
+    io::Read,
+    os::unix::fs::MetadataExt,
+    path::Path,
+    sync::atomic::{
+        AtomicU64,
+        Ordering,
+    },
+    time::{
+        SystemTime,
+        UNIX_EPOCH,
+    },
+};
+
+//==================================================================================================
+// Constants
+//==================================================================================================
+
+/// Inode number of the root directory.
+const ROOT_INO: ino_t = 1;
+
+/// Lowest file descriptor handed out. Lower ones are the standard streams.
+const FD_MIN: i32 = 3;
+
+/// Maximum number of open files.
+const OPEN_MAX: usize = 1024;
+
+/// Maximum number of symbolic links followed while resolving a path.
+const SYMLOOP_MAX: usize = 40;
+
+/// Maximum size of a regular file.
+const FILE_SIZE_MAX: off_t = 1 << 30;
+
+/// Block size reported for all files.
+const BLOCK_SIZE: i64 = 4096;
+
+/// Size reported for every entry of a directory, like tmpfs does.
+const DIRENT_SIZE: i64 = 20;
+
+/// Permission bits of a file mode.
+const PERMISSION_MASK: mode_t = 0o7777;
+
+/// Next device ID to hand out. Device IDs start high, to stay clear of host devices.
+static NEXT_DEV: AtomicU64 = AtomicU64::new(0xffff_0000);
+
+//==================================================================================================
+// Structures
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Contents of an inode.
+///
+#[derive(Debug, Clone)]
+enum Node {
+    /// Directory, with its entries and parent directory.
+    Directory {
+        entries: BTreeMap<String, ino_t>,
+        parent: ino_t,
+    },
+    /// Regular file, with its data.
+    RegularFile(Vec<u8>),
+    /// Symbolic link, with its target.
+    SymbolicLink(String),
+}
+
+///
+/// # Description
+///
+/// In-memory inode.
+///
+#[derive(Debug, Clone)]
+struct Inode {
+    /// Contents.
+    node: Node,
+    /// Permission bits.
+    mode: mode_t,
+    /// Number of hard links.
+    nlink: u64,
+    /// Last access time.
+    atim: timespec,
+    /// Last modification time.
+    mtim: timespec,
+    /// Last status change time.
+    ctim: timespec,
+}
+
+///
+/// # Description
+///
+/// Open file description.
+///
+#[derive(Debug, Clone, Copy)]
+struct OpenFile {
+    /// Inode of the file.
+    ino: ino_t,
+    /// Current offset.
+    offset: off_t,
+    /// Access mode, as guests encode it.
+    access_mode: i32,
+    /// Whether writes go to the end of the file.
+    append: bool,
+}
+
+///
+/// # Description
+///
+/// Outcome of resolving a path.
+///
+struct Resolved {
+    /// Directory that contains the last component of the path.
+    dir: ino_t,
+    /// Last component of the path. Empty if the path refers to its starting directory.
+    name: String,
+    /// Inode the path refers to.
+    ino: ino_t,
+}
+
+///
+/// # Description
+///
+/// Filesystem backend that keeps directories, regular files and symbolic links in memory, like
+/// tmpfs does. Nothing ever reaches the host disk, and all contents are lost once the backend is
+/// dropped.
+///
+#[derive(Debug)]
+pub struct MemoryFileSystem {
+    /// Device ID.
+    dev: dev_t,
+    /// Inodes.
+    inodes: BTreeMap<ino_t, Inode>,
+    /// Next inode number to hand out.
+    next_ino: ino_t,
+    /// Open files.
+    files: BTreeMap<i32, OpenFile>,
+}
+
+//==================================================================================================
+// Implementations
+//==================================================================================================
+
+impl Inode {
+    fn new(node: Node, mode: mode_t) -> Self {
+        let now: timespec = now();
+        Self {
+            node,
+            mode: mode & PERMISSION_MASK,
+            nlink: 0,
+            atim: now,
+            mtim: now,
+            ctim: now,
+        }
+    }
+
+    fn is_directory(&self) -> bool {
+        matches!(self.node, Node::Directory { .. })
+    }
+
+    fn entries(&self) -> Result<&BTreeMap<String, ino_t>, i32> {
+        match &self.node {
+            Node::Directory { entries, .. } => Ok(entries),
+            _ => Err(libc::ENOTDIR),
+        }
+    }
+
+    fn entries_mut(&mut self) -> Result<&mut BTreeMap<String, ino_t>, i32> {
+        match &mut self.node {
+            Node::Directory { entries, .. } => Ok(entries),
+            _ => Err(libc::ENOTDIR),
+        }
+    }
+
+    fn touch(&mut self) {
+        let now: timespec = now();
+        self.mtim = now;
+        self.ctim = now;
+    }
+}
+
+impl OpenFile {
+    fn is_readable(&self) -> bool {
+        self.access_mode != fcntl::O_WRONLY
+    }
+
+    fn is_writable(&self) -> bool {
+        self.access_mode == fcntl::O_WRONLY || self.access_mode == fcntl::O_RDWR
+    }
+}
+
+impl MemoryFileSystem {
+    ///
+    /// # Description
+    ///
+    /// Creates an empty in-memory filesystem.
+    ///
+    pub fn new() -> Self {
+        let mut root: Inode = Inode::new(
+            Node::Directory {
+                entries: BTreeMap::new(),
+                parent: ROOT_INO,
+            },
+            0o755,
+        );
+        root.nlink = 2;
+
+        Self {
+            dev: NEXT_DEV.fetch_add(1, Ordering::Relaxed),
+            inodes: BTreeMap::from([(ROOT_INO, root)]),
+            next_ino: ROOT_INO + 1,
+            files: BTreeMap::new(),
+        }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Creates an in-memory filesystem that starts with the contents of a snapshot.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: Host path of the snapshot, either a directory or a tar archive.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the in-memory filesystem is returned. Upon failure, an error is returned
+    /// instead.
+    ///
+    pub fn from_snapshot(path: &Path) -> Result<Self> {
+        let mut fs: Self = Self::new();
+
+        if fs::metadata(path)?.is_dir() {
+            fs.load_directory(path, ROOT_INO, &mut HashMap::new())?;
+        } else {
+            fs.load_tar(path)?;
+        }
+
+        info!("loaded snapshot (path={:?}, inodes={:?})", path, fs.inodes.len());
+
+        Ok(fs)
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Creates a copy of this filesystem, without open files and with a device ID of its own.
+    ///
+    pub fn duplicate(&self) -> Self {
+        Self {
+            dev: NEXT_DEV.fetch_add(1, Ordering::Relaxed),
+            inodes: self.inodes.clone(),
+            next_ino: self.next_ino,
+            files: BTreeMap::new(),
+        }
+    }
+
+    fn load_directory(
+        &mut self,
+        host: &Path,
+        dir: ino_t,
+        links: &mut HashMap<(u64, u64), ino_t>,
+    ) -> Result<()> {
+        for entry in fs::read_dir(host)? {
+            let path = entry?.path();
+            let metadata: fs::Metadata = fs::symlink_metadata(&path)?;
+            let name: String = match path.file_name().and_then(|name| name.to_str()) {
+                Some(name) => name.to_string(),
+                None => anyhow::bail!("invalid file name: {:?}", path),
+            };
+
+            // Hard links are preserved.
+            let key: (u64, u64) = (metadata.dev(), metadata.ino());
+            if metadata.is_file() && metadata.nlink() > 1 {
+                if let Some(ino) = links.get(&key) {
+                    self.link(dir, &name, *ino)
+                        .map_err(|errno| anyhow::anyhow!("failed to link {:?}: {}", path, errno))?;
+                    continue;
+                }
+            }
+
+            let node: Node = if metadata.is_dir() {
+                Node::Directory {
+                    entries: BTreeMap::new(),
+                    parent: dir,
+                }
+            } else if metadata.is_file() {
+                Node::RegularFile(fs::read(&path)?)
+            } else if metadata.is_symlink() {
+                match fs::read_link(&path)?.into_os_string().into_string() {
+                    Ok(target) => Node::SymbolicLink(target),
+                    Err(target) => anyhow::bail!("invalid symbolic link target: {:?}", target),
+                }
+            } else {
+                warn!("skipping special file (path={:?})", path);
+                continue;
+            };
+
+            let ino: ino_t = self
+                .create(dir, &name, node, metadata.mode())
+                .map_err(|errno| anyhow::anyhow!("failed to create {:?}: {}", path, errno))?;
+            self.set_times(ino, metadata.atime(), metadata.mtime());
+
+            if metadata.is_dir() {
+                self.load_directory(&path, ino, links)?;
+            } else if metadata.is_file() && metadata.nlink() > 1 {
+                links.insert(key, ino);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn load_tar(&mut self, host: &Path) -> Result<()> {
+        let mut archive: tar::Archive<fs::File> = tar::Archive::new(fs::File::open(host)?);
+
+        for entry in archive.entries()? {
+            let mut entry: tar::Entry<fs::File> = entry?;
+            let path: String = entry.path()?.to_string_lossy().into_owned();
+            let mode: mode_t = entry.header().mode()?;
+            let mtime: i64 = entry.header().mtime()? as i64;
+            let kind: tar::EntryType = entry.header().entry_type();
+
+            // Archives may omit parent directories.
+            let (dir, name): (ino_t, String) = self.make_parents(&path)?;
+            if name.is_empty() {
+                continue;
+            }
+
+            let node: Node = if kind.is_dir() {
+                // Directories may have been created already, along with their children.
+                let existing: Option<ino_t> = self
+                    .inode(dir)
+                    .entries()
+                    .ok()
+                    .and_then(|entries| entries.get(&name).copied());
+                if let Some(ino) = existing {
+                    self.inode_mut(ino).mode = mode & PERMISSION_MASK;
+                    self.set_times(ino, mtime, mtime);
+                    continue;
+                }
+                Node::Directory {
+                    entries: BTreeMap::new(),
+                    parent: dir,
+                }
+            } else if kind.is_file() {
+                let mut data: Vec<u8> = Vec::new();
+                entry.read_to_end(&mut data)?;
+                Node::RegularFile(data)
+            } else if kind.is_symlink() {
+                match entry.link_name()? {
+                    Some(target) => Node::SymbolicLink(target.to_string_lossy().into_owned()),
+                    None => anyhow::bail!("missing symbolic link target: {:?}", path),
+                }
+            } else if kind.is_hard_link() {
+                let target: String = match entry.link_name()? {
+                    Some(target) => target.to_string_lossy().into_owned(),
+                    None => anyhow::bail!("missing hard link target: {:?}", path),
+                };
+                let ino: ino_t = self
+                    .resolve(fcntl::AT_FDCWD, &target, false)
+                    .map_err(|errno| anyhow::anyhow!("failed to resolve {:?}: {}", target, errno))?
+                    .ino;
+                self.link(dir, &name, ino)
+                    .map_err(|errno| anyhow::anyhow!("failed to link {:?}: {}", path, errno))?;
+                continue;
+            } else {
+                warn!("skipping unsupported entry (path={:?}, kind={:?})", path, kind);
+                continue;
+            };
+
+            let ino: ino_t = self
+                .create(dir, &name, node, mode)
+                .map_err(|errno| anyhow::anyhow!("failed to create {:?}: {}", path, errno))?;
+            self.set_times(ino, mtime, mtime);
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Creates the missing parent directories of a path in a snapshot.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the parent directory and the last component of the path are returned. The
+    /// latter is empty if the path refers to the root directory.
+    ///
+    fn make_parents(&mut self, path: &str) -> Result<(ino_t, String)> {
+        let mut components: Vec<&str> = path
+            .split('/')
+            .filter(|c| !c.is_empty() && *c != ".")
+            .collect();
+        let name: String = components.pop().unwrap_or_default().to_string();
+
+        let mut dir: ino_t = ROOT_INO;
+        for component in components {
+            let existing: Option<ino_t> = self
+                .inode(dir)
+                .entries()
+                .ok()
+                .and_then(|entries| entries.get(component).copied());
+            dir = match existing {
+                Some(ino) if self.inode(ino).is_directory() => ino,
+                Some(_) => anyhow::bail!("not a directory: {:?}", component),
+                None => {
+                    let node: Node = Node::Directory {
+                        entries: BTreeMap::new(),
+                        parent: dir,
+                    };
+                    self.create(dir, component, node, 0o755).map_err(|errno| {
+                        anyhow::anyhow!("failed to create {:?}: {}", path, errno)
+                    })?
+                },
+            };
+        }
+
+        Ok((dir, name))
+    }
+
+    fn set_times(&mut self, ino: ino_t, atime: i64, mtime: i64) {
+        let inode: &mut Inode = self.inode_mut(ino);
+        inode.atim = timespec {
+            tv_sec: atime,
+            tv_nsec: 0,
+        };
+        inode.mtim = timespec {
+            tv_sec: mtime,
+            tv_nsec: 0,
+        };
+    }
+
+    fn inode(&self, ino: ino_t) -> &Inode {
+        self.inodes.get(&ino).expect("inode is referenced")
+    }
+
+    fn inode_mut(&mut self, ino: ino_t) -> &mut Inode {
+        self.inodes.get_mut(&ino).expect("inode is referenced")
+    }
+
+    fn file(&self, fd: i32) -> Result<OpenFile, i32> {
+        self.files.get(&fd).copied().ok_or(libc::EBADF)
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the directory from which a relative path is resolved.
+    ///
+    fn start(&self, dirfd: i32, path: &str) -> Result<ino_t, i32> {
+        if path.starts_with('/') || dirfd == fcntl::AT_FDCWD {
+            return Ok(ROOT_INO);
+        }
+
+        let file: OpenFile = self.file(dirfd)?;
+        if !self.inode(file.ino).is_directory() {
+            return Err(libc::ENOTDIR);
+        }
+
+        Ok(file.ino)
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Resolves a path.
+    ///
+    /// # Parameters
+    ///
+    /// - `dirfd`: Directory from which relative paths are resolved.
+    /// - `path`: Path to resolve.
+    /// - `follow`: Whether a symbolic link in the last component is followed.
+    ///
+    fn resolve(&self, dirfd: i32, path: &str, follow: bool) -> Result<Resolved, i32> {
+        if path.is_empty() {
+            return Err(libc::ENOENT);
+        }
+
+        let start: ino_t = self.start(dirfd, path)?;
+        let mut links: usize = 0;
+        self.walk(start, path, follow, &mut links)
+    }
+
+    fn walk(
+        &self,
+        start: ino_t,
+        path: &str,
+        follow: bool,
+        links: &mut usize,
+    ) -> Result<Resolved, i32> {
+        let mut resolved: Resolved = Resolved {
+            dir: start,
+            name: String::new(),
+            ino: if path.starts_with('/') {
+                ROOT_INO
+            } else {
+                start
+            },
+        };
+
+        let components: Vec<&str> = path
+            .split('/')
+            .filter(|c| !c.is_empty() && *c != ".")
+            .collect();
+
+        for (i, component) in components.iter().enumerate() {
+            let dir: ino_t = resolved.ino;
+            let next: ino_t = match &self.inode(dir).node {
+                Node::Directory { parent, .. } if *component == ".." => *parent,
+                Node::Directory { entries, .. } => *entries.get(*component).ok_or(libc::ENOENT)?,
+                _ => return Err(libc::ENOTDIR),
+            };
+
+            let last: bool = i + 1 == components.len();
+            if let Node::SymbolicLink(target) = &self.inode(next).node {
+                if !last || follow {
+                    *links += 1;
+                    if *links > SYMLOOP_MAX {
+                        return Err(libc::ELOOP);
+                    }
+                    resolved = self.walk(dir, target, true, links)?;
+                    continue;
+                }
+            }
+
+            resolved = Resolved {
+                dir,
+                name: component.to_string(),
+                ino: next,
+            };
+        }
+
+        // A trailing slash requires a directory.
+        if path.ends_with('/') && !self.inode(resolved.ino).is_directory() {
+            return Err(libc::ENOTDIR);
+        }
+
+        Ok(resolved)
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Resolves all but the last component of a path.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the parent directory and the last component of the path are returned. Upon
+    /// failure, the error number is returned instead.
+    ///
+    fn resolve_parent(&self, dirfd: i32, path: &str) -> Result<(ino_t, String), i32> {
+        let trimmed: &str = path.trim_end_matches('/');
+        let (parent, name): (&str, &str) = match trimmed.rsplit_once('/') {
+            Some(("", name)) => ("/", name),
+            Some((parent, name)) => (parent, name),
+            None => (".", trimmed),
+        };
+
+        if path.is_empty() {
+            return Err(libc::ENOENT);
+        }
+        if name.is_empty() || name == "." || name == ".." {
+            return Err(libc::EINVAL);
+        }
+        if name.len() > limits::NAME_MAX {
+            return Err(libc::ENAMETOOLONG);
+        }
+
+        let start: ino_t = self.start(dirfd, path)?;
+        let mut links: usize = 0;
+        let dir: ino_t = self.walk(start, parent, true, &mut links)?.ino;
+        if !self.inode(dir).is_directory() {
+            return Err(libc::ENOTDIR);
+        }
+
+        Ok((dir, name.to_string()))
+    }
+
+    fn lookup(&self, dir: ino_t, name: &str) -> Result<Option<ino_t>, i32> {
+        Ok(self.inode(dir).entries()?.get(name).copied())
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Creates a new inode and links it into a directory.
+    ///
+    fn create(&mut self, dir: ino_t, name: &str, node: Node, mode: mode_t) -> Result<ino_t, i32> {
+        if self.lookup(dir, name)?.is_some() {
+            return Err(libc::EEXIST);
+        }
+
+        let ino: ino_t = self.next_ino;
+        self.next_ino += 1;
+
+        let is_directory: bool = matches!(node, Node::Directory { .. });
+        self.inodes.insert(ino, Inode::new(node, mode));
+        self.link(dir, name, ino)?;
+
+        // Directories are also linked from their own "." entry and from ".." entries of children.
+        if is_directory {
+            self.inode_mut(ino).nlink += 1;
+            self.inode_mut(dir).nlink += 1;
+        }
+
+        Ok(ino)
+    }
+
+    fn link(&mut self, dir: ino_t, name: &str, ino: ino_t) -> Result<(), i32> {
+        let parent: &mut Inode = self.inode_mut(dir);
+        if parent
+            .entries_mut()?
+            .insert(name.to_string(), ino)
+            .is_some()
+        {
+            unreachable!("directory entry was checked not to exist");
+        }
+        parent.touch();
+
+        let inode: &mut Inode = self.inode_mut(ino);
+        inode.nlink += 1;
+        inode.ctim = now();
+
+        Ok(())
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Removes a directory entry, releasing its inode once it is neither linked nor open.
+    ///
+    fn unlink(&mut self, dir: ino_t, name: &str) -> Result<(), i32> {
+        let parent: &mut Inode = self.inode_mut(dir);
+        let ino: ino_t = parent.entries_mut()?.remove(name).ok_or(libc::ENOENT)?;
+        parent.touch();
+
+        let inode: &mut Inode = self.inode_mut(ino);
+        if inode.is_directory() {
+            inode.nlink = 0;
+            self.inode_mut(dir).nlink -= 1;
+        } else {
+            inode.nlink -= 1;
+            inode.ctim = now();
+        }
+
+        self.release(ino);
+
+        Ok(())
+    }
+
+    fn release(&mut self, ino: ino_t) {
+        if self.inode(ino).nlink == 0 && !self.files.values().any(|f| f.ino == ino) {
+            self.inodes.remove(&ino);
+        }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Checks whether a directory lies within the tree rooted at another directory.
+    ///
+    fn is_within(&self, dir: ino_t, ancestor: ino_t) -> bool {
+        let mut current: ino_t = dir;
+        loop {
+            if current == ancestor {
+                return true;
+            }
+            match &self.inode(current).node {
+                Node::Directory { parent, .. } if *parent != current => current = *parent,
+                _ => return false,
+            }
+        }
+    }
+
+    fn set_parent(&mut self, ino: ino_t, dir: ino_t) {
+        if let Node::Directory { parent, .. } = &mut self.inode_mut(ino).node {
+            *parent = dir;
+        }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Builds the absolute path of a directory.
+    ///
+    fn path_of(&self, dir: ino_t) -> String {
+        let mut components: Vec<&str> = Vec::new();
+        let mut current: ino_t = dir;
+        while current != ROOT_INO {
+            let parent: ino_t = match &self.inode(current).node {
+                Node::Directory { parent, .. } => *parent,
+                _ => break,
+            };
+            if let Ok(entries) = self.inode(parent).entries() {
+                if let Some((name, _)) = entries.iter().find(|(_, ino)| **ino == current) {
+                    components.push(name);
+                }
+            }
+            current = parent;
+        }
+
+        components.reverse();
+        format!("/{}", components.join("/"))
+    }
+
+    fn read_at(&mut self, ino: ino_t, buffer: &mut [u8], offset: off_t) -> Result<usize, i32> {
+        if offset < 0 {
+            return Err(libc::EINVAL);
+        }
+
+        let inode: &mut Inode = self.inode_mut(ino);
+        let count: usize = match &inode.node {
+            Node::RegularFile(data) => {
+                let start: usize = cmp::min(offset as usize, data.len());
+                let count: usize = cmp::min(buffer.len(), data.len() - start);
+                buffer[..count].copy_from_slice(&data[start..(start + count)]);
+                count
+            },
+            Node::Directory { .. } => return Err(libc::EISDIR),
+            Node::SymbolicLink(_) => return Err(libc::EINVAL),
+        };
+        inode.atim = now();
+
+        Ok(count)
+    }
+
+    fn write_at(&mut self, ino: ino_t, buffer: &[u8], offset: off_t) -> Result<usize, i32> {
+        if offset < 0 {
+            return Err(libc::EINVAL);
+        }
+        let end: off_t = offset
+            .checked_add(buffer.len() as off_t)
+            .ok_or(libc::EFBIG)?;
+        if end > FILE_SIZE_MAX {
+            return Err(libc::EFBIG);
+        }
+
+        let inode: &mut Inode = self.inode_mut(ino);
+        match &mut inode.node {
+            Node::RegularFile(data) => {
+                let (start, end): (usize, usize) = (offset as usize, end as usize);
+                if data.len() < end {
+                    data.resize(end, 0);
+                }
+                data[start..end].copy_from_slice(buffer);
+            },
+            Node::Directory { .. } => return Err(libc::EISDIR),
+            Node::SymbolicLink(_) => return Err(libc::EINVAL),
+        }
+        inode.touch();
+
+        Ok(buffer.len())
+    }
+
+    fn resize(&mut self, ino: ino_t, length: off_t) -> Result<(), i32> {
+        if length < 0 {
+            return Err(libc::EINVAL);
+        }
+        if length > FILE_SIZE_MAX {
+            return Err(libc::EFBIG);
+        }
+
+        let inode: &mut Inode = self.inode_mut(ino);
+        match &mut inode.node {
+            Node::RegularFile(data) => data.resize(length as usize, 0),
+            Node::Directory { .. } => return Err(libc::EISDIR),
+            Node::SymbolicLink(_) => return Err(libc::EINVAL),
+        }
+        inode.touch();
+
+        Ok(())
+    }
+
+    fn stat(&self, ino: ino_t) -> stat {
+        let inode: &Inode = self.inode(ino);
+        let (kind, size): (mode_t, i64) = match &inode.node {
+            Node::Directory { entries, .. } => {
+                (libc::S_IFDIR, (entries.len() as i64 + 2) * DIRENT_SIZE)
+            },
+            Node::RegularFile(data) => (libc::S_IFREG, data.len() as i64),
+            Node::SymbolicLink(target) => (libc::S_IFLNK, target.len() as i64),
+        };
+        let blocks: i64 = match &inode.node {
+            Node::RegularFile(_) => ((size + BLOCK_SIZE - 1) / BLOCK_SIZE) * (BLOCK_SIZE / 512),
+            _ => 0,
+        };
+
+        stat {
+            st_dev: self.dev,
+            st_ino: ino,
+            st_mode: kind | inode.mode,
+            st_nlink: inode.nlink,
+            st_uid: 0,
+            st_gid: 0,
+            st_rdev: 0,
+            st_size: size,
+            st_atim: inode.atim,
+            st_mtim: inode.mtim,
+            st_ctim: inode.ctim,
+            st_blksize: BLOCK_SIZE,
+            st_blocks: blocks,
+        }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Resolves the offset of a transfer, which is either given or taken from the open file.
+    ///
+    fn transfer_offset(file: &OpenFile, offset: &Option<&mut off_t>) -> off_t {
+        match offset {
+            Some(offset) => **offset,
+            None => file.offset,
+        }
+    }
+
+    fn advance(&mut self, fd: i32, offset: Option<&mut off_t>, count: usize) {
+        match offset {
+            Some(offset) => *offset += count as off_t,
+            None => {
+                if let Some(file) = self.files.get_mut(&fd) {
+                    file.offset += count as off_t;
+                }
+            },
+        }
+    }
+}
+
+impl Default for MemoryFileSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FileSystem for MemoryFileSystem {
+    fn open_at(&mut self, dirfd: i32, path: &str, flags: i32, mode: mode_t) -> Result<i32, i32> {
+        trace!(
+            "open_at(): dirfd={:?}, path={:?}, flags={:#x}, mode={:#o}",
+            dirfd,
+            path,
+            flags,
+            mode
+        );
+
+        let ino: ino_t = match self.resolve(dirfd, path, true) {
+            Ok(resolved) => {
+                if (flags & fcntl::O_CREAT) != 0 && (flags & fcntl::O_EXCL) != 0 {
+                    return Err(libc::EEXIST);
+                }
+                resolved.ino
+            },
+            Err(libc::ENOENT) if (flags & fcntl::O_CREAT) != 0 => {
+                let (dir, name): (ino_t, String) = self.resolve_parent(dirfd, path)?;
+                // The last component is a dangling symbolic link.
+                if self.lookup(dir, &name)?.is_some() {
+                    return Err(libc::ENOENT);
+                }
+                self.create(dir, &name, Node::RegularFile(Vec::new()), mode)?
+            },
+            Err(errno) => return Err(errno),
+        };
+
+        let access_mode: i32 = flags & (fcntl::O_RDONLY | fcntl::O_WRONLY | fcntl::O_RDWR);
+        let file: OpenFile = OpenFile {
+            ino,
+            offset: 0,
+            access_mode: if access_mode == 0 {
+                fcntl::O_RDONLY
+            } else {
+                access_mode
+            },
+            append: (flags & fcntl::O_APPEND) != 0,
+        };
+
+        if self.inode(ino).is_directory() && file.is_writable() {
+            return Err(libc::EISDIR);
+        }
+        if (flags & fcntl::O_TRUNC) != 0 && file.is_writable() {
+            self.resize(ino, 0)?;
+        }
+
+        let fd: i32 = (FD_MIN..(FD_MIN + OPEN_MAX as i32))
+            .find(|fd| !self.files.contains_key(fd))
+            .ok_or(libc::EMFILE)?;
+        self.files.insert(fd, file);
+
+        Ok(fd)
+    }
+
+    fn close(&mut self, fd: i32) -> Result<(), i32> {
+        let file: OpenFile = self.files.remove(&fd).ok_or(libc::EBADF)?;
+        self.release(file.ino);
+        Ok(())
+    }
+
+    fn read(&mut self, fd: i32, buffer: &mut [u8]) -> Result<usize, i32> {
+        let file: OpenFile = self.file(fd)?;
+        if !file.is_readable() {
+            return Err(libc::EBADF);
+        }
+
+        let count: usize = self.read_at(file.ino, buffer, file.offset)?;
+        self.advance(fd, None, count);
+
+        Ok(count)
+    }
+
+    fn write(&mut self, fd: i32, buffer: &[u8]) -> Result<usize, i32> {
+        let mut file: OpenFile = self.file(fd)?;
+        if !file.is_writable() {
+            return Err(libc::EBADF);
+        }
+
+        if file.append {
+            file.offset = self.stat(file.ino).st_size;
+        }
+        let count: usize = self.write_at(file.ino, buffer, file.offset)?;
+        file.offset += count as off_t;
+        self.files.insert(fd, file);
+
+        Ok(count)
+    }
+
+    fn pread(&mut self, fd: i32, buffer: &mut [u8], offset: off_t) -> Result<usize, i32> {
+        let file: OpenFile = self.file(fd)?;
+        if !file.is_readable() {
+            return Err(libc::EBADF);
+        }
+
+        self.read_at(file.ino, buffer, offset)
+    }
+
+    fn pwrite(&mut self, fd: i32, buffer: &[u8], offset: off_t) -> Result<usize, i32> {
+        let file: OpenFile = self.file(fd)?;
+        if !file.is_writable() {
+            return Err(libc::EBADF);
+        }
+
+        self.write_at(file.ino, buffer, offset)
+    }
+
+    fn lseek(&mut self, fd: i32, offset: off_t, whence: i32) -> Result<off_t, i32> {
+        let file: OpenFile = self.file(fd)?;
+        let size: off_t = self.stat(file.ino).st_size;
+
+        // Files have no holes, so data spans the whole file.
+        let offset: off_t = match whence {
+            unistd::SEEK_SET => offset,
+            unistd::SEEK_CUR => file.offset.checked_add(offset).ok_or(libc::EOVERFLOW)?,
+            unistd::SEEK_END => size.checked_add(offset).ok_or(libc::EOVERFLOW)?,
+            unistd::SEEK_DATA if (0..size).contains(&offset) => offset,
+            unistd::SEEK_HOLE if (0..size).contains(&offset) => size,
+            unistd::SEEK_DATA | unistd::SEEK_HOLE => return Err(libc::ENXIO),
+            _ => return Err(libc::EINVAL),
+        };
+        if offset < 0 {
+            return Err(libc::EINVAL);
+        }
+
+        if let Some(file) = self.files.get_mut(&fd) {
+            file.offset = offset;
+        }
+
+        Ok(offset)
+    }
+
+    fn fsync(&mut self, fd: i32) -> Result<(), i32> {
+        self.file(fd).map(|_| ())
+    }
+
+    fn fdatasync(&mut self, fd: i32) -> Result<(), i32> {
+        self.file(fd).map(|_| ())
+    }
+
+    fn ftruncate(&mut self, fd: i32, length: off_t) -> Result<(), i32> {
+        let file: OpenFile = self.file(fd)?;
+        if !file.is_writable() {
+            return Err(libc::EINVAL);
+        }
+
+        self.resize(file.ino, length)
+    }
+
+    fn truncate(&mut self, path: &str, length: off_t) -> Result<(), i32> {
+        let ino: ino_t = self.resolve(fcntl::AT_FDCWD, path, true)?.ino;
+        self.resize(ino, length)
+    }
+
+    fn fallocate(&mut self, fd: i32, offset: off_t, len: off_t) -> Result<(), i32> {
+        let file: OpenFile = self.file(fd)?;
+        if !file.is_writable() {
+            return Err(libc::EBADF);
+        }
+        if offset < 0 || len <= 0 {
+            return Err(libc::EINVAL);
+        }
+        if !matches!(self.inode(file.ino).node, Node::RegularFile(_)) {
+            return Err(libc::ENODEV);
+        }
+
+        let end: off_t = offset.checked_add(len).ok_or(libc::EFBIG)?;
+        if end > self.stat(file.ino).st_size {
+            self.resize(file.ino, end)?;
+        }
+
+        Ok(())
+    }
+
+    fn fadvise(&mut self, fd: i32, _offset: off_t, len: off_t, advice: i32) -> Result<(), i32> {
+        self.file(fd)?;
+        if len < 0 || !(fcntl::POSIX_FADV_NORMAL..=fcntl::POSIX_FADV_NOREUSE).contains(&advice) {
+            return Err(libc::EINVAL);
+        }
+
+        // Everything is in memory already.
+        Ok(())
+    }
+
+    fn fstat(&mut self, fd: i32) -> Result<stat, i32> {
+        let file: OpenFile = self.file(fd)?;
+        Ok(self.stat(file.ino))
+    }
+
+    fn fstat_at(&mut self, dirfd: i32, path: &str, flag: i32) -> Result<stat, i32> {
+        if (flag & !(libc::AT_SYMLINK_NOFOLLOW | libc::AT_EMPTY_PATH)) != 0 {
+            return Err(libc::EINVAL);
+        }
+
+        if path.is_empty() && (flag & libc::AT_EMPTY_PATH) != 0 {
+            let ino: ino_t = self.start(dirfd, path)?;
+            return Ok(self.stat(ino));
+        }
+
+        let follow: bool = (flag & libc::AT_SYMLINK_NOFOLLOW) == 0;
+        let ino: ino_t = self.resolve(dirfd, path, follow)?.ino;
+        Ok(self.stat(ino))
+    }
+
+    fn unlink_at(&mut self, dirfd: i32, path: &str, flags: i32) -> Result<(), i32> {
+        if (flags & !fcntl::AT_REMOVEDIR) != 0 {
+            return Err(libc::EINVAL);
+        }
+
+        let (dir, name): (ino_t, String) = self.resolve_parent(dirfd, path)?;
+        let ino: ino_t = self.lookup(dir, &name)?.ok_or(libc::ENOENT)?;
+
+        let inode: &Inode = self.inode(ino);
+        if (flags & fcntl::AT_REMOVEDIR) != 0 {
+            if !inode.entries()?.is_empty() {
+                return Err(libc::ENOTEMPTY);
+            }
+        } else if inode.is_directory() {
+            return Err(libc::EISDIR);
+        }
+
+        self.unlink(dir, &name)
+    }
+
+    fn rename_at(
+        &mut self,
+        olddirfd: i32,
+        oldpath: &str,
+        newdirfd: i32,
+        newpath: &str,
+        flags: u32,
+    ) -> Result<(), i32> {
+        if (flags & !(fcntl::RENAME_NOREPLACE | fcntl::RENAME_EXCHANGE)) != 0
+            || flags == (fcntl::RENAME_NOREPLACE | fcntl::RENAME_EXCHANGE)
+        {
+            return Err(libc::EINVAL);
+        }
+
+        let (olddir, oldname): (ino_t, String) = self.resolve_parent(olddirfd, oldpath)?;
+        let (newdir, newname): (ino_t, String) = self.resolve_parent(newdirfd, newpath)?;
+        let ino: ino_t = self.lookup(olddir, &oldname)?.ok_or(libc::ENOENT)?;
+        let existing: Option<ino_t> = self.lookup(newdir, &newname)?;
+
+        // Directories cannot be moved into themselves.
+        let is_directory: bool = self.inode(ino).is_directory();
+        if is_directory && self.is_within(newdir, ino) {
+            return Err(libc::EINVAL);
+        }
+
+        if (flags & fcntl::RENAME_EXCHANGE) != 0 {
+            let other: ino_t = existing.ok_or(libc::ENOENT)?;
+            if self.inode(other).is_directory() && self.is_within(olddir, other) {
+                return Err(libc::EINVAL);
+            }
+
+            self.inode_mut(olddir).entries_mut()?.insert(oldname, other);
+            self.inode_mut(newdir).entries_mut()?.insert(newname, ino);
+            for (moved, dir, from) in [(ino, newdir, olddir), (other, olddir, newdir)] {
+                if self.inode(moved).is_directory() {
+                    self.set_parent(moved, dir);
+                    self.inode_mut(from).nlink -= 1;
+                    self.inode_mut(dir).nlink += 1;
+                }
+                self.inode_mut(moved).ctim = now();
+            }
+            self.inode_mut(olddir).touch();
+            self.inode_mut(newdir).touch();
+
+            return Ok(());
+        }
+
+        if let Some(other) = existing {
+            if (flags & fcntl::RENAME_NOREPLACE) != 0 {
+                return Err(libc::EEXIST);
+            }
+            // Both names refer to the same file.
+            if other == ino {
+                return Ok(());
+            }
+
+            let target: &Inode = self.inode(other);
+            match (is_directory, target.is_directory()) {
+                (true, true) if !target.entries()?.is_empty() => return Err(libc::ENOTEMPTY),
+                (true, false) => return Err(libc::ENOTDIR),
+                (false, true) => return Err(libc::EISDIR),
+                _ => {},
+            }
+
+            self.unlink(newdir, &newname)?;
+        }
+
+        self.inode_mut(olddir).entries_mut()?.remove(&oldname);
+        self.inode_mut(newdir).entries_mut()?.insert(newname, ino);
+        if is_directory {
+            self.set_parent(ino, newdir);
+            self.inode_mut(olddir).nlink -= 1;
+            self.inode_mut(newdir).nlink += 1;
+        }
+        self.inode_mut(ino).ctim = now();
+        self.inode_mut(olddir).touch();
+        self.inode_mut(newdir).touch();
+
+        Ok(())
+    }
+
+    fn link_at(
+        &mut self,
+        olddirfd: i32,
+        oldpath: &str,
+        newdirfd: i32,
+        newpath: &str,
+        flags: i32,
+    ) -> Result<(), i32> {
+        if (flags & !libc::AT_SYMLINK_FOLLOW) != 0 {
+            return Err(libc::EINVAL);
+        }
+
+        let follow: bool = (flags & libc::AT_SYMLINK_FOLLOW) != 0;
+        let ino: ino_t = self.resolve(olddirfd, oldpath, follow)?.ino;
+        if self.inode(ino).is_directory() {
+            return Err(libc::EPERM);
+        }
+
+        let (dir, name): (ino_t, String) = self.resolve_parent(newdirfd, newpath)?;
+        if self.lookup(dir, &name)?.is_some() {
+            return Err(libc::EEXIST);
+        }
+
+        self.link(dir, &name, ino)
+    }
+
+    fn symlink_at(&mut self, target: &str, dirfd: i32, linkpath: &str) -> Result<(), i32> {
+        if target.is_empty() {
+            return Err(libc::ENOENT);
+        }
+
+        let (dir, name): (ino_t, String) = self.resolve_parent(dirfd, linkpath)?;
+        self.create(dir, &name, Node::SymbolicLink(target.to_string()), 0o777)?;
+
+        Ok(())
+    }
+
+    fn readlink_at(&mut self, dirfd: i32, path: &str, buffer: &mut [u8]) -> Result<usize, i32> {
+        let ino: ino_t = self.resolve(dirfd, path, false)?.ino;
+        let inode: &mut Inode = self.inode_mut(ino);
+        let target: &[u8] = match &inode.node {
+            Node::SymbolicLink(target) => target.as_bytes(),
+            _ => return Err(libc::EINVAL),
+        };
+
+        let count: usize = cmp::min(target.len(), buffer.len());
+        buffer[..count].copy_from_slice(&target[..count]);
+        inode.atim = now();
+
+        Ok(count)
+    }
+
+    fn copy_file_range(
+        &mut self,
+        fd_in: i32,
+        off_in: Option<&mut off_t>,
+        fd_out: i32,
+        off_out: Option<&mut off_t>,
+        len: usize,
+        flags: u32,
+    ) -> Result<usize, i32> {
+        if flags != 0 {
+            return Err(libc::EINVAL);
+        }
+
+        let file_in: OpenFile = self.file(fd_in)?;
+        let file_out: OpenFile = self.file(fd_out)?;
+        if !file_in.is_readable() || !file_out.is_writable() || file_out.append {
+            return Err(libc::EBADF);
+        }
+
+        // Read everything first, so that overlapping ranges of the same file are copied as-is.
+        let mut data: Vec<u8> = vec![0; cmp::min(len, FILE_SIZE_MAX as usize)];
+        let count: usize =
+            self.read_at(file_in.ino, &mut data, Self::transfer_offset(&file_in, &off_in))?;
+        let count: usize = self.write_at(
+            file_out.ino,
+            &data[..count],
+            Self::transfer_offset(&file_out, &off_out),
+        )?;
+
+        self.advance(fd_in, off_in, count);
+        self.advance(fd_out, off_out, count);
+
+        Ok(count)
+    }
+
+    fn sendfile(
+        &mut self,
+        out_fd: i32,
+        in_fd: i32,
+        offset: Option<&mut off_t>,
+        count: usize,
+    ) -> Result<usize, i32> {
+        let file_in: OpenFile = self.file(in_fd)?;
+        if !file_in.is_readable() {
+            return Err(libc::EBADF);
+        }
+        let file_out: OpenFile = self.file(out_fd)?;
+        if !matches!(self.inode(file_out.ino).node, Node::RegularFile(_)) {
+            return Err(libc::EINVAL);
+        }
+
+        let mut data: Vec<u8> = vec![0; cmp::min(count, FILE_SIZE_MAX as usize)];
+        let count: usize =
+            self.read_at(file_in.ino, &mut data, Self::transfer_offset(&file_in, &offset))?;
+        let count: usize = self.write(out_fd, &data[..count])?;
+
+        self.advance(in_fd, offset, count);
+
+        Ok(count)
+    }
+
+    fn access_mode(&mut self, fd: i32) -> Result<i32, i32> {
+        Ok(self.file(fd)?.access_mode)
+    }
+
+    fn realpath(&mut self, path: &str) -> Result<String, i32> {
+        let resolved: Resolved = self.resolve(fcntl::AT_FDCWD, path, true)?;
+
+        if self.inode(resolved.ino).is_directory() {
+            return Ok(self.path_of(resolved.ino));
+        }
+
+        Ok(match self.path_of(resolved.dir).as_str() {
+            "/" => format!("/{}", resolved.name),
+            dir => format!("{}/{}", dir, resolved.name),
+        })
+    }
+}
+
+//==================================================================================================
+// Standalone Functions
+//==================================================================================================
+
+fn now() -> timespec {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+
+    timespec {
+        tv_sec: now.as_secs() as i64,
+        tv_nsec: now.subsec_nanos() as i64,
+    }
+}
@@ -0,0 +1,505 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Modules
+//==================================================================================================
+
+mod host;
+mod memory;
+mod overlay;
+mod quota;
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::resource::{
+    Resource,
+    ResourceLimits,
+    ResourceUsage,
+};
+use ::anyhow::Result;
+use ::linuxd::{
+    sys::{
+        stat::stat,
+        types::{
+            mode_t,
+            off_t,
+        },
+    },
+    venv::VirtualEnvironmentIdentifier,
+};
+use ::std::{
+    collections::HashMap,
+    fs,
+    path::{
+        Path,
+        PathBuf,
+    },
+};
+
+//==================================================================================================
+// Exports
+//==================================================================================================
+
+pub use self::{
+    host::HostFileSystem,
+    memory::MemoryFileSystem,
+    overlay::OverlayFileSystem,
+    quota::QuotaFileSystem,
+};
+
+//==================================================================================================
+// Traits
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Filesystem that serves the file operations of guest processes.
+///
+/// Flags, modes and other arguments are encoded as guests see them, and each implementation
+/// translates them as needed. File descriptors are private to each implementation. Upon failure,
+/// all operations return a host error number.
+///
+pub trait FileSystem {
+    /// Opens a file relative to a directory.
+    fn open_at(&mut self, dirfd: i32, path: &str, flags: i32, mode: mode_t) -> Result<i32, i32>;
+
+    /// Closes a file.
+    fn close(&mut self, fd: i32) -> Result<(), i32>;
+
+    /// Reads from a file at its current offset.
+    fn read(&mut self, fd: i32, buffer: &mut [u8]) -> Result<usize, i32>;
+
+    /// Writes to a file at its current offset.
+    fn write(&mut self, fd: i32, buffer: &[u8]) -> Result<usize, i32>;
+
+    /// Reads from a file at a given offset.
+    fn pread(&mut self, fd: i32, buffer: &mut [u8], offset: off_t) -> Result<usize, i32>;
+
+    /// Writes to a file at a given offset.
+    fn pwrite(&mut self, fd: i32, buffer: &[u8], offset: off_t) -> Result<usize, i32>;
+
+    /// Repositions the offset of a file.
+    fn lseek(&mut self, fd: i32, offset: off_t, whence: i32) -> Result<off_t, i32>;
+
+    /// Synchronizes data and metadata of a file.
+    fn fsync(&mut self, fd: i32) -> Result<(), i32>;
+
+    /// Synchronizes data of a file.
+    fn fdatasync(&mut self, fd: i32) -> Result<(), i32>;
+
+    /// Truncates an open file.
+    fn ftruncate(&mut self, fd: i32, length: off_t) -> Result<(), i32>;
+
+    /// Truncates a file at a path.
+    fn truncate(&mut self, path: &str, length: off_t) -> Result<(), i32>;
+
+    /// Allocates space for a file.
+    fn fallocate(&mut self, fd: i32, offset: off_t, len: off_t) -> Result<(), i32>;
+
+    /// Announces an access pattern for a file.
+    fn fadvise(&mut self, fd: i32, offset: off_t, len: off_t, advice: i32) -> Result<(), i32>;
+
+    /// Gets the status of an open file.
+    fn fstat(&mut self, fd: i32) -> Result<stat, i32>;
+
+    /// Gets the status of a file relative to a directory.
+    fn fstat_at(&mut self, dirfd: i32, path: &str, flag: i32) -> Result<stat, i32>;
+
+    /// Removes a directory entry relative to a directory.
+    fn unlink_at(&mut self, dirfd: i32, path: &str, flags: i32) -> Result<(), i32>;
+
+    /// Renames a file, with the same flags as `renameat2()`.
+    fn rename_at(
+        &mut self,
+        olddirfd: i32,
+        oldpath: &str,
+        newdirfd: i32,
+        newpath: &str,
+        flags: u32,
+    ) -> Result<(), i32>;
+
+    /// Creates a hard link.
+    fn link_at(
+        &mut self,
+        olddirfd: i32,
+        oldpath: &str,
+        newdirfd: i32,
+        newpath: &str,
+        flags: i32,
+    ) -> Result<(), i32>;
+
+    /// Creates a symbolic link.
+    fn symlink_at(&mut self, target: &str, dirfd: i32, linkpath: &str) -> Result<(), i32>;
+
+    /// Reads the target of a symbolic link, truncating it to the size of the buffer.
+    fn readlink_at(&mut self, dirfd: i32, path: &str, buffer: &mut [u8]) -> Result<usize, i32>;
+
+    /// Copies a range of data between files. Offsets, if given, are updated in place.
+    fn copy_file_range(
+        &mut self,
+        fd_in: i32,
+        off_in: Option<&mut off_t>,
+        fd_out: i32,
+        off_out: Option<&mut off_t>,
+        len: usize,
+        flags: u32,
+    ) -> Result<usize, i32>;
+
+    /// Transfers data between files. The offset, if given, is updated in place.
+    fn sendfile(
+        &mut self,
+        out_fd: i32,
+        in_fd: i32,
+        offset: Option<&mut off_t>,
+        count: usize,
+    ) -> Result<usize, i32>;
+
+    /// Gets the access mode of an open file, as guests encode it.
+    fn access_mode(&mut self, fd: i32) -> Result<i32, i32>;
+
+    /// Resolves a path into a canonical path relative to the root of the filesystem.
+    fn realpath(&mut self, path: &str) -> Result<String, i32>;
+}
+
+//==================================================================================================
+// Structures
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Kind of filesystem backend.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BackendKind {
+    /// Host filesystem, accessed through the C library.
+    Host,
+    /// In-memory filesystem, optionally initialized from a snapshot.
+    Memory(Option<PathBuf>),
+    /// Copy-on-write overlay of a shared lower directory, with private upper directories created
+    /// under a base directory.
+    Overlay { lower: PathBuf, upper: PathBuf },
+}
+
+///
+/// # Description
+///
+/// Template from which the backends of environments are instantiated.
+///
+enum Template {
+    /// Host filesystem.
+    Host,
+    /// In-memory filesystem, copied for each environment.
+    Memory(MemoryFileSystem),
+    /// Overlay filesystem, with an upper layer of its own for each environment.
+    Overlay { lower: PathBuf, upper: PathBuf },
+}
+
+///
+/// # Description
+///
+/// Filesystem backends of all virtual environments. Backends are instantiated the first time an
+/// environment uses them, and processes that have not joined an environment share one of their
+/// own.
+///
+pub struct FileSystems {
+    /// Canonical host path of the root of virtual environments.
+    root: PathBuf,
+    /// Template for environments without a specific one.
+    default: Template,
+    /// Templates for specific environments.
+    envs: HashMap<VirtualEnvironmentIdentifier, Template>,
+    /// Resource limits, keyed by environment identifier.
+    limits: Vec<(Option<u32>, Resource, u64)>,
+    /// Instantiated backends.
+    instances: HashMap<Option<VirtualEnvironmentIdentifier>, QuotaFileSystem>,
+}
+
+//==================================================================================================
+// Implementations
+//==================================================================================================
+
+impl BackendKind {
+    ///
+    /// # Description
+    ///
+    /// Parses a backend kind.
+    ///
+    /// # Parameters
+    ///
+    /// - `spec`: Specification of the backend.
+    ///
+    /// # Returns
+    ///
+    /// The backend kind, or `None` if the specification is invalid.
+    ///
+    pub fn parse(spec: &str) -> Option<Self> {
+        match spec.split_once(':') {
+            None if spec == "host" => Some(Self::Host),
+            None if spec == "memory" => Some(Self::Memory(None)),
+            Some(("memory", snapshot)) if !snapshot.is_empty() => {
+                Some(Self::Memory(Some(PathBuf::from(snapshot))))
+            },
+            Some(("overlay", layers)) => match layers.split_once(':') {
+                Some((lower, upper)) if !lower.is_empty() && !upper.is_empty() => {
+                    Some(Self::Overlay {
+                        lower: PathBuf::from(lower),
+                        upper: PathBuf::from(upper),
+                    })
+                },
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+impl Template {
+    fn load(kind: &BackendKind) -> Result<Self> {
+        match kind {
+            BackendKind::Host => Ok(Self::Host),
+            BackendKind::Memory(None) => Ok(Self::Memory(MemoryFileSystem::new())),
+            BackendKind::Memory(Some(snapshot)) => {
+                Ok(Self::Memory(MemoryFileSystem::from_snapshot(snapshot)?))
+            },
+            BackendKind::Overlay { lower, upper } => {
+                let lower: PathBuf = lower.canonicalize()?;
+                if !lower.is_dir() {
+                    anyhow::bail!("lower layer is not a directory: {:?}", lower);
+                }
+                fs::create_dir_all(upper)?;
+                let upper: PathBuf = upper.canonicalize()?;
+                // Upper layers must not be reachable through the lower layer.
+                if upper.starts_with(&lower) || lower.starts_with(&upper) {
+                    anyhow::bail!("overlapping layers: {:?}, {:?}", lower, upper);
+                }
+                Ok(Self::Overlay { lower, upper })
+            },
+        }
+    }
+
+    fn duplicate(&self) -> Self {
+        match self {
+            Self::Host => Self::Host,
+            Self::Memory(template) => Self::Memory(template.duplicate()),
+            Self::Overlay { lower, upper } => Self::Overlay {
+                lower: lower.clone(),
+                upper: upper.clone(),
+            },
+        }
+    }
+
+    fn instantiate(
+        &self,
+        root: &Path,
+        env: Option<VirtualEnvironmentIdentifier>,
+    ) -> Result<Box<dyn FileSystem>> {
+        match self {
+            Self::Host => Ok(Box::new(HostFileSystem::new(root.to_path_buf()))),
+            Self::Memory(template) => Ok(Box::new(template.duplicate())),
+            Self::Overlay { lower, upper } => {
+                let name: String = match env {
+                    Some(env) => env.id().to_string(),
+                    None => String::from("default"),
+                };
+                Ok(Box::new(OverlayFileSystem::new(lower.clone(), upper.join(name))?))
+            },
+        }
+    }
+}
+
+impl FileSystems {
+    ///
+    /// # Description
+    ///
+    /// Creates the filesystem backends of all virtual environments.
+    ///
+    /// # Parameters
+    ///
+    /// - `rules`: Backend kinds, keyed by environment identifier. Rules without an environment
+    ///   identifier apply to all environments without a specific rule.
+    /// - `limits`: Resource limits, keyed by environment identifier.
+    /// - `root`: Canonical host path of the root of virtual environments.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the filesystem backends are returned. Upon failure, an error is returned
+    /// instead, for instance if a snapshot cannot be loaded.
+    ///
+    pub fn new(
+        rules: &[(Option<u32>, BackendKind)],
+        limits: &[(Option<u32>, Resource, u64)],
+        root: PathBuf,
+    ) -> Result<Self> {
+        let mut backends: Self = Self {
+            root,
+            default: Template::Host,
+            envs: HashMap::new(),
+            limits: limits.to_vec(),
+            instances: HashMap::new(),
+        };
+
+        // Snapshots are loaded once, and copied whenever an environment needs a fresh backend.
+        for (env, kind) in rules {
+            match env {
+                Some(env) => {
+                    info!("selecting filesystem backend (env={:?}, kind={:?})", env, kind);
+                    backends
+                        .envs
+                        .insert(VirtualEnvironmentIdentifier::from(*env), Template::load(kind)?);
+                },
+                None => {
+                    info!("selecting filesystem backend (kind={:?})", kind);
+                    backends.default = Template::load(kind)?;
+                },
+            }
+        }
+
+        Ok(backends)
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Creates backends from the same templates, none of which is instantiated yet, so that
+    /// files do not carry over from one connection to the next.
+    ///
+    /// # Returns
+    ///
+    /// The new backends.
+    ///
+    pub fn duplicate(&self) -> Self {
+        Self {
+            root: self.root.clone(),
+            default: self.default.duplicate(),
+            envs: self
+                .envs
+                .iter()
+                .map(|(env, template)| (*env, template.duplicate()))
+                .collect(),
+            limits: self.limits.clone(),
+            instances: HashMap::new(),
+        }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Checks whether an environment uses the host filesystem backend.
+    ///
+    /// # Parameters
+    ///
+    /// - `env`: Environment of the process, if any.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the environment uses the host filesystem, `false` otherwise.
+    ///
+    pub fn is_host(&self, env: Option<VirtualEnvironmentIdentifier>) -> bool {
+        matches!(self.template(env), Template::Host)
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Instantiates the filesystem backend of an environment, unless it is already instantiated.
+    ///
+    /// # Parameters
+    ///
+    /// - `env`: Environment of the process, if any.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, empty is returned. Upon failure, a host error number is returned instead, and
+    /// the environment is left without a backend.
+    ///
+    pub fn instantiate(&mut self, env: Option<VirtualEnvironmentIdentifier>) -> Result<(), i32> {
+        if self.instances.contains_key(&env) {
+            return Ok(());
+        }
+
+        debug!("instantiating filesystem backend (env={:?})", env);
+        let backend: Box<dyn FileSystem> = match self.template(env).instantiate(&self.root, env) {
+            Ok(backend) => backend,
+            Err(e) => {
+                error!("failed to instantiate filesystem backend (env={:?}, error={:?})", env, e);
+                return Err(libc::EIO);
+            },
+        };
+
+        let limits: ResourceLimits = ResourceLimits::select(&self.limits, env);
+        self.instances
+            .insert(env, QuotaFileSystem::new(backend, limits));
+
+        Ok(())
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the filesystem backend of an environment.
+    ///
+    /// # Parameters
+    ///
+    /// - `env`: Environment of the process, if any.
+    ///
+    /// # Returns
+    ///
+    /// The filesystem backend of the target environment, which enforces its resource limits.
+    ///
+    /// # Panics
+    ///
+    /// The backend must have been instantiated with [`FileSystems::instantiate`] beforehand.
+    ///
+    pub fn get(&mut self, env: Option<VirtualEnvironmentIdentifier>) -> &mut dyn FileSystem {
+        self.instances
+            .get_mut(&env)
+            .expect("filesystem backend is not instantiated")
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the resource usage of the filesystem backend of an environment.
+    ///
+    /// # Parameters
+    ///
+    /// - `env`: Environment of the process, if any.
+    ///
+    /// # Returns
+    ///
+    /// The resource usage of the target environment. Environments that have not used their
+    /// backend yet have no usage.
+    ///
+    pub fn usage(&self, env: Option<VirtualEnvironmentIdentifier>) -> ResourceUsage {
+        self.instances
+            .get(&env)
+            .map(QuotaFileSystem::usage)
+            .unwrap_or_default()
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Drops the filesystem backend of an environment that no longer exists, discarding any
+    /// private state. The next process that uses the environment starts afresh.
+    ///
+    /// # Parameters
+    ///
+    /// - `env`: Target environment.
+    ///
+    pub fn release(&mut self, env: VirtualEnvironmentIdentifier) {
+        if self.instances.remove(&Some(env)).is_some() {
+            debug!("released filesystem backend (env={:?})", env);
+        }
+    }
+
+    fn template(&self, env: Option<VirtualEnvironmentIdentifier>) -> &Template {
+        env.and_then(|env| self.envs.get(&env))
+            .unwrap_or(&self.default)
+    }
+}
@@ -0,0 +1,784 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::backend::{
+    FileSystem,
+    HostFileSystem,
+};
+use ::anyhow::Result;
+use ::linuxd::{
+    fcntl,
+    sys::{
+        stat::stat,
+        types::{
+            mode_t,
+            off_t,
+        },
+    },
+};
+use ::std::{
+    collections::{
+        HashMap,
+        VecDeque,
+    },
+    ffi::OsString,
+    fs,
+    io,
+    os::unix::fs as unix_fs,
+    path::{
+        Component,
+        Path,
+        PathBuf,
+    },
+};
+
+//==================================================================================================
+// Constants
+//==================================================================================================
+
+/// Prefix of whiteouts, which hide files of the lower layer.
+const WHITEOUT_PREFIX: &str = ".wh.";
+
+/// Marker of opaque directories, whose counterpart in the lower layer is hidden.
+const OPAQUE_MARKER: &str = ".wh..wh..opq";
+
+/// Maximum number of symbolic links followed while resolving a path.
+const SYMLOOP_MAX: usize = 40;
+
+//==================================================================================================
+// Structures
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Layer in which a file lives.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Layer {
+    /// Private, writable layer.
+    Upper,
+    /// Shared, read-only layer.
+    Lower,
+}
+
+///
+/// # Description
+///
+/// Filesystem backend that merges a shared, read-only lower directory with a private, writable
+/// upper directory. Reads fall through to the lower layer, while files are copied up to the upper
+/// layer as soon as they are modified. Removed files of the lower layer are hidden by whiteouts.
+///
+/// File descriptors handed out to guests are host file descriptors, of either layer. The upper
+/// layer is discarded when the backend is dropped.
+///
+#[derive(Debug)]
+pub struct OverlayFileSystem {
+    /// Canonical host path of the lower layer.
+    lower: PathBuf,
+    /// Canonical host path of the upper layer.
+    upper: PathBuf,
+    /// Backend that serves operations on file descriptors.
+    host: HostFileSystem,
+    /// Paths of open files, relative to the root of the overlay.
+    files: HashMap<i32, PathBuf>,
+}
+
+//==================================================================================================
+// Implementations
+//==================================================================================================
+
+impl OverlayFileSystem {
+    ///
+    /// # Description
+    ///
+    /// Creates an overlay filesystem with an empty upper layer. Leftovers of a previous upper layer
+    /// at the same path are discarded.
+    ///
+    /// # Parameters
+    ///
+    /// - `lower`: Canonical host path of the lower layer.
+    /// - `upper`: Host path of the upper layer.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the overlay filesystem is returned. Upon failure, an error is returned
+    /// instead, for instance if a stale upper layer cannot be discarded.
+    ///
+    pub fn new(lower: PathBuf, upper: PathBuf) -> Result<Self> {
+        if upper.exists() {
+            warn!("discarding stale upper layer (path={:?})", upper);
+            if let Err(e) = fs::remove_dir_all(&upper) {
+                anyhow::bail!("failed to discard upper layer (path={:?}, error={:?})", upper, e);
+            }
+        }
+        if let Err(e) = fs::create_dir_all(&upper) {
+            anyhow::bail!("failed to create upper layer (path={:?}, error={:?})", upper, e);
+        }
+
+        Ok(Self {
+            host: HostFileSystem::new(upper.clone()),
+            lower,
+            upper,
+            files: HashMap::new(),
+        })
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Lexically normalizes a path into a path relative to the root of the overlay.
+    ///
+    /// # Parameters
+    ///
+    /// - `dirfd`: Directory from which relative paths are resolved.
+    /// - `path`: Path to normalize.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the normalized path is returned. Upon failure, the error number is returned
+    /// instead.
+    ///
+    fn normalize(&mut self, dirfd: i32, path: &str) -> Result<PathBuf, i32> {
+        if path.is_empty() {
+            return Err(libc::ENOENT);
+        }
+
+        let mut rel: PathBuf = if path.starts_with('/') || dirfd == fcntl::AT_FDCWD {
+            PathBuf::new()
+        } else {
+            let dir: PathBuf = self.files.get(&dirfd).cloned().ok_or(libc::EBADF)?;
+            if (self.host.fstat(dirfd)?.st_mode & libc::S_IFMT) != libc::S_IFDIR {
+                return Err(libc::ENOTDIR);
+            }
+            dir
+        };
+
+        for component in Path::new(path).components() {
+            match component {
+                Component::ParentDir => {
+                    rel.pop();
+                },
+                Component::Normal(name) => {
+                    // Guests must not tamper with whiteouts.
+                    if name.to_string_lossy().starts_with(WHITEOUT_PREFIX) {
+                        return Err(libc::EINVAL);
+                    }
+                    rel.push(name);
+                },
+                _ => {},
+            }
+        }
+
+        Ok(rel)
+    }
+
+    fn whiteout_path(&self, rel: &Path) -> Option<PathBuf> {
+        let name: &str = rel.file_name()?.to_str()?;
+        let parent: &Path = rel.parent()?;
+        Some(
+            self.upper
+                .join(parent)
+                .join(format!("{}{}", WHITEOUT_PREFIX, name)),
+        )
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Checks whether a path of the lower layer is hidden, either by a whiteout or by an opaque
+    /// directory.
+    ///
+    fn is_hidden(&self, rel: &Path) -> bool {
+        rel.ancestors()
+            .filter(|prefix| !prefix.as_os_str().is_empty())
+            .any(|prefix| {
+                let whiteout: bool = self.whiteout_path(prefix).is_some_and(|path| exists(&path));
+                let opaque: bool =
+                    prefix != rel && exists(&self.upper.join(prefix).join(OPAQUE_MARKER));
+                whiteout || opaque
+            })
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Finds the layer in which a file lives.
+    ///
+    fn locate(&self, rel: &Path) -> Result<Layer, i32> {
+        if exists(&self.upper.join(rel)) {
+            Ok(Layer::Upper)
+        } else if !self.is_hidden(rel) && exists(&self.lower.join(rel)) {
+            Ok(Layer::Lower)
+        } else {
+            Err(libc::ENOENT)
+        }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the host path of a file, in the layer in which it lives.
+    ///
+    fn host_path(&self, rel: &Path) -> Result<PathBuf, i32> {
+        Ok(match self.locate(rel)? {
+            Layer::Upper => self.upper.join(rel),
+            Layer::Lower => self.lower.join(rel),
+        })
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Copies a file up to the upper layer, along with its parent directories. The contents of
+    /// directories are not copied.
+    ///
+    fn copy_up(&self, rel: &Path) -> Result<(), i32> {
+        if self.locate(rel)? == Layer::Upper {
+            return Ok(());
+        }
+        if let Some(parent) = rel.parent() {
+            self.copy_up(parent)?;
+        }
+
+        let lower: PathBuf = self.lower.join(rel);
+        let upper: PathBuf = self.upper.join(rel);
+        let metadata: fs::Metadata = fs::symlink_metadata(&lower).map_err(to_errno)?;
+        debug!("copy_up(): path={:?}", rel);
+
+        if metadata.is_dir() {
+            fs::create_dir(&upper).map_err(to_errno)?;
+            fs::set_permissions(&upper, metadata.permissions()).map_err(to_errno)?;
+        } else if metadata.is_symlink() {
+            unix_fs::symlink(fs::read_link(&lower).map_err(to_errno)?, &upper).map_err(to_errno)?;
+        } else if metadata.is_file() {
+            fs::copy(&lower, &upper).map_err(to_errno)?;
+        } else {
+            return Err(libc::EPERM);
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Copies a file up to the upper layer, along with the whole tree below it.
+    ///
+    fn copy_up_tree(&self, rel: &Path) -> Result<(), i32> {
+        self.copy_up(rel)?;
+
+        let lower: PathBuf = self.lower.join(rel);
+        if !fs::symlink_metadata(&lower).is_ok_and(|m| m.is_dir()) || self.is_opaque(rel) {
+            return Ok(());
+        }
+
+        for entry in fs::read_dir(&lower).map_err(to_errno)? {
+            let child: PathBuf = rel.join(entry.map_err(to_errno)?.file_name());
+            if self.locate(&child) == Ok(Layer::Lower) {
+                self.copy_up_tree(&child)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Prepares the upper layer for the creation of a new file: the parent directory is copied
+    /// up, and any whiteout that hides the name is removed.
+    ///
+    fn prepare(&self, rel: &Path) -> Result<(), i32> {
+        let parent: &Path = rel.parent().ok_or(libc::EEXIST)?;
+        let parent_host: PathBuf = self.host_path(parent)?;
+        if !fs::metadata(parent_host).map_err(to_errno)?.is_dir() {
+            return Err(libc::ENOTDIR);
+        }
+        self.copy_up(parent)?;
+
+        if let Some(whiteout) = self.whiteout_path(rel) {
+            if exists(&whiteout) {
+                fs::remove_file(whiteout).map_err(to_errno)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Hides a file of the lower layer, if any, once it was removed from the overlay.
+    ///
+    fn whiteout(&self, rel: &Path) -> Result<(), i32> {
+        if self.is_hidden(rel) || !exists(&self.lower.join(rel)) {
+            return Ok(());
+        }
+
+        if let Some(parent) = rel.parent() {
+            self.copy_up(parent)?;
+        }
+        if let Some(whiteout) = self.whiteout_path(rel) {
+            debug!("whiteout(): path={:?}", rel);
+            fs::File::create(whiteout).map_err(to_errno)?;
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Hides the lower counterpart of a directory of the upper layer, if any.
+    ///
+    fn make_opaque(&self, rel: &Path) -> Result<(), i32> {
+        let upper: PathBuf = self.upper.join(rel);
+        if fs::symlink_metadata(&upper).is_ok_and(|m| m.is_dir()) && exists(&self.lower.join(rel)) {
+            fs::File::create(upper.join(OPAQUE_MARKER)).map_err(to_errno)?;
+        }
+
+        Ok(())
+    }
+
+    fn is_opaque(&self, rel: &Path) -> bool {
+        exists(&self.upper.join(rel).join(OPAQUE_MARKER))
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Checks whether a directory of the overlay has no entries.
+    ///
+    fn is_empty_dir(&self, rel: &Path) -> Result<bool, i32> {
+        let upper: PathBuf = self.upper.join(rel);
+        if let Ok(entries) = fs::read_dir(&upper) {
+            for entry in entries {
+                let name: OsString = entry.map_err(to_errno)?.file_name();
+                if !name.to_string_lossy().starts_with(WHITEOUT_PREFIX) {
+                    return Ok(false);
+                }
+            }
+        }
+
+        let lower: PathBuf = self.lower.join(rel);
+        if self.is_opaque(rel) || self.is_hidden(rel) {
+            return Ok(true);
+        }
+        if let Ok(entries) = fs::read_dir(&lower) {
+            for entry in entries {
+                let child: PathBuf = rel.join(entry.map_err(to_errno)?.file_name());
+                if self.locate(&child).is_ok() {
+                    return Ok(false);
+                }
+            }
+        }
+
+        Ok(true)
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Removes a directory of the upper layer, along with its whiteouts.
+    ///
+    fn remove_upper_dir(&self, rel: &Path) -> Result<(), i32> {
+        let upper: PathBuf = self.upper.join(rel);
+        for entry in fs::read_dir(&upper).map_err(to_errno)? {
+            fs::remove_file(entry.map_err(to_errno)?.path()).map_err(to_errno)?;
+        }
+        fs::remove_dir(&upper).map_err(to_errno)
+    }
+
+    fn is_dir(&self, rel: &Path) -> Result<bool, i32> {
+        let metadata: fs::Metadata =
+            fs::symlink_metadata(self.host_path(rel)?).map_err(to_errno)?;
+        Ok(metadata.is_dir())
+    }
+}
+
+impl FileSystem for OverlayFileSystem {
+    fn open_at(&mut self, dirfd: i32, path: &str, flags: i32, mode: mode_t) -> Result<i32, i32> {
+        let rel: PathBuf = self.normalize(dirfd, path)?;
+        let creat: bool = (flags & fcntl::O_CREAT) != 0;
+        let writes: bool =
+            (flags & (fcntl::O_WRONLY | fcntl::O_RDWR | fcntl::O_TRUNC)) != 0 || creat;
+
+        let host: PathBuf = match self.locate(&rel) {
+            Ok(_) if creat && (flags & fcntl::O_EXCL) != 0 => return Err(libc::EEXIST),
+            Ok(Layer::Lower) if !writes => self.lower.join(&rel),
+            Ok(_) => {
+                self.copy_up(&rel)?;
+                self.upper.join(&rel)
+            },
+            Err(libc::ENOENT) if creat => {
+                self.prepare(&rel)?;
+                self.upper.join(&rel)
+            },
+            Err(errno) => return Err(errno),
+        };
+
+        let fd: i32 = self
+            .host
+            .open_at(fcntl::AT_FDCWD, to_str(&host)?, flags, mode)?;
+        self.files.insert(fd, rel);
+
+        Ok(fd)
+    }
+
+    fn close(&mut self, fd: i32) -> Result<(), i32> {
+        self.host.close(fd)?;
+        self.files.remove(&fd);
+        Ok(())
+    }
+
+    fn read(&mut self, fd: i32, buffer: &mut [u8]) -> Result<usize, i32> {
+        self.host.read(fd, buffer)
+    }
+
+    fn write(&mut self, fd: i32, buffer: &[u8]) -> Result<usize, i32> {
+        self.host.write(fd, buffer)
+    }
+
+    fn pread(&mut self, fd: i32, buffer: &mut [u8], offset: off_t) -> Result<usize, i32> {
+        self.host.pread(fd, buffer, offset)
+    }
+
+    fn pwrite(&mut self, fd: i32, buffer: &[u8], offset: off_t) -> Result<usize, i32> {
+        self.host.pwrite(fd, buffer, offset)
+    }
+
+    fn lseek(&mut self, fd: i32, offset: off_t, whence: i32) -> Result<off_t, i32> {
+        self.host.lseek(fd, offset, whence)
+    }
+
+    fn fsync(&mut self, fd: i32) -> Result<(), i32> {
+        self.host.fsync(fd)
+    }
+
+    fn fdatasync(&mut self, fd: i32) -> Result<(), i32> {
+        self.host.fdatasync(fd)
+    }
+
+    fn ftruncate(&mut self, fd: i32, length: off_t) -> Result<(), i32> {
+        self.host.ftruncate(fd, length)
+    }
+
+    fn truncate(&mut self, path: &str, length: off_t) -> Result<(), i32> {
+        let rel: PathBuf = self.normalize(fcntl::AT_FDCWD, path)?;
+        self.copy_up(&rel)?;
+        self.host.truncate(to_str(&self.upper.join(&rel))?, length)
+    }
+
+    fn fallocate(&mut self, fd: i32, offset: off_t, len: off_t) -> Result<(), i32> {
+        self.host.fallocate(fd, offset, len)
+    }
+
+    fn fadvise(&mut self, fd: i32, offset: off_t, len: off_t, advice: i32) -> Result<(), i32> {
+        self.host.fadvise(fd, offset, len, advice)
+    }
+
+    fn fstat(&mut self, fd: i32) -> Result<stat, i32> {
+        self.host.fstat(fd)
+    }
+
+    fn fstat_at(&mut self, dirfd: i32, path: &str, flag: i32) -> Result<stat, i32> {
+        // File descriptors are host file descriptors already.
+        if path.is_empty() && (flag & libc::AT_EMPTY_PATH) != 0 {
+            return self.host.fstat_at(dirfd, path, flag);
+        }
+
+        let rel: PathBuf = self.normalize(dirfd, path)?;
+        let host: PathBuf = self.host_path(&rel)?;
+        self.host.fstat_at(fcntl::AT_FDCWD, to_str(&host)?, flag)
+    }
+
+    fn unlink_at(&mut self, dirfd: i32, path: &str, flags: i32) -> Result<(), i32> {
+        let rel: PathBuf = self.normalize(dirfd, path)?;
+        if rel.as_os_str().is_empty() {
+            return Err(libc::EBUSY);
+        }
+
+        let layer: Layer = self.locate(&rel)?;
+        let is_dir: bool = self.is_dir(&rel)?;
+        if (flags & fcntl::AT_REMOVEDIR) != 0 {
+            if !is_dir {
+                return Err(libc::ENOTDIR);
+            }
+            if !self.is_empty_dir(&rel)? {
+                return Err(libc::ENOTEMPTY);
+            }
+        } else if is_dir {
+            return Err(libc::EISDIR);
+        }
+
+        if layer == Layer::Upper {
+            let upper: PathBuf = self.upper.join(&rel);
+            if is_dir {
+                self.remove_upper_dir(&rel)?;
+            } else {
+                fs::remove_file(upper).map_err(to_errno)?;
+            }
+        }
+
+        self.whiteout(&rel)
+    }
+
+    fn rename_at(
+        &mut self,
+        olddirfd: i32,
+        oldpath: &str,
+        newdirfd: i32,
+        newpath: &str,
+        flags: u32,
+    ) -> Result<(), i32> {
+        let noreplace: bool = (flags & fcntl::RENAME_NOREPLACE) != 0;
+        let exchange: bool = (flags & fcntl::RENAME_EXCHANGE) != 0;
+        if noreplace && exchange {
+            return Err(libc::EINVAL);
+        }
+
+        let old: PathBuf = self.normalize(olddirfd, oldpath)?;
+        let new: PathBuf = self.normalize(newdirfd, newpath)?;
+        if old.as_os_str().is_empty() || new.as_os_str().is_empty() {
+            return Err(libc::EBUSY);
+        }
+
+        let old_is_dir: bool = self.is_dir(&old)?;
+        let new_is_dir: Option<bool> = match self.is_dir(&new) {
+            Ok(is_dir) => Some(is_dir),
+            Err(libc::ENOENT) => None,
+            Err(errno) => return Err(errno),
+        };
+
+        // Directories cannot be moved into themselves.
+        if (old_is_dir && new != old && new.starts_with(&old))
+            || (exchange && new_is_dir == Some(true) && old != new && old.starts_with(&new))
+        {
+            return Err(libc::EINVAL);
+        }
+
+        match new_is_dir {
+            Some(_) if noreplace => return Err(libc::EEXIST),
+            None if exchange => return Err(libc::ENOENT),
+            Some(_) if old == new => return Ok(()),
+            Some(true) if !exchange && !old_is_dir => return Err(libc::EISDIR),
+            Some(false) if !exchange && old_is_dir => return Err(libc::ENOTDIR),
+            Some(true) if !exchange && !self.is_empty_dir(&new)? => return Err(libc::ENOTEMPTY),
+            _ => {},
+        }
+
+        // Directories are moved as a whole, since their lower counterparts stay behind.
+        self.copy_up_tree(&old)?;
+        if exchange {
+            self.copy_up_tree(&new)?;
+        } else {
+            self.prepare(&new)?;
+            if new_is_dir == Some(true) && self.locate(&new) == Ok(Layer::Upper) {
+                self.remove_upper_dir(&new)?;
+            }
+        }
+
+        let old_upper: PathBuf = self.upper.join(&old);
+        let new_upper: PathBuf = self.upper.join(&new);
+        self.host.rename_at(
+            fcntl::AT_FDCWD,
+            to_str(&old_upper)?,
+            fcntl::AT_FDCWD,
+            to_str(&new_upper)?,
+            flags,
+        )?;
+
+        if !exchange {
+            self.whiteout(&old)?;
+        }
+        self.make_opaque(&old)?;
+        self.make_opaque(&new)
+    }
+
+    fn link_at(
+        &mut self,
+        olddirfd: i32,
+        oldpath: &str,
+        newdirfd: i32,
+        newpath: &str,
+        flags: i32,
+    ) -> Result<(), i32> {
+        let old: PathBuf = self.normalize(olddirfd, oldpath)?;
+        let new: PathBuf = self.normalize(newdirfd, newpath)?;
+        if self.locate(&new).is_ok() {
+            return Err(libc::EEXIST);
+        }
+
+        self.copy_up(&old)?;
+        self.prepare(&new)?;
+
+        let old_upper: PathBuf = self.upper.join(&old);
+        let new_upper: PathBuf = self.upper.join(&new);
+        self.host.link_at(
+            fcntl::AT_FDCWD,
+            to_str(&old_upper)?,
+            fcntl::AT_FDCWD,
+            to_str(&new_upper)?,
+            flags,
+        )
+    }
+
+    fn symlink_at(&mut self, target: &str, dirfd: i32, linkpath: &str) -> Result<(), i32> {
+        let rel: PathBuf = self.normalize(dirfd, linkpath)?;
+        if self.locate(&rel).is_ok() {
+            return Err(libc::EEXIST);
+        }
+
+        self.prepare(&rel)?;
+        self.host
+            .symlink_at(target, fcntl::AT_FDCWD, to_str(&self.upper.join(&rel))?)
+    }
+
+    fn readlink_at(&mut self, dirfd: i32, path: &str, buffer: &mut [u8]) -> Result<usize, i32> {
+        let rel: PathBuf = self.normalize(dirfd, path)?;
+        let host: PathBuf = self.host_path(&rel)?;
+        self.host
+            .readlink_at(fcntl::AT_FDCWD, to_str(&host)?, buffer)
+    }
+
+    fn copy_file_range(
+        &mut self,
+        fd_in: i32,
+        off_in: Option<&mut off_t>,
+        fd_out: i32,
+        off_out: Option<&mut off_t>,
+        len: usize,
+        flags: u32,
+    ) -> Result<usize, i32> {
+        self.host
+            .copy_file_range(fd_in, off_in, fd_out, off_out, len, flags)
+    }
+
+    fn sendfile(
+        &mut self,
+        out_fd: i32,
+        in_fd: i32,
+        offset: Option<&mut off_t>,
+        count: usize,
+    ) -> Result<usize, i32> {
+        self.host.sendfile(out_fd, in_fd, offset, count)
+    }
+
+    fn access_mode(&mut self, fd: i32) -> Result<i32, i32> {
+        self.host.access_mode(fd)
+    }
+
+    fn realpath(&mut self, path: &str) -> Result<String, i32> {
+        if path.is_empty() {
+            return Err(libc::ENOENT);
+        }
+
+        // Symbolic links are resolved within the merged view, so they never escape the overlay.
+        let mut resolved: PathBuf = PathBuf::new();
+        let mut pending: VecDeque<OsString> = components(Path::new(path));
+        let mut links: usize = 0;
+        while let Some(name) = pending.pop_front() {
+            if name == ".." {
+                resolved.pop();
+                continue;
+            }
+            if name.to_string_lossy().starts_with(WHITEOUT_PREFIX) {
+                return Err(libc::ENOENT);
+            }
+
+            let candidate: PathBuf = resolved.join(&name);
+            let host: PathBuf = self.host_path(&candidate)?;
+            let metadata: fs::Metadata = fs::symlink_metadata(&host).map_err(to_errno)?;
+            if metadata.is_symlink() {
+                links += 1;
+                if links > SYMLOOP_MAX {
+                    return Err(libc::ELOOP);
+                }
+                let target: PathBuf = fs::read_link(&host).map_err(to_errno)?;
+                if target.is_absolute() {
+                    resolved = PathBuf::new();
+                }
+                for component in components(&target).into_iter().rev() {
+                    pending.push_front(component);
+                }
+            } else if !metadata.is_dir() && !pending.is_empty() {
+                return Err(libc::ENOTDIR);
+            } else {
+                resolved = candidate;
+            }
+        }
+
+        Path::new("/")
+            .join(resolved)
+            .into_os_string()
+            .into_string()
+            .map_err(|_| libc::EINVAL)
+    }
+}
+
+impl Drop for OverlayFileSystem {
+    fn drop(&mut self) {
+        // Files left open refer to the upper layer, which is about to vanish.
+        for fd in self.files.keys() {
+            unsafe { libc::close(*fd) };
+        }
+
+        info!("discarding upper layer (path={:?})", self.upper);
+        if let Err(e) = fs::remove_dir_all(&self.upper) {
+            error!("failed to discard upper layer (path={:?}, error={:?})", self.upper, e);
+        }
+    }
+}
+
+//==================================================================================================
+// Standalone Functions
+//==================================================================================================
+
+fn exists(path: &Path) -> bool {
+    fs::symlink_metadata(path).is_ok()
+}
+
+fn to_errno(e: io::Error) -> i32 {
+    e.raw_os_error().unwrap_or(libc::EIO)
+}
+
+fn to_str(path: &Path) -> Result<&str, i32> {
+    path.to_str().ok_or(libc::EINVAL)
+}
+
+fn components(path: &Path) -> VecDeque<OsString> {
+    path.components()
+        .filter_map(|component| match component {
+            Component::ParentDir => Some(OsString::from("..")),
+            Component::Normal(name) => Some(name.to_os_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+//==================================================================================================
+// Unit Tests
+//==================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::tempfile::TempDir;
+
+    #[test]
+    fn new_fails_without_upper_layer() {
+        let dir: TempDir = TempDir::new().expect("failed to create directory");
+        let lower: PathBuf = dir.path().join("lower");
+        let file: PathBuf = dir.path().join("file");
+        fs::create_dir(&lower).expect("failed to create lower layer");
+        fs::write(&file, b"").expect("failed to create file");
+
+        // The upper layer cannot be created under a regular file.
+        assert!(OverlayFileSystem::new(lower, file.join("upper")).is_err());
+    }
+}
@@ -0,0 +1,525 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::{
+    backend::FileSystem,
+    resource::{
+        ResourceLimits,
+        ResourceUsage,
+    },
+};
+use ::linuxd::{
+    fcntl,
+    sys::{
+        stat::stat,
+        types::{
+            mode_t,
+            off_t,
+        },
+    },
+    unistd,
+};
+use ::std::collections::BTreeSet;
+
+//==================================================================================================
+// Structures
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Filesystem backend that enforces the resource limits of an environment on top of another
+/// backend.
+///
+/// Disk usage only accounts for the growth of files caused by the environment itself: writes and
+/// resizes that grow a file count against it, while shrinking or removing files gives space back.
+///
+pub struct QuotaFileSystem {
+    /// Underlying backend.
+    inner: Box<dyn FileSystem>,
+    /// Resource limits.
+    limits: ResourceLimits,
+    /// Resource usage.
+    usage: ResourceUsage,
+    /// Open file descriptors.
+    files: BTreeSet<i32>,
+    /// Open file descriptors whose writes land at the end of the file.
+    appends: BTreeSet<i32>,
+}
+
+//==================================================================================================
+// Implementations
+//==================================================================================================
+
+impl QuotaFileSystem {
+    pub fn new(inner: Box<dyn FileSystem>, limits: ResourceLimits) -> Self {
+        Self {
+            inner,
+            limits,
+            usage: ResourceUsage::default(),
+            files: BTreeSet::new(),
+            appends: BTreeSet::new(),
+        }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the resource usage of the environment.
+    ///
+    pub fn usage(&self) -> ResourceUsage {
+        ResourceUsage {
+            open_files: self.files.len() as u64,
+            ..self.usage
+        }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Checks whether writing a number of bytes is within limits.
+    ///
+    fn check_write(&self, count: usize) -> Result<(), i32> {
+        match self.limits.bytes_written {
+            Some(max) if self.usage.bytes_written.saturating_add(count as u64) > max => {
+                debug!("check_write(): quota exceeded (written={:?})", self.usage.bytes_written);
+                Err(libc::EDQUOT)
+            },
+            _ => Ok(()),
+        }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Checks whether a file may grow up to a given size.
+    ///
+    /// # Parameters
+    ///
+    /// - `size`: Current size of the file.
+    /// - `end`: Size of the file once the operation completes, if it grows.
+    ///
+    fn check_growth(&self, size: off_t, end: off_t) -> Result<(), i32> {
+        if let Some(max) = self.limits.file_size {
+            if end > 0 && end as u64 > max {
+                return Err(libc::EFBIG);
+            }
+        }
+
+        if let Some(max) = self.limits.disk_usage {
+            let growth: u64 = end.saturating_sub(size).max(0) as u64;
+            if self.usage.disk_usage.saturating_add(growth) > max {
+                debug!("check_growth(): quota exceeded (usage={:?})", self.usage.disk_usage);
+                return Err(libc::EDQUOT);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn account_growth(&mut self, before: off_t, after: off_t) {
+        let delta: i64 = after.saturating_sub(before);
+        self.usage.disk_usage = self.usage.disk_usage.saturating_add_signed(delta);
+    }
+
+    fn account_write(&mut self, count: usize) {
+        self.usage.bytes_written = self.usage.bytes_written.saturating_add(count as u64);
+    }
+
+    fn size(&mut self, fd: i32) -> Result<off_t, i32> {
+        Ok(self.inner.fstat(fd)?.st_size)
+    }
+
+    fn offset(&mut self, fd: i32, offset: Option<&off_t>) -> Result<off_t, i32> {
+        match offset {
+            Some(offset) => Ok(*offset),
+            None => self.inner.lseek(fd, 0, unistd::SEEK_CUR),
+        }
+    }
+
+    // Get the offset at which a write on a file lands, which is the end of the file in append mode.
+    fn write_offset(&mut self, fd: i32, offset: Option<&off_t>) -> Result<off_t, i32> {
+        match self.appends.contains(&fd) {
+            true => self.size(fd),
+            false => self.offset(fd, offset),
+        }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Clamps the length of a transfer between files to what the source has left and to what
+    /// limits still allow, so that transfers up to the end of the source succeed in part.
+    ///
+    /// # Parameters
+    ///
+    /// - `fd_in`: Source file.
+    /// - `off_in`: Offset in the source file, if not its current offset.
+    /// - `fd_out`: Target file.
+    /// - `start`: Offset at which the transfer lands in the target file.
+    /// - `len`: Requested length.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the length that may be transferred is returned, which is zero at the end of
+    /// the source. Upon failure, the error number of the first limit that leaves no room at all is
+    /// returned instead.
+    ///
+    fn clamp_transfer(
+        &mut self,
+        fd_in: i32,
+        off_in: Option<&off_t>,
+        fd_out: i32,
+        start: off_t,
+        len: usize,
+    ) -> Result<usize, i32> {
+        let available: off_t = self
+            .size(fd_in)?
+            .saturating_sub(self.offset(fd_in, off_in)?);
+        let mut len: u64 = (len as u64).min(available.max(0) as u64);
+        if len == 0 {
+            return Ok(0);
+        }
+
+        let mut rooms: Vec<(u64, i32)> = Vec::new();
+        if let Some(max) = self.limits.bytes_written {
+            rooms.push((max.saturating_sub(self.usage.bytes_written), libc::EDQUOT));
+        }
+        if let Some(max) = self.limits.file_size {
+            rooms.push((max.saturating_sub(start.max(0) as u64), libc::EFBIG));
+        }
+        if let Some(max) = self.limits.disk_usage {
+            // Overwriting what the target already holds takes no space.
+            let size: u64 = self.size(fd_out)?.max(0) as u64;
+            let end: u64 = size.saturating_add(max.saturating_sub(self.usage.disk_usage));
+            rooms.push((end.saturating_sub(start.max(0) as u64), libc::EDQUOT));
+        }
+
+        for (room, errno) in rooms {
+            if room == 0 {
+                debug!("clamp_transfer(): quota exceeded (fd_out={:?})", fd_out);
+                return Err(errno);
+            }
+            len = len.min(room);
+        }
+
+        Ok(len as usize)
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Runs an operation that may grow a file, enforcing and accounting for limits.
+    ///
+    /// # Parameters
+    ///
+    /// - `fd`: File that may grow.
+    /// - `end`: Size of the file once the operation completes, if it grows.
+    /// - `operation`: Operation to run.
+    ///
+    fn grow<T>(
+        &mut self,
+        fd: i32,
+        end: off_t,
+        operation: impl FnOnce(&mut dyn FileSystem) -> Result<T, i32>,
+    ) -> Result<T, i32> {
+        let before: off_t = self.size(fd)?;
+        self.check_growth(before, end)?;
+
+        let result: T = operation(self.inner.as_mut())?;
+
+        let after: off_t = self.size(fd)?;
+        self.account_growth(before, after);
+
+        Ok(result)
+    }
+}
+
+impl FileSystem for QuotaFileSystem {
+    fn open_at(&mut self, dirfd: i32, path: &str, flags: i32, mode: mode_t) -> Result<i32, i32> {
+        if let Some(max) = self.limits.open_files {
+            if self.files.len() as u64 >= max {
+                return Err(libc::EMFILE);
+            }
+        }
+
+        // Truncation gives space back.
+        let before: Option<off_t> = match (flags & fcntl::O_TRUNC) != 0 {
+            true => self
+                .inner
+                .fstat_at(dirfd, path, 0)
+                .ok()
+                .map(|st| st.st_size),
+            false => None,
+        };
+
+        let fd: i32 = self.inner.open_at(dirfd, path, flags, mode)?;
+        self.files.insert(fd);
+        if (flags & fcntl::O_APPEND) != 0 {
+            self.appends.insert(fd);
+        }
+
+        if let Some(before) = before {
+            self.account_growth(before, 0);
+        }
+
+        Ok(fd)
+    }
+
+    fn close(&mut self, fd: i32) -> Result<(), i32> {
+        self.inner.close(fd)?;
+        self.files.remove(&fd);
+        self.appends.remove(&fd);
+        Ok(())
+    }
+
+    fn read(&mut self, fd: i32, buffer: &mut [u8]) -> Result<usize, i32> {
+        self.inner.read(fd, buffer)
+    }
+
+    fn write(&mut self, fd: i32, buffer: &[u8]) -> Result<usize, i32> {
+        self.check_write(buffer.len())?;
+
+        let end: off_t = self
+            .write_offset(fd, None)?
+            .saturating_add(buffer.len() as off_t);
+        let count: usize = self.grow(fd, end, |fs| fs.write(fd, buffer))?;
+        self.account_write(count);
+
+        Ok(count)
+    }
+
+    fn pread(&mut self, fd: i32, buffer: &mut [u8], offset: off_t) -> Result<usize, i32> {
+        self.inner.pread(fd, buffer, offset)
+    }
+
+    fn pwrite(&mut self, fd: i32, buffer: &[u8], offset: off_t) -> Result<usize, i32> {
+        self.check_write(buffer.len())?;
+
+        let end: off_t = offset.saturating_add(buffer.len() as off_t);
+        let count: usize = self.grow(fd, end, |fs| fs.pwrite(fd, buffer, offset))?;
+        self.account_write(count);
+
+        Ok(count)
+    }
+
+    fn lseek(&mut self, fd: i32, offset: off_t, whence: i32) -> Result<off_t, i32> {
+        self.inner.lseek(fd, offset, whence)
+    }
+
+    fn fsync(&mut self, fd: i32) -> Result<(), i32> {
+        self.inner.fsync(fd)
+    }
+
+    fn fdatasync(&mut self, fd: i32) -> Result<(), i32> {
+        self.inner.fdatasync(fd)
+    }
+
+    fn ftruncate(&mut self, fd: i32, length: off_t) -> Result<(), i32> {
+        self.grow(fd, length, |fs| fs.ftruncate(fd, length))
+    }
+
+    fn truncate(&mut self, path: &str, length: off_t) -> Result<(), i32> {
+        let before: off_t = self.inner.fstat_at(fcntl::AT_FDCWD, path, 0)?.st_size;
+        self.check_growth(before, length)?;
+
+        self.inner.truncate(path, length)?;
+
+        let after: off_t = self.inner.fstat_at(fcntl::AT_FDCWD, path, 0)?.st_size;
+        self.account_growth(before, after);
+
+        Ok(())
+    }
+
+    fn fallocate(&mut self, fd: i32, offset: off_t, len: off_t) -> Result<(), i32> {
+        let end: off_t = offset.saturating_add(len);
+        self.grow(fd, end, |fs| fs.fallocate(fd, offset, len))
+    }
+
+    fn fadvise(&mut self, fd: i32, offset: off_t, len: off_t, advice: i32) -> Result<(), i32> {
+        self.inner.fadvise(fd, offset, len, advice)
+    }
+
+    fn fstat(&mut self, fd: i32) -> Result<stat, i32> {
+        self.inner.fstat(fd)
+    }
+
+    fn fstat_at(&mut self, dirfd: i32, path: &str, flag: i32) -> Result<stat, i32> {
+        self.inner.fstat_at(dirfd, path, flag)
+    }
+
+    fn unlink_at(&mut self, dirfd: i32, path: &str, flags: i32) -> Result<(), i32> {
+        // Space is only given back once the last link goes away.
+        let freed: Option<off_t> = self
+            .inner
+            .fstat_at(dirfd, path, libc::AT_SYMLINK_NOFOLLOW)
+            .ok()
+            .filter(|st| (st.st_mode & libc::S_IFMT) == libc::S_IFREG && st.st_nlink == 1)
+            .map(|st| st.st_size);
+
+        self.inner.unlink_at(dirfd, path, flags)?;
+
+        if let Some(size) = freed {
+            self.account_growth(size, 0);
+        }
+
+        Ok(())
+    }
+
+    fn rename_at(
+        &mut self,
+        olddirfd: i32,
+        oldpath: &str,
+        newdirfd: i32,
+        newpath: &str,
+        flags: u32,
+    ) -> Result<(), i32> {
+        self.inner
+            .rename_at(olddirfd, oldpath, newdirfd, newpath, flags)
+    }
+
+    fn link_at(
+        &mut self,
+        olddirfd: i32,
+        oldpath: &str,
+        newdirfd: i32,
+        newpath: &str,
+        flags: i32,
+    ) -> Result<(), i32> {
+        self.inner
+            .link_at(olddirfd, oldpath, newdirfd, newpath, flags)
+    }
+
+    fn symlink_at(&mut self, target: &str, dirfd: i32, linkpath: &str) -> Result<(), i32> {
+        self.inner.symlink_at(target, dirfd, linkpath)
+    }
+
+    fn readlink_at(&mut self, dirfd: i32, path: &str, buffer: &mut [u8]) -> Result<usize, i32> {
+        self.inner.readlink_at(dirfd, path, buffer)
+    }
+
+    fn copy_file_range(
+        &mut self,
+        fd_in: i32,
+        off_in: Option<&mut off_t>,
+        fd_out: i32,
+        off_out: Option<&mut off_t>,
+        len: usize,
+        flags: u32,
+    ) -> Result<usize, i32> {
+        let start: off_t = self.write_offset(fd_out, off_out.as_deref())?;
+        let len: usize = self.clamp_transfer(fd_in, off_in.as_deref(), fd_out, start, len)?;
+
+        let end: off_t = start.saturating_add(len as off_t);
+        let count: usize = self.grow(fd_out, end, |fs| {
+            fs.copy_file_range(fd_in, off_in, fd_out, off_out, len, flags)
+        })?;
+        self.account_write(count);
+
+        Ok(count)
+    }
+
+    fn sendfile(
+        &mut self,
+        out_fd: i32,
+        in_fd: i32,
+        offset: Option<&mut off_t>,
+        count: usize,
+    ) -> Result<usize, i32> {
+        let start: off_t = self.write_offset(out_fd, None)?;
+        let count: usize = self.clamp_transfer(in_fd, offset.as_deref(), out_fd, start, count)?;
+
+        let end: off_t = start.saturating_add(count as off_t);
+        let count: usize =
+            self.grow(out_fd, end, |fs| fs.sendfile(out_fd, in_fd, offset, count))?;
+        self.account_write(count);
+
+        Ok(count)
+    }
+
+    fn access_mode(&mut self, fd: i32) -> Result<i32, i32> {
+        self.inner.access_mode(fd)
+    }
+
+    fn realpath(&mut self, path: &str) -> Result<String, i32> {
+        self.inner.realpath(path)
+    }
+}
+
+//==================================================================================================
+// Unit Tests
+//==================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::MemoryFileSystem;
+
+    // Build a quota backend on top of an empty in-memory filesystem.
+    fn quota(limits: ResourceLimits) -> QuotaFileSystem {
+        QuotaFileSystem::new(Box::new(MemoryFileSystem::new()), limits)
+    }
+
+    // Create a file with some contents, open for reading and writing with extra flags.
+    fn create(fs: &mut dyn FileSystem, path: &str, contents: &[u8], flags: i32) -> i32 {
+        let fd: i32 = fs
+            .open_at(fcntl::AT_FDCWD, path, fcntl::O_CREAT | fcntl::O_RDWR | flags, fcntl::S_IRUSR)
+            .expect("failed to create file");
+        assert_eq!(fs.pwrite(fd, contents, 0), Ok(contents.len()));
+        fd
+    }
+
+    #[test]
+    fn appends_count_against_file_size() {
+        let mut fs: QuotaFileSystem = quota(ResourceLimits {
+            file_size: Some(8),
+            ..Default::default()
+        });
+        let fd: i32 = create(&mut fs, "file", b"", fcntl::O_APPEND);
+
+        // Appends land at the end of the file, wherever the offset is.
+        assert_eq!(fs.write(fd, b"abcd"), Ok(4));
+        assert_eq!(fs.lseek(fd, 0, unistd::SEEK_SET), Ok(0));
+        assert_eq!(fs.write(fd, b"efgh"), Ok(4));
+        assert_eq!(fs.lseek(fd, 0, unistd::SEEK_SET), Ok(0));
+        assert_eq!(fs.write(fd, b"i"), Err(libc::EFBIG));
+        assert_eq!(fs.fstat(fd).map(|st| st.st_size), Ok(8));
+    }
+
+    #[test]
+    fn copy_file_range_to_end_of_source_is_clamped() {
+        let mut fs: QuotaFileSystem = quota(ResourceLimits {
+            bytes_written: Some(1024),
+            file_size: Some(1024),
+            disk_usage: Some(1024),
+            ..Default::default()
+        });
+        let fd_in: i32 = create(&mut fs, "in", b"x", 0);
+        let fd_out: i32 = create(&mut fs, "out", b"", 0);
+        assert_eq!(fs.lseek(fd_in, 0, unistd::SEEK_SET), Ok(0));
+
+        assert_eq!(fs.copy_file_range(fd_in, None, fd_out, None, isize::MAX as usize, 0), Ok(1));
+        assert_eq!(fs.copy_file_range(fd_in, None, fd_out, None, isize::MAX as usize, 0), Ok(0));
+    }
+
+    #[test]
+    fn sendfile_is_cut_short_by_limits() {
+        let mut fs: QuotaFileSystem = quota(ResourceLimits {
+            file_size: Some(6),
+            ..Default::default()
+        });
+        let in_fd: i32 = create(&mut fs, "in", b"abcdef", 0);
+        let out_fd: i32 = create(&mut fs, "out", b"xy", 0);
+        assert_eq!(fs.lseek(out_fd, 0, unistd::SEEK_END), Ok(2));
+
+        // Only what fits under the limit is transferred, and nothing once the limit is reached.
+        let mut offset: off_t = 0;
+        assert_eq!(fs.sendfile(out_fd, in_fd, Some(&mut offset), 8), Ok(4));
+        assert_eq!(fs.sendfile(out_fd, in_fd, Some(&mut offset), 8), Err(libc::EFBIG));
+        assert_eq!(fs.fstat(out_fd).map(|st| st.st_size), Ok(6));
+    }
+}
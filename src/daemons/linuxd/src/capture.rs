@@ -0,0 +1,114 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use ::anyhow::Result;
+use ::linuxd::{
+    capture::{
+        CaptureHeader,
+        CaptureRecord,
+        Direction,
+    },
+    protocol::Hello,
+};
+use ::nvx::sys::config;
+use ::std::{
+    ffi::OsString,
+    fs::File,
+    io::Write,
+    path::{
+        Path,
+        PathBuf,
+    },
+    time::Instant,
+};
+
+//==================================================================================================
+// Structures
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Capture of the raw frames exchanged on a connection, in both directions, so that the session
+/// can be replayed against another daemon. A disabled capture records nothing.
+///
+pub struct Capture {
+    /// File that records are appended to, if the capture is enabled.
+    file: Option<File>,
+    /// Instant at which the connection was established.
+    started: Instant,
+}
+
+//==================================================================================================
+// Implementations
+//==================================================================================================
+
+impl Capture {
+    ///
+    /// # Description
+    ///
+    /// Opens a capture for a connection. Each connection is captured to its own file, named after
+    /// the prefix and the sequence number of the connection.
+    ///
+    /// # Parameters
+    ///
+    /// - `prefix`: Prefix of capture files, or `None` to disable the capture.
+    /// - `connection`: Sequence number of the connection.
+    /// - `peer`: Version and capabilities that were negotiated on the connection.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the capture is returned. Upon failure, an error is returned instead.
+    ///
+    pub fn open(prefix: Option<&Path>, connection: u64, peer: Hello) -> Result<Self> {
+        let file: Option<File> = match prefix {
+            Some(prefix) => {
+                let mut path: OsString = prefix.as_os_str().to_owned();
+                path.push(format!(".{}", connection));
+                let path: PathBuf = PathBuf::from(path);
+
+                let mut file: File = File::create(&path)
+                    .map_err(|e| anyhow::anyhow!("failed to create capture {:?}: {}", path, e))?;
+                file.write_all(&CaptureHeader::new(peer).to_bytes())?;
+                info!("capturing connection to {:?}", path);
+                Some(file)
+            },
+            None => None,
+        };
+
+        Ok(Self {
+            file,
+            started: Instant::now(),
+        })
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Records a frame. The capture is disabled if it cannot be written to.
+    ///
+    /// # Parameters
+    ///
+    /// - `direction`: Direction in which the frame travels.
+    /// - `frame`: Frame.
+    ///
+    pub fn record(&mut self, direction: Direction, frame: &[u8; config::kernel::IPC_MESSAGE_SIZE]) {
+        if let Some(file) = self.file.as_mut() {
+            let record: CaptureRecord = CaptureRecord {
+                direction,
+                timestamp_us: u64::try_from(self.started.elapsed().as_micros()).unwrap_or(u64::MAX),
+                frame: *frame,
+            };
+
+            // Records are written unbuffered, so that captures of crashed daemons are complete.
+            if let Err(e) = file.write_all(&record.to_bytes()) {
+                error!("failed to write capture, disabling it (error={:?})", e);
+                self.file = None;
+            }
+        }
+    }
+}
@@ -0,0 +1,425 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::stdlib;
+use ::linuxd::{
+    message::MessagePartitioner,
+    sys::inotify::{
+        self,
+        message::{
+            InotifyAddWatchRequest,
+            InotifyAddWatchResponse,
+            InotifyEvent,
+            InotifyRemoveWatchRequest,
+            InotifyRemoveWatchResponse,
+        },
+    },
+};
+use ::nvx::{
+    ipc::Message,
+    pm::ProcessIdentifier,
+    sys::error::{
+        Error,
+        ErrorCode,
+    },
+};
+use ::std::{
+    ffi::CString,
+    mem,
+    os::unix::ffi::OsStrExt,
+    path::{
+        Path,
+        PathBuf,
+    },
+    ptr,
+};
+
+//==================================================================================================
+// Constants
+//==================================================================================================
+
+/// Mappings between guest and host event masks.
+const MASK_MAPPINGS: [(u32, u32); 8] = [
+    (inotify::IN_MODIFY, libc::IN_MODIFY),
+    (inotify::IN_MOVED_FROM, libc::IN_MOVED_FROM),
+    (inotify::IN_MOVED_TO, libc::IN_MOVED_TO),
+    (inotify::IN_CREATE, libc::IN_CREATE),
+    (inotify::IN_DELETE, libc::IN_DELETE),
+    (inotify::IN_DELETE_SELF, libc::IN_DELETE_SELF),
+    (inotify::IN_MOVE_SELF, libc::IN_MOVE_SELF),
+    (inotify::IN_ISDIR, libc::IN_ISDIR),
+];
+
+/// Size of the buffer used to drain host events.
+const EVENT_BUFFER_SIZE: usize = 4096;
+
+//==================================================================================================
+// Structures
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Watch added by a guest process.
+///
+#[derive(Debug, Clone, Copy)]
+struct Watch {
+    /// Process that added the watch.
+    pid: ProcessIdentifier,
+    /// Watch descriptor handed out to the process.
+    wd: i32,
+    /// Watch descriptor of the underlying host watch.
+    host_wd: i32,
+    /// Mask of events the process is interested in.
+    mask: u32,
+}
+
+///
+/// # Description
+///
+/// Filesystem watches of guest processes. All watches are backed by a single host inotify
+/// instance, and watches of different processes on the same path share the same host watch.
+///
+pub struct WatchManager {
+    /// Host inotify instance.
+    fd: i32,
+    /// Active watches.
+    watches: Vec<Watch>,
+    /// Next watch descriptor to hand out.
+    next_wd: i32,
+}
+
+//==================================================================================================
+// Implementations
+//==================================================================================================
+
+impl WatchManager {
+    pub fn new() -> Result<Self, Error> {
+        let fd: i32 = unsafe { libc::inotify_init1(libc::IN_NONBLOCK | libc::IN_CLOEXEC) };
+        if fd < 0 {
+            let errno: i32 = unsafe { *libc::__errno_location() };
+            error!("libc::inotify_init1(): errno={:?}", errno);
+            return Err(Error::new(
+                ErrorCode::OperationNotSupported,
+                "failed to initialize inotify",
+            ));
+        }
+
+        Ok(Self {
+            fd,
+            watches: Vec::new(),
+            next_wd: 1,
+        })
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the host file descriptor on which events become readable.
+    ///
+    pub fn fd(&self) -> i32 {
+        self.fd
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Handles a request of the `inotify_add_watch()` system call.
+    ///
+    /// # Parameters
+    ///
+    /// - `pid`: Requesting process.
+    /// - `request`: Request message.
+    /// - `root`: Canonical host path of the root of the virtual environment.
+    ///
+    /// # Returns
+    ///
+    /// Response messages.
+    ///
+    pub fn add_watch(
+        &mut self,
+        pid: ProcessIdentifier,
+        request: InotifyAddWatchRequest,
+        root: &Path,
+    ) -> Vec<Message> {
+        trace!("inotify_add_watch(): pid={:?}, request={:?}", pid, request);
+
+        let host_mask: u32 = match Self::host_mask(request.mask) {
+            Ok(host_mask) => host_mask,
+            Err(errno) => return vec![crate::build_errno(pid, errno)],
+        };
+
+        // Watches must not reach outside of the virtual environment.
+        let path: PathBuf = match stdlib::canonicalize(&request.path, root) {
+            Ok(path) => path,
+            Err(errno) => return vec![crate::build_errno(pid, errno)],
+        };
+        let path: CString = match CString::new(path.as_os_str().as_bytes()) {
+            Ok(path) => path,
+            Err(_) => return vec![crate::build_error(pid, ErrorCode::InvalidArgument)],
+        };
+
+        // Masks of other processes watching the same path are preserved.
+        debug!("libc::inotify_add_watch(): path={:?}, mask={:#x}", path, host_mask);
+        let host_wd: i32 = unsafe {
+            libc::inotify_add_watch(self.fd, path.as_ptr(), host_mask | libc::IN_MASK_ADD)
+        };
+        if host_wd < 0 {
+            let errno: i32 = unsafe { *libc::__errno_location() };
+            debug!("libc::inotify_add_watch(): errno={:?}", errno);
+            return vec![crate::build_errno(pid, errno)];
+        }
+
+        // Adding a watch twice on the same path updates the existing watch.
+        if let Some(watch) = self
+            .watches
+            .iter_mut()
+            .find(|w| w.pid == pid && w.host_wd == host_wd)
+        {
+            watch.mask = request.mask;
+            return vec![InotifyAddWatchResponse::build(pid, watch.wd)];
+        }
+
+        let wd: i32 = self.next_wd;
+        self.next_wd += 1;
+        self.watches.push(Watch {
+            pid,
+            wd,
+            host_wd,
+            mask: request.mask,
+        });
+
+        vec![InotifyAddWatchResponse::build(pid, wd)]
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Handles a request of the `inotify_rm_watch()` system call.
+    ///
+    /// # Parameters
+    ///
+    /// - `pid`: Requesting process.
+    /// - `request`: Request message.
+    ///
+    /// # Returns
+    ///
+    /// Response messages.
+    ///
+    pub fn remove_watch(
+        &mut self,
+        pid: ProcessIdentifier,
+        request: InotifyRemoveWatchRequest,
+    ) -> Vec<Message> {
+        trace!("inotify_rm_watch(): pid={:?}, request={:?}", pid, request);
+
+        let wd: i32 = request.wd;
+        let index: usize = match self.watches.iter().position(|w| w.pid == pid && w.wd == wd) {
+            Some(index) => index,
+            None => return vec![crate::build_errno(pid, libc::EINVAL)],
+        };
+
+        let watch: Watch = self.watches.swap_remove(index);
+        self.release_host_watch(watch.host_wd);
+
+        vec![InotifyRemoveWatchResponse::build(pid, 0)]
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Removes all watches of a process.
+    ///
+    /// # Parameters
+    ///
+    /// - `pid`: Target process.
+    ///
+    pub fn release_process(&mut self, pid: ProcessIdentifier) {
+        let (released, kept): (Vec<Watch>, Vec<Watch>) =
+            self.watches.drain(..).partition(|w| w.pid == pid);
+        self.watches = kept;
+
+        if !released.is_empty() {
+            debug!("release_process(): released {} watches (pid={:?})", released.len(), pid);
+        }
+
+        for watch in released {
+            self.release_host_watch(watch.host_wd);
+        }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Drains pending host events and translates them into events for watching processes.
+    ///
+    /// # Returns
+    ///
+    /// Event messages to deliver.
+    ///
+    pub fn read_events(&mut self) -> Vec<Message> {
+        let mut messages: Vec<Message> = Vec::new();
+        let mut buffer: [u8; EVENT_BUFFER_SIZE] = [0; EVENT_BUFFER_SIZE];
+
+        loop {
+            let len: isize = unsafe {
+                libc::read(self.fd, buffer.as_mut_ptr() as *mut libc::c_void, buffer.len())
+            };
+            if len < 0 {
+                let errno: i32 = unsafe { *libc::__errno_location() };
+                if errno != libc::EAGAIN {
+                    error!("libc::read(): errno={:?}", errno);
+                }
+                break;
+            }
+
+            let len: usize = len as usize;
+            let mut offset: usize = 0;
+            while offset + mem::size_of::<libc::inotify_event>() <= len {
+                let event: libc::inotify_event = unsafe {
+                    ptr::read_unaligned(buffer[offset..].as_ptr() as *const libc::inotify_event)
+                };
+                let name_offset: usize = offset + mem::size_of::<libc::inotify_event>();
+                let name_end: usize = (name_offset + event.len as usize).min(len);
+                offset = name_offset + event.len as usize;
+
+                // Names are padded with null bytes.
+                let name: &[u8] = &buffer[name_offset..name_end];
+                let name: &[u8] = match name.iter().position(|b| *b == 0) {
+                    Some(end) => &name[..end],
+                    None => name,
+                };
+                let name: String = String::from_utf8_lossy(name).into_owned();
+
+                messages.extend(self.dispatch(event, name));
+            }
+        }
+
+        messages
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Translates a single host event into events for the processes watching it.
+    ///
+    fn dispatch(&mut self, event: libc::inotify_event, name: String) -> Vec<Message> {
+        trace!("dispatch(): wd={:?}, mask={:#x}, name={:?}", event.wd, event.mask, name);
+
+        if (event.mask & libc::IN_Q_OVERFLOW) != 0 {
+            warn!("host event queue overflowed, events were lost");
+            return Vec::new();
+        }
+
+        let mut messages: Vec<Message> = Vec::new();
+
+        // The host watch is gone, so are the watches of all processes that shared it.
+        if (event.mask & libc::IN_IGNORED) != 0 {
+            let (released, kept): (Vec<Watch>, Vec<Watch>) =
+                self.watches.drain(..).partition(|w| w.host_wd == event.wd);
+            self.watches = kept;
+            for watch in released {
+                messages.extend(Self::build_event(
+                    watch.pid,
+                    watch.wd,
+                    inotify::IN_IGNORED,
+                    0,
+                    String::new(),
+                ));
+            }
+            return messages;
+        }
+
+        let mask: u32 = Self::guest_mask(event.mask);
+        for watch in self.watches.iter().filter(|w| w.host_wd == event.wd) {
+            // Skip processes that are not interested in this event.
+            if (mask & watch.mask & inotify::IN_ALL_EVENTS) == 0 {
+                continue;
+            }
+
+            messages.extend(Self::build_event(
+                watch.pid,
+                watch.wd,
+                mask,
+                event.cookie,
+                name.clone(),
+            ));
+        }
+
+        messages
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Removes a host watch, unless it is still shared with another process.
+    ///
+    fn release_host_watch(&mut self, host_wd: i32) {
+        if self.watches.iter().any(|w| w.host_wd == host_wd) {
+            return;
+        }
+
+        debug!("libc::inotify_rm_watch(): wd={:?}", host_wd);
+        if unsafe { libc::inotify_rm_watch(self.fd, host_wd) } != 0 {
+            let errno: i32 = unsafe { *libc::__errno_location() };
+            debug!("libc::inotify_rm_watch(): errno={:?}", errno);
+        }
+    }
+
+    fn build_event(
+        pid: ProcessIdentifier,
+        wd: i32,
+        mask: u32,
+        cookie: u32,
+        name: String,
+    ) -> Vec<Message> {
+        let event: InotifyEvent = match InotifyEvent::new(wd, mask, cookie, name) {
+            Ok(event) => event,
+            Err(e) => {
+                warn!("failed to build event (error={:?})", e);
+                return Vec::new();
+            },
+        };
+
+        match event.into_parts(pid) {
+            Ok(messages) => messages,
+            Err(e) => {
+                warn!("failed to partition event (error={:?})", e);
+                Vec::new()
+            },
+        }
+    }
+
+    fn host_mask(mask: u32) -> Result<u32, i32> {
+        // Check for unsupported or missing events.
+        if (mask & !inotify::IN_ALL_EVENTS) != 0 || mask == 0 {
+            return Err(libc::EINVAL);
+        }
+
+        Ok(MASK_MAPPINGS
+            .iter()
+            .filter(|(guest, _)| (mask & guest) != 0)
+            .fold(0, |acc, (_, host)| acc | host))
+    }
+
+    fn guest_mask(mask: u32) -> u32 {
+        MASK_MAPPINGS
+            .iter()
+            .filter(|(_, host)| (mask & host) != 0)
+            .fold(0, |acc, (guest, _)| acc | guest)
+    }
+}
+
+impl Drop for WatchManager {
+    fn drop(&mut self) {
+        if !self.watches.is_empty() {
+            info!("dropping {} watches", self.watches.len());
+        }
+
+        unsafe { libc::close(self.fd) };
+    }
+}
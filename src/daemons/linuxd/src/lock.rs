@@ -0,0 +1,913 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::backend::FileSystem;
+use ::linuxd::{
+    fcntl,
+    fcntl::{
+        flock,
+        message::{
+            RecordLockRequest,
+            RecordLockResponse,
+        },
+    },
+    sys::{
+        file,
+        file::message::{
+            FileLockRequest,
+            FileLockResponse,
+        },
+        stat::stat,
+        types::{
+            dev_t,
+            ino_t,
+            off_t,
+            pid_t,
+        },
+    },
+    unistd,
+};
+use ::nvx::{
+    ipc::Message,
+    pm::ProcessIdentifier,
+};
+use ::std::collections::{
+    BTreeMap,
+    BTreeSet,
+    VecDeque,
+};
+
+//==================================================================================================
+// Structures
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Identity of a file (device ID and file serial number), regardless of the file descriptor used to
+/// reach it.
+///
+type FileKey = (dev_t, ino_t);
+
+///
+/// # Description
+///
+/// Kind of an advisory lock.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LockKind {
+    /// Shared (read) lock.
+    Shared,
+    /// Exclusive (write) lock.
+    Exclusive,
+}
+
+///
+/// # Description
+///
+/// Whole-file lock placed with `flock()`. These locks are owned by the open file description.
+///
+#[derive(Debug, Clone, Copy)]
+struct WholeFileLock {
+    /// Process that placed the lock.
+    pid: ProcessIdentifier,
+    /// File descriptor that owns the lock.
+    fd: i32,
+    /// Kind of lock.
+    kind: LockKind,
+}
+
+///
+/// # Description
+///
+/// Byte-range lock placed with `fcntl()`. These locks are owned by the process.
+///
+#[derive(Debug, Clone, Copy)]
+struct RecordLock {
+    /// Process that owns the lock.
+    pid: ProcessIdentifier,
+    /// Kind of lock.
+    kind: LockKind,
+    /// First byte of the locked range.
+    start: u64,
+    /// One past the last byte of the locked range (`u64::MAX` means end-of-file).
+    end: u64,
+}
+
+///
+/// # Description
+///
+/// Locks held on a single host file.
+///
+#[derive(Default)]
+struct FileLocks {
+    /// Whole-file locks.
+    whole: Vec<WholeFileLock>,
+    /// Byte-range locks.
+    records: Vec<RecordLock>,
+}
+
+///
+/// # Description
+///
+/// Lock request that is waiting for conflicting locks to be released.
+///
+#[derive(Debug, Clone, Copy)]
+enum PendingRequest {
+    /// Blocking `flock()`.
+    WholeFile { fd: i32, kind: LockKind },
+    /// `fcntl()` with `F_SETLKW`.
+    Record {
+        fd: i32,
+        lock: RecordLock,
+        reply: flock,
+    },
+}
+
+///
+/// # Description
+///
+/// Blocked lock request.
+///
+#[derive(Debug, Clone, Copy)]
+struct PendingLock {
+    /// Process that is blocked.
+    pid: ProcessIdentifier,
+    /// File the lock is requested on.
+    key: FileKey,
+    /// Request that is waiting.
+    request: PendingRequest,
+}
+
+///
+/// # Description
+///
+/// Advisory lock manager.
+///
+/// Locks are tracked by the daemon itself rather than delegated to the host, because every guest
+/// process shares the daemon's host process and thus would never conflict with each other. Blocked
+/// requests are queued and answered once the lock is granted, so that a waiting process never
+/// stalls requests from other processes.
+///
+pub struct FileLockManager {
+    /// Locks held, per file.
+    files: BTreeMap<FileKey, FileLocks>,
+    /// Blocked requests, in arrival order.
+    pending: VecDeque<PendingLock>,
+}
+
+//==================================================================================================
+// Implementations
+//==================================================================================================
+
+impl FileLockManager {
+    pub fn new() -> Self {
+        Self {
+            files: BTreeMap::new(),
+            pending: VecDeque::new(),
+        }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Handles a `flock()` request.
+    ///
+    /// # Parameters
+    ///
+    /// - `pid`: Requesting process identifier.
+    /// - `request`: Lock request.
+    /// - `fs`: Filesystem backend of the requesting process.
+    ///
+    /// # Returns
+    ///
+    /// Response messages to send. The list is empty if the requesting process is blocked, and may
+    /// contain responses to other processes whose blocked requests were granted.
+    ///
+    pub fn flock(
+        &mut self,
+        pid: ProcessIdentifier,
+        request: FileLockRequest,
+        fs: &mut dyn FileSystem,
+    ) -> Vec<Message> {
+        trace!("flock(): pid={:?}, request={:?}", pid, request);
+
+        let fd: i32 = request.fd;
+        let operation: i32 = request.operation;
+
+        let key: FileKey = match Self::file_key(fd, fs) {
+            Ok(key) => key,
+            Err(errno) => return vec![crate::build_errno(pid, errno)],
+        };
+
+        let nonblocking: bool = (operation & file::LOCK_NB) != 0;
+        let kind: Option<LockKind> = match operation & !file::LOCK_NB {
+            file::LOCK_SH => Some(LockKind::Shared),
+            file::LOCK_EX => Some(LockKind::Exclusive),
+            file::LOCK_UN => None,
+            _ => return vec![crate::build_errno(pid, libc::EINVAL)],
+        };
+
+        // Drop any lock that this open file description holds, so that conversions do not
+        // conflict with themselves.
+        let locks: &mut FileLocks = self.files.entry(key).or_default();
+        locks.whole.retain(|l| l.fd != fd);
+
+        let mut messages: Vec<Message> = Vec::new();
+        match kind {
+            None => {
+                debug!("flock(): released lock (fd={:?})", fd);
+                messages.push(FileLockResponse::build(pid, 0));
+            },
+            Some(kind) if !Self::whole_conflicts(locks, fd, kind) => {
+                debug!("flock(): granted lock (fd={:?}, kind={:?})", fd, kind);
+                locks.whole.push(WholeFileLock { pid, fd, kind });
+                messages.push(FileLockResponse::build(pid, 0));
+            },
+            Some(_) if nonblocking => {
+                debug!("flock(): lock is busy (fd={:?})", fd);
+                messages.push(crate::build_errno(pid, libc::EWOULDBLOCK));
+            },
+            Some(kind) => {
+                debug!("flock(): blocking (pid={:?}, fd={:?})", pid, fd);
+                self.pending.push_back(PendingLock {
+                    pid,
+                    key,
+                    request: PendingRequest::WholeFile { fd, kind },
+                });
+            },
+        }
+
+        // Releasing or converting a lock may unblock other processes.
+        messages.extend(self.wake());
+
+        messages
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Handles a record locking `fcntl()` request.
+    ///
+    /// # Parameters
+    ///
+    /// - `pid`: Requesting process identifier.
+    /// - `request`: Lock request.
+    /// - `fs`: Filesystem backend of the requesting process.
+    ///
+    /// # Returns
+    ///
+    /// Response messages to send. The list is empty if the requesting process is blocked, and may
+    /// contain responses to other processes whose blocked requests were granted.
+    ///
+    pub fn fcntl(
+        &mut self,
+        pid: ProcessIdentifier,
+        request: RecordLockRequest,
+        fs: &mut dyn FileSystem,
+    ) -> Vec<Message> {
+        trace!("fcntl(): pid={:?}, request={:?}", pid, request);
+
+        let fd: i32 = request.fd;
+        let cmd: i32 = request.cmd;
+        let reply: flock = flock {
+            l_type: request.l_type,
+            l_whence: request.l_whence,
+            l_start: request.l_start,
+            l_len: request.l_len,
+            l_pid: 0,
+        };
+
+        let key: FileKey = match Self::file_key(fd, fs) {
+            Ok(key) => key,
+            Err(errno) => return vec![crate::build_errno(pid, errno)],
+        };
+
+        let kind: Option<LockKind> = match reply.l_type {
+            fcntl::F_RDLCK => Some(LockKind::Shared),
+            fcntl::F_WRLCK => Some(LockKind::Exclusive),
+            fcntl::F_UNLCK => None,
+            _ => return vec![crate::build_errno(pid, libc::EINVAL)],
+        };
+
+        // Check if file was opened with a compatible access mode.
+        if cmd != fcntl::F_GETLK {
+            if let Err(errno) = Self::check_access_mode(fd, kind, fs) {
+                return vec![crate::build_errno(pid, errno)];
+            }
+        }
+
+        let (start, end): (u64, u64) = match Self::lock_range(fd, &reply, fs) {
+            Ok(range) => range,
+            Err(errno) => return vec![crate::build_errno(pid, errno)],
+        };
+
+        let lock: RecordLock = RecordLock {
+            pid,
+            kind: kind.unwrap_or(LockKind::Shared),
+            start,
+            end,
+        };
+
+        match cmd {
+            fcntl::F_GETLK => vec![self.get_record_lock(pid, key, kind, lock)],
+            fcntl::F_SETLK | fcntl::F_SETLKW => {
+                let locks: &mut FileLocks = self.files.entry(key).or_default();
+
+                let kind: LockKind = match kind {
+                    Some(kind) => kind,
+                    None => {
+                        debug!("fcntl(): released range (pid={:?}, start={})", pid, start);
+                        Self::set_record(locks, pid, None, start, end);
+                        let mut messages: Vec<Message> =
+                            vec![RecordLockResponse::build(pid, 0, &reply)];
+                        messages.extend(self.wake());
+                        return messages;
+                    },
+                };
+
+                let blockers: Vec<ProcessIdentifier> = Self::record_conflicts(locks, &lock);
+                if blockers.is_empty() {
+                    debug!("fcntl(): granted range (pid={:?}, start={})", pid, start);
+                    Self::set_record(locks, pid, Some(kind), start, end);
+                    let mut messages: Vec<Message> =
+                        vec![RecordLockResponse::build(pid, 0, &reply)];
+                    // Downgrading a lock may unblock other processes.
+                    messages.extend(self.wake());
+                    return messages;
+                }
+
+                if cmd == fcntl::F_SETLK {
+                    debug!("fcntl(): range is busy (pid={:?}, start={})", pid, start);
+                    return vec![crate::build_errno(pid, libc::EAGAIN)];
+                }
+
+                if self.would_deadlock(pid, blockers) {
+                    warn!("fcntl(): deadlock detected (pid={:?}, fd={:?})", pid, fd);
+                    return vec![crate::build_errno(pid, libc::EDEADLK)];
+                }
+
+                debug!("fcntl(): blocking (pid={:?}, fd={:?})", pid, fd);
+                self.pending.push_back(PendingLock {
+                    pid,
+                    key,
+                    request: PendingRequest::Record { fd, lock, reply },
+                });
+
+                Vec::new()
+            },
+            _ => vec![crate::build_errno(pid, libc::EINVAL)],
+        }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Releases locks associated with a file descriptor that is about to be closed. This drops
+    /// whole-file locks owned by the file descriptor and every record lock that the process holds
+    /// on the same file.
+    ///
+    /// # Parameters
+    ///
+    /// - `pid`: Process that is closing the file descriptor.
+    /// - `fd`: File descriptor that is about to be closed.
+    /// - `fs`: Filesystem backend of the process.
+    ///
+    /// # Returns
+    ///
+    /// Response messages to processes whose blocked requests were granted or cancelled.
+    ///
+    pub fn release_fd(
+        &mut self,
+        pid: ProcessIdentifier,
+        fd: i32,
+        fs: &mut dyn FileSystem,
+    ) -> Vec<Message> {
+        let key: FileKey = match Self::file_key(fd, fs) {
+            Ok(key) => key,
+            Err(_) => return Vec::new(),
+        };
+
+        if let Some(locks) = self.files.get_mut(&key) {
+            locks.whole.retain(|l| l.fd != fd);
+            locks.records.retain(|l| l.pid != pid);
+        }
+
+        // Cancel requests that are blocked on the file descriptor. File descriptors are numbered
+        // per backend, so the same number in another environment refers to another file.
+        let mut messages: Vec<Message> = Vec::new();
+        self.pending.retain(|p| {
+            let waiting_fd: i32 = match p.request {
+                PendingRequest::WholeFile { fd, .. } => fd,
+                PendingRequest::Record { fd, .. } => fd,
+            };
+            if p.key == key && waiting_fd == fd {
+                messages.push(crate::build_errno(p.pid, libc::EBADF));
+                false
+            } else {
+                true
+            }
+        });
+
+        messages.extend(self.wake());
+        messages
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Releases all locks held by a process, as well as any request it has blocked.
+    ///
+    /// # Parameters
+    ///
+    /// - `pid`: Target process.
+    ///
+    /// # Returns
+    ///
+    /// Response messages to processes whose blocked requests were granted.
+    ///
+    pub fn release_process(&mut self, pid: ProcessIdentifier) -> Vec<Message> {
+        let mut released: usize = 0;
+        for locks in self.files.values_mut() {
+            let before: usize = locks.whole.len() + locks.records.len();
+            locks.whole.retain(|l| l.pid != pid);
+            locks.records.retain(|l| l.pid != pid);
+            released += before - locks.whole.len() - locks.records.len();
+        }
+        self.pending.retain(|p| p.pid != pid);
+
+        if released > 0 {
+            debug!("release_process(): released {} locks (pid={:?})", released, pid);
+        }
+
+        self.wake()
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Drops every lock and blocked request. This is used when the connection to the guest is
+    /// torn down.
+    ///
+    pub fn release_all(&mut self) {
+        if !self.files.is_empty() || !self.pending.is_empty() {
+            info!(
+                "releasing all locks (files={}, pending={})",
+                self.files.len(),
+                self.pending.len()
+            );
+        }
+        self.files.clear();
+        self.pending.clear();
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Grants blocked requests that no longer conflict, in arrival order.
+    ///
+    /// # Returns
+    ///
+    /// Response messages to processes whose blocked requests were granted.
+    ///
+    fn wake(&mut self) -> Vec<Message> {
+        let mut messages: Vec<Message> = Vec::new();
+        let mut still_pending: VecDeque<PendingLock> = VecDeque::new();
+
+        while let Some(pending) = self.pending.pop_front() {
+            let locks: &mut FileLocks = self.files.entry(pending.key).or_default();
+
+            match pending.request {
+                PendingRequest::WholeFile { fd, kind } => {
+                    if Self::whole_conflicts(locks, fd, kind) {
+                        still_pending.push_back(pending);
+                        continue;
+                    }
+                    debug!("wake(): granted lock (pid={:?}, fd={:?})", pending.pid, fd);
+                    locks.whole.push(WholeFileLock {
+                        pid: pending.pid,
+                        fd,
+                        kind,
+                    });
+                    messages.push(FileLockResponse::build(pending.pid, 0));
+                },
+                PendingRequest::Record { fd, lock, reply } => {
+                    if !Self::record_conflicts(locks, &lock).is_empty() {
+                        still_pending.push_back(pending);
+                        continue;
+                    }
+                    debug!("wake(): granted range (pid={:?}, fd={:?})", pending.pid, fd);
+                    Self::set_record(locks, lock.pid, Some(lock.kind), lock.start, lock.end);
+                    messages.push(RecordLockResponse::build(pending.pid, 0, &reply));
+                },
+            }
+        }
+
+        self.pending = still_pending;
+        self.files
+            .retain(|_, locks| !locks.whole.is_empty() || !locks.records.is_empty());
+
+        messages
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Handles `F_GETLK`, reporting the first lock that would block the given one.
+    ///
+    fn get_record_lock(
+        &self,
+        pid: ProcessIdentifier,
+        key: FileKey,
+        kind: Option<LockKind>,
+        lock: RecordLock,
+    ) -> Message {
+        // Check if lock type is valid for a query.
+        if kind.is_none() {
+            return crate::build_errno(pid, libc::EINVAL);
+        }
+
+        let mut reply: flock = flock {
+            l_type: fcntl::F_UNLCK,
+            l_whence: unistd::SEEK_SET as i16,
+            l_start: lock.start as off_t,
+            l_len: Self::range_len(lock.start, lock.end),
+            l_pid: 0,
+        };
+
+        if let Some(locks) = self.files.get(&key) {
+            if let Some(conflict) = locks
+                .records
+                .iter()
+                .find(|l| Self::records_conflict(l, &lock))
+            {
+                reply.l_type = match conflict.kind {
+                    LockKind::Shared => fcntl::F_RDLCK,
+                    LockKind::Exclusive => fcntl::F_WRLCK,
+                };
+                reply.l_start = conflict.start as off_t;
+                reply.l_len = Self::range_len(conflict.start, conflict.end);
+                reply.l_pid = u32::from(conflict.pid) as pid_t;
+            }
+        }
+
+        RecordLockResponse::build(pid, 0, &reply)
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Checks whether blocking `pid` on the given lock owners would create a cycle.
+    ///
+    fn would_deadlock(&self, pid: ProcessIdentifier, blockers: Vec<ProcessIdentifier>) -> bool {
+        let mut visited: BTreeSet<ProcessIdentifier> = BTreeSet::new();
+        let mut stack: Vec<ProcessIdentifier> = blockers;
+
+        while let Some(owner) = stack.pop() {
+            if owner == pid {
+                return true;
+            }
+            if !visited.insert(owner) {
+                continue;
+            }
+
+            // Follow requests that the owner has blocked.
+            for pending in self.pending.iter().filter(|p| p.pid == owner) {
+                if let PendingRequest::Record { lock, .. } = pending.request {
+                    if let Some(locks) = self.files.get(&pending.key) {
+                        stack.extend(Self::record_conflicts(locks, &lock));
+                    }
+                }
+            }
+        }
+
+        false
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Checks whether a whole-file lock conflicts with locks held through other file descriptors.
+    ///
+    fn whole_conflicts(locks: &FileLocks, fd: i32, kind: LockKind) -> bool {
+        locks
+            .whole
+            .iter()
+            .any(|l| l.fd != fd && (l.kind == LockKind::Exclusive || kind == LockKind::Exclusive))
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the owners of record locks that conflict with the given one.
+    ///
+    fn record_conflicts(locks: &FileLocks, lock: &RecordLock) -> Vec<ProcessIdentifier> {
+        locks
+            .records
+            .iter()
+            .filter(|l| Self::records_conflict(l, lock))
+            .map(|l| l.pid)
+            .collect()
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Checks whether two record locks conflict.
+    ///
+    fn records_conflict(held: &RecordLock, wanted: &RecordLock) -> bool {
+        held.pid != wanted.pid
+            && held.start < wanted.end
+            && wanted.start < held.end
+            && (held.kind == LockKind::Exclusive || wanted.kind == LockKind::Exclusive)
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Replaces the locks that a process holds on a range. Existing locks are split as needed.
+    ///
+    fn set_record(
+        locks: &mut FileLocks,
+        pid: ProcessIdentifier,
+        kind: Option<LockKind>,
+        start: u64,
+        end: u64,
+    ) {
+        let mut records: Vec<RecordLock> = Vec::with_capacity(locks.records.len() + 2);
+
+        for l in locks.records.drain(..) {
+            // Keep locks that do not belong to the process or do not overlap the range.
+            if l.pid != pid || l.end <= start || l.start >= end {
+                records.push(l);
+                continue;
+            }
+
+            // Keep the parts that lie outside the range.
+            if l.start < start {
+                records.push(RecordLock { end: start, ..l });
+            }
+            if l.end > end {
+                records.push(RecordLock { start: end, ..l });
+            }
+        }
+
+        if let Some(kind) = kind {
+            records.push(RecordLock {
+                pid,
+                kind,
+                start,
+                end,
+            });
+        }
+
+        locks.records = records;
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Computes the absolute byte range described by a lock.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the range `[start, end)` is returned. Upon failure, the error number is
+    /// returned instead.
+    ///
+    fn lock_range(fd: i32, lock: &flock, fs: &mut dyn FileSystem) -> Result<(u64, u64), i32> {
+        let base: off_t = match lock.l_whence as i32 {
+            unistd::SEEK_SET => 0,
+            unistd::SEEK_CUR => fs.lseek(fd, 0, unistd::SEEK_CUR)?,
+            unistd::SEEK_END => fs.fstat(fd)?.st_size,
+            _ => return Err(libc::EINVAL),
+        };
+
+        let start: off_t = base.checked_add(lock.l_start).ok_or(libc::EOVERFLOW)?;
+        let (start, end): (off_t, Option<off_t>) = match lock.l_len {
+            0 => (start, None),
+            len if len > 0 => (start, Some(start.checked_add(len).ok_or(libc::EOVERFLOW)?)),
+            len => (start.checked_add(len).ok_or(libc::EOVERFLOW)?, Some(start)),
+        };
+
+        if start < 0 {
+            return Err(libc::EINVAL);
+        }
+
+        Ok((start as u64, end.map(|end| end as u64).unwrap_or(u64::MAX)))
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Converts a range end into a lock length, where `0` stands for end-of-file.
+    ///
+    fn range_len(start: u64, end: u64) -> off_t {
+        if end == u64::MAX {
+            0
+        } else {
+            (end - start) as off_t
+        }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Checks that a file descriptor was opened with an access mode that allows the lock.
+    ///
+    fn check_access_mode(
+        fd: i32,
+        kind: Option<LockKind>,
+        fs: &mut dyn FileSystem,
+    ) -> Result<(), i32> {
+        let mode: i32 = fs.access_mode(fd)?;
+        match kind {
+            Some(LockKind::Shared) if mode == fcntl::O_WRONLY => Err(libc::EBADF),
+            Some(LockKind::Exclusive) if mode == fcntl::O_RDONLY => Err(libc::EBADF),
+            _ => Ok(()),
+        }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Identifies the file that a file descriptor refers to.
+    ///
+    fn file_key(fd: i32, fs: &mut dyn FileSystem) -> Result<FileKey, i32> {
+        let st: stat = fs.fstat(fd)?;
+        Ok((st.st_dev, st.st_ino))
+    }
+}
+
+//==================================================================================================
+// Unit Tests
+//==================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::MemoryFileSystem;
+    use ::linuxd::LinuxDaemonMessage;
+
+    // Open a file for reading and writing, creating it if needed.
+    fn open(fs: &mut dyn FileSystem, path: &str) -> i32 {
+        fs.open_at(fcntl::AT_FDCWD, path, fcntl::O_CREAT | fcntl::O_RDWR, fcntl::S_IRUSR)
+            .expect("failed to open file")
+    }
+
+    // Issue a `flock()` request.
+    fn flock(
+        locks: &mut FileLockManager,
+        pid: ProcessIdentifier,
+        fd: i32,
+        operation: i32,
+        fs: &mut dyn FileSystem,
+    ) -> Vec<Message> {
+        let request: Message = FileLockRequest::build(pid, fd, operation);
+        let payload: [u8; LinuxDaemonMessage::PAYLOAD_SIZE] =
+            LinuxDaemonMessage::try_from_bytes(request.payload)
+                .expect("malformed message")
+                .payload;
+        locks.flock(pid, FileLockRequest::from_bytes(payload), fs)
+    }
+
+    // Issue a record locking `fcntl()` request on the whole file.
+    fn lock_file(
+        locks: &mut FileLockManager,
+        pid: ProcessIdentifier,
+        fd: i32,
+        cmd: i32,
+        fs: &mut dyn FileSystem,
+    ) -> Vec<Message> {
+        let lock: flock = flock {
+            l_type: fcntl::F_WRLCK,
+            l_whence: unistd::SEEK_SET as i16,
+            l_start: 0,
+            l_len: 0,
+            l_pid: 0,
+        };
+        let request: Message = RecordLockRequest::build(pid, fd, cmd, &lock);
+        let payload: [u8; LinuxDaemonMessage::PAYLOAD_SIZE] =
+            LinuxDaemonMessage::try_from_bytes(request.payload)
+                .expect("malformed message")
+                .payload;
+        locks.fcntl(pid, RecordLockRequest::from_bytes(payload), fs)
+    }
+
+    // Get the processes that responses go to, along with their status.
+    fn outcomes(messages: &[Message]) -> Vec<(ProcessIdentifier, i32)> {
+        messages.iter().map(|m| (m.destination, m.status)).collect()
+    }
+
+    #[test]
+    fn close_grants_blocked_lock() {
+        let (owner, waiter): (ProcessIdentifier, ProcessIdentifier) =
+            (ProcessIdentifier::from(2), ProcessIdentifier::from(3));
+        let mut fs: MemoryFileSystem = MemoryFileSystem::new();
+        let mut locks: FileLockManager = FileLockManager::new();
+        let owner_fd: i32 = open(&mut fs, "file");
+        let waiter_fd: i32 = open(&mut fs, "file");
+
+        assert_eq!(
+            outcomes(&flock(&mut locks, owner, owner_fd, file::LOCK_EX, &mut fs)),
+            [(owner, 0)]
+        );
+        assert!(flock(&mut locks, waiter, waiter_fd, file::LOCK_EX, &mut fs).is_empty());
+
+        // Closing the file descriptor that holds the lock lets the waiter through.
+        assert_eq!(outcomes(&locks.release_fd(owner, owner_fd, &mut fs)), [(waiter, 0)]);
+    }
+
+    #[test]
+    fn close_cancels_blocked_requests() {
+        let (owner, waiter): (ProcessIdentifier, ProcessIdentifier) =
+            (ProcessIdentifier::from(2), ProcessIdentifier::from(3));
+        let mut fs: MemoryFileSystem = MemoryFileSystem::new();
+        let mut locks: FileLockManager = FileLockManager::new();
+        let owner_fd: i32 = open(&mut fs, "file");
+        let waiter_fd: i32 = open(&mut fs, "file");
+
+        assert!(lock_file(&mut locks, owner, owner_fd, fcntl::F_SETLK, &mut fs)[0].status == 0);
+        assert!(lock_file(&mut locks, waiter, waiter_fd, fcntl::F_SETLKW, &mut fs).is_empty());
+
+        let messages: Vec<Message> = locks.release_fd(waiter, waiter_fd, &mut fs);
+        assert_eq!(outcomes(&messages), [(waiter, -libc::EBADF)]);
+        assert!(locks.pending.is_empty());
+    }
+
+    #[test]
+    fn close_keeps_blocked_requests_of_other_environments() {
+        let (owner, waiter, other): (ProcessIdentifier, ProcessIdentifier, ProcessIdentifier) =
+            (ProcessIdentifier::from(2), ProcessIdentifier::from(3), ProcessIdentifier::from(4));
+        let mut env: MemoryFileSystem = MemoryFileSystem::new();
+        let mut other_env: MemoryFileSystem = MemoryFileSystem::new();
+        let mut locks: FileLockManager = FileLockManager::new();
+
+        let owner_fd: i32 = open(&mut env, "file");
+        let waiter_fd: i32 = open(&mut env, "file");
+        assert_eq!(open(&mut other_env, "file"), owner_fd);
+        assert_eq!(open(&mut other_env, "file"), waiter_fd);
+
+        assert_eq!(
+            outcomes(&flock(&mut locks, owner, owner_fd, file::LOCK_EX, &mut env)),
+            [(owner, 0)]
+        );
+        assert!(lock_file(&mut locks, owner, owner_fd, fcntl::F_SETLK, &mut env)[0].status == 0);
+        assert!(flock(&mut locks, waiter, waiter_fd, file::LOCK_EX, &mut env).is_empty());
+        assert!(lock_file(&mut locks, waiter, waiter_fd, fcntl::F_SETLKW, &mut env).is_empty());
+
+        // The same file descriptor number in another environment refers to another file.
+        assert!(locks
+            .release_fd(other, waiter_fd, &mut other_env)
+            .is_empty());
+        assert!(locks.release_fd(other, owner_fd, &mut other_env).is_empty());
+
+        let messages: Vec<Message> = locks.release_fd(owner, owner_fd, &mut env);
+        assert_eq!(outcomes(&messages), [(waiter, 0), (waiter, 0)]);
+    }
+
+    #[test]
+    fn leave_releases_locks_of_process() {
+        let (owner, waiter): (ProcessIdentifier, ProcessIdentifier) =
+            (ProcessIdentifier::from(2), ProcessIdentifier::from(3));
+        let mut fs: MemoryFileSystem = MemoryFileSystem::new();
+        let mut locks: FileLockManager = FileLockManager::new();
+        let owner_fd: i32 = open(&mut fs, "file");
+        let waiter_fd: i32 = open(&mut fs, "file");
+
+        assert!(lock_file(&mut locks, owner, owner_fd, fcntl::F_SETLK, &mut fs)[0].status == 0);
+        assert!(lock_file(&mut locks, waiter, waiter_fd, fcntl::F_SETLKW, &mut fs).is_empty());
+
+        assert_eq!(outcomes(&locks.release_process(owner)), [(waiter, 0)]);
+        assert!(locks.release_process(waiter).is_empty());
+        assert!(locks.files.is_empty() && locks.pending.is_empty());
+    }
+
+    #[test]
+    fn disconnect_releases_all_locks() {
+        let (owner, waiter): (ProcessIdentifier, ProcessIdentifier) =
+            (ProcessIdentifier::from(2), ProcessIdentifier::from(3));
+        let mut fs: MemoryFileSystem = MemoryFileSystem::new();
+        let mut locks: FileLockManager = FileLockManager::new();
+        let owner_fd: i32 = open(&mut fs, "file");
+        let waiter_fd: i32 = open(&mut fs, "file");
+
+        assert_eq!(
+            outcomes(&flock(&mut locks, owner, owner_fd, file::LOCK_EX, &mut fs)),
+            [(owner, 0)]
+        );
+        assert!(flock(&mut locks, waiter, waiter_fd, file::LOCK_EX, &mut fs).is_empty());
+
+        locks.release_all();
+        assert!(locks.files.is_empty() && locks.pending.is_empty());
+
+        let operation: i32 = file::LOCK_EX | file::LOCK_NB;
+        assert_eq!(
+            outcomes(&flock(&mut locks, waiter, waiter_fd, operation, &mut fs)),
+            [(waiter, 0)]
+        );
+    }
+}
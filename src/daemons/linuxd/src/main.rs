@@ -12,11 +12,25 @@
 //==================================================================================================
 
 mod args;
+mod audit;
+mod auth;
+mod backend;
+mod capture;
 mod fcntl;
+mod inotify;
+mod lock;
 mod message;
+mod metrics;
+mod policy;
+mod protocol;
+mod resource;
+mod stdio;
+mod stdlib;
 mod time;
 mod unistd;
 mod venv;
+mod worker;
+mod xattr;
 
 //==================================================================================================
 // Imports
@@ -30,32 +44,79 @@ extern crate alloc;
 
 use self::{
     args::Args,
+    audit::AuditLog,
+    auth::Authenticator,
+    backend::{
+        FileSystem,
+        FileSystems,
+    },
+    capture::Capture,
+    inotify::WatchManager,
+    lock::FileLockManager,
     message::{
         RequestAssembler,
-        RequestAssemblerTrait,
-        RequestAssemblerType,
+        RequestProcessorTrait,
+    },
+    metrics::{
+        Metrics,
+        MetricsRecorder,
+    },
+    policy::{
+        AccessPolicy,
+        AccessRequest,
+        AccessSubject,
     },
+    resource::ResourceManager,
+    stdio::StandardStreams,
     venv::VirtualEnviromentDirectory,
+    worker::{
+        FileDescriptorRequest,
+        WorkerPool,
+    },
+    xattr::{
+        XattrNamespaces,
+        XattrPolicy,
+    },
 };
 use ::anyhow::Result;
 use ::flexi_logger::Logger;
 use ::linuxd::{
+    capture::Direction,
     fcntl::message::{
         FileAdvisoryInformationRequest,
         FileSpaceControlRequest,
         OpenAtRequest,
         ReadLinkAtRequest,
+        RecordLockRequest,
+        RenameAt2Request,
         RenameAtRequest,
         SymbolicLinkAtRequest,
         UnlinkAtRequest,
     },
     message::{
-        LinuxDaemonLongMessage,
         LinuxDaemonMessagePart,
+        MessagePartitioner,
     },
-    sys::stat::message::{
-        FileStatAtRequest,
-        FileStatRequest,
+    protocol::{
+        Hello,
+        FRAMED_PARTS_PROTOCOL_VERSION,
+    },
+    stdlib::message::RealPathRequest,
+    sys::{
+        file::message::FileLockRequest,
+        inotify::message::{
+            InotifyAddWatchRequest,
+            InotifyRemoveWatchRequest,
+        },
+        resource::message::{
+            GetResourceLimitRequest,
+            GetResourceUsageRequest,
+        },
+        sendfile::message::SendFileRequest,
+        stat::message::{
+            FileStatAtRequest,
+            FileStatRequest,
+        },
     },
     time::message::{
         ClockResolutionRequest,
@@ -63,6 +124,7 @@ use ::linuxd::{
     },
     unistd::message::{
         CloseRequest,
+        CopyFileRangeRequest,
         FileDataSyncRequest,
         FileSyncRequest,
         FileTruncateRequest,
@@ -71,11 +133,15 @@ use ::linuxd::{
         PartialWriteRequest,
         ReadRequest,
         SeekRequest,
+        TruncateRequest,
         WriteRequest,
     },
-    venv::message::{
-        JoinEnvRequest,
-        LeaveEnvRequest,
+    venv::{
+        message::{
+            JoinEnvRequest,
+            LeaveEnvRequest,
+        },
+        VirtualEnvironmentIdentifier,
     },
     LinuxDaemonMessage,
     LinuxDaemonMessageHeader,
@@ -96,6 +162,8 @@ use ::nvx::{
 };
 use ::std::{
     env,
+    ffi::OsString,
+    fs,
     io::{
         ErrorKind,
         Read,
@@ -105,18 +173,89 @@ use ::std::{
         TcpListener,
         TcpStream,
     },
-    sync::Once,
+    os::fd::AsRawFd,
+    path::{
+        Path,
+        PathBuf,
+    },
+    sync::{
+        Arc,
+        Mutex,
+        Once,
+    },
+    thread,
+    time::Duration,
 };
 
+//==================================================================================================
+// Constants
+//==================================================================================================
+
+/// Time to wait before accepting connections again, after failing to.
+const ACCEPT_RETRY_DELAY: Duration = Duration::from_millis(100);
+
 //==================================================================================================
 // Structures
 //==================================================================================================
 
+///
+/// # Description
+///
+/// Outcome of reading a frame from the TCP stream.
+///
+enum Frame {
+    /// Frame that decodes to a message.
+    Message(Message),
+    /// Frame that does not decode to a message.
+    Malformed(Error),
+    /// Connection closed by the client.
+    Closed,
+}
+
+///
+/// # Description
+///
+/// State that is set up for a connection before it is served.
+///
+pub struct Session {
+    /// Policy for extended attribute namespaces.
+    pub xattr: XattrPolicy,
+    /// Mapping of standard streams.
+    pub stdio: StandardStreams,
+    /// Filesystem backends of virtual environments.
+    pub fs: FileSystems,
+    /// Resource quotas of virtual environments.
+    pub resources: ResourceManager,
+    /// Access policy of virtual environments.
+    pub policy: AccessPolicy,
+    /// Audit log of completed requests.
+    pub audit: AuditLog,
+    /// Capture of exchanged frames.
+    pub capture: Capture,
+    /// Recorder of per-operation metrics.
+    pub metrics: MetricsRecorder,
+    /// Pool of workers that run blocking operations.
+    pub workers: WorkerPool,
+}
+
 pub struct ProcessDaemon {
     pid: ProcessIdentifier,
     assembler: RequestAssembler,
     stream: TcpStream,
+    peer: Hello,
     venv: VirtualEnviromentDirectory,
+    locks: FileLockManager,
+    xattr: XattrPolicy,
+    root: PathBuf,
+    watches: WatchManager,
+    stdio: StandardStreams,
+    fs: FileSystems,
+    resources: ResourceManager,
+    policy: AccessPolicy,
+    audit: AuditLog,
+    metrics: MetricsRecorder,
+    capture: Capture,
+    workers: WorkerPool,
 }
 
 //==================================================================================================
@@ -124,27 +263,67 @@ pub struct ProcessDaemon {
 //==================================================================================================
 
 impl ProcessDaemon {
-    pub fn init(stream: TcpStream) -> Result<Self, Error> {
+    pub fn init(
+        stream: TcpStream,
+        peer: Hello,
+        root: PathBuf,
+        session: Session,
+    ) -> Result<Self, Error> {
+        let Session {
+            xattr,
+            stdio,
+            fs,
+            resources,
+            policy,
+            audit,
+            capture,
+            metrics,
+            workers,
+        } = session;
+
         Ok(Self {
             pid: ProcessIdentifier::from(0),
             assembler: RequestAssembler::default(),
             stream,
+            peer,
             venv: VirtualEnviromentDirectory::new(),
+            locks: FileLockManager::new(),
+            xattr,
+            root,
+            watches: WatchManager::new()?,
+            stdio,
+            fs,
+            resources,
+            policy,
+            audit,
+            metrics,
+            capture,
+            workers,
         })
     }
 
     pub fn run(&mut self) {
         loop {
+            // Deliver filesystem events until a request arrives.
+            if !self.poll() {
+                continue;
+            }
+
             let message: Message = match self.recv() {
-                Ok(Some(message)) => message,
-                Ok(None) => {
+                Ok(Frame::Message(message)) => message,
+                Ok(Frame::Malformed(e)) => {
+                    // The sender of a frame that does not decode is unknown, so it cannot be
+                    // answered.
+                    error!("dropped malformed frame (error={:?})", e);
+                    continue;
+                },
+                Ok(Frame::Closed) => {
                     info!("connection closed");
                     break;
                 },
-
                 Err(e) => {
-                    error!("failed to receive message (error={:?})", e);
-                    continue;
+                    error!("connection broken (error={:?})", e);
+                    break;
                 },
             };
 
@@ -155,178 +334,473 @@ impl ProcessDaemon {
                 message.message_type,
             );
 
-            let source = message.source;
+            // Requests of a process run in order, so they wait for the one that a worker runs.
+            if self.workers.is_busy(message.source) {
+                self.workers.defer(message);
+                continue;
+            }
 
-            match message.message_type {
-                nvx::ipc::MessageType::Empty => panic!("received empty message"),
-                nvx::ipc::MessageType::Interrupt => panic!("received interrupt message"),
-                nvx::ipc::MessageType::Exception => panic!("received exception message"),
-                nvx::ipc::MessageType::Ipc => panic!("received IPC message"),
-                nvx::ipc::MessageType::SchedulingEvent => {
-                    panic!("received scheduling event message")
-                },
-                nvx::ipc::MessageType::Ikc => {
-                    match LinuxDaemonMessage::try_from_bytes(message.payload) {
-                        Ok(message) => {
-                            let message: Message = match message.header {
-                                LinuxDaemonMessageHeader::JoinEnvRequest => {
-                                    let request: JoinEnvRequest =
-                                        JoinEnvRequest::from_bytes(message.payload);
-                                    self.venv.join(source, request)
-                                },
-                                LinuxDaemonMessageHeader::LeaveEnvRequest => {
-                                    let request: LeaveEnvRequest =
-                                        LeaveEnvRequest::from_bytes(message.payload);
-                                    self.venv.leave(source, request)
-                                },
-                                LinuxDaemonMessageHeader::GetClockResolutionRequest => {
-                                    let request: ClockResolutionRequest =
-                                        ClockResolutionRequest::from_bytes(message.payload);
-                                    time::do_clock_getres(source, request)
-                                },
-                                LinuxDaemonMessageHeader::GetClockTimeRequest => {
-                                    let request: GetClockTimeRequest =
-                                        GetClockTimeRequest::from_bytes(message.payload);
-                                    time::do_clock_gettime(source, request)
-                                },
-                                LinuxDaemonMessageHeader::OpenAtRequest => {
-                                    let request: OpenAtRequest =
-                                        OpenAtRequest::from_bytes(message.payload);
-                                    fcntl::do_open_at(source, request)
-                                },
-                                LinuxDaemonMessageHeader::UnlinkAtRequest => {
-                                    let request: UnlinkAtRequest =
-                                        UnlinkAtRequest::from_bytes(message.payload);
-                                    fcntl::do_unlink_at(source, request)
-                                },
-                                LinuxDaemonMessageHeader::CloseRequest => {
-                                    let request: CloseRequest =
-                                        CloseRequest::from_bytes(message.payload);
-                                    unistd::do_close(source, request)
-                                },
-                                LinuxDaemonMessageHeader::RenameAtRequest => {
-                                    let request: RenameAtRequest =
-                                        RenameAtRequest::from_bytes(message.payload);
-                                    fcntl::do_rename_at(source, request)
-                                },
-                                LinuxDaemonMessageHeader::FileStatAtRequestPart => {
-                                    self.handle_fstatat_request(source, message);
-                                    continue;
-                                },
-                                LinuxDaemonMessageHeader::FileDataSyncRequest => {
-                                    let request: FileDataSyncRequest =
-                                        FileDataSyncRequest::from_bytes(message.payload);
-                                    unistd::do_fdatasync(source, request)
-                                },
-                                LinuxDaemonMessageHeader::FileSyncRequest => {
-                                    let request: FileSyncRequest =
-                                        FileSyncRequest::from_bytes(message.payload);
-                                    unistd::do_fsync(source, request)
-                                },
-                                LinuxDaemonMessageHeader::SeekRequest => {
-                                    let request: SeekRequest =
-                                        SeekRequest::from_bytes(message.payload);
-                                    unistd::do_lseek(source, request)
-                                },
-                                LinuxDaemonMessageHeader::FileSpaceControlRequest => {
-                                    let request: FileSpaceControlRequest =
-                                        FileSpaceControlRequest::from_bytes(message.payload);
-                                    fcntl::do_posix_fallocate(source, request)
-                                },
-                                LinuxDaemonMessageHeader::FileTruncateRequest => {
-                                    let request: FileTruncateRequest =
-                                        FileTruncateRequest::from_bytes(message.payload);
-                                    unistd::do_ftruncate(source, request)
-                                },
-                                LinuxDaemonMessageHeader::FileAdvisoryInformationRequest => {
-                                    let request: FileAdvisoryInformationRequest =
-                                        FileAdvisoryInformationRequest::from_bytes(message.payload);
-                                    fcntl::do_posix_fadvise(source, request)
-                                },
-                                LinuxDaemonMessageHeader::FileStatRequest => {
-                                    self.handle_fstat_request(source, message);
-                                    continue;
-                                },
-                                LinuxDaemonMessageHeader::WriteRequest => {
-                                    let request: WriteRequest =
-                                        WriteRequest::from_bytes(message.payload);
-                                    unistd::do_write(source, request)
-                                },
-                                LinuxDaemonMessageHeader::ReadRequest => {
-                                    let request: ReadRequest =
-                                        ReadRequest::from_bytes(message.payload);
-                                    unistd::do_read(source, request)
-                                },
-                                LinuxDaemonMessageHeader::PartialWriteRequest => {
-                                    let request: PartialWriteRequest =
-                                        PartialWriteRequest::from_bytes(message.payload);
-                                    unistd::do_pwrite(source, request)
-                                },
-                                LinuxDaemonMessageHeader::PartialReadRequest => {
-                                    let request: PartialReadRequest =
-                                        PartialReadRequest::from_bytes(message.payload);
-                                    unistd::do_pread(source, request)
-                                },
-                                LinuxDaemonMessageHeader::SymbolicLinkAtRequestPart => {
-                                    self.handle_symlinkat_request(source, message);
-                                    continue;
-                                },
-                                LinuxDaemonMessageHeader::LinkAtRequestPart => {
-                                    debug!("received linkat request");
-                                    self.handle_linkat_request(source, message);
-                                    continue;
-                                },
-                                LinuxDaemonMessageHeader::ReadLinkAtRequestPart => {
-                                    self.handle_readlinkat_request(source, message);
-                                    continue;
-                                },
-                                _ => self.do_error(source, ErrorCode::InvalidMessage),
-                            };
-                            self.send(message).unwrap();
-                        },
-                        Err(e) => {
-                            error!("failed to parse Linux daemon message (error={:?})", e);
-                            continue;
-                        },
-                    }
-                },
+            self.dispatch(message);
+        }
+
+        // Locks live in the lock table of the daemon, not on the host, so that table is purged
+        // whenever processes leave and once the connection goes away.
+        self.locks.release_all();
+    }
+
+    // Handle a message.
+    fn dispatch(&mut self, message: Message) {
+        let source: ProcessIdentifier = message.source;
+
+        match message.message_type {
+            nvx::ipc::MessageType::Empty
+            | nvx::ipc::MessageType::Interrupt
+            | nvx::ipc::MessageType::Exception
+            | nvx::ipc::MessageType::Ipc
+            | nvx::ipc::MessageType::SchedulingEvent => {
+                error!("unexpected message (type={:?})", message.message_type);
+                self.audit.begin(source, self.venv.lookup(source), None);
+                self.metrics.begin(source, self.venv.lookup(source), None);
+                let response: Message = self.do_error(source, ErrorCode::InvalidMessage);
+                self.send_all(vec![response]);
+            },
+            nvx::ipc::MessageType::Ikc => {
+                match LinuxDaemonMessage::try_from_bytes(message.payload) {
+                    Ok(message) => {
+                        let env: Option<VirtualEnvironmentIdentifier> = self.venv.lookup(source);
+                        self.audit.begin(source, env, Some(&message));
+                        self.metrics.begin(source, env, Some(&message));
+                        if let Err(errno) = self.resources.admit(env, message.header) {
+                            self.send_all(vec![build_errno(source, errno)]);
+                            return;
+                        }
+
+                        // Backends are instantiated on first use, which fails if, for instance, the
+                        // upper layer of an overlay cannot be set up.
+                        if let Err(errno) = self.fs.instantiate(env) {
+                            self.send_all(vec![build_errno(source, errno)]);
+                            return;
+                        }
+
+                        // Multipart requests are checked once assembled.
+                        if let Some(request) = AccessRequest::from_message(&message) {
+                            if let Err(errno) = self.authorize(source, &request) {
+                                self.send_all(vec![build_errno(source, errno)]);
+                                return;
+                            }
+                        }
+
+                        let message: Message = match message.header {
+                            LinuxDaemonMessageHeader::JoinEnvRequest => {
+                                let request: JoinEnvRequest =
+                                    JoinEnvRequest::from_bytes(message.payload);
+                                self.venv.join(source, request)
+                            },
+                            LinuxDaemonMessageHeader::LeaveEnvRequest => {
+                                let request: LeaveEnvRequest =
+                                    LeaveEnvRequest::from_bytes(message.payload);
+                                self.handle_leave_request(source, request);
+                                return;
+                            },
+                            LinuxDaemonMessageHeader::GetClockResolutionRequest => {
+                                let request: ClockResolutionRequest =
+                                    ClockResolutionRequest::from_bytes(message.payload);
+                                time::do_clock_getres(source, request)
+                            },
+                            LinuxDaemonMessageHeader::GetClockTimeRequest => {
+                                let request: GetClockTimeRequest =
+                                    GetClockTimeRequest::from_bytes(message.payload);
+                                time::do_clock_gettime(source, request)
+                            },
+                            LinuxDaemonMessageHeader::OpenAtRequest => {
+                                match OpenAtRequest::try_from_bytes(message.payload) {
+                                    Ok(request) => {
+                                        fcntl::do_open_at(source, request, self.backend(source))
+                                    },
+                                    Err(e) => {
+                                        error!("malformed openat request (error={:?})", e);
+                                        self.do_error(source, e.code)
+                                    },
+                                }
+                            },
+                            LinuxDaemonMessageHeader::UnlinkAtRequest => {
+                                let request: UnlinkAtRequest =
+                                    UnlinkAtRequest::from_bytes(message.payload);
+                                fcntl::do_unlink_at(source, request, self.backend(source))
+                            },
+                            LinuxDaemonMessageHeader::CloseRequest => {
+                                let request: CloseRequest =
+                                    CloseRequest::from_bytes(message.payload);
+                                self.handle_close_request(source, request);
+                                return;
+                            },
+                            LinuxDaemonMessageHeader::RenameAtRequest => {
+                                let request: RenameAtRequest =
+                                    RenameAtRequest::from_bytes(message.payload);
+                                fcntl::do_rename_at(source, request, self.backend(source))
+                            },
+                            LinuxDaemonMessageHeader::FileStatAtRequestPart => {
+                                self.handle_multipart_request::<FileStatAtRequest>(source, message);
+                                return;
+                            },
+                            LinuxDaemonMessageHeader::FileDataSyncRequest => {
+                                let request: FileDataSyncRequest =
+                                    FileDataSyncRequest::from_bytes(message.payload);
+                                match self.offload(source, env, request, unistd::do_fdatasync) {
+                                    Some(response) => response,
+                                    None => return,
+                                }
+                            },
+                            LinuxDaemonMessageHeader::FileSyncRequest => {
+                                let request: FileSyncRequest =
+                                    FileSyncRequest::from_bytes(message.payload);
+                                match self.offload(source, env, request, unistd::do_fsync) {
+                                    Some(response) => response,
+                                    None => return,
+                                }
+                            },
+                            LinuxDaemonMessageHeader::SeekRequest => {
+                                let request: SeekRequest = SeekRequest::from_bytes(message.payload);
+                                unistd::do_lseek(source, request, self.backend(source))
+                            },
+                            LinuxDaemonMessageHeader::FileSpaceControlRequest => {
+                                let request: FileSpaceControlRequest =
+                                    FileSpaceControlRequest::from_bytes(message.payload);
+                                fcntl::do_posix_fallocate(source, request, self.backend(source))
+                            },
+                            LinuxDaemonMessageHeader::FileTruncateRequest => {
+                                let request: FileTruncateRequest =
+                                    FileTruncateRequest::from_bytes(message.payload);
+                                unistd::do_ftruncate(source, request, self.backend(source))
+                            },
+                            LinuxDaemonMessageHeader::FileAdvisoryInformationRequest => {
+                                let request: FileAdvisoryInformationRequest =
+                                    FileAdvisoryInformationRequest::from_bytes(message.payload);
+                                fcntl::do_posix_fadvise(source, request, self.backend(source))
+                            },
+                            LinuxDaemonMessageHeader::FileStatRequest => {
+                                self.handle_fstat_request(source, message);
+                                return;
+                            },
+                            LinuxDaemonMessageHeader::WriteRequest => {
+                                let request: WriteRequest =
+                                    WriteRequest::from_bytes(message.payload);
+                                if StandardStreams::is_standard(request.fd) {
+                                    self.stdio.write(source, env, request)
+                                } else {
+                                    unistd::do_write(source, request, self.backend(source))
+                                }
+                            },
+                            LinuxDaemonMessageHeader::ReadRequest => {
+                                let request: ReadRequest = ReadRequest::from_bytes(message.payload);
+                                if StandardStreams::is_standard(request.fd) {
+                                    self.stdio.read(source, env, request)
+                                } else {
+                                    match self.offload(source, env, request, unistd::do_read) {
+                                        Some(response) => response,
+                                        None => return,
+                                    }
+                                }
+                            },
+                            LinuxDaemonMessageHeader::PartialWriteRequest => {
+                                let request: PartialWriteRequest =
+                                    PartialWriteRequest::from_bytes(message.payload);
+                                // Standard streams are not seekable.
+                                if StandardStreams::is_standard(request.fd) {
+                                    build_errno(source, libc::ESPIPE)
+                                } else {
+                                    unistd::do_pwrite(source, request, self.backend(source))
+                                }
+                            },
+                            LinuxDaemonMessageHeader::PartialReadRequest => {
+                                let request: PartialReadRequest =
+                                    PartialReadRequest::from_bytes(message.payload);
+                                if StandardStreams::is_standard(request.fd) {
+                                    build_errno(source, libc::ESPIPE)
+                                } else {
+                                    match self.offload(source, env, request, unistd::do_pread) {
+                                        Some(response) => response,
+                                        None => return,
+                                    }
+                                }
+                            },
+                            LinuxDaemonMessageHeader::SymbolicLinkAtRequestPart => {
+                                self.handle_multipart_request::<SymbolicLinkAtRequest>(
+                                    source, message,
+                                );
+                                return;
+                            },
+                            LinuxDaemonMessageHeader::LinkAtRequestPart => {
+                                debug!("received linkat request");
+                                self.handle_multipart_request::<LinkAtRequest>(source, message);
+                                return;
+                            },
+                            LinuxDaemonMessageHeader::ReadLinkAtRequestPart => {
+                                self.handle_multipart_request::<ReadLinkAtRequest>(source, message);
+                                return;
+                            },
+                            LinuxDaemonMessageHeader::FileLockRequest => {
+                                let request: FileLockRequest =
+                                    FileLockRequest::from_bytes(message.payload);
+                                let messages: Vec<Message> =
+                                    self.locks.flock(source, request, self.fs.get(env));
+                                self.send_all(messages);
+                                return;
+                            },
+                            LinuxDaemonMessageHeader::RecordLockRequest => {
+                                let request: RecordLockRequest =
+                                    RecordLockRequest::from_bytes(message.payload);
+                                let messages: Vec<Message> =
+                                    self.locks.fcntl(source, request, self.fs.get(env));
+                                self.send_all(messages);
+                                return;
+                            },
+                            LinuxDaemonMessageHeader::CopyFileRangeRequest => {
+                                let request: CopyFileRangeRequest =
+                                    CopyFileRangeRequest::from_bytes(message.payload);
+                                unistd::do_copy_file_range(source, request, self.backend(source))
+                            },
+                            LinuxDaemonMessageHeader::SendFileRequest => {
+                                let request: SendFileRequest =
+                                    SendFileRequest::from_bytes(message.payload);
+                                unistd::do_sendfile(source, request, self.backend(source))
+                            },
+                            LinuxDaemonMessageHeader::GetXattrRequestPart => {
+                                self.handle_xattr_request(source, message, xattr::do_getxattr);
+                                return;
+                            },
+                            LinuxDaemonMessageHeader::SetXattrRequestPart => {
+                                self.handle_xattr_request(source, message, xattr::do_setxattr);
+                                return;
+                            },
+                            LinuxDaemonMessageHeader::ListXattrRequestPart => {
+                                self.handle_xattr_request(source, message, xattr::do_listxattr);
+                                return;
+                            },
+                            LinuxDaemonMessageHeader::RemoveXattrRequestPart => {
+                                self.handle_xattr_request(source, message, xattr::do_removexattr);
+                                return;
+                            },
+                            LinuxDaemonMessageHeader::TruncateRequestPart => {
+                                self.handle_multipart_request::<TruncateRequest>(source, message);
+                                return;
+                            },
+                            LinuxDaemonMessageHeader::RenameAt2RequestPart => {
+                                self.handle_multipart_request::<RenameAt2Request>(source, message);
+                                return;
+                            },
+                            LinuxDaemonMessageHeader::RealPathRequestPart => {
+                                self.handle_realpath_request(source, message);
+                                return;
+                            },
+                            LinuxDaemonMessageHeader::InotifyAddWatchRequestPart => {
+                                self.handle_inotify_add_watch_request(source, message);
+                                return;
+                            },
+                            LinuxDaemonMessageHeader::InotifyRemoveWatchRequest => {
+                                let request: InotifyRemoveWatchRequest =
+                                    InotifyRemoveWatchRequest::from_bytes(message.payload);
+                                let messages: Vec<Message> =
+                                    self.watches.remove_watch(source, request);
+                                self.send_all(messages);
+                                return;
+                            },
+                            LinuxDaemonMessageHeader::GetResourceLimitRequest => {
+                                let request: GetResourceLimitRequest =
+                                    GetResourceLimitRequest::from_bytes(message.payload);
+                                self.resources.do_getrlimit(source, env, request)
+                            },
+                            LinuxDaemonMessageHeader::GetResourceUsageRequest => {
+                                let request: GetResourceUsageRequest =
+                                    GetResourceUsageRequest::from_bytes(message.payload);
+                                self.resources.do_getrusage(source, env, request, &self.fs)
+                            },
+                            _ => self.do_error(source, ErrorCode::InvalidMessage),
+                        };
+                        self.send_all(vec![message]);
+                    },
+                    Err(e) => {
+                        // Requests of newer clients are not understood.
+                        error!("failed to parse Linux daemon message (error={:?})", e);
+                        self.audit.begin(source, self.venv.lookup(source), None);
+                        self.metrics.begin(source, self.venv.lookup(source), None);
+                        self.send_all(vec![build_errno(source, libc::ENOSYS)]);
+                    },
+                }
+            },
+        }
+    }
+
+    // Wait until either a message, a filesystem event or a completed job is available, delivering
+    // the latter two. Returns whether a message can be read from the TCP stream.
+    fn poll(&mut self) -> bool {
+        let mut fds: [libc::pollfd; 3] = [
+            libc::pollfd {
+                fd: self.stream.as_raw_fd(),
+                events: libc::POLLIN,
+                revents: 0,
+            },
+            libc::pollfd {
+                fd: self.watches.fd(),
+                events: libc::POLLIN,
+                revents: 0,
+            },
+            libc::pollfd {
+                fd: self.workers.fd(),
+                events: libc::POLLIN,
+                revents: 0,
+            },
+        ];
+
+        if unsafe { libc::poll(fds.as_mut_ptr(), fds.len() as libc::nfds_t, -1) } < 0 {
+            let errno: i32 = unsafe { *libc::__errno_location() };
+            if errno == libc::EINTR {
+                return false;
             }
+            // Fall back to a blocking read on the TCP stream.
+            error!("libc::poll(): errno={:?}", errno);
+            return true;
         }
+
+        if (fds[1].revents & libc::POLLIN) != 0 {
+            let messages: Vec<Message> = self.watches.read_events();
+            self.send_all(messages);
+        }
+
+        if (fds[2].revents & libc::POLLIN) != 0 {
+            self.complete_jobs();
+        }
+
+        // Hang ups and errors are reported by the subsequent read.
+        fds[0].revents != 0
     }
 
-    // Read a message from the TCP stream.
-    fn recv(&mut self) -> Result<Option<Message>> {
+    // Read a frame from the TCP stream. Fails if the connection is broken.
+    fn recv(&mut self) -> Result<Frame> {
         let mut buf = [0u8; config::kernel::IPC_MESSAGE_SIZE];
-        let mut buf_reader = std::io::BufReader::new(&self.stream);
-        if let Err(e) = buf_reader.read_exact(&mut buf) {
-            match e.kind() {
-                ErrorKind::UnexpectedEof => return Ok(None),
-                _ => {
-                    let reason: String = format!("failed to read message (error={:?})", e);
-                    unimplemented!("handle: {}", reason);
-                },
-            }
+        if let Err(e) = self.stream.read_exact(&mut buf) {
+            return match e.kind() {
+                ErrorKind::UnexpectedEof => Ok(Frame::Closed),
+                _ => Err(anyhow::anyhow!("failed to read message (error={:?})", e)),
+            };
         };
+        self.capture.record(Direction::Inbound, &buf);
 
-        let message = match Message::try_from_bytes(buf) {
-            Ok(message) => message,
-            Err(e) => {
-                let reason: String = format!("failed to parse message (error={:?})", e);
-                unimplemented!("handle: {}", reason);
+        match Message::try_from_bytes(buf) {
+            Ok(message) => Ok(Frame::Message(message)),
+            Err(e) => Ok(Frame::Malformed(e)),
+        }
+    }
+
+    // Send a message to the TCP stream. Fails if the connection is broken.
+    fn send(&mut self, message: Message) -> Result<()> {
+        let message: Message = self.downgrade(message);
+        self.audit.complete(&message);
+        self.metrics.complete(&message);
+        let bytes = message.to_bytes();
+        self.capture.record(Direction::Outbound, &bytes);
+        self.stream
+            .write_all(&bytes)
+            .map_err(|e| anyhow::anyhow!("failed to write message (error={:?})", e))
+    }
+
+    // Replace responses that the client does not understand with an error.
+    fn downgrade(&self, message: Message) -> Message {
+        if message.status != 0 {
+            return message;
+        }
+
+        match LinuxDaemonMessage::try_from_bytes(message.payload) {
+            Ok(response) if !self.peer.capabilities.supports(response.header) => {
+                debug!("downgrade(): unsupported response (header={:?})", { response.header });
+                build_errno(message.destination, libc::ENOSYS)
             },
+            _ => message,
+        }
+    }
+
+    // Send a batch of messages to the TCP stream.
+    fn send_all(&mut self, messages: Vec<Message>) {
+        for message in messages {
+            if let Err(e) = self.send(message) {
+                error!("failed to send message (error={:?})", e);
+            }
+        }
+    }
+
+    // Get the filesystem backend of the environment of a process.
+    fn backend(&mut self, source: ProcessIdentifier) -> &mut dyn FileSystem {
+        let env: Option<VirtualEnvironmentIdentifier> = self.venv.lookup(source);
+        self.fs.get(env)
+    }
+
+    // Hand a blocking request on a file descriptor over to a worker, if the environment of the
+    // process uses the host filesystem, or handle it inline otherwise. Returns the response, unless
+    // the request was handed over.
+    fn offload<R: FileDescriptorRequest + Send + 'static>(
+        &mut self,
+        source: ProcessIdentifier,
+        env: Option<VirtualEnvironmentIdentifier>,
+        mut request: R,
+        handler: fn(ProcessIdentifier, R, &mut dyn FileSystem) -> Message,
+    ) -> Option<Message> {
+        // Other backends keep file descriptors of their own, which workers cannot reach.
+        if !self.fs.is_host(env) {
+            return Some(handler(source, request, self.backend(source)));
+        }
+
+        let fd: i32 = request.fd();
+        let operation = move |fs: &mut dyn FileSystem, fd: i32| {
+            request.set_fd(fd);
+            handler(source, request, fs)
         };
+        match self.workers.submit(source, fd, operation) {
+            Ok(()) => None,
+            Err(operation) => Some(operation(self.backend(source), fd)),
+        }
+    }
 
-        Ok(Some(message))
+    // Send the responses of jobs that workers completed, then resume the messages that their
+    // processes sent in the meantime.
+    fn complete_jobs(&mut self) {
+        for (source, response) in self.workers.complete() {
+            self.send_all(vec![response]);
+            while let Some(message) = self.workers.resume(source) {
+                self.dispatch(message);
+            }
+        }
     }
 
-    // Send a message to the TCP stream.
-    fn send(&mut self, message: Message) -> Result<()> {
-        let bytes = message.to_bytes();
-        match self.stream.write_all(&bytes) {
-            Ok(_) => Ok(()),
+    // Check a request against the access policy of the environment of a process.
+    fn authorize(&mut self, source: ProcessIdentifier, request: &AccessRequest) -> Result<(), i32> {
+        self.audit.describe(source, request);
+        let env: Option<VirtualEnvironmentIdentifier> = self.venv.lookup(source);
+        self.policy.check(source, env, request, self.fs.get(env))
+    }
+
+    // Parse a part of a multipart request, replying with an error if it is malformed.
+    fn parse_part(
+        &mut self,
+        source: ProcessIdentifier,
+        message: LinuxDaemonMessage,
+    ) -> Option<LinuxDaemonMessagePart> {
+        // Parts of older protocol versions are laid out differently and cannot be assembled.
+        if self.peer.version < FRAMED_PARTS_PROTOCOL_VERSION {
+            error!("unsupported message part (version={:?})", self.peer.version);
+            // Those parts open with their part number, so reply once, to the last part.
+            if message.payload[..4] == [0; 4] {
+                self.send_all(vec![build_errno(source, libc::ENOSYS)]);
+            }
+            return None;
+        }
+
+        match LinuxDaemonMessagePart::try_from_bytes(message.payload) {
+            Ok(part) => Some(part),
             Err(e) => {
-                let reason: String = format!("failed to write message (error={:?})", e);
-                unimplemented!("handle: {}", reason);
+                error!("failed to parse message part (error={:?})", e);
+                let response: Message = self.do_error(source, e.code);
+                self.send_all(vec![response]);
+                None
             },
         }
     }
@@ -335,33 +809,51 @@ impl ProcessDaemon {
         Message::new(self.pid, source, MessageType::Ikc, Some(code), [0u8; Message::PAYLOAD_SIZE])
     }
 
-    fn handle_fstatat_request(&mut self, source: ProcessIdentifier, message: LinuxDaemonMessage) {
-        let part: LinuxDaemonMessagePart = LinuxDaemonMessagePart::from_bytes(message.payload);
+    fn handle_leave_request(&mut self, source: ProcessIdentifier, request: LeaveEnvRequest) {
+        let env: VirtualEnvironmentIdentifier = request.env;
+        let mut messages: Vec<Message> = vec![self.venv.leave(source, request)];
 
-        match self
-            .assembler
-            .process_message::<FileStatAtRequest>(source, part)
-        {
-            Ok(Some(messages)) => {
-                for message in messages {
-                    if let Err(e) = self.send(message) {
-                        error!("failed to send message (error={:?})", e);
-                    }
-                }
-            },
-            Ok(None) => {},
-            Err(e) => {
-                if let Err(e) = self.send(self.do_error(source, e.code)) {
-                    error!("failed to send error message (error={:?})", e);
-                }
-            },
+        // Locks, watches and inflight requests do not outlive the process membership to an
+        // environment.
+        if messages[0].status == 0 {
+            messages.extend(self.locks.release_process(source));
+            self.watches.release_process(source);
+            self.assembler.release_process(source);
+            self.stdio.release_process(source);
+
+            // Private filesystem state goes away along with the environment.
+            if !self.venv.is_active(env) {
+                self.fs.release(env);
+                self.resources.release(env);
+            }
+        }
+
+        self.send_all(messages);
+    }
+
+    fn handle_close_request(&mut self, source: ProcessIdentifier, request: CloseRequest) {
+        // Standard streams are shared with the environment and never closed on the host.
+        if StandardStreams::is_standard(request.fd) {
+            let response: Message = self.stdio.close(source, request);
+            self.send_all(vec![response]);
+            return;
         }
+
+        // Locks must be released while the file descriptor is still valid.
+        let env: Option<VirtualEnvironmentIdentifier> = self.venv.lookup(source);
+        let wakeups: Vec<Message> = self.locks.release_fd(source, request.fd, self.fs.get(env));
+
+        let mut messages: Vec<Message> =
+            vec![unistd::do_close(source, request, self.backend(source))];
+        messages.extend(wakeups);
+
+        self.send_all(messages);
     }
 
     fn handle_fstat_request(&mut self, source: ProcessIdentifier, message: LinuxDaemonMessage) {
         let request: FileStatRequest = FileStatRequest::from_bytes(message.payload);
 
-        let messages = fcntl::do_fstat(source, request);
+        let messages = fcntl::do_fstat(source, request, self.backend(source));
         for message in messages {
             if let Err(e) = self.send(message) {
                 error!("failed to send message (error={:?})", e);
@@ -369,78 +861,118 @@ impl ProcessDaemon {
         }
     }
 
-    fn handle_symlinkat_request(&mut self, source: ProcessIdentifier, message: LinuxDaemonMessage) {
-        let part: LinuxDaemonMessagePart = LinuxDaemonMessagePart::from_bytes(message.payload);
+    fn handle_xattr_request<T: MessagePartitioner + AccessSubject>(
+        &mut self,
+        source: ProcessIdentifier,
+        message: LinuxDaemonMessage,
+        handler: fn(ProcessIdentifier, T, &XattrNamespaces) -> Vec<Message>,
+    ) {
+        let part: LinuxDaemonMessagePart = match self.parse_part(source, message) {
+            Some(part) => part,
+            None => return,
+        };
 
-        match self
-            .assembler
-            .process_message::<SymbolicLinkAtRequest>(source, part)
-        {
-            Ok(Some(messages)) => {
-                for message in messages {
-                    if let Err(e) = self.send(message) {
-                        error!("failed to send message (error={:?})", e);
-                    }
+        let messages: Vec<Message> = match self.assembler.assemble::<T>(source, part) {
+            Ok(Some(request)) => {
+                let env: Option<VirtualEnvironmentIdentifier> = self.venv.lookup(source);
+                // Extended attributes are only available on the host filesystem.
+                if !self.fs.is_host(env) {
+                    vec![build_errno(source, libc::EOPNOTSUPP)]
+                } else if let Err(errno) = self.authorize(source, &request.describe()) {
+                    vec![build_errno(source, errno)]
+                } else {
+                    handler(source, request, self.xattr.namespaces(env))
                 }
             },
-            Ok(None) => {},
+            Ok(None) => return,
             Err(e) => {
-                if let Err(e) = self.send(self.do_error(source, e.code)) {
-                    error!("failed to send error message (error={:?})", e);
-                }
+                error!("failed to process xattr request (error={:?})", e);
+                vec![self.do_error(source, e.code)]
             },
-        }
+        };
+
+        self.send_all(messages);
     }
-    fn handle_linkat_request(&mut self, source: ProcessIdentifier, message: LinuxDaemonMessage) {
-        let part: LinuxDaemonMessagePart = LinuxDaemonMessagePart::from_bytes(message.payload);
 
-        match self
-            .assembler
-            .process_message::<LinkAtRequest>(source, part)
+    fn handle_multipart_request<T: RequestProcessorTrait + AccessSubject>(
+        &mut self,
+        source: ProcessIdentifier,
+        message: LinuxDaemonMessage,
+    ) {
+        let part: LinuxDaemonMessagePart = match self.parse_part(source, message) {
+            Some(part) => part,
+            None => return,
+        };
+
+        let messages: Vec<Message> = match self.assembler.assemble::<T>(source, part) {
+            Ok(Some(request)) => match self.authorize(source, &request.describe()) {
+                Ok(()) => T::process_request(source, request, self.backend(source)),
+                Err(errno) => vec![build_errno(source, errno)],
+            },
+            Ok(None) => return,
+            Err(e) => {
+                error!("failed to process request (error={:?})", e);
+                vec![self.do_error(source, e.code)]
+            },
+        };
+
+        self.send_all(messages);
+    }
+
+    fn handle_realpath_request(&mut self, source: ProcessIdentifier, message: LinuxDaemonMessage) {
+        let part: LinuxDaemonMessagePart = match self.parse_part(source, message) {
+            Some(part) => part,
+            None => return,
+        };
+
+        let messages: Vec<Message> = match self.assembler.assemble::<RealPathRequest>(source, part)
         {
-            Ok(Some(messages)) => {
-                for message in messages {
-                    if let Err(e) = self.send(message) {
-                        error!("failed to send message (error={:?})", e);
-                    }
-                }
+            Ok(Some(request)) => match self.authorize(source, &request.describe()) {
+                Ok(()) => stdlib::do_realpath(source, request, self.backend(source)),
+                Err(errno) => vec![build_errno(source, errno)],
             },
-            Ok(None) => {},
+            Ok(None) => return,
             Err(e) => {
-                error!("failed to process linkat request (error={:?})", e);
-                if let Err(e) = self.send(self.do_error(source, e.code)) {
-                    error!("failed to send error message (error={:?})", e);
-                }
+                error!("failed to process realpath request (error={:?})", e);
+                vec![self.do_error(source, e.code)]
             },
-        }
+        };
+
+        self.send_all(messages);
     }
 
-    fn handle_readlinkat_request(
+    fn handle_inotify_add_watch_request(
         &mut self,
         source: ProcessIdentifier,
         message: LinuxDaemonMessage,
     ) {
-        let part: LinuxDaemonMessagePart = LinuxDaemonMessagePart::from_bytes(message.payload);
+        let part: LinuxDaemonMessagePart = match self.parse_part(source, message) {
+            Some(part) => part,
+            None => return,
+        };
 
-        match self
+        let messages: Vec<Message> = match self
             .assembler
-            .process_message::<ReadLinkAtRequest>(source, part)
+            .assemble::<InotifyAddWatchRequest>(source, part)
         {
-            Ok(Some(messages)) => {
-                for message in messages {
-                    if let Err(e) = self.send(message) {
-                        error!("failed to send message (error={:?})", e);
-                    }
+            Ok(Some(request)) => {
+                // Watches are backed by host inotify, so they only see the host filesystem.
+                if !self.fs.is_host(self.venv.lookup(source)) {
+                    vec![build_errno(source, libc::EOPNOTSUPP)]
+                } else if let Err(errno) = self.authorize(source, &request.describe()) {
+                    vec![build_errno(source, errno)]
+                } else {
+                    self.watches.add_watch(source, request, &self.root)
                 }
             },
-            Ok(None) => {},
+            Ok(None) => return,
             Err(e) => {
-                error!("failed to process readlinkat request (error={:?})", e);
-                if let Err(e) = self.send(self.do_error(source, e.code)) {
-                    error!("failed to send error message (error={:?})", e);
-                }
+                error!("failed to process inotify_add_watch request (error={:?})", e);
+                vec![self.do_error(source, e.code)]
             },
-        }
+        };
+
+        self.send_all(messages);
     }
 }
 
@@ -451,30 +983,205 @@ pub fn main() -> Result<()> {
     let args: Args = args::Args::parse(env::args().collect())?;
     let sockaddr: String = args.server_sockaddr();
 
+    // Metrics outlive connections. Signals are set up first, so that later threads do not get them.
+    let metrics: Option<Arc<Mutex<Metrics>>> =
+        if args.metrics().is_some() || args.metrics_port().is_some() {
+            Some(Arc::new(Mutex::new(Metrics::new())))
+        } else {
+            None
+        };
+    if let (Some(metrics), Some(path)) = (metrics.as_ref(), args.metrics()) {
+        metrics::dump_on_signal(metrics.clone(), path.to_path_buf())?;
+    }
+
     let listener = match TcpListener::bind(sockaddr.clone()) {
         Ok(l) => l,
         Err(e) => {
             anyhow::bail!("Failed to bind: {}", e);
         },
     };
-    let stream: TcpStream = match listener.accept() {
-        Ok((s, sockaddr)) => {
-            info!("Connected to: {}", sockaddr);
-            s
+
+    info!("listening on {}", listener.local_addr()?);
+
+    if let (Some(metrics), Some(port)) = (metrics.as_ref(), args.metrics_port()) {
+        metrics::serve(metrics.clone(), port)?;
+    }
+
+    let auth: Option<Authenticator> = match args.auth_key() {
+        Some(path) => Some(Authenticator::load(path)?),
+        None => {
+            warn!("authentication disabled, accepting unauthenticated connections");
+            None
         },
+    };
+
+    // Paths are resolved against the working directory, which is the root of virtual environments.
+    let root: PathBuf = match env::current_dir().and_then(|dir| dir.canonicalize()) {
+        Ok(root) => root,
         Err(e) => {
-            anyhow::bail!("Failed to connect: {}", e);
+            anyhow::bail!("Failed to resolve root directory: {}", e);
         },
     };
 
-    let mut procd: ProcessDaemon = match ProcessDaemon::init(stream) {
-        Ok(procd) => procd,
-        Err(e) => panic!("failed to initialize process manager daemon (error={:?})", e),
+    // Configuration is checked once, so that mistakes surface before any connection is accepted.
+    let fs: FileSystems = FileSystems::new(args.backends(), args.limits(), root.clone())?;
+    let policy: AccessPolicy = match args.policy() {
+        Some(path) => AccessPolicy::load(path)?,
+        None => AccessPolicy::default(),
     };
 
-    procd.run();
+    // Whoever spawned the daemon on an ephemeral port learns which one it got, once the daemon is
+    // about to accept connections.
+    if let Some(path) = args.port_file() {
+        write_port_file(path, listener.local_addr()?.port())?;
+    }
+
+    // Serve one connection at a time, so that a rebooted microVM can reconnect.
+    let mut connection: u64 = 0;
+    loop {
+        let (stream, peer): (TcpStream, Hello) = accept(&listener, auth.as_ref());
+        connection += 1;
+
+        // Failing to set up a connection must not bring the daemon down for the next one.
+        let session: Session =
+            match open_session(&args, &root, &fs, &policy, metrics.clone(), connection, peer) {
+                Ok(session) => session,
+                Err(e) => {
+                    error!("failed to set up connection (error={:?})", e);
+                    continue;
+                },
+            };
+
+        let mut procd: ProcessDaemon =
+            match ProcessDaemon::init(stream, peer, root.clone(), session) {
+                Ok(procd) => procd,
+                Err(e) => {
+                    error!("failed to initialize process manager daemon (error={:?})", e);
+                    continue;
+                },
+            };
+
+        procd.run();
+
+        info!("waiting for a new connection");
+    }
+}
+
+///
+/// # Description
+///
+/// Sets up the state of a connection, which does not carry over to the next one.
+///
+/// # Parameters
+///
+/// - `args`: Command-line arguments.
+/// - `root`: Directory that paths of guests are resolved against.
+/// - `fs`: Filesystem backends, from which those of the connection are created.
+/// - `policy`: Access policy.
+/// - `metrics`: Metrics shared by all connections, if they are collected.
+/// - `connection`: Number of the connection.
+/// - `peer`: What the client supports.
+///
+/// # Returns
+///
+/// Upon success, the state of the connection is returned. Upon failure, an error is returned
+/// instead.
+///
+fn open_session(
+    args: &Args,
+    root: &Path,
+    fs: &FileSystems,
+    policy: &AccessPolicy,
+    metrics: Option<Arc<Mutex<Metrics>>>,
+    connection: u64,
+    peer: Hello,
+) -> Result<Session> {
+    Ok(Session {
+        xattr: XattrPolicy::new(args.xattr_namespaces()),
+        stdio: StandardStreams::new(args.stdio()),
+        fs: fs.duplicate(),
+        resources: ResourceManager::new(args.limits()),
+        policy: policy.clone(),
+        audit: AuditLog::open(args.audit_log(), connection)?,
+        capture: Capture::open(args.capture(), connection, peer)?,
+        metrics: MetricsRecorder::new(metrics),
+        workers: WorkerPool::new(args.workers(), root.to_path_buf())?,
+    })
+}
+
+///
+/// # Description
+///
+/// Accepts the first connection that authenticates and negotiates, refusing all others.
+///
+/// # Parameters
+///
+/// - `listener`: Listening socket.
+/// - `auth`: Authenticator, if connections must authenticate.
+///
+/// # Returns
+///
+/// The accepted connection, along with what the client supports.
+///
+fn accept(listener: &TcpListener, auth: Option<&Authenticator>) -> (TcpStream, Hello) {
+    loop {
+        let (mut stream, sockaddr) = match listener.accept() {
+            Ok(connection) => connection,
+            Err(e) => {
+                // Failures to accept are transient, such as running out of file descriptors.
+                warn!("failed to accept connection (error={:?})", e);
+                thread::sleep(ACCEPT_RETRY_DELAY);
+                continue;
+            },
+        };
+
+        if let Some(auth) = auth {
+            if let Err(e) = auth.authenticate(&mut stream) {
+                warn!("refused connection (sockaddr={}, error={:?})", sockaddr, e);
+                continue;
+            }
+        }
+
+        let peer: Hello = match protocol::negotiate(&mut stream) {
+            Ok(peer) => peer,
+            Err(e) => {
+                warn!("refused connection (sockaddr={}, error={:?})", sockaddr, e);
+                continue;
+            },
+        };
+
+        // Responses are small and sent as they complete, so they must not wait for ACKs.
+        if let Err(e) = stream.set_nodelay(true) {
+            warn!("failed to disable Nagle's algorithm (error={:?})", e);
+        }
+
+        info!("Connected to: {}", sockaddr);
+        return (stream, peer);
+    }
+}
+
+///
+/// # Description
+///
+/// Writes the port on which connections are accepted to a file. The file is written aside and
+/// renamed into place, so that readers never see it half-written.
+///
+/// # Parameters
+///
+/// - `path`: Path to the file.
+/// - `port`: Port on which connections are accepted.
+///
+/// # Returns
+///
+/// Upon success, empty is returned. Upon failure, an error is returned instead.
+///
+fn write_port_file(path: &Path, port: u16) -> Result<()> {
+    let mut staging: OsString = path.as_os_str().to_owned();
+    staging.push(".tmp");
 
-    Ok(())
+    fs::write(&staging, format!("{}\n", port))
+        .and_then(|()| fs::rename(&staging, path))
+        .map_err(|e| anyhow::anyhow!("failed to write port file {:?}: {}", path, e))
 }
 
 ///
@@ -514,154 +1221,86 @@ pub fn build_error(pid: ProcessIdentifier, error: ErrorCode) -> Message {
     Message::new(linuxd::LINUXD, pid, MessageType::Ikc, Some(error), [0u8; Message::PAYLOAD_SIZE])
 }
 
-impl RequestAssemblerTrait for FileStatAtRequest {
-    fn new_assembler() -> RequestAssemblerType {
-        let capacity: usize = Self::MAX_SIZE.div_ceil(LinuxDaemonMessagePart::PAYLOAD_SIZE);
-        RequestAssemblerType::FileStatAtRequest(
-            LinuxDaemonLongMessage::new(capacity).expect("capacity is set to a valid value"),
-        )
-    }
-
-    fn add_part(
-        assembler: &mut RequestAssemblerType,
-        part: LinuxDaemonMessagePart,
-    ) -> Result<(), Error> {
-        match assembler {
-            RequestAssemblerType::FileStatAtRequest(assembler) => assembler.add_part(part),
-            _ => Err(Error::new(ErrorCode::InvalidArgument, "invalid assembler type")),
-        }
-    }
-
-    fn is_complete(assembler: &RequestAssemblerType) -> Result<bool, Error> {
-        match assembler {
-            RequestAssemblerType::FileStatAtRequest(assembler) => Ok(assembler.is_complete()),
-            _ => Err(Error::new(ErrorCode::InvalidArgument, "invalid assembler type")),
-        }
-    }
-
-    fn take_parts(assembler: RequestAssemblerType) -> Vec<LinuxDaemonMessagePart> {
-        match assembler {
-            RequestAssemblerType::FileStatAtRequest(assembler) => assembler.take_parts(),
-            _ => unreachable!("invalid assembler type"),
-        }
-    }
-
-    fn process_request(source: ProcessIdentifier, request: Self) -> Vec<Message> {
-        fcntl::do_fstat_at(source, request)
+///
+/// # Description
+///
+/// Builds an error response message from a host error number.
+///
+/// # Parameters
+///
+/// - `pid`: Process identifier.
+/// - `errno`: Host error number.
+///
+/// # Returns
+///
+/// A message with the error response.
+///
+pub fn build_errno(pid: ProcessIdentifier, errno: i32) -> Message {
+    match ErrorCode::try_from(-errno) {
+        Ok(error) => build_error(pid, error),
+        Err(_) => {
+            warn!("unknown error number (errno={:?})", errno);
+            build_error(pid, ErrorCode::InvalidArgument)
+        },
     }
 }
 
-impl RequestAssemblerTrait for SymbolicLinkAtRequest {
-    fn new_assembler() -> RequestAssemblerType {
-        let capacity: usize = Self::MAX_SIZE.div_ceil(LinuxDaemonMessagePart::PAYLOAD_SIZE);
-        RequestAssemblerType::SymbolicLinkAtRequest(
-            LinuxDaemonLongMessage::new(capacity).expect("capacity is set to a valid value"),
-        )
-    }
-
-    fn add_part(
-        assembler: &mut RequestAssemblerType,
-        part: LinuxDaemonMessagePart,
-    ) -> Result<(), Error> {
-        match assembler {
-            RequestAssemblerType::SymbolicLinkAtRequest(assembler) => assembler.add_part(part),
-            _ => Err(Error::new(ErrorCode::InvalidArgument, "invalid assembler type")),
-        }
-    }
-
-    fn is_complete(assembler: &RequestAssemblerType) -> Result<bool, Error> {
-        match assembler {
-            RequestAssemblerType::SymbolicLinkAtRequest(assembler) => Ok(assembler.is_complete()),
-            _ => Err(Error::new(ErrorCode::InvalidArgument, "invalid assembler type")),
-        }
-    }
-
-    fn take_parts(assembler: RequestAssemblerType) -> Vec<LinuxDaemonMessagePart> {
-        match assembler {
-            RequestAssemblerType::SymbolicLinkAtRequest(assembler) => assembler.take_parts(),
-            _ => unreachable!("invalid assembler type"),
-        }
-    }
-
-    fn process_request(source: ProcessIdentifier, request: Self) -> Vec<Message> {
-        fcntl::do_symlinkat(source, request)
+impl RequestProcessorTrait for FileStatAtRequest {
+    fn process_request(
+        source: ProcessIdentifier,
+        request: Self,
+        fs: &mut dyn FileSystem,
+    ) -> Vec<Message> {
+        fcntl::do_fstat_at(source, request, fs)
     }
 }
 
-impl RequestAssemblerTrait for LinkAtRequest {
-    fn new_assembler() -> RequestAssemblerType {
-        debug!("creating linkat request assembler");
-        let capacity: usize = Self::MAX_SIZE.div_ceil(LinuxDaemonMessagePart::PAYLOAD_SIZE);
-        RequestAssemblerType::LinkAtRequest(
-            LinuxDaemonLongMessage::new(capacity).expect("capacity is set to a valid value"),
-        )
-    }
-
-    fn add_part(
-        assembler: &mut RequestAssemblerType,
-        part: LinuxDaemonMessagePart,
-    ) -> Result<(), Error> {
-        debug!("adding part to linkat request");
-        match assembler {
-            RequestAssemblerType::LinkAtRequest(assembler) => assembler.add_part(part),
-            _ => Err(Error::new(ErrorCode::InvalidArgument, "invalid assembler type")),
-        }
-    }
-
-    fn is_complete(assembler: &RequestAssemblerType) -> Result<bool, Error> {
-        debug!("checking if linkat request is complete");
-        match assembler {
-            RequestAssemblerType::LinkAtRequest(assembler) => Ok(assembler.is_complete()),
-            _ => Err(Error::new(ErrorCode::InvalidArgument, "invalid assembler type")),
-        }
-    }
-
-    fn take_parts(assembler: RequestAssemblerType) -> Vec<LinuxDaemonMessagePart> {
-        debug!("taking parts from linkat request");
-        match assembler {
-            RequestAssemblerType::LinkAtRequest(assembler) => assembler.take_parts(),
-            _ => unreachable!("invalid assembler type"),
-        }
-    }
-
-    fn process_request(source: ProcessIdentifier, request: Self) -> Vec<Message> {
-        unistd::do_linkat(source, request)
+impl RequestProcessorTrait for SymbolicLinkAtRequest {
+    fn process_request(
+        source: ProcessIdentifier,
+        request: Self,
+        fs: &mut dyn FileSystem,
+    ) -> Vec<Message> {
+        fcntl::do_symlinkat(source, request, fs)
     }
 }
 
-impl RequestAssemblerTrait for ReadLinkAtRequest {
-    fn new_assembler() -> RequestAssemblerType {
-        let capacity: usize = Self::MAX_SIZE.div_ceil(LinuxDaemonMessagePart::PAYLOAD_SIZE);
-        RequestAssemblerType::ReadLinkAtRequest(
-            LinuxDaemonLongMessage::new(capacity).expect("capacity is set to a valid value"),
-        )
-    }
-
-    fn add_part(
-        assembler: &mut RequestAssemblerType,
-        part: LinuxDaemonMessagePart,
-    ) -> Result<(), Error> {
-        match assembler {
-            RequestAssemblerType::ReadLinkAtRequest(assembler) => assembler.add_part(part),
-            _ => Err(Error::new(ErrorCode::InvalidArgument, "invalid assembler type")),
-        }
+impl RequestProcessorTrait for LinkAtRequest {
+    fn process_request(
+        source: ProcessIdentifier,
+        request: Self,
+        fs: &mut dyn FileSystem,
+    ) -> Vec<Message> {
+        unistd::do_linkat(source, request, fs)
     }
+}
 
-    fn is_complete(assembler: &RequestAssemblerType) -> Result<bool, Error> {
-        match assembler {
-            RequestAssemblerType::ReadLinkAtRequest(assembler) => Ok(assembler.is_complete()),
-            _ => Err(Error::new(ErrorCode::InvalidArgument, "invalid assembler type")),
-        }
+impl RequestProcessorTrait for ReadLinkAtRequest {
+    fn process_request(
+        source: ProcessIdentifier,
+        request: Self,
+        fs: &mut dyn FileSystem,
+    ) -> Vec<Message> {
+        fcntl::do_readlinkat(source, request, fs)
     }
+}
 
-    fn take_parts(assembler: RequestAssemblerType) -> Vec<LinuxDaemonMessagePart> {
-        match assembler {
-            RequestAssemblerType::ReadLinkAtRequest(assembler) => assembler.take_parts(),
-            _ => unreachable!("invalid assembler type"),
-        }
+impl RequestProcessorTrait for TruncateRequest {
+    fn process_request(
+        source: ProcessIdentifier,
+        request: Self,
+        fs: &mut dyn FileSystem,
+    ) -> Vec<Message> {
+        unistd::do_truncate(source, request, fs)
     }
+}
 
-    fn process_request(source: ProcessIdentifier, request: Self) -> Vec<Message> {
-        fcntl::do_readlinkat(source, request)
+impl RequestProcessorTrait for RenameAt2Request {
+    fn process_request(
+        source: ProcessIdentifier,
+        request: Self,
+        fs: &mut dyn FileSystem,
+    ) -> Vec<Message> {
+        fcntl::do_renameat2(source, request, fs)
     }
 }
@@ -5,6 +5,7 @@
 // Imports
 //==================================================================================================
 
+use crate::backend::FileSystem;
 use ::alloc::collections::BTreeMap;
 use ::linuxd::message::{
     LinuxDaemonLongMessage,
@@ -14,108 +15,264 @@ use ::linuxd::message::{
 use ::nvx::{
     ipc::Message,
     pm::ProcessIdentifier,
-    sys::error::Error,
+    sys::error::{
+        Error,
+        ErrorCode,
+    },
 };
+use ::std::{
+    mem,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+//==================================================================================================
+// Constants
+//==================================================================================================
+
+/// Time that a process has to send all parts of a request.
+const STREAM_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Maximum size of the parts of incomplete requests, across all processes (in bytes).
+const BUFFERED_SIZE_MAX: usize = 8 * 1024 * 1024;
+
+//==================================================================================================
+// Types
+//==================================================================================================
+
+/// Identifies a request by its source process, its identifier and the header of its parts.
+type StreamKey = (ProcessIdentifier, u32, u16);
 
 //==================================================================================================
 // Structures
 //==================================================================================================
 
+///
+/// # Description
+///
+/// Request that is being assembled from its parts.
+///
+struct InflightRequest {
+    /// Time by which all parts must have been received.
+    deadline: Instant,
+    /// Size reserved for the parts (in bytes).
+    reserved: usize,
+    /// Parts received so far, or `None` if the request was rejected and its remaining parts are
+    /// dropped.
+    message: Option<LinuxDaemonLongMessage>,
+}
+
 #[derive(Default)]
 pub struct RequestAssembler {
-    inflight: BTreeMap<ProcessIdentifier, RequestAssemblerType>,
+    inflight: BTreeMap<StreamKey, InflightRequest>,
+    /// Size reserved for the parts of all inflight requests (in bytes).
+    buffered: usize,
 }
 
 impl RequestAssembler {
-    pub fn process_message<T: RequestAssemblerTrait>(
+    ///
+    /// # Description
+    ///
+    /// Adds a part to an inflight request of a process.
+    ///
+    /// # Parameters
+    ///
+    /// - `source`: Source process identifier.
+    /// - `part`: Message part.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the request is returned if all its parts were received, or `None` otherwise.
+    /// Upon failure, the remaining parts of the request are dropped and an error is returned
+    /// instead.
+    ///
+    pub fn assemble<T: MessagePartitioner>(
         &mut self,
         source: ProcessIdentifier,
         part: LinuxDaemonMessagePart,
-    ) -> Result<Option<Vec<Message>>, Error> {
-        match self.process_message_internal::<T>(source, part) {
-            Ok(messages) => Ok(messages),
+    ) -> Result<Option<T>, Error> {
+        self.expire(Instant::now());
+
+        let key: StreamKey = (source, part.request_id, T::HEADER as u16);
+        let is_last: bool = part.part_number == 0;
+        match self.assemble_internal::<T>(key, part) {
+            Ok(request) => Ok(request),
             Err(e) => {
-                self.inflight.remove(&source);
+                self.reject(key, is_last);
                 Err(e)
             },
         }
     }
 
-    fn process_message_internal<T: RequestAssemblerTrait>(
+    ///
+    /// # Description
+    ///
+    /// Drops all inflight requests of a process.
+    ///
+    /// # Parameters
+    ///
+    /// - `pid`: Target process.
+    ///
+    pub fn release_process(&mut self, pid: ProcessIdentifier) {
+        let keys: Vec<StreamKey> = self
+            .inflight
+            .keys()
+            .filter(|k| k.0 == pid)
+            .copied()
+            .collect();
+
+        if !keys.is_empty() {
+            debug!("release_process(): released {} requests (pid={:?})", keys.len(), pid);
+        }
+
+        for key in keys {
+            self.remove(&key);
+        }
+    }
+
+    fn assemble_internal<T: MessagePartitioner>(
         &mut self,
-        source: ProcessIdentifier,
+        key: StreamKey,
         part: LinuxDaemonMessagePart,
-    ) -> Result<Option<Vec<Message>>, Error> {
-        let message_complete: bool = {
-            match self.assemble_parts::<T>(source, part) {
-                Ok(message_complete) => message_complete,
-                Err(e) => {
-                    return Err(e);
-                },
-            }
+    ) -> Result<Option<T>, Error> {
+        if !self.inflight.contains_key(&key) {
+            self.open::<T>(key, &part)?;
+        }
+
+        let request: &mut InflightRequest = self
+            .inflight
+            .get_mut(&key)
+            .expect("inflight request does exist");
+        let message: &mut LinuxDaemonLongMessage = match request.message.as_mut() {
+            Some(message) => message,
+            None => {
+                if part.part_number == 0 {
+                    self.inflight.remove(&key);
+                }
+                return Ok(None);
+            },
         };
 
-        if !message_complete {
+        message.add_part(part)?;
+
+        if !message.is_complete() {
             return Ok(None);
         }
 
-        match self.process_request::<T>(source) {
-            Ok(messages) => Ok(Some(messages)),
-            Err(e) => Err(e),
-        }
+        let request: InflightRequest = self.remove(&key).expect("inflight request does exist");
+        let parts: Vec<LinuxDaemonMessagePart> = request
+            .message
+            .expect("inflight request is not rejected")
+            .take_parts();
+        let request: T = T::from_parts(&parts)?;
+        Ok(Some(request))
     }
 
-    fn assemble_parts<T: RequestAssemblerTrait>(
+    // Start assembling a request from its first part.
+    fn open<T: MessagePartitioner>(
         &mut self,
-        source: ProcessIdentifier,
-        part: LinuxDaemonMessagePart,
-    ) -> Result<bool, Error> {
-        let assembler: &mut RequestAssemblerType = self
+        key: StreamKey,
+        part: &LinuxDaemonMessagePart,
+    ) -> Result<(), Error> {
+        // Processes send one request at a time, so other requests of the process were abandoned.
+        let stale: Vec<(StreamKey, bool)> = self
             .inflight
-            .entry(source)
-            .or_insert_with(|| T::new_assembler());
-        T::add_part(assembler, part)?;
-        T::is_complete(assembler)
+            .iter()
+            .filter(|(k, _)| k.0 == key.0)
+            .map(|(k, r)| (*k, r.message.is_some()))
+            .collect();
+        let is_interleaved: bool = stale.iter().any(|(_, is_active)| *is_active);
+        for (key, _) in stale {
+            self.remove(&key);
+        }
+
+        if is_interleaved {
+            return Err(Error::new(ErrorCode::InvalidMessage, "interleaved message stream"));
+        }
+
+        // The first part announces how many parts the request has.
+        let capacity: usize = LinuxDaemonLongMessage::capacity(T::MAX_SIZE);
+        let num_parts: usize = part.part_number as usize + 1;
+        if num_parts > capacity {
+            return Err(Error::new(ErrorCode::MessageTooLong, "message too long"));
+        }
+
+        let reserved: usize = num_parts * mem::size_of::<LinuxDaemonMessagePart>();
+        if self.buffered + reserved > BUFFERED_SIZE_MAX {
+            return Err(Error::new(ErrorCode::ResourceBusy, "too many buffered message parts"));
+        }
+
+        self.buffered += reserved;
+        self.inflight.insert(
+            key,
+            InflightRequest {
+                deadline: Instant::now() + STREAM_TIMEOUT,
+                reserved,
+                message: Some(
+                    LinuxDaemonLongMessage::new(capacity)
+                        .expect("capacity is set to a valid value"),
+                ),
+            },
+        );
+
+        Ok(())
     }
 
-    fn process_request<T: RequestAssemblerTrait>(
-        &mut self,
-        source: ProcessIdentifier,
-    ) -> Result<Vec<Message>, Error> {
-        let assembler: RequestAssemblerType = self
+    // Drop a request, keeping track of it until its last part arrives so that its remaining parts
+    // are dropped as well.
+    fn reject(&mut self, key: StreamKey, is_last: bool) {
+        self.remove(&key);
+
+        if !is_last {
+            self.inflight.insert(
+                key,
+                InflightRequest {
+                    deadline: Instant::now() + STREAM_TIMEOUT,
+                    reserved: 0,
+                    message: None,
+                },
+            );
+        }
+    }
+
+    // Drop requests whose deadline has passed.
+    fn expire(&mut self, now: Instant) {
+        let expired: Vec<StreamKey> = self
             .inflight
-            .remove(&source)
-            .expect("inflight request does exist");
+            .iter()
+            .filter(|(_, r)| r.deadline <= now)
+            .map(|(k, _)| *k)
+            .collect();
 
-        let parts: Vec<LinuxDaemonMessagePart> = T::take_parts(assembler);
-        let request: T = T::from_parts(&parts)?;
-        Ok(T::process_request(source, request))
+        for key in expired {
+            if let Some(InflightRequest {
+                message: Some(_), ..
+            }) = self.remove(&key)
+            {
+                warn!(
+                    "expire(): dropped incomplete request (pid={:?}, request_id={:?})",
+                    key.0, key.1
+                );
+            }
+        }
     }
-}
 
-#[allow(clippy::enum_variant_names)]
-pub enum RequestAssemblerType {
-    FileStatAtRequest(LinuxDaemonLongMessage),
-    SymbolicLinkAtRequest(LinuxDaemonLongMessage),
-    LinkAtRequest(LinuxDaemonLongMessage),
-    ReadLinkAtRequest(LinuxDaemonLongMessage),
+    fn remove(&mut self, key: &StreamKey) -> Option<InflightRequest> {
+        let request: InflightRequest = self.inflight.remove(key)?;
+        self.buffered -= request.reserved;
+        Some(request)
+    }
 }
 
-pub trait RequestAssemblerTrait
+pub trait RequestProcessorTrait
 where
-    Self: Sized,
     Self: MessagePartitioner,
 {
-    fn new_assembler() -> RequestAssemblerType;
-
-    fn add_part(
-        assembler: &mut RequestAssemblerType,
-        part: LinuxDaemonMessagePart,
-    ) -> Result<(), Error>;
-
-    fn is_complete(assembler: &RequestAssemblerType) -> Result<bool, Error>;
-
-    fn take_parts(assembler: RequestAssemblerType) -> Vec<LinuxDaemonMessagePart>;
-
-    fn process_request(source: ProcessIdentifier, request: Self) -> Vec<Message>;
+    fn process_request(
+        source: ProcessIdentifier,
+        request: Self,
+        fs: &mut dyn FileSystem,
+    ) -> Vec<Message>;
 }
@@ -0,0 +1,679 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use ::anyhow::Result;
+use ::linuxd::{
+    sys::sendfile::message::SendFileResponse,
+    unistd::message::{
+        CopyFileRangeResponse,
+        PartialReadResponse,
+        PartialWriteResponse,
+        ReadResponse,
+        WriteResponse,
+    },
+    venv::VirtualEnvironmentIdentifier,
+    LinuxDaemonMessage,
+    LinuxDaemonMessageHeader,
+};
+use ::nvx::{
+    ipc::Message,
+    pm::ProcessIdentifier,
+};
+use ::serde::Serialize;
+use ::std::{
+    collections::BTreeMap,
+    ffi::OsString,
+    fmt::Write as _,
+    fs,
+    io::{
+        Read,
+        Write,
+    },
+    mem,
+    net::{
+        Ipv4Addr,
+        TcpListener,
+        TcpStream,
+    },
+    path::{
+        Path,
+        PathBuf,
+    },
+    process,
+    ptr,
+    sync::{
+        Arc,
+        Mutex,
+    },
+    thread,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+//==================================================================================================
+// Constants
+//==================================================================================================
+
+/// Upper bounds of the latency buckets, in microseconds. Latencies above the last bound fall in an
+/// implicit overflow bucket.
+const LATENCY_BOUNDS_US: [u64; 17] = [
+    10, 25, 50, 100, 250, 500, 1_000, 2_500, 5_000, 10_000, 25_000, 50_000, 100_000, 250_000,
+    500_000, 1_000_000, 2_500_000,
+];
+
+/// Largest HTTP request that the metrics endpoint reads.
+const HTTP_REQUEST_SIZE_MAX: usize = 8192;
+
+/// Time that clients of the metrics endpoint have to send their request.
+const HTTP_TIMEOUT: Duration = Duration::from_secs(1);
+
+//==================================================================================================
+// Structures
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Histogram of latencies.
+///
+#[derive(Default)]
+struct Histogram {
+    /// Number of samples that fall in each bucket, including the overflow bucket.
+    counts: [u64; LATENCY_BOUNDS_US.len() + 1],
+    /// Sum of all samples, in microseconds.
+    sum_us: u64,
+}
+
+///
+/// # Description
+///
+/// Metrics of an operation in an environment.
+///
+#[derive(Default)]
+struct OperationMetrics {
+    /// Number of requests answered.
+    requests: u64,
+    /// Number of requests that failed.
+    errors: u64,
+    /// Number of bytes read or written.
+    bytes: u64,
+    /// Latency of requests.
+    latency: Histogram,
+}
+
+///
+/// # Description
+///
+/// Metrics of the Linux Daemon, which outlive connections.
+///
+pub struct Metrics {
+    /// Instant at which the daemon started.
+    started: Instant,
+    /// Metrics of operations, by environment and type of request. Requests that are not understood
+    /// have no type.
+    operations: BTreeMap<(Option<u32>, Option<LinuxDaemonMessageHeader>), OperationMetrics>,
+}
+
+///
+/// # Description
+///
+/// Metrics of an operation, as they are dumped.
+///
+#[derive(Serialize)]
+struct OperationSnapshot {
+    /// Environment, if any.
+    venv: Option<u32>,
+    /// Type of the request.
+    header: String,
+    /// Name of the operation.
+    operation: &'static str,
+    /// Number of requests answered.
+    requests: u64,
+    /// Number of requests that failed.
+    errors: u64,
+    /// Number of bytes read or written.
+    bytes: u64,
+    /// Sum of latencies, in microseconds.
+    latency_sum_us: u64,
+    /// Cumulative number of requests that completed within each bound of `latency_bounds_us`, and
+    /// then of all requests.
+    latency_buckets: Vec<u64>,
+}
+
+///
+/// # Description
+///
+/// Metrics of the Linux Daemon, as they are dumped.
+///
+#[derive(Serialize)]
+struct Snapshot {
+    /// Microseconds since the daemon started.
+    uptime_us: u64,
+    /// Upper bounds of the latency buckets, in microseconds.
+    latency_bounds_us: &'static [u64],
+    /// Metrics of operations.
+    operations: Vec<OperationSnapshot>,
+}
+
+///
+/// # Description
+///
+/// Request that was received but not answered yet.
+///
+struct InflightRequest {
+    /// Type of the request, or of its parts, if it is understood.
+    header: Option<LinuxDaemonMessageHeader>,
+    /// Environment of the process, if any.
+    env: Option<u32>,
+    /// Instant at which the request was received.
+    started: Instant,
+}
+
+///
+/// # Description
+///
+/// Records the requests of a connection into the metrics of the daemon. A disabled recorder
+/// records nothing.
+///
+pub struct MetricsRecorder {
+    /// Metrics of the daemon, if metrics are enabled.
+    metrics: Option<Arc<Mutex<Metrics>>>,
+    /// Requests that were received but not answered yet, by process.
+    inflight: BTreeMap<ProcessIdentifier, InflightRequest>,
+}
+
+//==================================================================================================
+// Implementations
+//==================================================================================================
+
+impl Histogram {
+    // Record a sample.
+    fn observe(&mut self, latency_us: u64) {
+        let bucket: usize = LATENCY_BOUNDS_US
+            .iter()
+            .position(|bound| latency_us <= *bound)
+            .unwrap_or(LATENCY_BOUNDS_US.len());
+        self.counts[bucket] += 1;
+        self.sum_us = self.sum_us.saturating_add(latency_us);
+    }
+
+    // Number of samples that fall within each bound, and then of all samples.
+    fn cumulative(&self) -> Vec<u64> {
+        self.counts
+            .iter()
+            .scan(0, |total, count| {
+                *total += count;
+                Some(*total)
+            })
+            .collect()
+    }
+}
+
+impl Metrics {
+    ///
+    /// # Description
+    ///
+    /// Creates empty metrics.
+    ///
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            operations: BTreeMap::new(),
+        }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Dumps metrics to a file, as a JSON object. The file is replaced atomically, so that readers
+    /// never observe a partial dump.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: File to dump metrics to.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, empty is returned. Upon failure, an error is returned instead.
+    ///
+    pub fn dump(&self, path: &Path) -> Result<()> {
+        let mut json: Vec<u8> = serde_json::to_vec_pretty(&self.snapshot())?;
+        json.push(b'\n');
+
+        let mut staging: OsString = path.as_os_str().to_owned();
+        staging.push(".tmp");
+        fs::write(&staging, json)
+            .and_then(|_| fs::rename(&staging, path))
+            .map_err(|e| anyhow::anyhow!("failed to dump metrics to {:?}: {}", path, e))
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Renders metrics in the Prometheus text exposition format.
+    ///
+    /// # Returns
+    ///
+    /// The rendered metrics.
+    ///
+    pub fn to_prometheus(&self) -> String {
+        let mut text: String = String::new();
+        let snapshot: Snapshot = self.snapshot();
+
+        let _ = writeln!(text, "# HELP linuxd_uptime_seconds Time since the daemon started.");
+        let _ = writeln!(text, "# TYPE linuxd_uptime_seconds gauge");
+        let _ = writeln!(text, "linuxd_uptime_seconds {}", seconds(snapshot.uptime_us));
+
+        counter(&mut text, "linuxd_requests_total", "Requests answered.", &snapshot, |op| {
+            op.requests
+        });
+        counter(&mut text, "linuxd_errors_total", "Requests that failed.", &snapshot, |op| {
+            op.errors
+        });
+        counter(&mut text, "linuxd_bytes_total", "Bytes read or written.", &snapshot, |op| {
+            op.bytes
+        });
+
+        let name: &str = "linuxd_request_duration_seconds";
+        let _ = writeln!(text, "# HELP {} Time taken to answer requests.", name);
+        let _ = writeln!(text, "# TYPE {} histogram", name);
+        for op in &snapshot.operations {
+            let labels: String = labels(op);
+            for (i, count) in op.latency_buckets.iter().enumerate() {
+                let bound: String = match LATENCY_BOUNDS_US.get(i) {
+                    Some(bound) => seconds(*bound),
+                    None => "+Inf".to_string(),
+                };
+                let _ = writeln!(text, "{}_bucket{{{},le=\"{}\"}} {}", name, labels, bound, count);
+            }
+            let _ = writeln!(text, "{}_sum{{{}}} {}", name, labels, seconds(op.latency_sum_us));
+            let count: u64 = op.latency_buckets.last().copied().unwrap_or(0);
+            let _ = writeln!(text, "{}_count{{{}}} {}", name, labels, count);
+        }
+
+        text
+    }
+
+    // Record a request that was answered.
+    fn record(
+        &mut self,
+        env: Option<u32>,
+        header: Option<LinuxDaemonMessageHeader>,
+        failed: bool,
+        bytes: u64,
+        latency: Duration,
+    ) {
+        let operation: &mut OperationMetrics = self.operations.entry((env, header)).or_default();
+        operation.requests += 1;
+        if failed {
+            operation.errors += 1;
+        }
+        operation.bytes = operation.bytes.saturating_add(bytes);
+        operation
+            .latency
+            .observe(u64::try_from(latency.as_micros()).unwrap_or(u64::MAX));
+    }
+
+    // Take a snapshot of the metrics.
+    fn snapshot(&self) -> Snapshot {
+        let operations: Vec<OperationSnapshot> = self
+            .operations
+            .iter()
+            .map(|((env, header), metrics)| OperationSnapshot {
+                venv: *env,
+                header: match header {
+                    Some(header) => format!("{:?}", header),
+                    None => "unknown".to_string(),
+                },
+                operation: header
+                    .and_then(LinuxDaemonMessageHeader::operation)
+                    .unwrap_or("unknown"),
+                requests: metrics.requests,
+                errors: metrics.errors,
+                bytes: metrics.bytes,
+                latency_sum_us: metrics.latency.sum_us,
+                latency_buckets: metrics.latency.cumulative(),
+            })
+            .collect();
+
+        Snapshot {
+            uptime_us: u64::try_from(self.started.elapsed().as_micros()).unwrap_or(u64::MAX),
+            latency_bounds_us: &LATENCY_BOUNDS_US,
+            operations,
+        }
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MetricsRecorder {
+    ///
+    /// # Description
+    ///
+    /// Creates a recorder for a connection.
+    ///
+    /// # Parameters
+    ///
+    /// - `metrics`: Metrics of the daemon, or `None` to disable the recorder.
+    ///
+    pub fn new(metrics: Option<Arc<Mutex<Metrics>>>) -> Self {
+        Self {
+            metrics,
+            inflight: BTreeMap::new(),
+        }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Records that a message was received. Parts of a multipart request are recorded once, as of
+    /// the first part.
+    ///
+    /// # Parameters
+    ///
+    /// - `source`: Process that sent the message.
+    /// - `env`: Environment of the process, if any.
+    /// - `message`: Message, or `None` if it is not understood.
+    ///
+    pub fn begin(
+        &mut self,
+        source: ProcessIdentifier,
+        env: Option<VirtualEnvironmentIdentifier>,
+        message: Option<&LinuxDaemonMessage>,
+    ) {
+        if self.metrics.is_none() {
+            return;
+        }
+
+        let header: Option<LinuxDaemonMessageHeader> = message.map(|message| message.header);
+        if let (Some(header), Some(inflight)) = (header, self.inflight.get(&source)) {
+            if header.is_part() && inflight.header == Some(header) {
+                return;
+            }
+        }
+
+        self.inflight.insert(
+            source,
+            InflightRequest {
+                header,
+                env: env.map(|env| env.id()),
+                started: Instant::now(),
+            },
+        );
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Records that a message is sent. Responses complete the request of the process they are
+    /// addressed to, and all other messages are ignored.
+    ///
+    /// # Parameters
+    ///
+    /// - `message`: Message.
+    ///
+    pub fn complete(&mut self, message: &Message) {
+        let metrics: &Arc<Mutex<Metrics>> = match self.metrics.as_ref() {
+            Some(metrics) => metrics,
+            None => return,
+        };
+
+        let mut bytes: u64 = 0;
+        if message.status == 0 {
+            match LinuxDaemonMessage::try_from_bytes(message.payload) {
+                Ok(response)
+                    if matches!(
+                        { response.header },
+                        LinuxDaemonMessageHeader::InotifyEventPart
+                    ) =>
+                {
+                    return
+                },
+                Ok(response) => bytes = transferred(&response),
+                Err(_) => {},
+            }
+        }
+
+        let destination: ProcessIdentifier = message.destination;
+        let inflight: InflightRequest = match self.inflight.remove(&destination) {
+            Some(inflight) => inflight,
+            None => return,
+        };
+
+        // Metrics are only ever updated while the lock is held, so they cannot be inconsistent.
+        let mut metrics = metrics.lock().unwrap_or_else(|e| e.into_inner());
+        metrics.record(
+            inflight.env,
+            inflight.header,
+            message.status != 0,
+            bytes,
+            inflight.started.elapsed(),
+        );
+    }
+}
+
+//==================================================================================================
+// Standalone Functions
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Dumps metrics whenever the daemon receives `SIGUSR1`, and once more when it is asked to shut
+/// down with `SIGINT` or `SIGTERM`.
+///
+/// # Parameters
+///
+/// - `metrics`: Metrics of the daemon.
+/// - `path`: File to dump metrics to.
+///
+/// # Returns
+///
+/// Upon success, empty is returned. Upon failure, an error is returned instead.
+///
+/// # Note
+///
+/// Signals are handled by a dedicated thread, so this function must be called before any other
+/// thread is spawned, for all of them to inherit a signal mask that blocks those signals.
+///
+pub fn dump_on_signal(metrics: Arc<Mutex<Metrics>>, path: PathBuf) -> Result<()> {
+    let mut signals: libc::sigset_t = unsafe { mem::zeroed() };
+    unsafe {
+        libc::sigemptyset(&mut signals);
+        libc::sigaddset(&mut signals, libc::SIGUSR1);
+        libc::sigaddset(&mut signals, libc::SIGINT);
+        libc::sigaddset(&mut signals, libc::SIGTERM);
+    }
+
+    let error: i32 = unsafe { libc::pthread_sigmask(libc::SIG_BLOCK, &signals, ptr::null_mut()) };
+    if error != 0 {
+        anyhow::bail!("failed to block signals (errno={:?})", error);
+    }
+
+    thread::Builder::new()
+        .name("metrics-signals".to_string())
+        .spawn(move || loop {
+            let mut signal: i32 = 0;
+            let error: i32 = unsafe { libc::sigwait(&signals, &mut signal) };
+            if error != 0 {
+                error!("failed to wait for signals (errno={:?})", error);
+                continue;
+            }
+
+            let metrics = metrics.lock().unwrap_or_else(|e| e.into_inner());
+            match metrics.dump(&path) {
+                Ok(()) => info!("dumped metrics (path={:?}, signal={:?})", path, signal),
+                Err(e) => error!("{}", e),
+            }
+
+            if signal != libc::SIGUSR1 {
+                info!("shutting down (signal={:?})", signal);
+                process::exit(128 + signal);
+            }
+        })?;
+
+    Ok(())
+}
+
+///
+/// # Description
+///
+/// Serves metrics in the Prometheus text exposition format at `/metrics`, on a local port.
+///
+/// # Parameters
+///
+/// - `metrics`: Metrics of the daemon.
+/// - `port`: Port to listen on, or zero to pick an ephemeral one.
+///
+/// # Returns
+///
+/// Upon success, empty is returned. Upon failure, an error is returned instead.
+///
+pub fn serve(metrics: Arc<Mutex<Metrics>>, port: u16) -> Result<()> {
+    let listener: TcpListener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+        .map_err(|e| anyhow::anyhow!("failed to bind metrics endpoint: {}", e))?;
+    info!("serving metrics on {}", listener.local_addr()?);
+
+    thread::Builder::new()
+        .name("metrics-http".to_string())
+        .spawn(move || {
+            for stream in listener.incoming() {
+                let result: Result<()> = match stream {
+                    Ok(stream) => answer(stream, &metrics),
+                    Err(e) => Err(e.into()),
+                };
+                if let Err(e) = result {
+                    warn!("failed to serve metrics (error={:?})", e);
+                }
+            }
+        })?;
+
+    Ok(())
+}
+
+// Answer an HTTP request to the metrics endpoint.
+fn answer(mut stream: TcpStream, metrics: &Mutex<Metrics>) -> Result<()> {
+    stream.set_read_timeout(Some(HTTP_TIMEOUT))?;
+    stream.set_write_timeout(Some(HTTP_TIMEOUT))?;
+
+    // Only the request line matters, but the rest of the head is drained so that the client does
+    // not see a reset.
+    let mut request: Vec<u8> = Vec::new();
+    let mut buf: [u8; 512] = [0; 512];
+    while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+        let count: usize = stream.read(&mut buf)?;
+        if count == 0 || request.len() + count > HTTP_REQUEST_SIZE_MAX {
+            break;
+        }
+        request.extend_from_slice(&buf[..count]);
+    }
+
+    let line: String = String::from_utf8_lossy(&request)
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .to_string();
+    let mut fields = line.split_ascii_whitespace();
+    let (status, body): (&str, String) = match (fields.next(), fields.next()) {
+        (Some("GET"), Some("/metrics")) => {
+            let metrics = metrics.lock().unwrap_or_else(|e| e.into_inner());
+            ("200 OK", metrics.to_prometheus())
+        },
+        (Some("GET"), Some(_)) => ("404 Not Found", "not found\n".to_string()),
+        _ => ("405 Method Not Allowed", "method not allowed\n".to_string()),
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\n\
+         Connection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )?;
+
+    Ok(())
+}
+
+///
+/// # Description
+///
+/// Returns the number of bytes that a successful response reports as read or written.
+///
+fn transferred(response: &LinuxDaemonMessage) -> u64 {
+    let count: i64 = match response.header {
+        LinuxDaemonMessageHeader::WriteResponse => {
+            WriteResponse::from_bytes(response.payload).count as i64
+        },
+        LinuxDaemonMessageHeader::ReadResponse => {
+            ReadResponse::from_bytes(response.payload).count as i64
+        },
+        LinuxDaemonMessageHeader::PartialWriteResponse => {
+            PartialWriteResponse::from_bytes(response.payload).count as i64
+        },
+        LinuxDaemonMessageHeader::PartialReadResponse => {
+            PartialReadResponse::from_bytes(response.payload).count as i64
+        },
+        LinuxDaemonMessageHeader::CopyFileRangeResponse => {
+            CopyFileRangeResponse::from_bytes(response.payload).count as i64
+        },
+        LinuxDaemonMessageHeader::SendFileResponse => {
+            SendFileResponse::from_bytes(response.payload).count as i64
+        },
+        _ => 0,
+    };
+
+    u64::try_from(count).unwrap_or(0)
+}
+
+///
+/// # Description
+///
+/// Renders a counter of operations in the Prometheus text exposition format.
+///
+fn counter(
+    text: &mut String,
+    name: &str,
+    help: &str,
+    snapshot: &Snapshot,
+    value: fn(&OperationSnapshot) -> u64,
+) {
+    let _ = writeln!(text, "# HELP {} {}", name, help);
+    let _ = writeln!(text, "# TYPE {} counter", name);
+    for op in &snapshot.operations {
+        let _ = writeln!(text, "{}{{{}}} {}", name, labels(op), value(op));
+    }
+}
+
+///
+/// # Description
+///
+/// Renders the labels of an operation in the Prometheus text exposition format.
+///
+fn labels(op: &OperationSnapshot) -> String {
+    let mut labels: String = String::new();
+    if let Some(env) = op.venv {
+        let _ = write!(labels, "venv=\"{}\",", env);
+    }
+    let _ = write!(labels, "operation=\"{}\",header=\"{}\"", op.operation, op.header);
+    labels
+}
+
+///
+/// # Description
+///
+/// Converts microseconds into seconds, as rendered in the Prometheus text exposition format.
+///
+fn seconds(micros: u64) -> String {
+    format!("{}", micros as f64 / 1_000_000.0)
+}
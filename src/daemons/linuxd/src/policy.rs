@@ -0,0 +1,818 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::backend::FileSystem;
+use ::anyhow::Result;
+use ::linuxd::{
+    fcntl::{
+        self,
+        message::{
+            OpenAtRequest,
+            ReadLinkAtRequest,
+            RenameAt2Request,
+            RenameAtRequest,
+            SymbolicLinkAtRequest,
+            UnlinkAtRequest,
+        },
+    },
+    limits,
+    stdlib::message::RealPathRequest,
+    sys::{
+        inotify::message::InotifyAddWatchRequest,
+        stat::message::FileStatAtRequest,
+        xattr::message::{
+            GetXattrRequest,
+            ListXattrRequest,
+            RemoveXattrRequest,
+            SetXattrRequest,
+        },
+    },
+    unistd::message::{
+        LinkAtRequest,
+        TruncateRequest,
+    },
+    venv::VirtualEnvironmentIdentifier,
+    LinuxDaemonMessage,
+    LinuxDaemonMessageHeader,
+};
+use ::nvx::pm::ProcessIdentifier;
+use ::serde::Deserialize;
+use ::std::{
+    fs,
+    path::Path,
+};
+
+//==================================================================================================
+// Constants
+//==================================================================================================
+
+/// Names of operations that policy rules may refer to.
+const OPERATIONS: [&str; 36] = [
+    "clock_getres",
+    "clock_gettime",
+    "joinenv",
+    "openat",
+    "unlinkat",
+    "renameat",
+    "renameat2",
+    "fstatat",
+    "fstat",
+    "fdatasync",
+    "fsync",
+    "lseek",
+    "fallocate",
+    "ftruncate",
+    "truncate",
+    "posix_fadvise",
+    "read",
+    "write",
+    "pread",
+    "pwrite",
+    "symlinkat",
+    "linkat",
+    "readlinkat",
+    "flock",
+    "fcntl",
+    "copy_file_range",
+    "sendfile",
+    "getxattr",
+    "setxattr",
+    "listxattr",
+    "removexattr",
+    "realpath",
+    "inotify_add_watch",
+    "inotify_rm_watch",
+    "getrlimit",
+    "getrusage",
+];
+
+//==================================================================================================
+// Structures
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Outcome of a policy rule.
+///
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Action {
+    /// The request is permitted.
+    #[default]
+    Allow,
+    /// The request is denied.
+    Deny,
+}
+
+///
+/// # Description
+///
+/// Kind of access that a request makes to the paths it names.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Access {
+    /// The request only inspects files.
+    Read,
+    /// The request creates, modifies or removes files.
+    Write,
+}
+
+///
+/// # Description
+///
+/// Error number that denied requests fail with.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[allow(clippy::upper_case_acronyms)]
+enum Errno {
+    EACCES,
+    EPERM,
+}
+
+///
+/// # Description
+///
+/// Rule of an access policy. A rule matches a request if all of its filters match, and missing
+/// filters match everything.
+///
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Rule {
+    /// Target environment. If `None`, the rule applies to all environments.
+    env: Option<u32>,
+    /// Outcome of the rule.
+    action: Action,
+    /// Operations that the rule applies to.
+    operations: Option<Vec<String>>,
+    /// Kind of access that the rule applies to. Requests on open file descriptors make no access
+    /// of their own, as they were checked when the file was opened.
+    access: Option<Access>,
+    /// Path prefixes that the rule applies to. Requests that name no path never match them.
+    paths: Option<Vec<String>>,
+    /// Error number for denied requests. By default, `EPERM` for rules that filter on neither
+    /// paths nor access, and `EACCES` otherwise.
+    errno: Option<Errno>,
+}
+
+///
+/// # Description
+///
+/// Contents of a policy file.
+///
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PolicyFile {
+    /// Outcome for requests that match no rule.
+    #[serde(default)]
+    default: Action,
+    /// Rules, in order of precedence.
+    #[serde(default)]
+    rules: Vec<Rule>,
+}
+
+///
+/// # Description
+///
+/// Declarative access policy of all virtual environments.
+///
+/// Rules are evaluated in order and the first one that matches a request decides its outcome. A
+/// request that names several paths is only permitted if every path is. Paths are matched
+/// lexically, and wherever symbolic links lead must be permitted as well.
+///
+#[derive(Debug, Default, Clone)]
+pub struct AccessPolicy {
+    /// Outcome for requests that match no rule.
+    default: Action,
+    /// Rules, in order of precedence.
+    rules: Vec<Rule>,
+}
+
+///
+/// # Description
+///
+/// Path named by a request.
+///
+#[derive(Debug)]
+struct Target {
+    /// Directory file descriptor that the path is relative to.
+    dirfd: i32,
+    /// Path.
+    path: String,
+    /// Whether the request follows a symbolic link at the end of the path.
+    follow: bool,
+}
+
+///
+/// # Description
+///
+/// Description of a request, as seen by an access policy.
+///
+#[derive(Debug)]
+pub struct AccessRequest {
+    /// Name of the operation.
+    operation: &'static str,
+    /// Kind of access made to the named paths, if any.
+    access: Option<Access>,
+    /// Named paths.
+    targets: Vec<Target>,
+}
+
+///
+/// # Description
+///
+/// What a rule is matched against, for a single path of a request.
+///
+#[derive(Debug)]
+enum Subject<'a> {
+    /// The request names no path.
+    None,
+    /// The request names a path relative to a directory file descriptor, which cannot be resolved.
+    Unresolved,
+    /// The request names a path.
+    Path(&'a str),
+}
+
+//==================================================================================================
+// Traits
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Requests that can be checked against an access policy.
+///
+pub trait AccessSubject {
+    /// Describes the request.
+    fn describe(&self) -> AccessRequest;
+}
+
+//==================================================================================================
+// Implementations
+//==================================================================================================
+
+impl AccessPolicy {
+    ///
+    /// # Description
+    ///
+    /// Loads an access policy from a file. Files whose name ends in `.json` are parsed as JSON,
+    /// and all other files are parsed as TOML.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: Path to the policy file.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the access policy is returned. Upon failure, an error is returned instead.
+    ///
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents: String = fs::read_to_string(path)?;
+        let file: PolicyFile = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => serde_json::from_str(&contents)?,
+            _ => toml::from_str(&contents)?,
+        };
+
+        let mut rules: Vec<Rule> = Vec::with_capacity(file.rules.len());
+        for mut rule in file.rules {
+            for operation in rule.operations.iter().flatten() {
+                if !OPERATIONS.contains(&operation.as_str()) {
+                    anyhow::bail!("invalid operation: {}", operation);
+                }
+            }
+            if let Some(paths) = rule.paths.as_mut() {
+                for path in paths.iter_mut() {
+                    if !path.starts_with('/') {
+                        anyhow::bail!("path is not absolute: {}", path);
+                    }
+                    *path = normalize(path);
+                }
+            }
+            info!("adding access policy rule (rule={:?})", rule);
+            rules.push(rule);
+        }
+
+        info!("loaded access policy (path={:?}, default={:?})", path, file.default);
+
+        Ok(Self {
+            default: file.default,
+            rules,
+        })
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Checks whether a request is permitted. Denials are logged.
+    ///
+    /// # Parameters
+    ///
+    /// - `pid`: Requesting process.
+    /// - `env`: Environment of the requesting process, if any.
+    /// - `request`: Description of the request.
+    /// - `fs`: Filesystem backend of the environment, to resolve symbolic links with.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, empty is returned. Upon failure, the error number that the request should
+    /// fail with is returned instead.
+    ///
+    pub fn check(
+        &self,
+        pid: ProcessIdentifier,
+        env: Option<VirtualEnvironmentIdentifier>,
+        request: &AccessRequest,
+        fs: &mut dyn FileSystem,
+    ) -> Result<(), i32> {
+        if self.rules.is_empty() && self.default == Action::Allow {
+            return Ok(());
+        }
+
+        if request.targets.is_empty() {
+            return self.check_subject(pid, env, request, Subject::None);
+        }
+
+        for target in &request.targets {
+            for path in target.candidates(fs) {
+                let subject: Subject = match &path {
+                    Some(path) => Subject::Path(path),
+                    None => Subject::Unresolved,
+                };
+                self.check_subject(pid, env, request, subject)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_subject(
+        &self,
+        pid: ProcessIdentifier,
+        env: Option<VirtualEnvironmentIdentifier>,
+        request: &AccessRequest,
+        subject: Subject,
+    ) -> Result<(), i32> {
+        let rule: Option<&Rule> = self
+            .rules
+            .iter()
+            .find(|rule| rule.matches(env, request, &subject));
+
+        let action: Action = rule.map_or(self.default, |rule| rule.action);
+        if action == Action::Allow {
+            return Ok(());
+        }
+
+        // Denials that concern files fail with an access error, and others with a permission one.
+        let errno: i32 = match rule {
+            Some(Rule {
+                errno: Some(errno), ..
+            }) => (*errno).into(),
+            Some(rule) if rule.paths.is_none() && rule.access.is_none() => libc::EPERM,
+            Some(_) => libc::EACCES,
+            None if matches!(subject, Subject::None) => libc::EPERM,
+            None => libc::EACCES,
+        };
+
+        warn!(
+            "denied request (pid={:?}, env={:?}, operation={:?}, subject={:?}, rule={:?})",
+            pid, env, request.operation, subject, rule
+        );
+
+        Err(errno)
+    }
+}
+
+impl Rule {
+    fn matches(
+        &self,
+        env: Option<VirtualEnvironmentIdentifier>,
+        request: &AccessRequest,
+        subject: &Subject,
+    ) -> bool {
+        if self.env.is_some() && self.env.map(VirtualEnvironmentIdentifier::from) != env {
+            return false;
+        }
+
+        if let Some(operations) = &self.operations {
+            if !operations.iter().any(|op| op == request.operation) {
+                return false;
+            }
+        }
+
+        if self.access.is_some() && self.access != request.access {
+            return false;
+        }
+
+        match (&self.paths, subject) {
+            (None, _) => true,
+            (Some(_), Subject::None) => false,
+            // Paths that cannot be resolved may lie anywhere, so they are only ever denied.
+            (Some(_), Subject::Unresolved) => self.action == Action::Deny,
+            (Some(prefixes), Subject::Path(path)) => {
+                prefixes.iter().any(|prefix| is_under(path, prefix))
+            },
+        }
+    }
+}
+
+impl From<Errno> for i32 {
+    fn from(errno: Errno) -> Self {
+        match errno {
+            Errno::EACCES => libc::EACCES,
+            Errno::EPERM => libc::EPERM,
+        }
+    }
+}
+
+impl Target {
+    fn new(dirfd: i32, path: &str, follow: bool) -> Self {
+        Self {
+            dirfd,
+            path: path.to_string(),
+            follow,
+        }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the paths that a rule must permit for the target to be permitted: the path itself,
+    /// the entry it names once its parent is resolved, and the file it leads to if the request
+    /// follows symbolic links.
+    ///
+    fn candidates(&self, fs: &mut dyn FileSystem) -> Vec<Option<String>> {
+        let lexical: String = match self.resolve() {
+            Some(path) => path,
+            None => return vec![None],
+        };
+
+        let mut paths: Vec<Option<String>> = vec![Some(lexical.clone())];
+        let mut add = |path: String| {
+            let path: Option<String> = Some(normalize(&path));
+            if !paths.contains(&path) {
+                paths.push(path);
+            }
+        };
+
+        if let Some((parent, name)) = lexical.rsplit_once('/') {
+            let parent: &str = if parent.is_empty() { "/" } else { parent };
+            if let Ok(parent) = fs.realpath(parent) {
+                add(format!("{}/{}", parent, name));
+            }
+        }
+
+        if self.follow {
+            if let Ok(path) = fs.realpath(&lexical) {
+                add(path);
+            }
+        }
+
+        paths
+    }
+
+    fn resolve(&self) -> Option<String> {
+        if self.path.starts_with('/') || self.dirfd == fcntl::AT_FDCWD {
+            Some(normalize(&self.path))
+        } else {
+            None
+        }
+    }
+}
+
+impl AccessRequest {
+    ///
+    /// # Description
+    ///
+    /// Describes a request that names no path.
+    ///
+    fn unnamed(operation: &'static str) -> Self {
+        Self {
+            operation,
+            access: None,
+            targets: Vec::new(),
+        }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Describes a request that names paths. Empty paths stand for file descriptors and are
+    /// left out.
+    ///
+    fn named(operation: &'static str, access: Access, targets: Vec<Target>) -> Self {
+        Self {
+            operation,
+            access: Some(access),
+            targets: targets
+                .into_iter()
+                .filter(|target| !target.path.is_empty())
+                .collect(),
+        }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Describes a request that fits in a single message.
+    ///
+    /// # Parameters
+    ///
+    /// - `message`: Request message.
+    ///
+    /// # Returns
+    ///
+    /// The description of the request, or `None` if the message is not subject to access
+    /// policies. Parts of multipart requests are described once the request is assembled, and
+    /// requests that release resources are always permitted.
+    ///
+    pub fn from_message(message: &LinuxDaemonMessage) -> Option<Self> {
+        let header: LinuxDaemonMessageHeader = message.header;
+        let operation: &'static str = match header {
+            // Malformed requests are refused when they are dispatched.
+            LinuxDaemonMessageHeader::OpenAtRequest => {
+                return OpenAtRequest::try_from_bytes(message.payload)
+                    .ok()
+                    .map(|request| request.describe())
+            },
+            LinuxDaemonMessageHeader::UnlinkAtRequest => {
+                return Some(UnlinkAtRequest::from_bytes(message.payload).describe())
+            },
+            LinuxDaemonMessageHeader::RenameAtRequest => {
+                return Some(RenameAtRequest::from_bytes(message.payload).describe())
+            },
+            LinuxDaemonMessageHeader::GetClockResolutionRequest => "clock_getres",
+            LinuxDaemonMessageHeader::GetClockTimeRequest => "clock_gettime",
+            LinuxDaemonMessageHeader::JoinEnvRequest => "joinenv",
+            LinuxDaemonMessageHeader::FileDataSyncRequest => "fdatasync",
+            LinuxDaemonMessageHeader::FileSyncRequest => "fsync",
+            LinuxDaemonMessageHeader::SeekRequest => "lseek",
+            LinuxDaemonMessageHeader::FileSpaceControlRequest => "fallocate",
+            LinuxDaemonMessageHeader::FileTruncateRequest => "ftruncate",
+            LinuxDaemonMessageHeader::FileAdvisoryInformationRequest => "posix_fadvise",
+            LinuxDaemonMessageHeader::FileStatRequest => "fstat",
+            LinuxDaemonMessageHeader::WriteRequest => "write",
+            LinuxDaemonMessageHeader::ReadRequest => "read",
+            LinuxDaemonMessageHeader::PartialWriteRequest => "pwrite",
+            LinuxDaemonMessageHeader::PartialReadRequest => "pread",
+            LinuxDaemonMessageHeader::FileLockRequest => "flock",
+            LinuxDaemonMessageHeader::RecordLockRequest => "fcntl",
+            LinuxDaemonMessageHeader::CopyFileRangeRequest => "copy_file_range",
+            LinuxDaemonMessageHeader::SendFileRequest => "sendfile",
+            LinuxDaemonMessageHeader::InotifyRemoveWatchRequest => "inotify_rm_watch",
+            LinuxDaemonMessageHeader::GetResourceLimitRequest => "getrlimit",
+            LinuxDaemonMessageHeader::GetResourceUsageRequest => "getrusage",
+            _ => return None,
+        };
+
+        Some(Self::unnamed(operation))
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the name of the operation.
+    ///
+    pub fn operation(&self) -> &'static str {
+        self.operation
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the paths that the request names, as given by the client.
+    ///
+    pub fn paths(&self) -> impl Iterator<Item = &str> {
+        self.targets.iter().map(|target| target.path.as_str())
+    }
+}
+
+impl AccessSubject for OpenAtRequest {
+    fn describe(&self) -> AccessRequest {
+        let writes: i32 =
+            fcntl::O_WRONLY | fcntl::O_RDWR | fcntl::O_CREAT | fcntl::O_TRUNC | fcntl::O_APPEND;
+        let access: Access = if (self.flags & writes) != 0 {
+            Access::Write
+        } else {
+            Access::Read
+        };
+        AccessRequest::named(
+            "openat",
+            access,
+            vec![Target::new(
+                self.dirfd,
+                &parse_pathname(&self.pathname),
+                true,
+            )],
+        )
+    }
+}
+
+impl AccessSubject for UnlinkAtRequest {
+    fn describe(&self) -> AccessRequest {
+        AccessRequest::named(
+            "unlinkat",
+            Access::Write,
+            vec![Target::new(
+                self.dirfd,
+                &parse_pathname(&self.pathname),
+                false,
+            )],
+        )
+    }
+}
+
+impl AccessSubject for RenameAtRequest {
+    fn describe(&self) -> AccessRequest {
+        AccessRequest::named(
+            "renameat",
+            Access::Write,
+            vec![
+                Target::new(self.olddirfd, &parse_pathname(&self.oldpath), false),
+                Target::new(self.newdirfd, &parse_pathname(&self.newpath), false),
+            ],
+        )
+    }
+}
+
+impl AccessSubject for RenameAt2Request {
+    fn describe(&self) -> AccessRequest {
+        AccessRequest::named(
+            "renameat2",
+            Access::Write,
+            vec![
+                Target::new(self.olddirfd, &self.oldpath, false),
+                Target::new(self.newdirfd, &self.newpath, false),
+            ],
+        )
+    }
+}
+
+impl AccessSubject for FileStatAtRequest {
+    fn describe(&self) -> AccessRequest {
+        let follow: bool = (self.flag & libc::AT_SYMLINK_NOFOLLOW) == 0;
+        AccessRequest::named(
+            "fstatat",
+            Access::Read,
+            vec![Target::new(self.dirfd, &self.path, follow)],
+        )
+    }
+}
+
+impl AccessSubject for SymbolicLinkAtRequest {
+    fn describe(&self) -> AccessRequest {
+        // The target of the link is never accessed, but whatever opens the link later is checked.
+        AccessRequest::named(
+            "symlinkat",
+            Access::Write,
+            vec![Target::new(self.dirfd, &self.linkpath, false)],
+        )
+    }
+}
+
+impl AccessSubject for LinkAtRequest {
+    fn describe(&self) -> AccessRequest {
+        let follow: bool = (self.flags & libc::AT_SYMLINK_FOLLOW) != 0;
+        AccessRequest::named(
+            "linkat",
+            Access::Write,
+            vec![
+                Target::new(self.olddirfd, &self.oldpath, follow),
+                Target::new(self.newdirfd, &self.newpath, false),
+            ],
+        )
+    }
+}
+
+impl AccessSubject for ReadLinkAtRequest {
+    fn describe(&self) -> AccessRequest {
+        AccessRequest::named(
+            "readlinkat",
+            Access::Read,
+            vec![Target::new(self.dirfd, &self.path, false)],
+        )
+    }
+}
+
+impl AccessSubject for TruncateRequest {
+    fn describe(&self) -> AccessRequest {
+        AccessRequest::named(
+            "truncate",
+            Access::Write,
+            vec![Target::new(fcntl::AT_FDCWD, &self.path, true)],
+        )
+    }
+}
+
+impl AccessSubject for GetXattrRequest {
+    fn describe(&self) -> AccessRequest {
+        AccessRequest::named(
+            "getxattr",
+            Access::Read,
+            vec![Target::new(fcntl::AT_FDCWD, &self.path, true)],
+        )
+    }
+}
+
+impl AccessSubject for ListXattrRequest {
+    fn describe(&self) -> AccessRequest {
+        AccessRequest::named(
+            "listxattr",
+            Access::Read,
+            vec![Target::new(fcntl::AT_FDCWD, &self.path, true)],
+        )
+    }
+}
+
+impl AccessSubject for SetXattrRequest {
+    fn describe(&self) -> AccessRequest {
+        AccessRequest::named(
+            "setxattr",
+            Access::Write,
+            vec![Target::new(fcntl::AT_FDCWD, &self.path, true)],
+        )
+    }
+}
+
+impl AccessSubject for RemoveXattrRequest {
+    fn describe(&self) -> AccessRequest {
+        AccessRequest::named(
+            "removexattr",
+            Access::Write,
+            vec![Target::new(fcntl::AT_FDCWD, &self.path, true)],
+        )
+    }
+}
+
+impl AccessSubject for RealPathRequest {
+    fn describe(&self) -> AccessRequest {
+        AccessRequest::named(
+            "realpath",
+            Access::Read,
+            vec![Target::new(fcntl::AT_FDCWD, &self.path, true)],
+        )
+    }
+}
+
+impl AccessSubject for InotifyAddWatchRequest {
+    fn describe(&self) -> AccessRequest {
+        AccessRequest::named(
+            "inotify_add_watch",
+            Access::Read,
+            vec![Target::new(fcntl::AT_FDCWD, &self.path, true)],
+        )
+    }
+}
+
+//==================================================================================================
+// Standalone Functions
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Normalizes a path lexically into an absolute path, without following symbolic links.
+///
+fn normalize(path: &str) -> String {
+    let mut components: Vec<&str> = Vec::new();
+    for component in path.split('/') {
+        match component {
+            "" | "." => {},
+            ".." => {
+                components.pop();
+            },
+            component => components.push(component),
+        }
+    }
+
+    format!("/{}", components.join("/"))
+}
+
+///
+/// # Description
+///
+/// Checks whether a normalized path lies under a normalized prefix.
+///
+fn is_under(path: &str, prefix: &str) -> bool {
+    prefix == "/"
+        || path == prefix
+        || (path.starts_with(prefix) && path.as_bytes().get(prefix.len()) == Some(&b'/'))
+}
+
+///
+/// # Description
+///
+/// Parses a fixed-size path name buffer. Invalid path names are rejected by handlers later on.
+///
+fn parse_pathname(pathname: &[u8; limits::NAME_MAX]) -> String {
+    let len: usize = pathname
+        .iter()
+        .position(|b| *b == 0)
+        .unwrap_or(pathname.len());
+
+    String::from_utf8_lossy(&pathname[..len]).into_owned()
+}
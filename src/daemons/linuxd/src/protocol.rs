@@ -0,0 +1,196 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use ::anyhow::Result;
+use ::linuxd::protocol::{
+    Hello,
+    HELLO_MAGIC,
+};
+use ::std::{
+    io::{
+        ErrorKind,
+        Read,
+        Write,
+    },
+    net::TcpStream,
+    thread,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+//==================================================================================================
+// Constants
+//==================================================================================================
+
+/// Time that clients have to open the connection, either with a hello or with a request.
+const HELLO_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Time between two looks at a hello that arrived only in part.
+const HELLO_POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+//==================================================================================================
+// Standalone Functions
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Negotiates the protocol version and capabilities on a newly established connection.
+///
+/// Clients whose first frame is not a hello predate negotiation. They are assumed to speak the
+/// legacy protocol and nothing is sent to them, so that they keep working unchanged. Clients that
+/// send nothing in time are refused.
+///
+/// # Parameters
+///
+/// - `stream`: Connection to negotiate on.
+///
+/// # Returns
+///
+/// Upon success, the version and capabilities shared with the client are returned. Upon failure,
+/// an error is returned instead and the connection must be dropped.
+///
+pub fn negotiate(stream: &mut TcpStream) -> Result<Hello> {
+    let ours: Hello = Hello::current();
+
+    let theirs: Hello = if has_hello(stream)? {
+        let mut bytes: [u8; Hello::SIZE] = [0; Hello::SIZE];
+        stream.set_read_timeout(Some(HELLO_TIMEOUT))?;
+        stream.read_exact(&mut bytes)?;
+        stream.set_read_timeout(None)?;
+
+        Hello::from_bytes(bytes).map_err(|e| anyhow::anyhow!("invalid hello: {:?}", e))?
+    } else {
+        warn!("client opened with a request, assuming legacy protocol");
+        return ours
+            .negotiate(&Hello::legacy())
+            .map_err(|e| anyhow::anyhow!("unsupported legacy protocol: {:?}", e));
+    };
+
+    let shared: Hello = match ours.negotiate(&theirs) {
+        Ok(shared) => shared,
+        Err(e) => {
+            // Older clients learn about the version that they would have to speak.
+            let _ = stream.write_all(&ours.to_bytes());
+            anyhow::bail!("unsupported client (version={:?}, error={:?})", theirs.version, e);
+        },
+    };
+    stream.write_all(&ours.to_bytes())?;
+
+    info!("negotiated protocol (client={:?}, version={:?})", theirs.version, shared.version);
+
+    Ok(shared)
+}
+
+///
+/// # Description
+///
+/// Checks whether the client opens the connection with a hello, without consuming any data.
+///
+/// The decision rests on the first bytes that the client sends: a hello starts with
+/// [`HELLO_MAGIC`], whereas the first frame of a legacy client does not.
+///
+/// # Returns
+///
+/// Upon success, whether the client opens with a hello is returned. Upon failure, an error is
+/// returned instead, if the connection breaks or the client does not send enough bytes to tell
+/// in time.
+///
+fn has_hello(stream: &mut TcpStream) -> Result<bool> {
+    let deadline: Instant = Instant::now() + HELLO_TIMEOUT;
+
+    let mut magic: [u8; HELLO_MAGIC.len()] = [0; HELLO_MAGIC.len()];
+    let result: Result<bool> = loop {
+        // A zero read timeout is invalid, so an elapsed deadline must be caught beforehand.
+        let remaining: Duration = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            break Err(anyhow::anyhow!("client did not open the connection in time"));
+        }
+        stream.set_read_timeout(Some(remaining))?;
+
+        match stream.peek(&mut magic) {
+            Ok(0) => break Err(anyhow::anyhow!("connection closed during negotiation")),
+            // Data that does not look like a hello is a request from a legacy client.
+            Ok(count) if magic[..count] != HELLO_MAGIC[..count] => break Ok(false),
+            Ok(count) if count == HELLO_MAGIC.len() => break Ok(true),
+            // Peeking returns right away while part of the magic is pending, so back off.
+            Ok(_) => thread::sleep(HELLO_POLL_INTERVAL.min(remaining)),
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    ErrorKind::WouldBlock | ErrorKind::TimedOut | ErrorKind::Interrupted
+                ) =>
+            {
+                continue
+            },
+            Err(e) => break Err(e.into()),
+        }
+    };
+
+    stream.set_read_timeout(None)?;
+
+    result
+}
+
+//==================================================================================================
+// Unit Tests
+//==================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::std::net::TcpListener;
+
+    // Connect a client to a server, returning both ends.
+    fn connect() -> (TcpStream, TcpStream) {
+        let listener: TcpListener = TcpListener::bind("127.0.0.1:0").expect("failed to bind");
+        let client: TcpStream =
+            TcpStream::connect(listener.local_addr().expect("no address")).expect("no connection");
+        let (server, _): (TcpStream, _) = listener.accept().expect("failed to accept");
+        (client, server)
+    }
+
+    #[test]
+    fn has_hello_waits_for_the_rest_of_the_magic() {
+        let (mut client, mut server): (TcpStream, TcpStream) = connect();
+        client
+            .write_all(&HELLO_MAGIC[..3])
+            .expect("failed to write");
+
+        let writer: thread::JoinHandle<TcpStream> = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            client
+                .write_all(&HELLO_MAGIC[3..])
+                .expect("failed to write");
+            client
+        });
+
+        assert!(has_hello(&mut server).expect("has_hello() failed"));
+        let _client: TcpStream = writer.join().expect("writer panicked");
+    }
+
+    #[test]
+    fn has_hello_detects_legacy_requests() {
+        let (mut client, mut server): (TcpStream, TcpStream) = connect();
+        client
+            .write_all(&HELLO_MAGIC[..3])
+            .expect("failed to write");
+        client.write_all(&[0]).expect("failed to write");
+
+        assert!(!has_hello(&mut server).expect("has_hello() failed"));
+    }
+
+    #[test]
+    fn has_hello_reports_closed_connection() {
+        let (client, mut server): (TcpStream, TcpStream) = connect();
+        drop(client);
+
+        assert!(has_hello(&mut server).is_err());
+    }
+}
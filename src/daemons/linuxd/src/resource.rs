@@ -0,0 +1,403 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::backend::FileSystems;
+use ::linuxd::{
+    sys::resource::{
+        self,
+        message::{
+            GetResourceLimitRequest,
+            GetResourceLimitResponse,
+            GetResourceUsageRequest,
+            GetResourceUsageResponse,
+        },
+        rlimit,
+        rusage,
+    },
+    venv::VirtualEnvironmentIdentifier,
+    LinuxDaemonMessageHeader,
+};
+use ::nvx::{
+    ipc::Message,
+    pm::ProcessIdentifier,
+};
+use ::std::{
+    collections::HashMap,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+//==================================================================================================
+// Constants
+//==================================================================================================
+
+/// Window over which request rates are measured.
+const RATE_WINDOW: Duration = Duration::from_secs(1);
+
+//==================================================================================================
+// Structures
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Resource that can be limited per environment.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resource {
+    /// Number of open file descriptors.
+    OpenFiles,
+    /// Number of bytes written.
+    BytesWritten,
+    /// Number of bytes of disk space in use.
+    DiskUsage,
+    /// Size of a file.
+    FileSize,
+    /// Number of requests per second.
+    RequestRate,
+}
+
+///
+/// # Description
+///
+/// Resource limits of an environment. Missing limits stand for no limit.
+///
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ResourceLimits {
+    /// Maximum number of open file descriptors.
+    pub open_files: Option<u64>,
+    /// Maximum number of bytes written.
+    pub bytes_written: Option<u64>,
+    /// Maximum number of bytes of disk space in use.
+    pub disk_usage: Option<u64>,
+    /// Maximum size of a file.
+    pub file_size: Option<u64>,
+    /// Maximum number of requests per second.
+    pub request_rate: Option<u64>,
+}
+
+///
+/// # Description
+///
+/// Resource usage of an environment.
+///
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ResourceUsage {
+    /// Number of open file descriptors.
+    pub open_files: u64,
+    /// Number of bytes written.
+    pub bytes_written: u64,
+    /// Number of bytes of disk space in use, as grown by the environment itself.
+    pub disk_usage: u64,
+}
+
+///
+/// # Description
+///
+/// Requests issued by an environment within the current window.
+///
+#[derive(Debug, Clone, Copy)]
+struct RequestWindow {
+    /// Start of the window.
+    start: Instant,
+    /// Number of requests.
+    requests: u64,
+}
+
+///
+/// # Description
+///
+/// Resource limits of all environments. Filesystem limits are enforced by the filesystem
+/// backends, while request rates are enforced before requests are dispatched.
+///
+pub struct ResourceManager {
+    /// Resource limits, keyed by environment identifier.
+    rules: Vec<(Option<u32>, Resource, u64)>,
+    /// Request windows of environments.
+    windows: HashMap<Option<VirtualEnvironmentIdentifier>, RequestWindow>,
+}
+
+//==================================================================================================
+// Implementations
+//==================================================================================================
+
+impl Resource {
+    ///
+    /// # Description
+    ///
+    /// Parses the name of a resource.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: Name of the resource.
+    ///
+    /// # Returns
+    ///
+    /// The resource, or `None` if the name is invalid.
+    ///
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "nofile" => Some(Self::OpenFiles),
+            "wbytes" => Some(Self::BytesWritten),
+            "dbytes" => Some(Self::DiskUsage),
+            "fsize" => Some(Self::FileSize),
+            "nreqs" => Some(Self::RequestRate),
+            _ => None,
+        }
+    }
+
+    fn from_rlimit(resource: i32) -> Option<Self> {
+        match resource {
+            resource::RLIMIT_NOFILE => Some(Self::OpenFiles),
+            resource::RLIMIT_WBYTES => Some(Self::BytesWritten),
+            resource::RLIMIT_DBYTES => Some(Self::DiskUsage),
+            resource::RLIMIT_FSIZE => Some(Self::FileSize),
+            resource::RLIMIT_NREQS => Some(Self::RequestRate),
+            _ => None,
+        }
+    }
+}
+
+impl ResourceLimits {
+    ///
+    /// # Description
+    ///
+    /// Selects the resource limits of an environment.
+    ///
+    /// # Parameters
+    ///
+    /// - `rules`: Resource limits, keyed by environment identifier. Rules without an environment
+    ///   identifier apply to all environments without a specific rule.
+    /// - `env`: Target environment, if any.
+    ///
+    /// # Returns
+    ///
+    /// The resource limits of the target environment.
+    ///
+    pub fn select(
+        rules: &[(Option<u32>, Resource, u64)],
+        env: Option<VirtualEnvironmentIdentifier>,
+    ) -> Self {
+        let mut limits: Self = Self::default();
+
+        // Rules for specific environments take precedence.
+        let global = rules.iter().filter(|(e, _, _)| e.is_none());
+        let specific = rules
+            .iter()
+            .filter(|(e, _, _)| e.is_some() && e.map(VirtualEnvironmentIdentifier::from) == env);
+        for (_, resource, value) in global.chain(specific) {
+            *limits.get_mut(*resource) = Some(*value);
+        }
+
+        limits
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the limit of a resource.
+    ///
+    pub fn get(&self, resource: Resource) -> Option<u64> {
+        match resource {
+            Resource::OpenFiles => self.open_files,
+            Resource::BytesWritten => self.bytes_written,
+            Resource::DiskUsage => self.disk_usage,
+            Resource::FileSize => self.file_size,
+            Resource::RequestRate => self.request_rate,
+        }
+    }
+
+    fn get_mut(&mut self, resource: Resource) -> &mut Option<u64> {
+        match resource {
+            Resource::OpenFiles => &mut self.open_files,
+            Resource::BytesWritten => &mut self.bytes_written,
+            Resource::DiskUsage => &mut self.disk_usage,
+            Resource::FileSize => &mut self.file_size,
+            Resource::RequestRate => &mut self.request_rate,
+        }
+    }
+}
+
+impl ResourceManager {
+    pub fn new(rules: &[(Option<u32>, Resource, u64)]) -> Self {
+        for (env, resource, value) in rules {
+            info!("limiting resource (env={:?}, resource={:?}, value={:?})", env, resource, value);
+        }
+
+        Self {
+            rules: rules.to_vec(),
+            windows: HashMap::new(),
+        }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Accounts for a request and checks whether it may be dispatched.
+    ///
+    /// Parts of multipart requests are accounted for but always admitted, so that requests are
+    /// never rejected halfway through. Requests that release resources are always admitted.
+    ///
+    /// # Parameters
+    ///
+    /// - `env`: Environment of the requesting process, if any.
+    /// - `header`: Header of the request.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, empty is returned. Upon failure, `EAGAIN` is returned instead.
+    ///
+    pub fn admit(
+        &mut self,
+        env: Option<VirtualEnvironmentIdentifier>,
+        header: LinuxDaemonMessageHeader,
+    ) -> Result<(), i32> {
+        let limit: Option<u64> = ResourceLimits::select(&self.rules, env).request_rate;
+
+        let now: Instant = Instant::now();
+        let window: &mut RequestWindow = self.windows.entry(env).or_insert(RequestWindow {
+            start: now,
+            requests: 0,
+        });
+        if now.duration_since(window.start) >= RATE_WINDOW {
+            window.start = now;
+            window.requests = 0;
+        }
+        window.requests += 1;
+
+        match limit {
+            Some(limit) if window.requests > limit && Self::is_throttleable(header) => {
+                debug!("admit(): request rate exceeded (env={:?}, limit={:?})", env, limit);
+                Err(libc::EAGAIN)
+            },
+            _ => Ok(()),
+        }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Handles a request of the `getrlimit()` system call.
+    ///
+    /// # Parameters
+    ///
+    /// - `pid`: Requesting process.
+    /// - `env`: Environment of the requesting process, if any.
+    /// - `request`: Request message.
+    ///
+    /// # Returns
+    ///
+    /// The response message.
+    ///
+    pub fn do_getrlimit(
+        &self,
+        pid: ProcessIdentifier,
+        env: Option<VirtualEnvironmentIdentifier>,
+        request: GetResourceLimitRequest,
+    ) -> Message {
+        trace!("getrlimit(): pid={:?}, request={:?}", pid, request);
+
+        let resource: Resource = match Resource::from_rlimit(request.resource) {
+            Some(resource) => resource,
+            None => return crate::build_errno(pid, libc::EINVAL),
+        };
+
+        // Limits cannot be raised by guests, so soft and hard limits are the same.
+        let limit: u64 = ResourceLimits::select(&self.rules, env)
+            .get(resource)
+            .unwrap_or(resource::RLIM_INFINITY);
+
+        GetResourceLimitResponse::build(
+            pid,
+            rlimit {
+                rlim_cur: limit,
+                rlim_max: limit,
+            },
+        )
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Handles a request of the `getrusage()` system call.
+    ///
+    /// # Parameters
+    ///
+    /// - `pid`: Requesting process.
+    /// - `env`: Environment of the requesting process, if any.
+    /// - `request`: Request message.
+    /// - `fs`: Filesystem backends.
+    ///
+    /// # Returns
+    ///
+    /// The response message.
+    ///
+    pub fn do_getrusage(
+        &self,
+        pid: ProcessIdentifier,
+        env: Option<VirtualEnvironmentIdentifier>,
+        request: GetResourceUsageRequest,
+        fs: &FileSystems,
+    ) -> Message {
+        trace!("getrusage(): pid={:?}, request={:?}", pid, request);
+
+        if request.who != resource::RUSAGE_SELF {
+            return crate::build_errno(pid, libc::EINVAL);
+        }
+
+        let usage: ResourceUsage = fs.usage(env);
+        let requests: u64 = match self.windows.get(&env) {
+            Some(window) if window.start.elapsed() < RATE_WINDOW => window.requests,
+            _ => 0,
+        };
+
+        GetResourceUsageResponse::build(
+            pid,
+            rusage {
+                ru_nofile: usage.open_files,
+                ru_wbytes: usage.bytes_written,
+                ru_dbytes: usage.disk_usage,
+                ru_nreqs: requests,
+            },
+        )
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Forgets about an environment that no longer exists.
+    ///
+    /// # Parameters
+    ///
+    /// - `env`: Target environment.
+    ///
+    pub fn release(&mut self, env: VirtualEnvironmentIdentifier) {
+        self.windows.remove(&Some(env));
+    }
+
+    fn is_throttleable(header: LinuxDaemonMessageHeader) -> bool {
+        !matches!(
+            header,
+            LinuxDaemonMessageHeader::LeaveEnvRequest
+                | LinuxDaemonMessageHeader::CloseRequest
+                | LinuxDaemonMessageHeader::FileStatAtRequestPart
+                | LinuxDaemonMessageHeader::SymbolicLinkAtRequestPart
+                | LinuxDaemonMessageHeader::LinkAtRequestPart
+                | LinuxDaemonMessageHeader::ReadLinkAtRequestPart
+                | LinuxDaemonMessageHeader::GetXattrRequestPart
+                | LinuxDaemonMessageHeader::SetXattrRequestPart
+                | LinuxDaemonMessageHeader::ListXattrRequestPart
+                | LinuxDaemonMessageHeader::RemoveXattrRequestPart
+                | LinuxDaemonMessageHeader::TruncateRequestPart
+                | LinuxDaemonMessageHeader::RenameAt2RequestPart
+                | LinuxDaemonMessageHeader::RealPathRequestPart
+                | LinuxDaemonMessageHeader::InotifyAddWatchRequestPart
+        )
+    }
+}
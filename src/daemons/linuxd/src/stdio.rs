@@ -0,0 +1,467 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use ::linuxd::{
+    sys::types::{
+        size_t,
+        ssize_t,
+    },
+    unistd::message::{
+        CloseRequest,
+        CloseResponse,
+        ReadRequest,
+        ReadResponse,
+        WriteRequest,
+        WriteResponse,
+    },
+    venv::VirtualEnvironmentIdentifier,
+};
+use ::nvx::{
+    ipc::Message,
+    pm::ProcessIdentifier,
+    sys::error::ErrorCode,
+};
+use ::std::{
+    collections::{
+        BTreeMap,
+        BTreeSet,
+        HashMap,
+    },
+    fs::{
+        File,
+        OpenOptions,
+    },
+    io::{
+        self,
+        Read,
+        Write,
+    },
+    path::PathBuf,
+};
+
+//==================================================================================================
+// Constants
+//==================================================================================================
+
+/// Names of standard streams, indexed by file descriptor.
+pub const STDIO_STREAMS: [&str; 3] = ["stdin", "stdout", "stderr"];
+
+/// Maximum number of bytes retained by a capture buffer. Older output is discarded first.
+const CAPTURE_SIZE_MAX: usize = 64 * 1024;
+
+/// Maximum number of bytes of a partial console line, past which it is printed without waiting
+/// for a line break.
+const LINE_SIZE_MAX: usize = CAPTURE_SIZE_MAX;
+
+//==================================================================================================
+// Structures
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Where a standard stream of an environment is mapped to.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StreamTarget {
+    /// Console of the daemon, with every line prefixed by the environment and process.
+    Console,
+    /// In-memory buffer. Reading from it yields end-of-file. Captured output cannot be read back
+    /// while the connection is live: it is only logged once the connection is torn down.
+    Capture,
+    /// Host file.
+    File(PathBuf),
+}
+
+///
+/// # Description
+///
+/// Open standard stream.
+///
+enum Stream {
+    /// Console of the daemon.
+    Console,
+    /// In-memory buffer.
+    Capture(Vec<u8>),
+    /// Host file.
+    File(File),
+}
+
+///
+/// # Description
+///
+/// Standard streams of all environments. Streams are opened the first time an environment uses
+/// them, and processes that have not joined an environment share a set of their own.
+///
+pub struct StandardStreams {
+    /// Stream mappings, optionally for a specific environment.
+    targets: Vec<(Option<u32>, i32, StreamTarget)>,
+    /// Open streams.
+    streams: HashMap<Option<VirtualEnvironmentIdentifier>, [Stream; 3]>,
+    /// Partial console lines, pending a line break.
+    lines: BTreeMap<(ProcessIdentifier, i32), (Option<VirtualEnvironmentIdentifier>, Vec<u8>)>,
+    /// Standard streams closed by processes.
+    closed: BTreeSet<(ProcessIdentifier, i32)>,
+}
+
+//==================================================================================================
+// Implementations
+//==================================================================================================
+
+impl StreamTarget {
+    ///
+    /// # Description
+    ///
+    /// Parses a stream target.
+    ///
+    /// # Parameters
+    ///
+    /// - `spec`: Specification in the form `console`, `capture` or `file:<path>`.
+    ///
+    /// # Returns
+    ///
+    /// The stream target, or `None` if the specification is invalid.
+    ///
+    pub fn parse(spec: &str) -> Option<Self> {
+        match spec {
+            "console" => Some(Self::Console),
+            "capture" => Some(Self::Capture),
+            _ => match spec.strip_prefix("file:") {
+                Some(path) if !path.is_empty() => Some(Self::File(PathBuf::from(path))),
+                _ => None,
+            },
+        }
+    }
+}
+
+impl StandardStreams {
+    pub fn new(targets: &[(Option<u32>, i32, StreamTarget)]) -> Self {
+        Self {
+            targets: targets.to_vec(),
+            streams: HashMap::new(),
+            lines: BTreeMap::new(),
+            closed: BTreeSet::new(),
+        }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Checks whether a file descriptor refers to a standard stream.
+    ///
+    pub fn is_standard(fd: i32) -> bool {
+        (0..STDIO_STREAMS.len() as i32).contains(&fd)
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Handles a `write()` request on a standard stream.
+    ///
+    /// # Parameters
+    ///
+    /// - `pid`: Requesting process.
+    /// - `env`: Environment of the requesting process.
+    /// - `request`: Request message.
+    ///
+    /// # Returns
+    ///
+    /// The response message.
+    ///
+    pub fn write(
+        &mut self,
+        pid: ProcessIdentifier,
+        env: Option<VirtualEnvironmentIdentifier>,
+        request: WriteRequest,
+    ) -> Message {
+        trace!("write(): pid={:?}, env={:?}, fd={:?}", pid, env, { request.fd });
+
+        // Check if count is invalid.
+        if request.count > WriteRequest::BUFFER_SIZE as size_t {
+            return crate::build_error(pid, ErrorCode::InvalidArgument);
+        }
+        let fd: i32 = request.fd;
+        let buffer: &[u8] = &request.buffer[..request.count as usize];
+
+        if fd == libc::STDIN_FILENO || self.closed.contains(&(pid, fd)) {
+            return crate::build_errno(pid, libc::EBADF);
+        }
+
+        match self.stream(env, fd) {
+            Stream::Console => {},
+            Stream::Capture(captured) => {
+                captured.extend_from_slice(buffer);
+                if captured.len() > CAPTURE_SIZE_MAX {
+                    let excess: usize = captured.len() - CAPTURE_SIZE_MAX;
+                    captured.drain(..excess);
+                }
+            },
+            Stream::File(file) => {
+                if let Err(e) = file.write_all(buffer) {
+                    let errno: i32 = e.raw_os_error().unwrap_or(libc::EIO);
+                    return crate::build_errno(pid, errno);
+                }
+            },
+        }
+
+        if let Stream::Console = self.stream(env, fd) {
+            let (_, pending) = self.lines.entry((pid, fd)).or_insert((env, Vec::new()));
+            pending.extend_from_slice(buffer);
+
+            // Only complete lines are emitted, so that output of different processes does not
+            // interleave within a line.
+            if let Some(end) = pending.iter().rposition(|b| *b == b'\n') {
+                let lines: Vec<u8> = pending.drain(..=end).collect();
+                Self::print(env, pid, fd, &lines);
+            }
+
+            // Guests that never break lines would otherwise grow the pending line without bound.
+            if pending.len() > LINE_SIZE_MAX {
+                self.flush(pid, fd);
+            }
+        }
+
+        WriteResponse::build(pid, buffer.len() as ssize_t)
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Handles a `read()` request on a standard stream.
+    ///
+    /// # Parameters
+    ///
+    /// - `pid`: Requesting process.
+    /// - `env`: Environment of the requesting process.
+    /// - `request`: Request message.
+    ///
+    /// # Returns
+    ///
+    /// The response message.
+    ///
+    pub fn read(
+        &mut self,
+        pid: ProcessIdentifier,
+        env: Option<VirtualEnvironmentIdentifier>,
+        request: ReadRequest,
+    ) -> Message {
+        trace!("read(): pid={:?}, env={:?}, fd={:?}", pid, env, { request.fd });
+
+        // Check if count is invalid.
+        if request.count > ReadResponse::BUFFER_SIZE as size_t {
+            return crate::build_error(pid, ErrorCode::InvalidArgument);
+        }
+        let fd: i32 = request.fd;
+        let count: usize = request.count as usize;
+
+        if fd != libc::STDIN_FILENO || self.closed.contains(&(pid, fd)) {
+            return crate::build_errno(pid, libc::EBADF);
+        }
+
+        let mut buffer: [u8; ReadResponse::BUFFER_SIZE] = [0; ReadResponse::BUFFER_SIZE];
+        let count: usize = match self.stream(env, fd) {
+            Stream::Console | Stream::Capture(_) => 0,
+            Stream::File(file) => match file.read(&mut buffer[..count]) {
+                Ok(count) => count,
+                Err(e) => {
+                    let errno: i32 = e.raw_os_error().unwrap_or(libc::EIO);
+                    return crate::build_errno(pid, errno);
+                },
+            },
+        };
+
+        ReadResponse::build(pid, count as i32, buffer)
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Handles a `close()` request on a standard stream. The stream is closed for the requesting
+    /// process only, as it is shared with the rest of its environment.
+    ///
+    /// # Parameters
+    ///
+    /// - `pid`: Requesting process.
+    /// - `request`: Request message.
+    ///
+    /// # Returns
+    ///
+    /// The response message.
+    ///
+    pub fn close(&mut self, pid: ProcessIdentifier, request: CloseRequest) -> Message {
+        trace!("close(): pid={:?}, fd={:?}", pid, { request.fd });
+
+        let fd: i32 = request.fd;
+        if !self.closed.insert((pid, fd)) {
+            return crate::build_errno(pid, libc::EBADF);
+        }
+
+        self.flush(pid, fd);
+
+        CloseResponse::build(pid, 0)
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Flushes and forgets the state of a process that leaves its environment.
+    ///
+    /// # Parameters
+    ///
+    /// - `pid`: Target process.
+    ///
+    pub fn release_process(&mut self, pid: ProcessIdentifier) {
+        for fd in 0..STDIO_STREAMS.len() as i32 {
+            self.flush(pid, fd);
+        }
+        self.closed.retain(|(p, _)| *p != pid);
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Prints pending partial lines of a process.
+    ///
+    fn flush(&mut self, pid: ProcessIdentifier, fd: i32) {
+        if let Some((env, mut pending)) = self.lines.remove(&(pid, fd)) {
+            if !pending.is_empty() {
+                pending.push(b'\n');
+                Self::print(env, pid, fd, &pending);
+            }
+        }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Prints complete lines to the console of the daemon, prefixing each of them.
+    ///
+    fn print(
+        env: Option<VirtualEnvironmentIdentifier>,
+        pid: ProcessIdentifier,
+        fd: i32,
+        lines: &[u8],
+    ) {
+        let env: String = match env {
+            Some(env) => env.id().to_string(),
+            None => "-".to_string(),
+        };
+        let prefix: String = format!("[venv {} pid {}] ", env, u32::from(pid));
+
+        let mut output: Vec<u8> = Vec::new();
+        for line in lines.split_inclusive(|b| *b == b'\n') {
+            output.extend_from_slice(prefix.as_bytes());
+            output.extend_from_slice(line);
+        }
+
+        let result: io::Result<()> = if fd == libc::STDERR_FILENO {
+            io::stderr().lock().write_all(&output)
+        } else {
+            io::stdout().lock().write_all(&output)
+        };
+        if let Err(e) = result {
+            error!("failed to print guest output (error={:?})", e);
+        }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns a standard stream of an environment, opening the streams of the environment if
+    /// needed.
+    ///
+    fn stream(&mut self, env: Option<VirtualEnvironmentIdentifier>, fd: i32) -> &mut Stream {
+        if !self.streams.contains_key(&env) {
+            let streams: [Stream; 3] = [
+                self.open(env, libc::STDIN_FILENO),
+                self.open(env, libc::STDOUT_FILENO),
+                self.open(env, libc::STDERR_FILENO),
+            ];
+            self.streams.insert(env, streams);
+        }
+
+        &mut self
+            .streams
+            .get_mut(&env)
+            .expect("streams were just opened")[fd as usize]
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Opens a standard stream of an environment. Mappings for the environment take precedence over
+    /// mappings for all environments. Streams that fail to open fall back to their defaults.
+    ///
+    fn open(&self, env: Option<VirtualEnvironmentIdentifier>, fd: i32) -> Stream {
+        let id: Option<u32> = env.map(|env| env.id());
+        let target: Option<&StreamTarget> = self
+            .targets
+            .iter()
+            .rev()
+            .find(|(e, f, _)| *f == fd && e.is_some() && *e == id)
+            .or_else(|| {
+                self.targets
+                    .iter()
+                    .rev()
+                    .find(|(e, f, _)| *f == fd && e.is_none())
+            })
+            .map(|(_, _, target)| target);
+
+        match target {
+            Some(StreamTarget::File(path)) => {
+                let file: io::Result<File> = if fd == libc::STDIN_FILENO {
+                    File::open(path)
+                } else {
+                    OpenOptions::new().create(true).append(true).open(path)
+                };
+                match file {
+                    Ok(file) => return Stream::File(file),
+                    Err(e) => error!(
+                        "failed to open {} (env={:?}, path={:?}, error={:?})",
+                        STDIO_STREAMS[fd as usize], env, path, e
+                    ),
+                }
+            },
+            Some(StreamTarget::Capture) => return Stream::Capture(Vec::new()),
+            Some(StreamTarget::Console) => return Stream::Console,
+            None => {},
+        }
+
+        // Standard input is empty by default, so that guests never block on the console.
+        if fd == libc::STDIN_FILENO {
+            Stream::Capture(Vec::new())
+        } else {
+            Stream::Console
+        }
+    }
+}
+
+impl Drop for StandardStreams {
+    fn drop(&mut self) {
+        // Emit leftovers and captured output, which would otherwise be lost. This is the only place
+        // where captured output surfaces.
+        let pending: Vec<(ProcessIdentifier, i32)> = self.lines.keys().copied().collect();
+        for (pid, fd) in pending {
+            self.flush(pid, fd);
+        }
+
+        for (env, streams) in self.streams.iter() {
+            for (fd, stream) in streams.iter().enumerate() {
+                if let Stream::Capture(captured) = stream {
+                    if !captured.is_empty() {
+                        info!(
+                            "captured {} (env={:?}): {}",
+                            STDIO_STREAMS[fd],
+                            env,
+                            String::from_utf8_lossy(captured)
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,163 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::backend::FileSystem;
+use ::linuxd::{
+    message::MessagePartitioner,
+    stdlib::message::{
+        RealPathRequest,
+        RealPathResponse,
+    },
+};
+use ::nvx::{
+    ipc::Message,
+    pm::ProcessIdentifier,
+};
+use ::std::{
+    fs,
+    path::{
+        Path,
+        PathBuf,
+    },
+};
+
+//==================================================================================================
+// do_realpath
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Resolves a path into a canonical path relative to the root of the virtual environment. Paths
+/// that resolve outside of the root are rejected, so that host paths are never disclosed.
+///
+/// # Parameters
+///
+/// - `pid`: Process identifier.
+/// - `request`: Request message.
+/// - `fs`: Filesystem backend of the process.
+///
+/// # Returns
+///
+/// The response messages.
+///
+pub fn do_realpath(
+    pid: ProcessIdentifier,
+    request: RealPathRequest,
+    fs: &mut dyn FileSystem,
+) -> Vec<Message> {
+    trace!("realpath(): pid={:?}, request={:?}", pid, request);
+
+    let path: String = match fs.realpath(&request.path) {
+        Ok(path) => path,
+        Err(errno) => return vec![crate::build_errno(pid, errno)],
+    };
+
+    let response: RealPathResponse = match RealPathResponse::new(path) {
+        Ok(response) => response,
+        Err(e) => return vec![crate::build_error(pid, e.code)],
+    };
+
+    match response.into_parts(pid) {
+        Ok(messages) => messages,
+        Err(e) => vec![crate::build_error(pid, e.code)],
+    }
+}
+
+//==================================================================================================
+// Standalone Functions
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Resolves a path into a canonical host path that lies within the root of the virtual
+/// environment.
+///
+/// # Parameters
+///
+/// - `path`: Path to resolve.
+/// - `root`: Canonical host path of the root of the virtual environment.
+///
+/// # Returns
+///
+/// Upon success, the canonical host path is returned. Upon failure, a host error number is
+/// returned instead.
+///
+pub fn canonicalize(path: &str, root: &Path) -> Result<PathBuf, i32> {
+    let canonical: PathBuf = match fs::canonicalize(path) {
+        Ok(canonical) => canonical,
+        Err(e) => {
+            let errno: i32 = e.raw_os_error().unwrap_or(libc::EINVAL);
+            debug!("fs::canonicalize(): errno={:?}", errno);
+            return Err(errno);
+        },
+    };
+
+    // Never disclose anything that lies outside of the root.
+    if !canonical.starts_with(root) {
+        warn!("path escapes root (path={:?})", path);
+        return Err(libc::EACCES);
+    }
+
+    Ok(canonical)
+}
+
+//==================================================================================================
+// Unit Tests
+//==================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::MemoryFileSystem;
+    use ::linuxd::{
+        fcntl,
+        message::LinuxDaemonMessagePart,
+        LinuxDaemonMessage,
+    };
+
+    // Reassemble a multipart response.
+    fn assemble(messages: Vec<Message>) -> RealPathResponse {
+        let parts: Vec<LinuxDaemonMessagePart> = messages
+            .into_iter()
+            .map(|message| {
+                assert_eq!({ message.status }, 0);
+                let message: LinuxDaemonMessage =
+                    LinuxDaemonMessage::try_from_bytes(message.payload).expect("malformed message");
+                LinuxDaemonMessagePart::try_from_bytes(message.payload).expect("malformed part")
+            })
+            .collect();
+
+        RealPathResponse::from_parts(&parts).expect("malformed response")
+    }
+
+    #[test]
+    fn realpath_resolves_dot_components() {
+        let pid: ProcessIdentifier = ProcessIdentifier::from(1);
+        let mut fs: MemoryFileSystem = MemoryFileSystem::new();
+        fs.open_at(fcntl::AT_FDCWD, "file", fcntl::O_CREAT | fcntl::O_RDWR, fcntl::S_IRUSR)
+            .expect("failed to create file");
+
+        let request: RealPathRequest =
+            RealPathRequest::new("/./file".to_string()).expect("failed to build request");
+        let response: RealPathResponse = assemble(do_realpath(pid, request, &mut fs));
+        assert_eq!(response.path, "/file");
+    }
+
+    #[test]
+    fn realpath_reports_missing_path() {
+        let pid: ProcessIdentifier = ProcessIdentifier::from(1);
+        let mut fs: MemoryFileSystem = MemoryFileSystem::new();
+
+        let request: RealPathRequest =
+            RealPathRequest::new("/missing".to_string()).expect("failed to build request");
+        let responses: Vec<Message> = do_realpath(pid, request, &mut fs);
+        assert_eq!(responses.len(), 1);
+        assert_eq!({ responses[0].status }, -libc::ENOENT);
+    }
+}
@@ -5,18 +5,25 @@
 // Imports
 //==================================================================================================
 
-use ::alloc::ffi::CString;
-use ::core::ffi;
+use crate::backend::FileSystem;
+use ::core::cmp;
 use ::linuxd::{
-    sys::types::{
-        off_t,
-        size_t,
-        ssize_t,
+    sys::{
+        sendfile::message::{
+            SendFileRequest,
+            SendFileResponse,
+        },
+        types::{
+            off_t,
+            size_t,
+            ssize_t,
+        },
     },
-    unistd,
     unistd::message::{
         CloseRequest,
         CloseResponse,
+        CopyFileRangeRequest,
+        CopyFileRangeResponse,
         FileDataSyncRequest,
         FileDataSyncResponse,
         FileSyncRequest,
@@ -33,6 +40,8 @@ use ::linuxd::{
         ReadResponse,
         SeekRequest,
         SeekResponse,
+        TruncateRequest,
+        TruncateResponse,
         WriteRequest,
         WriteResponse,
     },
@@ -40,25 +49,19 @@ use ::linuxd::{
 use ::nvx::{
     ipc::Message,
     pm::ProcessIdentifier,
-    sys::error::{
-        Error,
-        ErrorCode,
-    },
+    sys::error::ErrorCode,
 };
 
 //==================================================================================================
 // do_close
 //==================================================================================================
 
-pub fn do_close(pid: ProcessIdentifier, request: CloseRequest) -> Message {
+pub fn do_close(pid: ProcessIdentifier, request: CloseRequest, fs: &mut dyn FileSystem) -> Message {
     trace!("close(): pid={:?}, request={:?}", pid, request);
 
-    let fd: i32 = request.fd;
-
-    debug!("libc::close(): fd={:?}", fd);
-    match unsafe { libc::close(fd) } {
-        ret if ret == 0 => CloseResponse::build(pid, ret),
-        _ => crate::build_error(pid, ErrorCode::InvalidArgument),
+    match fs.close(request.fd) {
+        Ok(()) => CloseResponse::build(pid, 0),
+        Err(errno) => crate::build_errno(pid, errno),
     }
 }
 
@@ -66,18 +69,16 @@ pub fn do_close(pid: ProcessIdentifier, request: CloseRequest) -> Message {
 // do_fdatasync
 //==================================================================================================
 
-pub fn do_fdatasync(pid: ProcessIdentifier, request: FileDataSyncRequest) -> Message {
+pub fn do_fdatasync(
+    pid: ProcessIdentifier,
+    request: FileDataSyncRequest,
+    fs: &mut dyn FileSystem,
+) -> Message {
     trace!("fdatasync(): pid={:?}, request={:?}", pid, request);
 
-    let fd: i32 = request.fd;
-
-    debug!("libc::fdatasync(): fd={:?}", fd);
-    match unsafe { libc::fdatasync(fd) } {
-        ret if ret == 0 => FileDataSyncResponse::build(pid, ret),
-        ret => crate::build_error(
-            pid,
-            ErrorCode::try_from(ret).unwrap_or_else(|_| panic!("invalid error code: {:?}", ret)),
-        ),
+    match fs.fdatasync(request.fd) {
+        Ok(()) => FileDataSyncResponse::build(pid, 0),
+        Err(errno) => crate::build_errno(pid, errno),
     }
 }
 
@@ -85,18 +86,16 @@ pub fn do_fdatasync(pid: ProcessIdentifier, request: FileDataSyncRequest) -> Mes
 // do_fsync
 //==================================================================================================
 
-pub fn do_fsync(pid: ProcessIdentifier, request: FileSyncRequest) -> Message {
+pub fn do_fsync(
+    pid: ProcessIdentifier,
+    request: FileSyncRequest,
+    fs: &mut dyn FileSystem,
+) -> Message {
     trace!("fsync(): pid={:?}, request={:?}", pid, request);
 
-    let fd: i32 = request.fd;
-
-    debug!("libc::fsync(): fd={:?}", fd);
-    match unsafe { libc::fsync(fd) } {
-        ret if ret == 0 => FileSyncResponse::build(pid, ret),
-        ret => crate::build_error(
-            pid,
-            ErrorCode::try_from(ret).unwrap_or_else(|_| panic!("invalid error code: {:?}", ret)),
-        ),
+    match fs.fsync(request.fd) {
+        Ok(()) => FileSyncResponse::build(pid, 0),
+        Err(errno) => crate::build_errno(pid, errno),
     }
 }
 
@@ -104,24 +103,12 @@ pub fn do_fsync(pid: ProcessIdentifier, request: FileSyncRequest) -> Message {
 // do_lseek
 //==================================================================================================
 
-pub fn do_lseek(pid: ProcessIdentifier, request: SeekRequest) -> Message {
+pub fn do_lseek(pid: ProcessIdentifier, request: SeekRequest, fs: &mut dyn FileSystem) -> Message {
     trace!("lseek(): pid={:?}, request={:?}", pid, request);
 
-    let fd: i32 = request.fd;
-    let offset: i64 = request.offset;
-    let whence: LibcSeek = match LibcSeek::try_from(request.whence) {
-        Ok(whence) => whence,
-        Err(_) => return crate::build_error(pid, ErrorCode::InvalidMessage),
-    };
-
-    debug!("libc::lseek(): fd={:?}, offset={:?}, whence={:?}", fd, offset, whence.inner());
-    match unsafe { libc::lseek(fd, offset, whence.inner()) } {
-        ret if ret >= 0 => SeekResponse::build(pid, ret),
-        ret => crate::build_error(
-            pid,
-            ErrorCode::try_from(ret as i32)
-                .unwrap_or_else(|_| panic!("invalid error code: {:?}", ret)),
-        ),
+    match fs.lseek(request.fd, request.offset, request.whence) {
+        Ok(offset) => SeekResponse::build(pid, offset),
+        Err(errno) => crate::build_errno(pid, errno),
     }
 }
 
@@ -129,19 +116,16 @@ pub fn do_lseek(pid: ProcessIdentifier, request: SeekRequest) -> Message {
 // do_ftruncate
 //==================================================================================================
 
-pub fn do_ftruncate(pid: ProcessIdentifier, request: FileTruncateRequest) -> Message {
+pub fn do_ftruncate(
+    pid: ProcessIdentifier,
+    request: FileTruncateRequest,
+    fs: &mut dyn FileSystem,
+) -> Message {
     trace!("ftruncate(): pid={:?}, request={:?}", pid, request);
 
-    let fd: i32 = request.fd;
-    let length: off_t = request.length;
-
-    debug!("libc::ftruncate(): fd={:?}, length={:?}", fd, length);
-    match unsafe { libc::ftruncate(fd, length) } {
-        ret if ret == 0 => FileTruncateResponse::build(pid, ret),
-        ret => crate::build_error(
-            pid,
-            ErrorCode::try_from(ret).unwrap_or_else(|_| panic!("invalid error code: {:?}", ret)),
-        ),
+    match fs.ftruncate(request.fd, request.length) {
+        Ok(()) => FileTruncateResponse::build(pid, 0),
+        Err(errno) => crate::build_errno(pid, errno),
     }
 }
 
@@ -149,26 +133,18 @@ pub fn do_ftruncate(pid: ProcessIdentifier, request: FileTruncateRequest) -> Mes
 // do_write
 //==================================================================================================
 
-pub fn do_write(pid: ProcessIdentifier, request: WriteRequest) -> Message {
+pub fn do_write(pid: ProcessIdentifier, request: WriteRequest, fs: &mut dyn FileSystem) -> Message {
     trace!("write(): pid={:?}, request={:?}", pid, request);
 
     // Check if count is invalid.
     if request.count > WriteRequest::BUFFER_SIZE as size_t {
         return crate::build_error(pid, ErrorCode::InvalidArgument);
     }
-    let fd: i32 = request.fd;
     let count: usize = request.count as usize;
 
-    let buffer: &[u8] = &request.buffer[..count];
-
-    debug!("libc::write(): fd={:?}, buffer={:?}", fd, buffer);
-    match unsafe { libc::write(fd, buffer.as_ptr() as *const _, count) } {
-        ret if ret >= 0 => WriteResponse::build(pid, ret as i32),
-        ret => crate::build_error(
-            pid,
-            ErrorCode::try_from(ret as i32)
-                .unwrap_or_else(|_| panic!("invalid error code: {:?}", ret)),
-        ),
+    match fs.write(request.fd, &request.buffer[..count]) {
+        Ok(count) => WriteResponse::build(pid, count as ssize_t),
+        Err(errno) => crate::build_errno(pid, errno),
     }
 }
 
@@ -176,26 +152,20 @@ pub fn do_write(pid: ProcessIdentifier, request: WriteRequest) -> Message {
 // do_read
 //==================================================================================================
 
-pub fn do_read(pid: ProcessIdentifier, request: ReadRequest) -> Message {
+pub fn do_read(pid: ProcessIdentifier, request: ReadRequest, fs: &mut dyn FileSystem) -> Message {
     trace!("read(): pid={:?}, request={:?}", pid, request);
 
     // Check if count is invalid.
     if request.count > ReadResponse::BUFFER_SIZE as size_t {
         return crate::build_error(pid, ErrorCode::InvalidArgument);
     }
-    let fd: i32 = request.fd;
     let count: usize = request.count as usize;
 
     let mut buffer: [u8; ReadResponse::BUFFER_SIZE] = [0; ReadResponse::BUFFER_SIZE];
 
-    debug!("libc::read(): fd={:?}, buffer={:?}", fd, buffer);
-    match unsafe { libc::read(fd, buffer.as_mut_ptr() as *mut _, count) } {
-        ret if ret >= 0 => ReadResponse::build(pid, ret as i32, buffer),
-        ret => crate::build_error(
-            pid,
-            ErrorCode::try_from(ret as i32)
-                .unwrap_or_else(|_| panic!("invalid error code: {:?}", ret)),
-        ),
+    match fs.read(request.fd, &mut buffer[..count]) {
+        Ok(count) => ReadResponse::build(pid, count as i32, buffer),
+        Err(errno) => crate::build_errno(pid, errno),
     }
 }
 
@@ -203,30 +173,22 @@ pub fn do_read(pid: ProcessIdentifier, request: ReadRequest) -> Message {
 // do_pwrite
 //==================================================================================================
 
-pub fn do_pwrite(pid: ProcessIdentifier, request: PartialWriteRequest) -> Message {
+pub fn do_pwrite(
+    pid: ProcessIdentifier,
+    request: PartialWriteRequest,
+    fs: &mut dyn FileSystem,
+) -> Message {
     trace!("pwrite(): pid={:?}, request={:?}", pid, request);
 
     // Check if count is invalid.
     if request.count > PartialWriteRequest::BUFFER_SIZE as size_t {
         return crate::build_error(pid, ErrorCode::InvalidArgument);
     }
-    let fd: i32 = request.fd;
     let count: usize = request.count as usize;
-    let offset: off_t = request.offset;
-
-    let buffer: &[u8] = &request.buffer[..count];
 
-    debug!(
-        "libc::pwrite(): fd={:?}, count={:?}, offset={:?}, buffer={:?}",
-        fd, count, offset, buffer
-    );
-    match unsafe { libc::pwrite(fd, buffer.as_ptr() as *const _, count, offset) } {
-        ret if ret >= 0 => PartialWriteResponse::build(pid, ret as ssize_t),
-        ret => crate::build_error(
-            pid,
-            ErrorCode::try_from(ret as i32)
-                .unwrap_or_else(|_| panic!("invalid error code: {:?}", ret)),
-        ),
+    match fs.pwrite(request.fd, &request.buffer[..count], request.offset) {
+        Ok(count) => PartialWriteResponse::build(pid, count as ssize_t),
+        Err(errno) => crate::build_errno(pid, errno),
     }
 }
 
@@ -234,30 +196,79 @@ pub fn do_pwrite(pid: ProcessIdentifier, request: PartialWriteRequest) -> Messag
 // do_pread
 //==================================================================================================
 
-pub fn do_pread(pid: ProcessIdentifier, request: PartialReadRequest) -> Message {
+pub fn do_pread(
+    pid: ProcessIdentifier,
+    request: PartialReadRequest,
+    fs: &mut dyn FileSystem,
+) -> Message {
     trace!("pread(): pid={:?}, request={:?}", pid, request);
 
     // Check if count is invalid.
     if request.count > PartialReadResponse::BUFFER_SIZE as size_t {
         return crate::build_error(pid, ErrorCode::InvalidArgument);
     }
-    let fd: i32 = request.fd;
     let count: usize = request.count as usize;
-    let offset: off_t = request.offset;
 
     let mut buffer: [u8; PartialReadResponse::BUFFER_SIZE] = [0; PartialReadResponse::BUFFER_SIZE];
 
-    debug!(
-        "libc::pread(): fd={:?}, count={:?}, offset={:?}, buffer={:?}",
-        fd, count, offset, buffer
-    );
-    match unsafe { libc::pread(fd, buffer.as_mut_ptr() as *mut _, count, offset) } {
-        ret if ret >= 0 => PartialReadResponse::build(pid, ret as ssize_t, buffer),
-        ret => crate::build_error(
+    match fs.pread(request.fd, &mut buffer[..count], request.offset) {
+        Ok(count) => PartialReadResponse::build(pid, count as ssize_t, buffer),
+        Err(errno) => crate::build_errno(pid, errno),
+    }
+}
+
+//==================================================================================================
+// do_copy_file_range
+//==================================================================================================
+
+pub fn do_copy_file_range(
+    pid: ProcessIdentifier,
+    request: CopyFileRangeRequest,
+    fs: &mut dyn FileSystem,
+) -> Message {
+    trace!("copy_file_range(): pid={:?}, request={:?}", pid, request);
+
+    let mut off_in: Option<off_t> = request.off_in();
+    let mut off_out: Option<off_t> = request.off_out();
+    // Clamp length, so that the number of bytes copied fits in the response.
+    let len: usize = cmp::min(request.len as usize, ssize_t::MAX as usize);
+
+    match fs.copy_file_range(
+        request.fd_in,
+        off_in.as_mut(),
+        request.fd_out,
+        off_out.as_mut(),
+        len,
+        request.flags,
+    ) {
+        Ok(count) => CopyFileRangeResponse::build(
             pid,
-            ErrorCode::try_from(ret as i32)
-                .unwrap_or_else(|_| panic!("invalid error code: {:?}", ret)),
+            count as ssize_t,
+            off_in.unwrap_or_default(),
+            off_out.unwrap_or_default(),
         ),
+        Err(errno) => crate::build_errno(pid, errno),
+    }
+}
+
+//==================================================================================================
+// do_sendfile
+//==================================================================================================
+
+pub fn do_sendfile(
+    pid: ProcessIdentifier,
+    request: SendFileRequest,
+    fs: &mut dyn FileSystem,
+) -> Message {
+    trace!("sendfile(): pid={:?}, request={:?}", pid, request);
+
+    let mut offset: Option<off_t> = request.offset();
+    // Clamp count, so that the number of bytes transferred fits in the response.
+    let count: usize = cmp::min(request.count as usize, ssize_t::MAX as usize);
+
+    match fs.sendfile(request.out_fd, request.in_fd, offset.as_mut(), count) {
+        Ok(count) => SendFileResponse::build(pid, count as ssize_t, offset.unwrap_or_default()),
+        Err(errno) => crate::build_errno(pid, errno),
     }
 }
 
@@ -265,55 +276,116 @@ pub fn do_pread(pid: ProcessIdentifier, request: PartialReadRequest) -> Message
 // do_linkat
 //==================================================================================================
 
-pub fn do_linkat(pid: ProcessIdentifier, request: LinkAtRequest) -> Vec<Message> {
+pub fn do_linkat(
+    pid: ProcessIdentifier,
+    request: LinkAtRequest,
+    fs: &mut dyn FileSystem,
+) -> Vec<Message> {
     trace!("linkat(): pid={:?}, request={:?}", pid, request);
 
-    let olddirfd: i32 = request.olddirfd;
-    let oldpath: CString = match CString::new(request.oldpath.as_str()) {
-        Ok(oldpath) => oldpath,
-        Err(_) => return vec![crate::build_error(pid, ErrorCode::InvalidArgument)],
-    };
-    let newdirfd: i32 = request.newdirfd;
-    let newpath: CString = match CString::new(request.newpath.as_str()) {
-        Ok(newpath) => newpath,
-        Err(_) => return vec![crate::build_error(pid, ErrorCode::InvalidArgument)],
-    };
-    let flags: i32 = request.flags;
-
-    debug!(
-        "libc::linkat(): olddirfd={:?}, oldpath={:?}, newdirfd={:?}, newpath={:?}, flags={:?}",
-        olddirfd, oldpath, newdirfd, newpath, flags
-    );
-    match unsafe { libc::linkat(olddirfd, oldpath.as_ptr(), newdirfd, newpath.as_ptr(), flags) } {
-        ret if ret == 0 => vec![LinkAtResponse::build(pid, ret)],
-        ret => vec![crate::build_error(
-            pid,
-            ErrorCode::try_from(ret).unwrap_or_else(|_| panic!("invalid error code: {:?}", ret)),
-        )],
+    match fs.link_at(
+        request.olddirfd,
+        &request.oldpath,
+        request.newdirfd,
+        &request.newpath,
+        request.flags,
+    ) {
+        Ok(()) => vec![LinkAtResponse::build(pid, 0)],
+        Err(errno) => vec![crate::build_errno(pid, errno)],
     }
 }
 
+//==================================================================================================
+// do_truncate
 //==================================================================================================
 
-struct LibcSeek(ffi::c_int);
+pub fn do_truncate(
+    pid: ProcessIdentifier,
+    request: TruncateRequest,
+    fs: &mut dyn FileSystem,
+) -> Vec<Message> {
+    trace!("truncate(): pid={:?}, request={:?}", pid, request);
 
-impl LibcSeek {
-    fn inner(&self) -> ffi::c_int {
-        self.0
+    match fs.truncate(&request.path, request.length) {
+        Ok(()) => vec![TruncateResponse::build(pid, 0)],
+        Err(errno) => vec![crate::build_errno(pid, errno)],
     }
 }
 
-impl TryFrom<i32> for LibcSeek {
-    type Error = Error;
-
-    fn try_from(value: i32) -> Result<Self, Self::Error> {
-        match value {
-            unistd::SEEK_CUR => Ok(LibcSeek(libc::SEEK_CUR)),
-            unistd::SEEK_END => Ok(LibcSeek(libc::SEEK_END)),
-            unistd::SEEK_SET => Ok(LibcSeek(libc::SEEK_SET)),
-            unistd::SEEK_HOLE => Ok(LibcSeek(libc::SEEK_HOLE)),
-            unistd::SEEK_DATA => Ok(LibcSeek(libc::SEEK_DATA)),
-            _ => Err(Error::new(ErrorCode::InvalidArgument, "invalid whence")),
-        }
+//==================================================================================================
+// Unit Tests
+//==================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::MemoryFileSystem;
+    use ::linuxd::{
+        fcntl,
+        LinuxDaemonMessage,
+    };
+
+    // Extract the payload of a request or response.
+    fn payload(message: Message) -> [u8; LinuxDaemonMessage::PAYLOAD_SIZE] {
+        LinuxDaemonMessage::try_from_bytes(message.payload)
+            .expect("malformed message")
+            .payload
+    }
+
+    // Create an empty file that is open for reading and writing.
+    fn create(fs: &mut dyn FileSystem) -> i32 {
+        fs.open_at(fcntl::AT_FDCWD, "file", fcntl::O_CREAT | fcntl::O_RDWR, fcntl::S_IRUSR)
+            .expect("failed to create file")
+    }
+
+    #[test]
+    fn write_then_pread_round_trips() {
+        let pid: ProcessIdentifier = ProcessIdentifier::from(1);
+        let mut fs: MemoryFileSystem = MemoryFileSystem::new();
+        let fd: i32 = create(&mut fs);
+
+        let data: &[u8] = b"hello";
+        let mut buffer: [u8; WriteRequest::BUFFER_SIZE] = [0; WriteRequest::BUFFER_SIZE];
+        buffer[..data.len()].copy_from_slice(data);
+        let request: Message = WriteRequest::build(pid, fd, data.len() as size_t, buffer);
+        let response: Message = do_write(pid, WriteRequest::from_bytes(payload(request)), &mut fs);
+        assert_eq!({ response.status }, 0);
+        assert_eq!({ WriteResponse::from_bytes(payload(response)).count }, data.len() as ssize_t);
+
+        let request: Message = PartialReadRequest::build(pid, fd, data.len() as size_t, 1);
+        let response: Message =
+            do_pread(pid, PartialReadRequest::from_bytes(payload(request)), &mut fs);
+        assert_eq!({ response.status }, 0);
+        let response: PartialReadResponse = PartialReadResponse::from_bytes(payload(response));
+        assert_eq!({ response.count }, (data.len() - 1) as ssize_t);
+        assert_eq!(&response.buffer[..data.len() - 1], &data[1..]);
+    }
+
+    #[test]
+    fn read_rejects_oversized_count() {
+        let pid: ProcessIdentifier = ProcessIdentifier::from(1);
+        let mut fs: MemoryFileSystem = MemoryFileSystem::new();
+        let fd: i32 = create(&mut fs);
+
+        let count: size_t = ReadResponse::BUFFER_SIZE as size_t + 1;
+        let request: Message = ReadRequest::build(pid, fd, count);
+        let response: Message = do_read(pid, ReadRequest::from_bytes(payload(request)), &mut fs);
+        assert_eq!({ response.status }, -libc::EINVAL);
+    }
+
+    #[test]
+    fn close_reports_bad_file_descriptor() {
+        let pid: ProcessIdentifier = ProcessIdentifier::from(1);
+        let mut fs: MemoryFileSystem = MemoryFileSystem::new();
+        let fd: i32 = create(&mut fs);
+
+        let request: Message = CloseRequest::build(pid, fd);
+        let response: Message = do_close(pid, CloseRequest::from_bytes(payload(request)), &mut fs);
+        assert_eq!({ response.status }, 0);
+        assert_eq!({ CloseResponse::from_bytes(payload(response)).ret }, 0);
+
+        let request: Message = CloseRequest::build(pid, fd);
+        let response: Message = do_close(pid, CloseRequest::from_bytes(payload(request)), &mut fs);
+        assert_eq!({ response.status }, -libc::EBADF);
     }
 }
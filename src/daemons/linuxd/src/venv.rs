@@ -135,4 +135,38 @@ impl VirtualEnviromentDirectory {
 
         LeaveEnvResponse::build(pid, env)
     }
+
+    ///
+    /// # Description
+    ///
+    /// Looks up the virtual environment of a process.
+    ///
+    /// # Parameters
+    ///
+    /// - `pid`: Process identifier.
+    ///
+    /// # Returns
+    ///
+    /// The virtual environment of the target process, or `None` if it has not joined any.
+    ///
+    pub fn lookup(&self, pid: ProcessIdentifier) -> Option<VirtualEnvironmentIdentifier> {
+        self.processes.get(&pid).copied()
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Checks whether an environment still has processes.
+    ///
+    /// # Parameters
+    ///
+    /// - `env`: Environment identifier.
+    ///
+    /// # Returns
+    ///
+    /// `true` if at least one process is in the target environment, `false` otherwise.
+    ///
+    pub fn is_active(&self, env: VirtualEnvironmentIdentifier) -> bool {
+        self.processes.values().any(|&v| v == env)
+    }
 }
@@ -0,0 +1,480 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::backend::{
+    FileSystem,
+    HostFileSystem,
+};
+use ::anyhow::Result;
+use ::linuxd::unistd::message::{
+    FileDataSyncRequest,
+    FileSyncRequest,
+    PartialReadRequest,
+    ReadRequest,
+};
+use ::nvx::{
+    ipc::Message,
+    pm::ProcessIdentifier,
+};
+use ::std::{
+    collections::{
+        BTreeMap,
+        BTreeSet,
+        VecDeque,
+    },
+    path::PathBuf,
+    sync::{
+        mpsc::{
+            self,
+            Receiver,
+            Sender,
+            SyncSender,
+            TrySendError,
+        },
+        Arc,
+        Mutex,
+    },
+    thread,
+};
+
+//==================================================================================================
+// Constants
+//==================================================================================================
+
+/// Number of jobs that may wait for a worker per worker, before more jobs run inline instead.
+const JOBS_PER_WORKER: usize = 4;
+
+//==================================================================================================
+// Types
+//==================================================================================================
+
+/// Operation that a worker runs, given a filesystem and a private duplicate of the file
+/// descriptor that the operation targets.
+pub type Operation = Box<dyn FnOnce(&mut dyn FileSystem, i32) -> Message + Send>;
+
+/// Response of a completed job, along with the process that requested it.
+type Completion = (ProcessIdentifier, Message);
+
+//==================================================================================================
+// Structures
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Operation handed over to a worker.
+///
+struct Job {
+    /// Process that requested the operation.
+    source: ProcessIdentifier,
+    /// Private duplicate of the file descriptor that the operation targets, closed once it runs.
+    fd: i32,
+    /// Operation.
+    operation: Operation,
+}
+
+///
+/// # Description
+///
+/// Event file descriptor that workers signal once they complete a job.
+///
+struct Doorbell {
+    /// Host event file descriptor.
+    fd: i32,
+}
+
+///
+/// # Description
+///
+/// Bounded pool of workers that run blocking operations on host file descriptors, so that a slow
+/// operation of a process does not hold back the requests of other processes.
+///
+/// Requests of each process still run in order: while a process has a job in flight, its later
+/// messages are deferred, and they are resumed once the job completes. A pool without workers runs
+/// nothing, and all operations run inline.
+///
+pub struct WorkerPool {
+    /// Queue of jobs, if the pool has workers.
+    jobs: Option<SyncSender<Job>>,
+    /// Responses of completed jobs.
+    completions: Receiver<Completion>,
+    /// Event file descriptor that becomes readable once jobs complete.
+    doorbell: Arc<Doorbell>,
+    /// Processes that have a job in flight.
+    busy: BTreeSet<ProcessIdentifier>,
+    /// Messages of busy processes, in order of arrival.
+    deferred: BTreeMap<ProcessIdentifier, VecDeque<Message>>,
+}
+
+//==================================================================================================
+// Traits
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Requests that target a single file descriptor, and thus may be handed over to a worker.
+///
+pub trait FileDescriptorRequest {
+    /// Returns the file descriptor that the request targets.
+    fn fd(&self) -> i32;
+    /// Changes the file descriptor that the request targets.
+    fn set_fd(&mut self, fd: i32);
+}
+
+//==================================================================================================
+// Implementations
+//==================================================================================================
+
+impl Doorbell {
+    fn new() -> Result<Self> {
+        let fd: i32 = unsafe { libc::eventfd(0, libc::EFD_NONBLOCK | libc::EFD_CLOEXEC) };
+        if fd < 0 {
+            let errno: i32 = unsafe { *libc::__errno_location() };
+            anyhow::bail!("failed to create event file descriptor (errno={:?})", errno);
+        }
+
+        Ok(Self { fd })
+    }
+
+    // Wake up whoever polls the doorbell.
+    fn ring(&self) {
+        let value: u64 = 1;
+        if unsafe { libc::write(self.fd, &value as *const u64 as *const libc::c_void, 8) } < 0 {
+            let errno: i32 = unsafe { *libc::__errno_location() };
+            error!("libc::write(): errno={:?}", errno);
+        }
+    }
+
+    // Acknowledge all rings so far.
+    fn acknowledge(&self) {
+        let mut value: u64 = 0;
+        unsafe { libc::read(self.fd, &mut value as *mut u64 as *mut libc::c_void, 8) };
+    }
+}
+
+impl Drop for Doorbell {
+    fn drop(&mut self) {
+        unsafe { libc::close(self.fd) };
+    }
+}
+
+impl WorkerPool {
+    ///
+    /// # Description
+    ///
+    /// Spawns a pool of workers.
+    ///
+    /// # Parameters
+    ///
+    /// - `workers`: Number of workers. Zero disables the pool.
+    /// - `root`: Canonical host path of the root of virtual environments.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the pool is returned. Upon failure, an error is returned instead.
+    ///
+    pub fn new(workers: usize, root: PathBuf) -> Result<Self> {
+        let doorbell: Arc<Doorbell> = Arc::new(Doorbell::new()?);
+        let (completed, completions): (Sender<Completion>, Receiver<Completion>) = mpsc::channel();
+
+        let jobs: Option<SyncSender<Job>> = if workers > 0 {
+            let (jobs, queue): (SyncSender<Job>, Receiver<Job>) =
+                mpsc::sync_channel(workers * JOBS_PER_WORKER);
+            let queue: Arc<Mutex<Receiver<Job>>> = Arc::new(Mutex::new(queue));
+
+            // Workers are detached, and leave once the pool is dropped and their job is done.
+            for id in 0..workers {
+                let queue: Arc<Mutex<Receiver<Job>>> = queue.clone();
+                let completed: Sender<Completion> = completed.clone();
+                let doorbell: Arc<Doorbell> = doorbell.clone();
+                let root: PathBuf = root.clone();
+                thread::Builder::new()
+                    .name(format!("worker-{}", id))
+                    .spawn(move || work(queue, completed, doorbell, root))?;
+            }
+
+            Some(jobs)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            jobs,
+            completions,
+            doorbell,
+            busy: BTreeSet::new(),
+            deferred: BTreeMap::new(),
+        })
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the host file descriptor on which completions become readable.
+    ///
+    pub fn fd(&self) -> i32 {
+        self.doorbell.fd
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Checks whether a process has a job in flight.
+    ///
+    pub fn is_busy(&self, pid: ProcessIdentifier) -> bool {
+        self.busy.contains(&pid)
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Defers a message of a busy process until its job completes.
+    ///
+    pub fn defer(&mut self, message: Message) {
+        self.deferred
+            .entry(message.source)
+            .or_default()
+            .push_back(message);
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Hands an operation on a file descriptor over to a worker. The worker runs it on a duplicate
+    /// of the file descriptor, so that the process may close the original in the meantime.
+    ///
+    /// # Parameters
+    ///
+    /// - `source`: Process that requested the operation.
+    /// - `fd`: Host file descriptor that the operation targets.
+    /// - `operation`: Operation to run.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, empty is returned. Otherwise, the operation is returned back, and it must run
+    /// inline on the original file descriptor instead, for instance if the pool has no workers, the
+    /// file descriptor is not valid, or the queue is full.
+    ///
+    pub fn submit(
+        &mut self,
+        source: ProcessIdentifier,
+        fd: i32,
+        operation: impl FnOnce(&mut dyn FileSystem, i32) -> Message + Send + 'static,
+    ) -> Result<(), Operation> {
+        let jobs: &SyncSender<Job> = match self.jobs.as_ref() {
+            Some(jobs) => jobs,
+            None => return Err(Box::new(operation)),
+        };
+
+        // Invalid file descriptors are reported by the inline operation.
+        let fd: i32 = unsafe { libc::fcntl(fd, libc::F_DUPFD_CLOEXEC, 0) };
+        if fd < 0 {
+            return Err(Box::new(operation));
+        }
+
+        // Never wait for room in the queue, as that would hold back the requests of all processes.
+        let job: Job = Job {
+            source,
+            fd,
+            operation: Box::new(operation),
+        };
+        match jobs.try_send(job) {
+            Ok(()) => {
+                self.busy.insert(source);
+                Ok(())
+            },
+            Err(TrySendError::Full(job)) => {
+                trace!("workers are saturated (source={:?})", source);
+                unsafe { libc::close(job.fd) };
+                Err(job.operation)
+            },
+            Err(TrySendError::Disconnected(job)) => {
+                error!("workers are gone (source={:?})", source);
+                unsafe { libc::close(job.fd) };
+                Err(job.operation)
+            },
+        }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Collects the responses of completed jobs. Processes that they belong to are no longer busy.
+    ///
+    /// # Returns
+    ///
+    /// The processes whose jobs completed, along with their responses, in order of completion.
+    ///
+    pub fn complete(&mut self) -> Vec<Completion> {
+        self.doorbell.acknowledge();
+
+        let completions: Vec<Completion> = self.completions.try_iter().collect();
+        for (source, _) in &completions {
+            self.busy.remove(source);
+        }
+
+        completions
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Takes the next deferred message of a process that is no longer busy.
+    ///
+    /// # Returns
+    ///
+    /// The next deferred message, or `None` if the process is busy or has no deferred messages.
+    ///
+    pub fn resume(&mut self, pid: ProcessIdentifier) -> Option<Message> {
+        if self.is_busy(pid) {
+            return None;
+        }
+
+        let deferred: &mut VecDeque<Message> = self.deferred.get_mut(&pid)?;
+        let message: Option<Message> = deferred.pop_front();
+        if deferred.is_empty() {
+            self.deferred.remove(&pid);
+        }
+
+        message
+    }
+}
+
+impl FileDescriptorRequest for FileDataSyncRequest {
+    fn fd(&self) -> i32 {
+        self.fd
+    }
+
+    fn set_fd(&mut self, fd: i32) {
+        self.fd = fd;
+    }
+}
+
+impl FileDescriptorRequest for FileSyncRequest {
+    fn fd(&self) -> i32 {
+        self.fd
+    }
+
+    fn set_fd(&mut self, fd: i32) {
+        self.fd = fd;
+    }
+}
+
+impl FileDescriptorRequest for ReadRequest {
+    fn fd(&self) -> i32 {
+        self.fd
+    }
+
+    fn set_fd(&mut self, fd: i32) {
+        self.fd = fd;
+    }
+}
+
+impl FileDescriptorRequest for PartialReadRequest {
+    fn fd(&self) -> i32 {
+        self.fd
+    }
+
+    fn set_fd(&mut self, fd: i32) {
+        self.fd = fd;
+    }
+}
+
+//==================================================================================================
+// Standalone Functions
+//==================================================================================================
+
+// Run jobs until the pool is dropped.
+fn work(
+    queue: Arc<Mutex<Receiver<Job>>>,
+    completed: Sender<Completion>,
+    doorbell: Arc<Doorbell>,
+    root: PathBuf,
+) {
+    let mut fs: HostFileSystem = HostFileSystem::new(root);
+
+    loop {
+        // The lock is released before running the job, so that other workers pick up jobs.
+        let job: Job = match queue.lock().unwrap_or_else(|e| e.into_inner()).recv() {
+            Ok(job) => job,
+            Err(_) => break,
+        };
+
+        let response: Message = (job.operation)(&mut fs, job.fd);
+        unsafe { libc::close(job.fd) };
+
+        if completed.send((job.source, response)).is_err() {
+            break;
+        }
+        doorbell.ring();
+    }
+}
+
+//==================================================================================================
+// Unit Tests
+//==================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::nvx::ipc::MessageType;
+    use ::std::os::fd::AsRawFd;
+
+    // Build an empty response for a process.
+    fn respond(source: ProcessIdentifier) -> Message {
+        Message::new(
+            ProcessIdentifier::KERNEL,
+            source,
+            MessageType::Ikc,
+            None,
+            [0; Message::PAYLOAD_SIZE],
+        )
+    }
+
+    #[test]
+    fn submit_runs_inline_without_workers() {
+        let file: ::std::fs::File = ::tempfile::tempfile().unwrap();
+        let mut pool: WorkerPool = WorkerPool::new(0, PathBuf::from("/")).unwrap();
+        let source: ProcessIdentifier = ProcessIdentifier::from(2);
+
+        assert!(pool
+            .submit(source, file.as_raw_fd(), move |_, _| respond(source))
+            .is_err());
+        assert!(!pool.is_busy(source));
+    }
+
+    #[test]
+    fn submit_runs_inline_once_queue_is_full() {
+        let file: ::std::fs::File = ::tempfile::tempfile().unwrap();
+        let mut pool: WorkerPool = WorkerPool::new(1, PathBuf::from("/")).unwrap();
+        let source: ProcessIdentifier = ProcessIdentifier::from(2);
+
+        // Hold the only worker back, so that jobs pile up in the queue.
+        let gate: Arc<Mutex<()>> = Arc::new(Mutex::new(()));
+        let guard = gate.lock().unwrap();
+
+        let mut rejected: Option<Operation> = None;
+        for _ in 0..(JOBS_PER_WORKER + 2) {
+            let gate: Arc<Mutex<()>> = gate.clone();
+            let operation = move |_: &mut dyn FileSystem, _: i32| {
+                let _guard = gate.lock().unwrap_or_else(|e| e.into_inner());
+                respond(source)
+            };
+            if let Err(operation) = pool.submit(source, file.as_raw_fd(), operation) {
+                rejected = Some(operation);
+                break;
+            }
+        }
+
+        // The operation that did not fit is handed back, and still runs.
+        let operation: Operation = rejected.expect("queue never filled up");
+        drop(guard);
+        let mut fs: HostFileSystem = HostFileSystem::new(PathBuf::from("/"));
+        assert_eq!(operation(&mut fs, file.as_raw_fd()).destination, source);
+    }
+}
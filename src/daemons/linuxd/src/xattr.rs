@@ -0,0 +1,445 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use ::alloc::ffi::CString;
+use ::core::{
+    cmp,
+    ptr,
+};
+use ::linuxd::{
+    limits,
+    message::MessagePartitioner,
+    sys::xattr::{
+        self,
+        message::{
+            GetXattrRequest,
+            GetXattrResponse,
+            ListXattrRequest,
+            ListXattrResponse,
+            RemoveXattrRequest,
+            RemoveXattrResponse,
+            SetXattrRequest,
+            SetXattrResponse,
+        },
+    },
+    venv::VirtualEnvironmentIdentifier,
+};
+use ::nvx::{
+    ipc::Message,
+    pm::ProcessIdentifier,
+};
+use ::std::collections::HashMap;
+
+//==================================================================================================
+// Constants
+//==================================================================================================
+
+/// Extended attribute namespaces known to the host.
+pub const XATTR_NAMESPACES: [&str; 4] = ["security", "system", "trusted", "user"];
+
+//==================================================================================================
+// Structures
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Extended attribute namespaces that processes of a virtual environment may access.
+///
+#[derive(Debug, Clone, Default)]
+pub struct XattrNamespaces {
+    /// Allowed namespaces. If `None`, all namespaces are allowed.
+    allowed: Option<Vec<String>>,
+}
+
+///
+/// # Description
+///
+/// Extended attribute access policy of all virtual environments.
+///
+#[derive(Debug, Default)]
+pub struct XattrPolicy {
+    /// Namespaces for processes whose environment has no specific policy.
+    default: XattrNamespaces,
+    /// Namespaces for specific environments.
+    envs: HashMap<VirtualEnvironmentIdentifier, XattrNamespaces>,
+}
+
+///
+/// # Description
+///
+/// File targeted by an extended attribute operation.
+///
+enum XattrTarget {
+    /// Open file.
+    Fd(i32),
+    /// File at path.
+    Path(CString),
+}
+
+//==================================================================================================
+// Implementations
+//==================================================================================================
+
+impl XattrNamespaces {
+    ///
+    /// # Description
+    ///
+    /// Checks whether an extended attribute may be accessed.
+    ///
+    /// # Parameters
+    ///
+    /// - `name`: Name of the extended attribute.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the extended attribute may be accessed, `false` otherwise.
+    ///
+    pub fn allows(&self, name: &str) -> bool {
+        match &self.allowed {
+            Some(allowed) => match name.split_once('.') {
+                Some((namespace, _)) => allowed.iter().any(|ns| ns == namespace),
+                None => false,
+            },
+            None => true,
+        }
+    }
+}
+
+impl XattrPolicy {
+    ///
+    /// # Description
+    ///
+    /// Creates an extended attribute access policy.
+    ///
+    /// # Parameters
+    ///
+    /// - `rules`: Allowed namespaces, keyed by environment identifier. Rules without an
+    ///   environment identifier apply to all environments without a specific rule.
+    ///
+    /// # Returns
+    ///
+    /// The extended attribute access policy.
+    ///
+    pub fn new(rules: &[(Option<u32>, Vec<String>)]) -> Self {
+        let mut policy: Self = Self::default();
+
+        for (env, namespaces) in rules {
+            let namespaces: XattrNamespaces = XattrNamespaces {
+                allowed: Some(namespaces.clone()),
+            };
+            match env {
+                Some(env) => {
+                    info!(
+                        "restricting xattr namespaces (env={:?}, namespaces={:?})",
+                        env, namespaces
+                    );
+                    policy
+                        .envs
+                        .insert(VirtualEnvironmentIdentifier::from(*env), namespaces);
+                },
+                None => {
+                    info!("restricting xattr namespaces (namespaces={:?})", namespaces);
+                    policy.default = namespaces;
+                },
+            }
+        }
+
+        policy
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the namespaces that processes of an environment may access.
+    ///
+    /// # Parameters
+    ///
+    /// - `env`: Environment of the process, if any.
+    ///
+    /// # Returns
+    ///
+    /// The namespaces that processes of the target environment may access.
+    ///
+    pub fn namespaces(&self, env: Option<VirtualEnvironmentIdentifier>) -> &XattrNamespaces {
+        env.and_then(|env| self.envs.get(&env))
+            .unwrap_or(&self.default)
+    }
+}
+
+impl XattrTarget {
+    fn new(fd: i32, path: &str) -> Result<Self, i32> {
+        if path.is_empty() {
+            return Ok(Self::Fd(fd));
+        }
+
+        match CString::new(path) {
+            Ok(path) => Ok(Self::Path(path)),
+            Err(_) => Err(libc::EINVAL),
+        }
+    }
+}
+
+//==================================================================================================
+// do_getxattr
+//==================================================================================================
+
+pub fn do_getxattr(
+    pid: ProcessIdentifier,
+    request: GetXattrRequest,
+    namespaces: &XattrNamespaces,
+) -> Vec<Message> {
+    trace!("getxattr(): pid={:?}, request={:?}", pid, request);
+
+    // Check if the namespace of the attribute is allowed.
+    if !namespaces.allows(&request.name) {
+        warn!("getxattr(): access denied (pid={:?}, name={:?})", pid, request.name);
+        return vec![crate::build_errno(pid, libc::EPERM)];
+    }
+
+    let target: XattrTarget = match XattrTarget::new(request.fd, &request.path) {
+        Ok(target) => target,
+        Err(errno) => return vec![crate::build_errno(pid, errno)],
+    };
+    let name: CString = match CString::new(request.name) {
+        Ok(name) => name,
+        Err(_) => return vec![crate::build_errno(pid, libc::EINVAL)],
+    };
+    let size: usize = cmp::min(request.size, limits::XATTR_SIZE_MAX);
+    let mut value: Vec<u8> = vec![0u8; size];
+    let value_ptr: *mut libc::c_void = if size == 0 {
+        ptr::null_mut()
+    } else {
+        value.as_mut_ptr() as *mut libc::c_void
+    };
+
+    debug!("libc::getxattr(): name={:?}, size={:?}", name, size);
+    let ret: isize = match &target {
+        XattrTarget::Fd(fd) => unsafe { libc::fgetxattr(*fd, name.as_ptr(), value_ptr, size) },
+        XattrTarget::Path(path) => unsafe {
+            libc::getxattr(path.as_ptr(), name.as_ptr(), value_ptr, size)
+        },
+    };
+    if ret < 0 {
+        let errno: i32 = unsafe { *libc::__errno_location() };
+        debug!("libc::getxattr(): errno={:?}", errno);
+        return vec![crate::build_errno(pid, errno)];
+    }
+
+    // Check if the value is too long to be transferred.
+    if ret as usize > limits::XATTR_SIZE_MAX {
+        return vec![crate::build_errno(pid, libc::E2BIG)];
+    }
+    value.truncate(ret as usize);
+
+    let response: GetXattrResponse = match GetXattrResponse::new(ret as usize, value) {
+        Ok(response) => response,
+        Err(e) => return vec![crate::build_error(pid, e.code)],
+    };
+
+    match response.into_parts(pid) {
+        Ok(messages) => messages,
+        Err(e) => vec![crate::build_error(pid, e.code)],
+    }
+}
+
+//==================================================================================================
+// do_setxattr
+//==================================================================================================
+
+pub fn do_setxattr(
+    pid: ProcessIdentifier,
+    request: SetXattrRequest,
+    namespaces: &XattrNamespaces,
+) -> Vec<Message> {
+    trace!("setxattr(): pid={:?}, request={:?}", pid, request);
+
+    // Check if the namespace of the attribute is allowed.
+    if !namespaces.allows(&request.name) {
+        warn!("setxattr(): access denied (pid={:?}, name={:?})", pid, request.name);
+        return vec![crate::build_errno(pid, libc::EPERM)];
+    }
+
+    let target: XattrTarget = match XattrTarget::new(request.fd, &request.path) {
+        Ok(target) => target,
+        Err(errno) => return vec![crate::build_errno(pid, errno)],
+    };
+    let name: CString = match CString::new(request.name) {
+        Ok(name) => name,
+        Err(_) => return vec![crate::build_errno(pid, libc::EINVAL)],
+    };
+    let flags: i32 = match request.flags {
+        0 => 0,
+        xattr::XATTR_CREATE => libc::XATTR_CREATE,
+        xattr::XATTR_REPLACE => libc::XATTR_REPLACE,
+        _ => return vec![crate::build_errno(pid, libc::EINVAL)],
+    };
+    let value: &[u8] = &request.value;
+
+    debug!("libc::setxattr(): name={:?}, size={:?}, flags={:?}", name, value.len(), flags);
+    let ret: i32 = match &target {
+        XattrTarget::Fd(fd) => unsafe {
+            libc::fsetxattr(*fd, name.as_ptr(), value.as_ptr() as *const _, value.len(), flags)
+        },
+        XattrTarget::Path(path) => unsafe {
+            libc::setxattr(
+                path.as_ptr(),
+                name.as_ptr(),
+                value.as_ptr() as *const _,
+                value.len(),
+                flags,
+            )
+        },
+    };
+    match ret {
+        0 => vec![SetXattrResponse::build(pid, ret)],
+        _ => {
+            let errno: i32 = unsafe { *libc::__errno_location() };
+            debug!("libc::setxattr(): errno={:?}", errno);
+            vec![crate::build_errno(pid, errno)]
+        },
+    }
+}
+
+//==================================================================================================
+// do_listxattr
+//==================================================================================================
+
+pub fn do_listxattr(
+    pid: ProcessIdentifier,
+    request: ListXattrRequest,
+    namespaces: &XattrNamespaces,
+) -> Vec<Message> {
+    trace!("listxattr(): pid={:?}, request={:?}", pid, request);
+
+    let target: XattrTarget = match XattrTarget::new(request.fd, &request.path) {
+        Ok(target) => target,
+        Err(errno) => return vec![crate::build_errno(pid, errno)],
+    };
+
+    // Retrieve all names, because the size of the filtered list is not known beforehand.
+    let list: Vec<u8> = match listxattr(&target) {
+        Ok(list) => list,
+        Err(errno) => return vec![crate::build_errno(pid, errno)],
+    };
+
+    // Hide attributes in namespaces that are not allowed.
+    let list: Vec<u8> = list
+        .split(|&b| b == 0)
+        .filter(|name| !name.is_empty())
+        .filter(|name| namespaces.allows(&String::from_utf8_lossy(name)))
+        .flat_map(|name| name.iter().copied().chain([0]))
+        .collect();
+
+    // Check if the list is too long to be transferred.
+    if list.len() > limits::XATTR_LIST_MAX {
+        return vec![crate::build_errno(pid, libc::E2BIG)];
+    }
+
+    let size: usize = list.len();
+    let list: Vec<u8> = if request.size == 0 {
+        Vec::new()
+    } else if request.size < size {
+        return vec![crate::build_errno(pid, libc::ERANGE)];
+    } else {
+        list
+    };
+
+    let response: ListXattrResponse = match ListXattrResponse::new(size, list) {
+        Ok(response) => response,
+        Err(e) => return vec![crate::build_error(pid, e.code)],
+    };
+
+    match response.into_parts(pid) {
+        Ok(messages) => messages,
+        Err(e) => vec![crate::build_error(pid, e.code)],
+    }
+}
+
+//==================================================================================================
+// do_removexattr
+//==================================================================================================
+
+pub fn do_removexattr(
+    pid: ProcessIdentifier,
+    request: RemoveXattrRequest,
+    namespaces: &XattrNamespaces,
+) -> Vec<Message> {
+    trace!("removexattr(): pid={:?}, request={:?}", pid, request);
+
+    // Check if the namespace of the attribute is allowed.
+    if !namespaces.allows(&request.name) {
+        warn!("removexattr(): access denied (pid={:?}, name={:?})", pid, request.name);
+        return vec![crate::build_errno(pid, libc::EPERM)];
+    }
+
+    let target: XattrTarget = match XattrTarget::new(request.fd, &request.path) {
+        Ok(target) => target,
+        Err(errno) => return vec![crate::build_errno(pid, errno)],
+    };
+    let name: CString = match CString::new(request.name) {
+        Ok(name) => name,
+        Err(_) => return vec![crate::build_errno(pid, libc::EINVAL)],
+    };
+
+    debug!("libc::removexattr(): name={:?}", name);
+    let ret: i32 = match &target {
+        XattrTarget::Fd(fd) => unsafe { libc::fremovexattr(*fd, name.as_ptr()) },
+        XattrTarget::Path(path) => unsafe { libc::removexattr(path.as_ptr(), name.as_ptr()) },
+    };
+    match ret {
+        0 => vec![RemoveXattrResponse::build(pid, ret)],
+        _ => {
+            let errno: i32 = unsafe { *libc::__errno_location() };
+            debug!("libc::removexattr(): errno={:?}", errno);
+            vec![crate::build_errno(pid, errno)]
+        },
+    }
+}
+
+//==================================================================================================
+// Standalone Functions
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Retrieves the names of all extended attributes of a file.
+///
+/// # Parameters
+///
+/// - `target`: Target file.
+///
+/// # Returns
+///
+/// Upon success, the null-terminated names are returned. Upon failure, a host error number is
+/// returned instead.
+///
+fn listxattr(target: &XattrTarget) -> Result<Vec<u8>, i32> {
+    let list = |buf: *mut libc::c_char, size: usize| -> isize {
+        match target {
+            XattrTarget::Fd(fd) => unsafe { libc::flistxattr(*fd, buf, size) },
+            XattrTarget::Path(path) => unsafe { libc::listxattr(path.as_ptr(), buf, size) },
+        }
+    };
+
+    // Query size of the list.
+    let size: isize = list(ptr::null_mut(), 0);
+    if size < 0 {
+        return Err(unsafe { *libc::__errno_location() });
+    }
+
+    // Retrieve the list.
+    let mut buf: Vec<u8> = vec![0u8; size as usize];
+    let len: isize = list(buf.as_mut_ptr() as *mut libc::c_char, buf.len());
+    if len < 0 {
+        return Err(unsafe { *libc::__errno_location() });
+    }
+    buf.truncate(len as usize);
+
+    Ok(buf)
+}
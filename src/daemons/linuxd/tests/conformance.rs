@@ -0,0 +1,735 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//! Conformance tests for the Linux Daemon.
+//!
+//! Each operation is issued through the daemon, on behalf of a guest, and directly against the host
+//! kernel on a sibling directory. Results, error codes and the resulting directory trees must be
+//! identical.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use ::anyhow::Result;
+use ::linuxd::{
+    fcntl::{
+        self,
+        message::{
+            OpenAtRequest,
+            OpenAtResponse,
+        },
+    },
+    sys::stat::stat,
+    unistd,
+    LinuxDaemonMessage,
+};
+use ::linuxd_client::{
+    Client,
+    Daemon,
+};
+use ::nvx::{
+    ipc::Message,
+    pm::ProcessIdentifier,
+    sys::error::ErrorCode,
+};
+use ::std::{
+    collections::BTreeMap,
+    ffi::CString,
+    fs,
+    io,
+    os::unix::ffi::OsStrExt,
+    path::{
+        Path,
+        PathBuf,
+    },
+    thread,
+};
+use ::tempfile::TempDir;
+
+//==================================================================================================
+// Constants
+//==================================================================================================
+
+/// Process on behalf of which system calls are issued.
+const PID: u32 = 1;
+
+/// Process that competes with [`PID`] in races.
+const RIVAL_PID: u32 = 2;
+
+/// Size of writes that span several messages.
+const LARGE_WRITE_SIZE: usize = 4096 + 123;
+
+/// Offset at which sparse files hold data.
+const SPARSE_OFFSET: i64 = 1 << 20;
+
+//==================================================================================================
+// Structures
+//==================================================================================================
+
+///
+/// # Description
+///
+/// File descriptors of the same file, opened through the daemon and on the host.
+///
+#[derive(Clone, Copy)]
+struct Fd {
+    guest: i32,
+    host: i32,
+}
+
+///
+/// # Description
+///
+/// File information that must match between the daemon and the host.
+///
+#[derive(Debug, PartialEq, Eq)]
+struct Attributes {
+    mode: u32,
+    nlink: u64,
+    size: i64,
+}
+
+///
+/// # Description
+///
+/// Node of a directory tree.
+///
+#[derive(Debug, PartialEq, Eq)]
+enum Node {
+    File(Vec<u8>),
+    Directory,
+    Symlink(PathBuf),
+    Other,
+}
+
+///
+/// # Description
+///
+/// Daemon that serves a guest directory, along with a host directory that mirrors it.
+///
+struct Fixture {
+    /// Directory that paths of guests are resolved against.
+    guest_root: TempDir,
+    /// Directory on which the host kernel is exercised directly.
+    host_root: TempDir,
+    /// File descriptor of the host directory.
+    host_dirfd: i32,
+    /// Connection to the daemon.
+    client: Client,
+    /// Daemon under test. Dropped after the connection.
+    _daemon: Daemon,
+}
+
+//==================================================================================================
+// Implementations
+//==================================================================================================
+
+impl Fixture {
+    fn new() -> Result<Self> {
+        let guest_root: TempDir = TempDir::new()?;
+        let host_root: TempDir = TempDir::new()?;
+
+        let daemon: Daemon = Daemon::spawn(
+            Path::new(env!("CARGO_BIN_EXE_linuxd")),
+            guest_root.path(),
+            &["-no-auth"],
+        )?;
+        let client: Client = daemon.connect(ProcessIdentifier::from(PID), None)?;
+
+        let path: CString = CString::new(host_root.path().as_os_str().as_bytes())?;
+        let host_dirfd: i32 =
+            unsafe { libc::open(path.as_ptr(), libc::O_RDONLY | libc::O_DIRECTORY) };
+        if host_dirfd < 0 {
+            anyhow::bail!("failed to open host directory: {}", io::Error::last_os_error());
+        }
+
+        Ok(Self {
+            guest_root,
+            host_root,
+            host_dirfd,
+            client,
+            _daemon: daemon,
+        })
+    }
+
+    fn openat(&mut self, path: &str, flags: i32, mode: u32) -> Result<Result<Fd, i32>> {
+        let guest: i32 = self.client.openat(fcntl::AT_FDCWD, path, flags, mode)?;
+        let cpath: CString = CString::new(path)?;
+        let host: i32 = check(unsafe {
+            libc::openat(self.host_dirfd, cpath.as_ptr(), host_open_flags(flags), mode)
+        });
+
+        match (guest >= 0, host >= 0) {
+            (true, true) => Ok(Ok(Fd { guest, host })),
+            _ => {
+                assert_eq!(guest, host, "openat({:?}, {:#x}) differs", path, flags);
+                Ok(Err(host))
+            },
+        }
+    }
+
+    fn close(&mut self, fd: Fd) -> Result<i32> {
+        let guest: i32 = self.client.close(fd.guest)?;
+        let host: i32 = check(unsafe { libc::close(fd.host) });
+        Ok(same("close", guest, host))
+    }
+
+    fn write(&mut self, fd: Fd, buffer: &[u8]) -> Result<i64> {
+        let guest: i64 = self.client.write(fd.guest, buffer)? as i64;
+        let host: i64 = check(unsafe {
+            libc::write(fd.host, buffer.as_ptr() as *const libc::c_void, buffer.len())
+        } as i64);
+        Ok(same("write", guest, host))
+    }
+
+    fn read(&mut self, fd: Fd, len: usize) -> Result<Result<Vec<u8>, i64>> {
+        let mut guest: Vec<u8> = vec![0; len];
+        let guest_count: i64 = self.client.read(fd.guest, &mut guest)? as i64;
+
+        // Reads of the host must also span several calls to be comparable.
+        let mut host: Vec<u8> = vec![0; len];
+        let mut host_count: i64 = 0;
+        while (host_count as usize) < len {
+            let count: i64 = check(unsafe {
+                libc::read(
+                    fd.host,
+                    host[host_count as usize..].as_mut_ptr() as *mut libc::c_void,
+                    len - host_count as usize,
+                )
+            } as i64);
+            if count < 0 && host_count == 0 {
+                host_count = count;
+            }
+            if count <= 0 {
+                break;
+            }
+            host_count += count;
+        }
+
+        if same("read", guest_count, host_count) < 0 {
+            return Ok(Err(host_count));
+        }
+        guest.truncate(guest_count as usize);
+        host.truncate(host_count as usize);
+        assert_eq!(guest, host, "read() contents differ");
+        Ok(Ok(host))
+    }
+
+    fn pwrite(&mut self, fd: Fd, buffer: &[u8], offset: i64) -> Result<i64> {
+        let guest: i64 = self.client.pwrite(fd.guest, buffer, offset)? as i64;
+        let host: i64 = check(unsafe {
+            libc::pwrite(fd.host, buffer.as_ptr() as *const libc::c_void, buffer.len(), offset)
+        } as i64);
+        Ok(same("pwrite", guest, host))
+    }
+
+    fn lseek(&mut self, fd: Fd, offset: i64, whence: i32) -> Result<i64> {
+        let guest: i64 = self.client.lseek(fd.guest, offset, whence)?;
+        let host: i64 = check(unsafe { libc::lseek(fd.host, offset, host_whence(whence)) });
+        Ok(same("lseek", guest, host))
+    }
+
+    fn ftruncate(&mut self, fd: Fd, length: i64) -> Result<i32> {
+        let guest: i32 = self.client.ftruncate(fd.guest, length)?;
+        let host: i32 = check(unsafe { libc::ftruncate(fd.host, length) });
+        Ok(same("ftruncate", guest, host))
+    }
+
+    fn fstat(&mut self, fd: Fd) -> Result<Result<Attributes, i32>> {
+        let mut guest: stat = stat::default();
+        let guest_ret: i32 = self.client.fstat(fd.guest, &mut guest)?;
+
+        let mut host: libc::stat = unsafe { std::mem::zeroed() };
+        let host_ret: i32 = check(unsafe { libc::fstat(fd.host, &mut host) });
+
+        Ok(same_attributes("fstat", (guest_ret, guest), (host_ret, host)))
+    }
+
+    fn fstatat(&mut self, path: &str, flag: i32) -> Result<Result<Attributes, i32>> {
+        let mut guest: stat = stat::default();
+        let guest_ret: i32 = self
+            .client
+            .fstatat(fcntl::AT_FDCWD, path, &mut guest, flag)?;
+
+        let cpath: CString = CString::new(path)?;
+        let mut host: libc::stat = unsafe { std::mem::zeroed() };
+        let host_ret: i32 =
+            check(unsafe { libc::fstatat(self.host_dirfd, cpath.as_ptr(), &mut host, flag) });
+
+        Ok(same_attributes("fstatat", (guest_ret, guest), (host_ret, host)))
+    }
+
+    fn unlinkat(&mut self, path: &str, flags: i32) -> Result<i32> {
+        let guest: i32 = self.client.unlinkat(fcntl::AT_FDCWD, path, flags)?;
+        let cpath: CString = CString::new(path)?;
+        let host: i32 = check(unsafe { libc::unlinkat(self.host_dirfd, cpath.as_ptr(), flags) });
+        Ok(same("unlinkat", guest, host))
+    }
+
+    fn mkdir(&mut self, path: &str) -> Result<()> {
+        fs::create_dir(self.guest_root.path().join(path))?;
+        fs::create_dir(self.host_root.path().join(path))?;
+        Ok(())
+    }
+
+    fn renameat(&mut self, oldpath: &str, newpath: &str) -> Result<i32> {
+        let guest: i32 =
+            self.client
+                .renameat(fcntl::AT_FDCWD, oldpath, fcntl::AT_FDCWD, newpath)?;
+        let (old, new): (CString, CString) = (CString::new(oldpath)?, CString::new(newpath)?);
+        let host: i32 = check(unsafe {
+            libc::renameat(self.host_dirfd, old.as_ptr(), self.host_dirfd, new.as_ptr())
+        });
+        Ok(same("renameat", guest, host))
+    }
+
+    fn linkat(&mut self, oldpath: &str, newpath: &str) -> Result<i32> {
+        let guest: i32 =
+            self.client
+                .linkat(fcntl::AT_FDCWD, oldpath, fcntl::AT_FDCWD, newpath, 0)?;
+        let (old, new): (CString, CString) = (CString::new(oldpath)?, CString::new(newpath)?);
+        let host: i32 = check(unsafe {
+            libc::linkat(self.host_dirfd, old.as_ptr(), self.host_dirfd, new.as_ptr(), 0)
+        });
+        Ok(same("linkat", guest, host))
+    }
+
+    fn symlinkat(&mut self, target: &str, linkpath: &str) -> Result<i32> {
+        let guest: i32 = self.client.symlinkat(target, fcntl::AT_FDCWD, linkpath)?;
+        let (target, link): (CString, CString) = (CString::new(target)?, CString::new(linkpath)?);
+        let host: i32 =
+            check(unsafe { libc::symlinkat(target.as_ptr(), self.host_dirfd, link.as_ptr()) });
+        Ok(same("symlinkat", guest, host))
+    }
+
+    fn readlinkat(&mut self, path: &str, bufsiz: usize) -> Result<Result<Vec<u8>, i32>> {
+        let mut guest: Vec<u8> = vec![0; bufsiz];
+        let guest_count: i32 = self.client.readlinkat(fcntl::AT_FDCWD, path, &mut guest)?;
+
+        let cpath: CString = CString::new(path)?;
+        let mut host: Vec<u8> = vec![0; bufsiz];
+        let host_count: i32 = check(unsafe {
+            libc::readlinkat(
+                self.host_dirfd,
+                cpath.as_ptr(),
+                host.as_mut_ptr() as *mut libc::c_char,
+                bufsiz,
+            )
+        } as i32);
+
+        if same("readlinkat", guest_count, host_count) < 0 {
+            return Ok(Err(host_count));
+        }
+        guest.truncate(guest_count as usize);
+        host.truncate(host_count as usize);
+        assert_eq!(guest, host, "readlinkat({:?}, {}) contents differ", path, bufsiz);
+        Ok(Ok(host))
+    }
+
+    // Check that both directory trees are identical.
+    fn assert_same_tree(&self) -> Result<()> {
+        let guest: BTreeMap<PathBuf, Node> = snapshot(self.guest_root.path())?;
+        let host: BTreeMap<PathBuf, Node> = snapshot(self.host_root.path())?;
+        assert_eq!(guest, host, "directory trees differ");
+        Ok(())
+    }
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        unsafe { libc::close(self.host_dirfd) };
+    }
+}
+
+//==================================================================================================
+// Standalone Functions
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Converts open flags of guests into open flags of the host.
+///
+fn host_open_flags(flags: i32) -> i32 {
+    [
+        (fcntl::O_APPEND, libc::O_APPEND),
+        (fcntl::O_CREAT, libc::O_CREAT),
+        (fcntl::O_EXCL, libc::O_EXCL),
+        (fcntl::O_TRUNC, libc::O_TRUNC),
+        (fcntl::O_RDONLY, libc::O_RDONLY),
+        (fcntl::O_WRONLY, libc::O_WRONLY),
+        (fcntl::O_RDWR, libc::O_RDWR),
+    ]
+    .iter()
+    .filter(|(guest, _)| flags & guest != 0)
+    .fold(0, |acc, (_, host)| acc | host)
+}
+
+///
+/// # Description
+///
+/// Converts a seek origin of guests into a seek origin of the host.
+///
+fn host_whence(whence: i32) -> i32 {
+    match whence {
+        unistd::SEEK_SET => libc::SEEK_SET,
+        unistd::SEEK_CUR => libc::SEEK_CUR,
+        unistd::SEEK_END => libc::SEEK_END,
+        unistd::SEEK_HOLE => libc::SEEK_HOLE,
+        unistd::SEEK_DATA => libc::SEEK_DATA,
+        _ => whence,
+    }
+}
+
+///
+/// # Description
+///
+/// Converts the return value of a host system call into the convention of the daemon.
+///
+fn check<T: From<i32> + PartialOrd>(ret: T) -> T {
+    if ret < T::from(0) {
+        T::from(-io::Error::last_os_error().raw_os_error().unwrap_or(0))
+    } else {
+        ret
+    }
+}
+
+///
+/// # Description
+///
+/// Asserts that the daemon and the host agree on the result of an operation.
+///
+fn same<T: PartialEq + std::fmt::Debug>(op: &str, guest: T, host: T) -> T {
+    assert_eq!(guest, host, "{}() differs (guest={:?}, host={:?})", op, guest, host);
+    host
+}
+
+///
+/// # Description
+///
+/// Asserts that the daemon and the host agree on file information.
+///
+fn same_attributes(
+    op: &str,
+    (guest_ret, guest): (i32, stat),
+    (host_ret, host): (i32, libc::stat),
+) -> Result<Attributes, i32> {
+    if same(op, guest_ret, host_ret) < 0 {
+        return Err(host_ret);
+    }
+
+    let guest: Attributes = Attributes {
+        mode: { guest.st_mode },
+        nlink: { guest.st_nlink },
+        size: { guest.st_size },
+    };
+    let host: Attributes = Attributes {
+        mode: host.st_mode,
+        nlink: host.st_nlink,
+        size: host.st_size,
+    };
+    Ok(same(op, guest, host))
+}
+
+///
+/// # Description
+///
+/// Takes a snapshot of a directory tree, without following symbolic links.
+///
+fn snapshot(root: &Path) -> Result<BTreeMap<PathBuf, Node>> {
+    let mut nodes: BTreeMap<PathBuf, Node> = BTreeMap::new();
+    let mut pending: Vec<PathBuf> = vec![root.to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let path: PathBuf = entry?.path();
+            let file_type: fs::FileType = fs::symlink_metadata(&path)?.file_type();
+            let node: Node = if file_type.is_symlink() {
+                Node::Symlink(fs::read_link(&path)?)
+            } else if file_type.is_dir() {
+                pending.push(path.clone());
+                Node::Directory
+            } else if file_type.is_file() {
+                Node::File(fs::read(&path)?)
+            } else {
+                Node::Other
+            };
+            nodes.insert(path.strip_prefix(root)?.to_path_buf(), node);
+        }
+    }
+
+    Ok(nodes)
+}
+
+///
+/// # Description
+///
+/// Decodes the response to an open request.
+///
+fn open_result(response: &Message) -> Result<i32> {
+    if response.status != 0 {
+        return match ErrorCode::try_from(response.status) {
+            Ok(code) => Ok(code.into_errno()),
+            Err(_) => Err(anyhow::anyhow!("invalid status (status={:?})", response.status)),
+        };
+    }
+
+    let message: LinuxDaemonMessage = LinuxDaemonMessage::try_from_bytes(response.payload)
+        .map_err(|e| anyhow::anyhow!("malformed response: {:?}", e))?;
+    Ok(OpenAtResponse::from_bytes(message.payload).ret)
+}
+
+//==================================================================================================
+// Tests
+//==================================================================================================
+
+#[test]
+fn write_seek_and_read_back() -> Result<()> {
+    let mut fx: Fixture = Fixture::new()?;
+    let data: Vec<u8> = (0..LARGE_WRITE_SIZE).map(|i| (i % 251) as u8).collect();
+
+    let fd: Fd = fx
+        .openat("file", fcntl::O_CREAT | fcntl::O_RDWR, 0o644)?
+        .expect("openat() failed");
+    assert_eq!(fx.write(fd, &data)?, data.len() as i64);
+    assert_eq!(fx.lseek(fd, 0, unistd::SEEK_CUR)?, data.len() as i64);
+    assert_eq!(fx.lseek(fd, 0, unistd::SEEK_SET)?, 0);
+    assert_eq!(fx.read(fd, data.len() + 16)?, Ok(data.clone()));
+    assert_eq!(fx.read(fd, 16)?, Ok(Vec::new()));
+    assert_eq!(fx.lseek(fd, -1, unistd::SEEK_SET)?, -libc::EINVAL as i64);
+    assert_eq!(fx.fstat(fd)?.map(|a| a.size), Ok(data.len() as i64));
+
+    assert_eq!(fx.ftruncate(fd, 10)?, 0);
+    assert_eq!(fx.fstat(fd)?.map(|a| a.size), Ok(10));
+    assert_eq!(fx.close(fd)?, 0);
+
+    // Reopening with truncation discards the contents.
+    let fd: Fd = fx
+        .openat("file", fcntl::O_TRUNC | fcntl::O_WRONLY, 0)?
+        .expect("openat() failed");
+    assert_eq!(fx.read(fd, 1)?, Err(-libc::EBADF as i64));
+    assert_eq!(fx.close(fd)?, 0);
+
+    fx.assert_same_tree()
+}
+
+#[test]
+fn closed_descriptors_are_invalid() -> Result<()> {
+    let mut fx: Fixture = Fixture::new()?;
+
+    let fd: Fd = fx
+        .openat("file", fcntl::O_CREAT | fcntl::O_WRONLY, 0o600)?
+        .expect("openat() failed");
+    assert_eq!(fx.close(fd)?, 0);
+    assert_eq!(fx.close(fd)?, -libc::EBADF);
+    assert_eq!(fx.write(fd, b"data")?, -libc::EBADF as i64);
+    assert_eq!(fx.lseek(fd, 0, unistd::SEEK_SET)?, -libc::EBADF as i64);
+    assert_eq!(fx.fstat(fd)?, Err(-libc::EBADF));
+
+    fx.assert_same_tree()
+}
+
+#[test]
+fn exclusive_create() -> Result<()> {
+    let mut fx: Fixture = Fixture::new()?;
+    let flags: i32 = fcntl::O_CREAT | fcntl::O_EXCL | fcntl::O_WRONLY;
+
+    let fd: Fd = fx.openat("file", flags, 0o644)?.expect("openat() failed");
+    assert_eq!(fx.openat("file", flags, 0o644)?.err(), Some(-libc::EEXIST));
+    assert_eq!(fx.close(fd)?, 0);
+    assert_eq!(fx.openat("missing", fcntl::O_RDONLY, 0)?.err(), Some(-libc::ENOENT));
+
+    fx.assert_same_tree()
+}
+
+#[test]
+fn exclusive_create_race() -> Result<()> {
+    let mut fx: Fixture = Fixture::new()?;
+    let flags: i32 = fcntl::O_CREAT | fcntl::O_EXCL | fcntl::O_WRONLY;
+    let pids: [ProcessIdentifier; 2] = [
+        ProcessIdentifier::from(PID),
+        ProcessIdentifier::from(RIVAL_PID),
+    ];
+
+    // Both requests are in flight before either response is read.
+    for pid in pids {
+        let request: Message = OpenAtRequest::build(pid, fcntl::AT_FDCWD, "file", flags, 0o644)
+            .map_err(|e| anyhow::anyhow!("failed to build request: {:?}", e))?;
+        fx.client.send(request)?;
+    }
+    let mut guest: Vec<i32> = Vec::new();
+    for pid in pids {
+        fx.client.set_pid(pid);
+        guest.push(open_result(&fx.client.recv()?)?);
+    }
+
+    let dirfd: i32 = fx.host_dirfd;
+    let host: Vec<i32> = (0..2)
+        .map(|_| {
+            thread::spawn(move || {
+                let path: CString = CString::new("file").expect("invalid path");
+                check(unsafe { libc::openat(dirfd, path.as_ptr(), host_open_flags(flags), 0o644) })
+            })
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .map(|handle| handle.join().expect("thread panicked"))
+        .collect();
+
+    // Exactly one of the contenders wins.
+    for results in [&guest, &host] {
+        assert_eq!(results.iter().filter(|ret| **ret >= 0).count(), 1, "{:?}", results);
+        assert_eq!(results.iter().filter(|ret| **ret == -libc::EEXIST).count(), 1, "{:?}", results);
+    }
+
+    for (pid, fd) in pids.iter().zip(&guest) {
+        if *fd >= 0 {
+            fx.client.set_pid(*pid);
+            assert_eq!(fx.client.close(*fd)?, 0);
+        }
+    }
+    for fd in host.iter().filter(|fd| **fd >= 0) {
+        unsafe { libc::close(*fd) };
+    }
+
+    fx.assert_same_tree()
+}
+
+#[test]
+fn seek_hole_and_data() -> Result<()> {
+    let mut fx: Fixture = Fixture::new()?;
+
+    let fd: Fd = fx
+        .openat("sparse", fcntl::O_CREAT | fcntl::O_RDWR, 0o644)?
+        .expect("openat() failed");
+    assert_eq!(fx.pwrite(fd, b"data", SPARSE_OFFSET)?, 4);
+    assert_eq!(fx.fstat(fd)?.map(|a| a.size), Ok(SPARSE_OFFSET + 4));
+
+    // Exact offsets depend on the file system, which both sides share.
+    fx.lseek(fd, 0, unistd::SEEK_DATA)?;
+    fx.lseek(fd, 0, unistd::SEEK_HOLE)?;
+    fx.lseek(fd, SPARSE_OFFSET, unistd::SEEK_HOLE)?;
+    assert_eq!(fx.lseek(fd, SPARSE_OFFSET + 4, unistd::SEEK_DATA)?, -libc::ENXIO as i64);
+    assert_eq!(fx.lseek(fd, SPARSE_OFFSET + 4, unistd::SEEK_HOLE)?, -libc::ENXIO as i64);
+    assert_eq!(fx.close(fd)?, 0);
+
+    fx.assert_same_tree()
+}
+
+#[test]
+fn dangling_symlinks() -> Result<()> {
+    let mut fx: Fixture = Fixture::new()?;
+
+    assert_eq!(fx.symlinkat("target", "link")?, 0);
+    assert_eq!(fx.symlinkat("target", "link")?, -libc::EEXIST);
+    assert_eq!(fx.fstatat("link", 0)?, Err(-libc::ENOENT));
+    let attributes: Attributes = fx
+        .fstatat("link", libc::AT_SYMLINK_NOFOLLOW)?
+        .expect("fstatat() failed");
+    assert_eq!(attributes.mode & libc::S_IFMT, libc::S_IFLNK);
+    assert_eq!(fx.openat("link", fcntl::O_RDONLY, 0)?.err(), Some(-libc::ENOENT));
+
+    let flags: i32 = fcntl::O_CREAT | fcntl::O_EXCL | fcntl::O_WRONLY;
+    assert_eq!(fx.openat("link", flags, 0o644)?.err(), Some(-libc::EEXIST));
+
+    // Creating through the link creates its target.
+    let fd: Fd = fx
+        .openat("link", fcntl::O_CREAT | fcntl::O_WRONLY, 0o644)?
+        .expect("openat() failed");
+    assert_eq!(fx.write(fd, b"data")?, 4);
+    assert_eq!(fx.close(fd)?, 0);
+    assert_eq!(fx.fstatat("link", 0)?.map(|a| a.size), Ok(4));
+
+    fx.assert_same_tree()
+}
+
+#[test]
+fn readlink_truncation() -> Result<()> {
+    let mut fx: Fixture = Fixture::new()?;
+    let target: &str = "some/rather/long/target";
+
+    assert_eq!(fx.symlinkat(target, "link")?, 0);
+    assert_eq!(fx.readlinkat("link", 4)?, Ok(target.as_bytes()[..4].to_vec()));
+    assert_eq!(fx.readlinkat("link", target.len())?, Ok(target.as_bytes().to_vec()));
+    assert_eq!(fx.readlinkat("link", 256)?, Ok(target.as_bytes().to_vec()));
+    assert_eq!(fx.readlinkat("link", 0)?, Err(-libc::EINVAL));
+    assert_eq!(fx.readlinkat("missing", 256)?, Err(-libc::ENOENT));
+
+    let fd: Fd = fx
+        .openat("file", fcntl::O_CREAT | fcntl::O_WRONLY, 0o644)?
+        .expect("openat() failed");
+    assert_eq!(fx.close(fd)?, 0);
+    assert_eq!(fx.readlinkat("file", 256)?, Err(-libc::EINVAL));
+
+    fx.assert_same_tree()
+}
+
+#[test]
+fn directories() -> Result<()> {
+    let mut fx: Fixture = Fixture::new()?;
+
+    fx.mkdir("dir")?;
+    fx.mkdir("dir/sub")?;
+    let attributes: Attributes = fx.fstatat("dir", 0)?.expect("fstatat() failed");
+    assert_eq!(attributes.mode & libc::S_IFMT, libc::S_IFDIR);
+    assert_eq!(attributes.nlink, 3);
+
+    assert_eq!(fx.openat("dir", fcntl::O_WRONLY, 0)?.err(), Some(-libc::EISDIR));
+    assert_eq!(fx.openat("dir/sub/file/x", fcntl::O_RDONLY, 0)?.err(), Some(-libc::ENOENT));
+    assert_eq!(fx.unlinkat("dir", 0)?, -libc::EISDIR);
+    assert_eq!(fx.unlinkat("dir", fcntl::AT_REMOVEDIR)?, -libc::ENOTEMPTY);
+    assert_eq!(fx.unlinkat("dir/sub", fcntl::AT_REMOVEDIR)?, 0);
+    assert_eq!(fx.fstatat("dir", 0)?.map(|a| a.nlink), Ok(2));
+
+    fx.assert_same_tree()
+}
+
+#[test]
+fn rename_and_link() -> Result<()> {
+    let mut fx: Fixture = Fixture::new()?;
+
+    let fd: Fd = fx
+        .openat("a", fcntl::O_CREAT | fcntl::O_WRONLY, 0o644)?
+        .expect("openat() failed");
+    assert_eq!(fx.write(fd, b"contents")?, 8);
+    assert_eq!(fx.close(fd)?, 0);
+
+    assert_eq!(fx.linkat("a", "b")?, 0);
+    assert_eq!(fx.linkat("a", "b")?, -libc::EEXIST);
+    assert_eq!(fx.fstatat("a", 0)?.map(|a| a.nlink), Ok(2));
+    assert_eq!(fx.renameat("b", "c")?, 0);
+    assert_eq!(fx.renameat("b", "c")?, -libc::ENOENT);
+    assert_eq!(fx.linkat("missing", "d")?, -libc::ENOENT);
+
+    fx.mkdir("dir")?;
+    assert_eq!(fx.renameat("c", "dir")?, -libc::EISDIR);
+    assert_eq!(fx.unlinkat("a", 0)?, 0);
+    assert_eq!(fx.unlinkat("a", 0)?, -libc::ENOENT);
+    assert_eq!(fx.fstatat("c", 0)?.map(|a| a.nlink), Ok(1));
+
+    fx.assert_same_tree()
+}
+
+#[test]
+fn authentication() -> Result<()> {
+    let root: TempDir = TempDir::new()?;
+    let program: &Path = Path::new(env!("CARGO_BIN_EXE_linuxd"));
+
+    // Connections authenticate, unless told explicitly otherwise.
+    assert!(Daemon::spawn(program, root.path(), &[]).is_err());
+
+    let key: PathBuf = root.path().join("key");
+    fs::write(&key, b"secret\n")?;
+    let key_arg: &str = key.to_str().expect("invalid key path");
+    let daemon: Daemon = Daemon::spawn(program, root.path(), &["-auth-key", key_arg])?;
+
+    // A refused connection does not keep the daemon from accepting the next one.
+    assert!(daemon
+        .connect(ProcessIdentifier::from(PID), Some(b"wrong"))
+        .is_err());
+    let mut client: Client = daemon.connect(ProcessIdentifier::from(PID), Some(b"secret"))?;
+    assert_eq!(client.openat(fcntl::AT_FDCWD, "missing", fcntl::O_RDONLY, 0)?, -libc::ENOENT);
+
+    Ok(())
+}
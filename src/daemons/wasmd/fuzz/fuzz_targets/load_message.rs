@@ -0,0 +1,41 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Configuration
+//==================================================================================================
+
+#![no_main]
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use ::libfuzzer_sys::fuzz_target;
+use ::nvx::ipc::Message;
+use ::wasmd::{
+    LoadMessage,
+    WasmdMessage,
+};
+
+//==================================================================================================
+// Fuzz Target
+//==================================================================================================
+
+// Decodes a message the way the WASM daemon does when it loads a module.
+fuzz_target!(|data: &[u8]| {
+    let mut bytes: [u8; Message::PAYLOAD_SIZE] = [0; Message::PAYLOAD_SIZE];
+    let len: usize = data.len().min(bytes.len());
+    bytes[..len].copy_from_slice(&data[..len]);
+
+    let message: LoadMessage = match WasmdMessage::from_bytes(bytes)
+        .and_then(|message| LoadMessage::try_from_bytes(message.payload))
+    {
+        Ok(message) => message,
+        Err(_) => return,
+    };
+
+    // The daemon copies that many bytes out of the payload.
+    let _ = &message.payload[..message.len as usize];
+    let _ = message.to_bytes();
+});
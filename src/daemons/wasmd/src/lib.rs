@@ -38,7 +38,7 @@ impl TryFrom<u8> for WasmdMessageHeader {
     fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
             1 => Ok(WasmdMessageHeader::Wasm),
-            _ => Err(Error::new(ErrorCode::InvalidArgument, "invalid wasmd message")),
+            _ => Err(Error::new(ErrorCode::InvalidMessage, "invalid wasmd message")),
         }
     }
 }
@@ -91,13 +91,16 @@ impl WasmdMessage {
     ///
     /// # Returns
     ///
-    /// A WASMD message.
+    /// Upon success, a WASMD message is returned. Upon failure, an error is returned instead, if
+    /// the message header is invalid.
     ///
     pub fn from_bytes(bytes: [u8; Self::SIZE]) -> Result<Self, Error> {
+        let (header, payload): (&[u8], &[u8]) =
+            bytes.split_at(mem::size_of::<WasmdMessageHeader>());
         // Check if message is valid.
-        let _header: WasmdMessageHeader = WasmdMessageHeader::try_from(bytes[0])?;
-        // Re-interpret bytes as a message.
-        let message: WasmdMessage = unsafe { core::mem::transmute(bytes) };
+        let header: WasmdMessageHeader = WasmdMessageHeader::try_from(header[0])?;
+        let mut message: WasmdMessage = Self::new(header, [0; Self::PAYLOAD_SIZE]);
+        message.payload.copy_from_slice(payload);
         Ok(message)
     }
 
@@ -111,7 +114,12 @@ impl WasmdMessage {
     /// The corresponding byte array.
     ///
     pub fn to_bytes(self) -> [u8; Self::SIZE] {
-        unsafe { core::mem::transmute(self) }
+        let mut bytes: [u8; Self::SIZE] = [0; Self::SIZE];
+        let (header, payload): (&mut [u8], &mut [u8]) =
+            bytes.split_at_mut(mem::size_of::<WasmdMessageHeader>());
+        header[0] = u8::from(&self.header);
+        payload.copy_from_slice(&self.payload);
+        bytes
     }
 }
 
@@ -158,11 +166,19 @@ impl LoadMessage {
     ///
     /// # Returns
     ///
-    /// A LOAD message.
+    /// Upon success, a LOAD message is returned. Upon failure, an error is returned instead, if
+    /// the length of the segment is out of bounds.
     ///
-    pub fn from_bytes(bytes: [u8; Self::SIZE]) -> Self {
-        // Re-interpret bytes as a message.
-        unsafe { core::mem::transmute(bytes) }
+    pub fn try_from_bytes(bytes: [u8; Self::SIZE]) -> Result<Self, Error> {
+        let (len, payload): (&[u8], &[u8]) = bytes.split_at(mem::size_of::<u32>());
+        let len: u32 = u32::from_le_bytes([len[0], len[1], len[2], len[3]]);
+        // Check if length is valid.
+        if len as usize > Self::PAYLOAD_SIZE {
+            return Err(Error::new(ErrorCode::InvalidMessage, "invalid segment length"));
+        }
+        let mut message: LoadMessage = Self::new(len, [0; Self::PAYLOAD_SIZE]);
+        message.payload.copy_from_slice(payload);
+        Ok(message)
     }
 
     ///
@@ -175,6 +191,10 @@ impl LoadMessage {
     /// The corresponding byte array.
     ///
     pub fn to_bytes(self) -> [u8; Self::SIZE] {
-        unsafe { core::mem::transmute(self) }
+        let mut bytes: [u8; Self::SIZE] = [0; Self::SIZE];
+        let (len, payload): (&mut [u8], &mut [u8]) = bytes.split_at_mut(mem::size_of::<u32>());
+        len.copy_from_slice(&{ self.len }.to_le_bytes());
+        payload.copy_from_slice(&self.payload);
+        bytes
     }
 }
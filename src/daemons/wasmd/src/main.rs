@@ -87,9 +87,10 @@ fn main() -> Result<(), Error> {
     let mut nmessages = 0;
     loop {
         match ::nvx::ipc::recv() {
-            Ok(message) => match WasmdMessage::from_bytes(message.payload) {
+            Ok(message) => match WasmdMessage::from_bytes(message.payload)
+                .and_then(|message| LoadMessage::try_from_bytes(message.payload))
+            {
                 Ok(message) => {
-                    let message = LoadMessage::from_bytes(message.payload);
                     if message.len == 0 {
                         ::nvx::log!("received last message");
                         break;
@@ -0,0 +1,456 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Configuration
+//==================================================================================================
+
+#![deny(clippy::all)]
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use ::proc_macro::TokenStream;
+use ::proc_macro2::TokenStream as TokenStream2;
+use ::quote::quote;
+use ::syn::{
+    parse_macro_input,
+    spanned::Spanned,
+    Data,
+    DeriveInput,
+    Expr,
+    Fields,
+    Ident,
+    LitStr,
+    Path,
+    Type,
+};
+
+//==================================================================================================
+// Structures
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Field of a message, along with its `#[message(...)]` options.
+///
+struct MessageField {
+    /// Name of the field.
+    ident: Ident,
+    /// Type of the field.
+    ty: Type,
+    /// Minimum length of the variable-length part of the field.
+    min_len: Option<Expr>,
+    /// Maximum length of the variable-length part of the field.
+    max_len: Option<Expr>,
+}
+
+///
+/// # Description
+///
+/// Structure of a message, along with its `#[message(...)]` options.
+///
+struct MessageStructure {
+    /// Name of the structure.
+    ident: Ident,
+    /// Fields of the structure, in declaration order.
+    fields: Vec<MessageField>,
+    /// Header of message parts, and whether they travel in responses.
+    part: Option<(Ident, bool)>,
+    /// Function that checks a decoded message.
+    validate: Option<Path>,
+}
+
+//==================================================================================================
+// Implementations
+//==================================================================================================
+
+impl MessageStructure {
+    ///
+    /// # Description
+    ///
+    /// Parses the input of a derive macro.
+    ///
+    /// # Parameters
+    ///
+    /// - `input`: Input of the derive macro.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the message structure is returned. Upon failure, a compile error is returned
+    /// instead.
+    ///
+    fn parse(input: DeriveInput) -> syn::Result<Self> {
+        let named = match &input.data {
+            Data::Struct(data) => match &data.fields {
+                Fields::Named(fields) => &fields.named,
+                _ => return Err(syn::Error::new(input.span(), "expected named fields")),
+            },
+            _ => return Err(syn::Error::new(input.span(), "expected a structure")),
+        };
+
+        // Sizes are checked in constant contexts, which cannot refer to generic parameters.
+        if !input.generics.params.is_empty() {
+            return Err(syn::Error::new(
+                input.generics.span(),
+                "generic messages are not supported",
+            ));
+        }
+
+        let mut part: Option<(Ident, bool)> = None;
+        let mut validate: Option<Path> = None;
+        for attr in input.attrs.iter().filter(|a| a.path().is_ident("message")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("request_part") {
+                    part = Some((meta.value()?.parse()?, false));
+                } else if meta.path.is_ident("response_part") {
+                    part = Some((meta.value()?.parse()?, true));
+                } else if meta.path.is_ident("validate") {
+                    validate = Some(meta.value()?.parse()?);
+                } else {
+                    return Err(meta.error("unknown message option"));
+                }
+                Ok(())
+            })?;
+        }
+
+        let mut fields: Vec<MessageField> = Vec::with_capacity(named.len());
+        for field in named {
+            let mut min_len: Option<Expr> = None;
+            let mut max_len: Option<Expr> = None;
+            for attr in field.attrs.iter().filter(|a| a.path().is_ident("message")) {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("min_len") {
+                        min_len = Some(meta.value()?.parse()?);
+                    } else if meta.path.is_ident("max_len") {
+                        max_len = Some(meta.value()?.parse()?);
+                    } else {
+                        return Err(meta.error("unknown field option"));
+                    }
+                    Ok(())
+                })?;
+            }
+
+            fields.push(MessageField {
+                ident: field.ident.clone().expect("fields are named"),
+                ty: field.ty.clone(),
+                min_len,
+                max_len,
+            });
+        }
+
+        Ok(Self {
+            ident: input.ident,
+            fields,
+            part,
+            validate,
+        })
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Builds an expression that evaluates to the size of the fixed part of the message.
+    ///
+    fn head_size(&self) -> TokenStream2 {
+        sum(self.fields.iter().map(|f| {
+            let ty: &Type = &f.ty;
+            quote! { <#ty as ::linuxd::message::MessageField>::HEAD_SIZE }
+        }))
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Builds an expression that evaluates to the maximum size of the message.
+    ///
+    fn max_size(&self) -> TokenStream2 {
+        let head_size: TokenStream2 = self.head_size();
+        let max_lens = self.fields.iter().filter_map(|f| f.max_len.as_ref());
+        quote! {
+            #head_size #(+ (#max_lens))*
+        }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Builds items that fail to compile if a variable-length field has no maximum length.
+    ///
+    fn check_max_lens(&self) -> TokenStream2 {
+        let checks = self.fields.iter().filter(|f| f.max_len.is_none()).map(|f| {
+            let ty: &Type = &f.ty;
+            let message: LitStr =
+                LitStr::new(&format!("field `{}` has no maximum length", f.ident), f.ident.span());
+            quote! {
+                const _: () = ::core::assert!(
+                    !<#ty as ::linuxd::message::MessageField>::IS_VARIABLE,
+                    #message
+                );
+            }
+        });
+        quote! {
+            #(#checks)*
+        }
+    }
+}
+
+//==================================================================================================
+// Standalone Functions
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Derives `WireFormat` for a fixed-size structure. Fields are encoded back to back, in
+/// declaration order.
+///
+#[proc_macro_derive(WireFormat)]
+pub fn derive_wire_format(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = parse_macro_input!(input as DeriveInput);
+    let structure: MessageStructure = match MessageStructure::parse(input) {
+        Ok(structure) => structure,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    let ident: &Ident = &structure.ident;
+    let names = structure.fields.iter().map(|f| &f.ident);
+    let wire_size: TokenStream2 = sum(structure.fields.iter().map(|f| {
+        let ty: &Type = &f.ty;
+        quote! { <#ty as ::linuxd::wire::WireFormat>::WIRE_SIZE }
+    }));
+    let puts = names.clone();
+    let gets = names.clone();
+
+    quote! {
+        impl ::linuxd::wire::WireFormat for #ident {
+            const WIRE_SIZE: usize = #wire_size;
+
+            fn encode(self, encoder: &mut ::linuxd::wire::WireEncoder) {
+                #(encoder.put(self.#puts);)*
+            }
+
+            fn decode(decoder: &mut ::linuxd::wire::WireDecoder) -> Self {
+                Self {
+                    #(#gets: decoder.get(),)*
+                }
+            }
+        }
+    }
+    .into()
+}
+
+///
+/// # Description
+///
+/// Derives `MessageSerializer` for a message. The fixed part of every field comes first, in
+/// declaration order, followed by the variable-length part of every field, in declaration order.
+///
+#[proc_macro_derive(MessageSerializer, attributes(message))]
+pub fn derive_message_serializer(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = parse_macro_input!(input as DeriveInput);
+    let structure: MessageStructure = match MessageStructure::parse(input) {
+        Ok(structure) => structure,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    let ident: &Ident = &structure.ident;
+    let head_size: TokenStream2 = structure.head_size();
+    let names = structure.fields.iter().map(|f| &f.ident);
+    let sizes = names.clone();
+    let heads = names.clone();
+    let tails = names.clone();
+
+    quote! {
+        impl ::linuxd::message::MessageSerializer for #ident {
+            fn to_bytes(&self) -> ::linuxd::message::__private::Vec<u8> {
+                use ::linuxd::message::MessageField;
+
+                let size: usize =
+                    #head_size #(+ MessageField::tail_size(&self.#sizes))*;
+                let mut bytes: ::linuxd::message::__private::Vec<u8> =
+                    ::linuxd::message::__private::Vec::new();
+                bytes.resize(size, 0);
+
+                let mut encoder: ::linuxd::wire::WireEncoder =
+                    ::linuxd::wire::WireEncoder::new(&mut bytes);
+                #(MessageField::encode_head(&self.#heads, &mut encoder);)*
+                #(MessageField::encode_tail(&self.#tails, &mut encoder);)*
+
+                bytes
+            }
+        }
+    }
+    .into()
+}
+
+///
+/// # Description
+///
+/// Derives `MessageDeserializer` for a message, in the layout of `MessageSerializer`. Decoding
+/// fails if the message is truncated, has trailing bytes, has a field whose length is out of the
+/// bounds set by `#[message(min_len = ..., max_len = ...)]`, or does not pass the check set by
+/// `#[message(validate = ...)]`. Every variable-length field must have a maximum length, so that
+/// `MAX_SIZE` is known at compile time.
+///
+#[proc_macro_derive(MessageDeserializer, attributes(message))]
+pub fn derive_message_deserializer(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = parse_macro_input!(input as DeriveInput);
+    let structure: MessageStructure = match MessageStructure::parse(input) {
+        Ok(structure) => structure,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    let ident: &Ident = &structure.ident;
+    let head_size: TokenStream2 = structure.head_size();
+    let max_size: TokenStream2 = structure.max_size();
+    let check_max_lens: TokenStream2 = structure.check_max_lens();
+    let names: Vec<&Ident> = structure.fields.iter().map(|f| &f.ident).collect();
+    let types: Vec<&Type> = structure.fields.iter().map(|f| &f.ty).collect();
+
+    let checks = structure.fields.iter().map(|f| {
+        let name: &Ident = &f.ident;
+        let message: LitStr = LitStr::new(&format!("invalid {}", name), name.span());
+        let min_len = f.min_len.iter();
+        let max_len = f.max_len.iter();
+        quote! {
+            #(
+                if MessageField::tail_size(&#name) < (#min_len) {
+                    return Err(Error::new(ErrorCode::InvalidMessage, #message));
+                }
+            )*
+            #(
+                if MessageField::tail_size(&#name) > (#max_len) {
+                    return Err(Error::new(ErrorCode::InvalidMessage, #message));
+                }
+            )*
+        }
+    });
+
+    let validate = structure.validate.iter();
+
+    quote! {
+        #check_max_lens
+
+        impl ::linuxd::message::MessageDeserializer for #ident {
+            const MAX_SIZE: usize = #max_size;
+
+            fn try_from_bytes(bytes: &[u8]) -> Result<Self, ::linuxd::message::__private::Error> {
+                use ::linuxd::message::{
+                    MessageField,
+                    __private::{
+                        Error,
+                        ErrorCode,
+                    },
+                };
+
+                // Check if the message is too short.
+                if bytes.len() < #head_size {
+                    return Err(Error::new(ErrorCode::InvalidMessage, "message too short"));
+                }
+
+                // Check if the message is too long.
+                if bytes.len() > Self::MAX_SIZE {
+                    return Err(Error::new(ErrorCode::InvalidMessage, "message too long"));
+                }
+
+                let mut decoder: ::linuxd::wire::WireDecoder =
+                    ::linuxd::wire::WireDecoder::new(bytes);
+                #(
+                    let #names = <#types as MessageField>::decode_head(&mut decoder);
+                )*
+                #(
+                    let #names: #types =
+                        <#types as MessageField>::decode_tail(#names, &mut decoder)?;
+                )*
+
+                // Check if the message has trailing bytes.
+                if decoder.remaining() != 0 {
+                    return Err(Error::new(ErrorCode::InvalidMessage, "message too long"));
+                }
+
+                #(#checks)*
+
+                let message: Self = Self { #(#names),* };
+                #(#validate(&message)?;)*
+
+                Ok(message)
+            }
+        }
+    }
+    .into()
+}
+
+///
+/// # Description
+///
+/// Derives `MessagePartitioner` for a message. The header of its parts is set by either
+/// `#[message(request_part = ...)]` or `#[message(response_part = ...)]`.
+///
+#[proc_macro_derive(MessagePartitioner, attributes(message))]
+pub fn derive_message_partitioner(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = parse_macro_input!(input as DeriveInput);
+    let span = input.span();
+    let structure: MessageStructure = match MessageStructure::parse(input) {
+        Ok(structure) => structure,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    let (header, is_response): &(Ident, bool) = match &structure.part {
+        Some(part) => part,
+        None => {
+            return syn::Error::new(span, "missing `#[message(request_part = ...)]` option")
+                .to_compile_error()
+                .into()
+        },
+    };
+
+    let ident: &Ident = &structure.ident;
+    let build: Ident = match is_response {
+        true => Ident::new("build_response", header.span()),
+        false => Ident::new("build_request", header.span()),
+    };
+
+    quote! {
+        impl ::linuxd::message::MessagePartitioner for #ident {
+            const HEADER: ::linuxd::LinuxDaemonMessageHeader =
+                ::linuxd::LinuxDaemonMessageHeader::#header;
+
+            fn new_part(
+                pid: ::linuxd::message::__private::ProcessIdentifier,
+                request_id: u32,
+                part_number: u32,
+                payload_size: u8,
+                payload: [u8; ::linuxd::message::LinuxDaemonMessagePart::PAYLOAD_SIZE],
+            ) -> Result<
+                ::linuxd::message::__private::Message,
+                ::linuxd::message::__private::Error,
+            > {
+                ::linuxd::message::LinuxDaemonMessagePart::#build(
+                    pid,
+                    Self::HEADER,
+                    request_id,
+                    part_number,
+                    payload_size,
+                    payload,
+                )
+            }
+        }
+    }
+    .into()
+}
+
+///
+/// # Description
+///
+/// Builds an expression that evaluates to the sum of some terms.
+///
+fn sum(terms: impl Iterator<Item = TokenStream2>) -> TokenStream2 {
+    let terms: Vec<TokenStream2> = terms.collect();
+    match terms.is_empty() {
+        true => quote! { 0 },
+        false => quote! { #(#terms)+* },
+    }
+}
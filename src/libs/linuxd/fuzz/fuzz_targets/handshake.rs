@@ -0,0 +1,40 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Configuration
+//==================================================================================================
+
+#![no_main]
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use ::libfuzzer_sys::fuzz_target;
+use ::linuxd::{
+    handshake::{
+        Challenge,
+        ChallengeResponse,
+        NONCE_SIZE,
+    },
+    protocol::Hello,
+};
+
+//==================================================================================================
+// Fuzz Target
+//==================================================================================================
+
+// Decodes what clients send while a connection is established.
+fuzz_target!(|data: &[u8]| {
+    let mut bytes: [u8; Hello::SIZE] = [0; Hello::SIZE];
+    let len: usize = data.len().min(bytes.len());
+    bytes[..len].copy_from_slice(&data[..len]);
+    let _ = Hello::from_bytes(bytes);
+
+    let mut bytes: [u8; ChallengeResponse::SIZE] = [0; ChallengeResponse::SIZE];
+    let len: usize = data.len().min(bytes.len());
+    bytes[..len].copy_from_slice(&data[..len]);
+    let response: ChallengeResponse = ChallengeResponse::from_bytes(bytes);
+    let _ = response.verify(data, &Challenge::new([0; NONCE_SIZE]));
+});
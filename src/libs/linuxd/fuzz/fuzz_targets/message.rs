@@ -0,0 +1,159 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Configuration
+//==================================================================================================
+
+#![no_main]
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use ::libfuzzer_sys::fuzz_target;
+use ::linuxd::{
+    fcntl::message::{
+        FileAdvisoryInformationRequest,
+        FileSpaceControlRequest,
+        OpenAtRequest,
+        RecordLockRequest,
+        RenameAtRequest,
+        UnlinkAtRequest,
+    },
+    message::LinuxDaemonMessagePart,
+    sys::{
+        file::message::FileLockRequest,
+        inotify::message::InotifyRemoveWatchRequest,
+        resource::message::{
+            GetResourceLimitRequest,
+            GetResourceUsageRequest,
+        },
+        sendfile::message::SendFileRequest,
+        stat::message::FileStatRequest,
+    },
+    time::message::{
+        ClockResolutionRequest,
+        GetClockTimeRequest,
+    },
+    unistd::message::{
+        CloseRequest,
+        CopyFileRangeRequest,
+        FileDataSyncRequest,
+        FileSyncRequest,
+        FileTruncateRequest,
+        PartialReadRequest,
+        PartialWriteRequest,
+        ReadRequest,
+        SeekRequest,
+        WriteRequest,
+    },
+    venv::message::{
+        JoinEnvRequest,
+        LeaveEnvRequest,
+    },
+    LinuxDaemonMessage,
+    LinuxDaemonMessageHeader,
+};
+use ::nvx::ipc::Message;
+
+//==================================================================================================
+// Fuzz Target
+//==================================================================================================
+
+// Decodes a message the way the Linux Daemon does when it receives one.
+fuzz_target!(|data: &[u8]| {
+    let mut bytes: [u8; Message::PAYLOAD_SIZE] = [0; Message::PAYLOAD_SIZE];
+    let len: usize = data.len().min(bytes.len());
+    bytes[..len].copy_from_slice(&data[..len]);
+
+    let message: LinuxDaemonMessage = match LinuxDaemonMessage::try_from_bytes(bytes) {
+        Ok(message) => message,
+        Err(_) => return,
+    };
+
+    let payload: [u8; LinuxDaemonMessage::PAYLOAD_SIZE] = message.payload;
+    match message.header {
+        LinuxDaemonMessageHeader::JoinEnvRequest => {
+            let _ = format!("{:?}", JoinEnvRequest::from_bytes(payload));
+        },
+        LinuxDaemonMessageHeader::LeaveEnvRequest => {
+            let _ = format!("{:?}", LeaveEnvRequest::from_bytes(payload));
+        },
+        LinuxDaemonMessageHeader::GetClockResolutionRequest => {
+            let _ = format!("{:?}", ClockResolutionRequest::from_bytes(payload));
+        },
+        LinuxDaemonMessageHeader::GetClockTimeRequest => {
+            let _ = format!("{:?}", GetClockTimeRequest::from_bytes(payload));
+        },
+        LinuxDaemonMessageHeader::OpenAtRequest => {
+            let _ = format!("{:?}", OpenAtRequest::try_from_bytes(payload));
+        },
+        LinuxDaemonMessageHeader::UnlinkAtRequest => {
+            let _ = format!("{:?}", UnlinkAtRequest::from_bytes(payload));
+        },
+        LinuxDaemonMessageHeader::CloseRequest => {
+            let _ = format!("{:?}", CloseRequest::from_bytes(payload));
+        },
+        LinuxDaemonMessageHeader::RenameAtRequest => {
+            let _ = format!("{:?}", RenameAtRequest::from_bytes(payload));
+        },
+        LinuxDaemonMessageHeader::FileStatRequest => {
+            let _ = format!("{:?}", FileStatRequest::from_bytes(payload));
+        },
+        LinuxDaemonMessageHeader::FileDataSyncRequest => {
+            let _ = format!("{:?}", FileDataSyncRequest::from_bytes(payload));
+        },
+        LinuxDaemonMessageHeader::FileSyncRequest => {
+            let _ = format!("{:?}", FileSyncRequest::from_bytes(payload));
+        },
+        LinuxDaemonMessageHeader::SeekRequest => {
+            let _ = format!("{:?}", SeekRequest::from_bytes(payload));
+        },
+        LinuxDaemonMessageHeader::FileSpaceControlRequest => {
+            let _ = format!("{:?}", FileSpaceControlRequest::from_bytes(payload));
+        },
+        LinuxDaemonMessageHeader::FileTruncateRequest => {
+            let _ = format!("{:?}", FileTruncateRequest::from_bytes(payload));
+        },
+        LinuxDaemonMessageHeader::FileAdvisoryInformationRequest => {
+            let _ = format!("{:?}", FileAdvisoryInformationRequest::from_bytes(payload));
+        },
+        LinuxDaemonMessageHeader::WriteRequest => {
+            let _ = format!("{:?}", WriteRequest::from_bytes(payload));
+        },
+        LinuxDaemonMessageHeader::ReadRequest => {
+            let _ = format!("{:?}", ReadRequest::from_bytes(payload));
+        },
+        LinuxDaemonMessageHeader::PartialWriteRequest => {
+            let _ = format!("{:?}", PartialWriteRequest::from_bytes(payload));
+        },
+        LinuxDaemonMessageHeader::PartialReadRequest => {
+            let _ = format!("{:?}", PartialReadRequest::from_bytes(payload));
+        },
+        LinuxDaemonMessageHeader::FileLockRequest => {
+            let _ = format!("{:?}", FileLockRequest::from_bytes(payload));
+        },
+        LinuxDaemonMessageHeader::RecordLockRequest => {
+            let _ = format!("{:?}", RecordLockRequest::from_bytes(payload));
+        },
+        LinuxDaemonMessageHeader::CopyFileRangeRequest => {
+            let _ = format!("{:?}", CopyFileRangeRequest::from_bytes(payload));
+        },
+        LinuxDaemonMessageHeader::SendFileRequest => {
+            let _ = format!("{:?}", SendFileRequest::from_bytes(payload));
+        },
+        LinuxDaemonMessageHeader::InotifyRemoveWatchRequest => {
+            let _ = format!("{:?}", InotifyRemoveWatchRequest::from_bytes(payload));
+        },
+        LinuxDaemonMessageHeader::GetResourceLimitRequest => {
+            let _ = format!("{:?}", GetResourceLimitRequest::from_bytes(payload));
+        },
+        LinuxDaemonMessageHeader::GetResourceUsageRequest => {
+            let _ = format!("{:?}", GetResourceUsageRequest::from_bytes(payload));
+        },
+        _ => {
+            let _ = LinuxDaemonMessagePart::try_from_bytes(payload);
+        },
+    }
+});
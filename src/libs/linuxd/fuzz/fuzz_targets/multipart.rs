@@ -0,0 +1,103 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Configuration
+//==================================================================================================
+
+#![no_main]
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use ::libfuzzer_sys::fuzz_target;
+use ::linuxd::{
+    fcntl::message::{
+        ReadLinkAtRequest,
+        RenameAt2Request,
+        SymbolicLinkAtRequest,
+    },
+    message::{
+        LinuxDaemonLongMessage,
+        LinuxDaemonMessagePart,
+        MessagePartitioner,
+    },
+    stdlib::message::RealPathRequest,
+    sys::{
+        inotify::message::InotifyAddWatchRequest,
+        stat::message::FileStatAtRequest,
+        xattr::message::{
+            GetXattrRequest,
+            ListXattrRequest,
+            RemoveXattrRequest,
+            SetXattrRequest,
+        },
+    },
+    unistd::message::{
+        LinkAtRequest,
+        TruncateRequest,
+    },
+    LinuxDaemonMessage,
+};
+
+//==================================================================================================
+// Standalone Functions
+//==================================================================================================
+
+// Reassembles and decodes a request the way the Linux Daemon does when it receives its parts.
+fn assemble<T: MessagePartitioner>(data: &[u8]) {
+    let _ = T::try_from_bytes(data);
+
+    let capacity: usize = LinuxDaemonLongMessage::capacity(T::MAX_SIZE);
+    let mut assembler: LinuxDaemonLongMessage = match LinuxDaemonLongMessage::new(capacity) {
+        Ok(assembler) => assembler,
+        Err(_) => return,
+    };
+
+    for chunk in data.chunks(LinuxDaemonMessage::PAYLOAD_SIZE) {
+        let mut bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE] =
+            [0; LinuxDaemonMessage::PAYLOAD_SIZE];
+        bytes[..chunk.len()].copy_from_slice(chunk);
+
+        let part: LinuxDaemonMessagePart = match LinuxDaemonMessagePart::try_from_bytes(bytes) {
+            Ok(part) => part,
+            Err(_) => return,
+        };
+
+        if assembler.add_part(part).is_err() {
+            return;
+        }
+
+        if assembler.is_complete() {
+            let _ = T::from_parts(&assembler.take_parts());
+            return;
+        }
+    }
+}
+
+//==================================================================================================
+// Fuzz Target
+//==================================================================================================
+
+fuzz_target!(|data: &[u8]| {
+    let (selector, data): (u8, &[u8]) = match data.split_first() {
+        Some((selector, data)) => (*selector, data),
+        None => return,
+    };
+
+    match selector % 12 {
+        0 => assemble::<FileStatAtRequest>(data),
+        1 => assemble::<SymbolicLinkAtRequest>(data),
+        2 => assemble::<LinkAtRequest>(data),
+        3 => assemble::<ReadLinkAtRequest>(data),
+        4 => assemble::<GetXattrRequest>(data),
+        5 => assemble::<SetXattrRequest>(data),
+        6 => assemble::<ListXattrRequest>(data),
+        7 => assemble::<RemoveXattrRequest>(data),
+        8 => assemble::<TruncateRequest>(data),
+        9 => assemble::<RenameAt2Request>(data),
+        10 => assemble::<RealPathRequest>(data),
+        _ => assemble::<InotifyAddWatchRequest>(data),
+    }
+});
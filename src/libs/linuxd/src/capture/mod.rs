@@ -0,0 +1,175 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::protocol::Hello;
+use ::core::convert::TryFrom;
+use ::nvx::sys::{
+    config,
+    error::{
+        Error,
+        ErrorCode,
+    },
+};
+
+//==================================================================================================
+// Constants
+//==================================================================================================
+
+/// Magic number that opens a capture.
+pub const CAPTURE_MAGIC: [u8; 8] = *b"LINUXD\x02\x00";
+
+/// Version of the capture format implemented by this revision.
+pub const CAPTURE_VERSION: u16 = 1;
+
+//==================================================================================================
+// Structures
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Header that opens a capture of the messages exchanged on a connection.
+///
+/// A capture is a header followed by [`CaptureRecord`]s, in the order in which the Linux Daemon
+/// read or wrote them. Frames are recorded verbatim, including those that do not decode.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CaptureHeader {
+    /// Version of the capture format.
+    pub version: u16,
+    /// Version and capabilities that were negotiated on the connection.
+    pub hello: Hello,
+}
+
+///
+/// # Description
+///
+/// Direction in which a frame travelled.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Direction {
+    /// From the client to the Linux Daemon.
+    Inbound = 0,
+    /// From the Linux Daemon to the client.
+    Outbound = 1,
+}
+
+///
+/// # Description
+///
+/// Frame of a capture.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CaptureRecord {
+    /// Direction in which the frame travelled.
+    pub direction: Direction,
+    /// Microseconds since the connection was established.
+    pub timestamp_us: u64,
+    /// Frame, as read from or written to the connection.
+    pub frame: [u8; config::kernel::IPC_MESSAGE_SIZE],
+}
+
+//==================================================================================================
+// Implementations
+//==================================================================================================
+
+impl CaptureHeader {
+    /// Size of a capture header (in bytes).
+    pub const SIZE: usize = CAPTURE_MAGIC.len() + 2 + Hello::SIZE;
+
+    ///
+    /// # Description
+    ///
+    /// Creates a header for a capture of this revision.
+    ///
+    /// # Parameters
+    ///
+    /// - `hello`: Version and capabilities that were negotiated on the connection.
+    ///
+    pub fn new(hello: Hello) -> Self {
+        Self {
+            version: CAPTURE_VERSION,
+            hello,
+        }
+    }
+
+    pub fn from_bytes(bytes: [u8; Self::SIZE]) -> Result<Self, Error> {
+        let (magic, rest): (&[u8], &[u8]) = bytes.split_at(CAPTURE_MAGIC.len());
+        if magic != CAPTURE_MAGIC {
+            return Err(Error::new(ErrorCode::InvalidMessage, "invalid capture magic"));
+        }
+
+        let (version, hello): (&[u8], &[u8]) = rest.split_at(2);
+        let version: u16 = u16::from_le_bytes([version[0], version[1]]);
+        if version != CAPTURE_VERSION {
+            return Err(Error::new(
+                ErrorCode::OperationNotSupported,
+                "unsupported capture version",
+            ));
+        }
+
+        let mut bytes: [u8; Hello::SIZE] = [0; Hello::SIZE];
+        bytes.copy_from_slice(hello);
+
+        Ok(Self {
+            version,
+            hello: Hello::from_bytes(bytes)?,
+        })
+    }
+
+    pub fn to_bytes(self) -> [u8; Self::SIZE] {
+        let mut bytes: [u8; Self::SIZE] = [0; Self::SIZE];
+        let (magic, rest): (&mut [u8], &mut [u8]) = bytes.split_at_mut(CAPTURE_MAGIC.len());
+        magic.copy_from_slice(&CAPTURE_MAGIC);
+        rest[..2].copy_from_slice(&self.version.to_le_bytes());
+        rest[2..].copy_from_slice(&self.hello.to_bytes());
+        bytes
+    }
+}
+
+impl TryFrom<u8> for Direction {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Inbound),
+            1 => Ok(Self::Outbound),
+            _ => Err(Error::new(ErrorCode::InvalidMessage, "invalid capture direction")),
+        }
+    }
+}
+
+impl CaptureRecord {
+    /// Size of a capture record (in bytes).
+    pub const SIZE: usize = 1 + 8 + config::kernel::IPC_MESSAGE_SIZE;
+
+    pub fn from_bytes(bytes: [u8; Self::SIZE]) -> Result<Self, Error> {
+        let direction: Direction = Direction::try_from(bytes[0])?;
+
+        let mut timestamp_us: [u8; 8] = [0; 8];
+        timestamp_us.copy_from_slice(&bytes[1..9]);
+
+        let mut frame: [u8; config::kernel::IPC_MESSAGE_SIZE] =
+            [0; config::kernel::IPC_MESSAGE_SIZE];
+        frame.copy_from_slice(&bytes[9..]);
+
+        Ok(Self {
+            direction,
+            timestamp_us: u64::from_le_bytes(timestamp_us),
+            frame,
+        })
+    }
+
+    pub fn to_bytes(self) -> [u8; Self::SIZE] {
+        let mut bytes: [u8; Self::SIZE] = [0; Self::SIZE];
+        bytes[0] = self.direction as u8;
+        bytes[1..9].copy_from_slice(&self.timestamp_us.to_le_bytes());
+        bytes[9..].copy_from_slice(&self.frame);
+        bytes
+    }
+}
@@ -7,6 +7,10 @@
 
 use crate::{
     sys::types::off_t,
+    wire::{
+        WireDecoder,
+        WireEncoder,
+    },
     LinuxDaemonMessage,
     LinuxDaemonMessageHeader,
 };
@@ -53,11 +57,23 @@ impl FileSpaceControlRequest {
     }
 
     pub fn from_bytes(bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE]) -> Self {
-        unsafe { mem::transmute(bytes) }
+        let mut decoder: WireDecoder = WireDecoder::new(&bytes);
+        Self {
+            fd: decoder.get(),
+            offset: decoder.get(),
+            len: decoder.get(),
+            _padding: [0; Self::PADDING_SIZE],
+        }
     }
 
     fn into_bytes(self) -> [u8; LinuxDaemonMessage::PAYLOAD_SIZE] {
-        unsafe { mem::transmute(self) }
+        let mut bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE] =
+            [0; LinuxDaemonMessage::PAYLOAD_SIZE];
+        let mut encoder: WireEncoder = WireEncoder::new(&mut bytes);
+        encoder.put(self.fd);
+        encoder.put(self.offset);
+        encoder.put(self.len);
+        bytes
     }
 
     pub fn build(
@@ -100,11 +116,19 @@ impl FileSpaceControlResponse {
     }
 
     pub fn from_bytes(bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE]) -> Self {
-        unsafe { mem::transmute(bytes) }
+        let mut decoder: WireDecoder = WireDecoder::new(&bytes);
+        Self {
+            ret: decoder.get(),
+            _padding: [0; Self::PADDING_SIZE],
+        }
     }
 
     fn into_bytes(self) -> [u8; LinuxDaemonMessage::PAYLOAD_SIZE] {
-        unsafe { mem::transmute(self) }
+        let mut bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE] =
+            [0; LinuxDaemonMessage::PAYLOAD_SIZE];
+        let mut encoder: WireEncoder = WireEncoder::new(&mut bytes);
+        encoder.put(self.ret);
+        bytes
     }
 
     pub fn build(pid: ProcessIdentifier, ret: i32) -> Message {
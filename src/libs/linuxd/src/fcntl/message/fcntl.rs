@@ -0,0 +1,221 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::{
+    fcntl::flock,
+    sys::types::{
+        off_t,
+        pid_t,
+    },
+    wire::{
+        WireDecoder,
+        WireEncoder,
+    },
+    LinuxDaemonMessage,
+    LinuxDaemonMessageHeader,
+};
+use ::core::mem;
+use ::nvx::{
+    ipc::{
+        Message,
+        MessageType,
+    },
+    pm::ProcessIdentifier,
+};
+
+//==================================================================================================
+// RecordLockRequest
+//==================================================================================================
+
+///
+/// # Description
+///
+/// This struct represents the request message of the record locking commands of the `fcntl()`
+/// system call.
+///
+#[derive(Debug)]
+#[repr(C, packed)]
+pub struct RecordLockRequest {
+    /// File descriptor.
+    pub fd: i32,
+    /// Command (`F_GETLK`, `F_SETLK` or `F_SETLKW`).
+    pub cmd: i32,
+    /// Type of lock.
+    pub l_type: i16,
+    /// Flag for starting offset.
+    pub l_whence: i16,
+    /// Relative offset in bytes.
+    pub l_start: off_t,
+    /// Size of the locked region.
+    pub l_len: off_t,
+    _padding: [u8; Self::PADDING_SIZE],
+}
+::nvx::sys::static_assert_size!(RecordLockRequest, LinuxDaemonMessage::PAYLOAD_SIZE);
+
+impl RecordLockRequest {
+    pub const PADDING_SIZE: usize = LinuxDaemonMessage::PAYLOAD_SIZE
+        - mem::size_of::<i32>()
+        - mem::size_of::<i32>()
+        - mem::size_of::<i16>()
+        - mem::size_of::<i16>()
+        - mem::size_of::<off_t>()
+        - mem::size_of::<off_t>();
+
+    fn new(fd: i32, cmd: i32, lock: &flock) -> Self {
+        Self {
+            fd,
+            cmd,
+            l_type: lock.l_type,
+            l_whence: lock.l_whence,
+            l_start: lock.l_start,
+            l_len: lock.l_len,
+            _padding: [0; Self::PADDING_SIZE],
+        }
+    }
+
+    pub fn from_bytes(bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE]) -> Self {
+        let mut decoder: WireDecoder = WireDecoder::new(&bytes);
+        Self {
+            fd: decoder.get(),
+            cmd: decoder.get(),
+            l_type: decoder.get(),
+            l_whence: decoder.get(),
+            l_start: decoder.get(),
+            l_len: decoder.get(),
+            _padding: [0; Self::PADDING_SIZE],
+        }
+    }
+
+    fn into_bytes(self) -> [u8; LinuxDaemonMessage::PAYLOAD_SIZE] {
+        let mut bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE] =
+            [0; LinuxDaemonMessage::PAYLOAD_SIZE];
+        let mut encoder: WireEncoder = WireEncoder::new(&mut bytes);
+        encoder.put(self.fd);
+        encoder.put(self.cmd);
+        encoder.put(self.l_type);
+        encoder.put(self.l_whence);
+        encoder.put(self.l_start);
+        encoder.put(self.l_len);
+        bytes
+    }
+
+    pub fn build(pid: ProcessIdentifier, fd: i32, cmd: i32, lock: &flock) -> Message {
+        let message: RecordLockRequest = RecordLockRequest::new(fd, cmd, lock);
+        let message: LinuxDaemonMessage = LinuxDaemonMessage::new(
+            LinuxDaemonMessageHeader::RecordLockRequest,
+            message.into_bytes(),
+        );
+        let message: Message =
+            Message::new(pid, crate::LINUXD, MessageType::Ikc, None, message.into_bytes());
+
+        message
+    }
+}
+
+//==================================================================================================
+// RecordLockResponse
+//==================================================================================================
+
+///
+/// # Description
+///
+/// This struct represents the response message of the record locking commands of the `fcntl()`
+/// system call.
+///
+#[derive(Debug)]
+#[repr(C, packed)]
+pub struct RecordLockResponse {
+    /// Return value.
+    pub ret: i32,
+    /// Type of lock.
+    pub l_type: i16,
+    /// Flag for starting offset.
+    pub l_whence: i16,
+    /// Relative offset in bytes.
+    pub l_start: off_t,
+    /// Size of the locked region.
+    pub l_len: off_t,
+    /// Process holding the lock.
+    pub l_pid: pid_t,
+    _padding: [u8; Self::PADDING_SIZE],
+}
+::nvx::sys::static_assert_size!(RecordLockResponse, LinuxDaemonMessage::PAYLOAD_SIZE);
+
+impl RecordLockResponse {
+    pub const PADDING_SIZE: usize = LinuxDaemonMessage::PAYLOAD_SIZE
+        - mem::size_of::<i32>()
+        - mem::size_of::<i16>()
+        - mem::size_of::<i16>()
+        - mem::size_of::<off_t>()
+        - mem::size_of::<off_t>()
+        - mem::size_of::<pid_t>();
+
+    fn new(ret: i32, lock: &flock) -> Self {
+        Self {
+            ret,
+            l_type: lock.l_type,
+            l_whence: lock.l_whence,
+            l_start: lock.l_start,
+            l_len: lock.l_len,
+            l_pid: lock.l_pid,
+            _padding: [0; Self::PADDING_SIZE],
+        }
+    }
+
+    pub fn from_bytes(bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE]) -> Self {
+        let mut decoder: WireDecoder = WireDecoder::new(&bytes);
+        Self {
+            ret: decoder.get(),
+            l_type: decoder.get(),
+            l_whence: decoder.get(),
+            l_start: decoder.get(),
+            l_len: decoder.get(),
+            l_pid: decoder.get(),
+            _padding: [0; Self::PADDING_SIZE],
+        }
+    }
+
+    fn into_bytes(self) -> [u8; LinuxDaemonMessage::PAYLOAD_SIZE] {
+        let mut bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE] =
+            [0; LinuxDaemonMessage::PAYLOAD_SIZE];
+        let mut encoder: WireEncoder = WireEncoder::new(&mut bytes);
+        encoder.put(self.ret);
+        encoder.put(self.l_type);
+        encoder.put(self.l_whence);
+        encoder.put(self.l_start);
+        encoder.put(self.l_len);
+        encoder.put(self.l_pid);
+        bytes
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the lock description carried by the response.
+    ///
+    pub fn lock(&self) -> flock {
+        flock {
+            l_type: self.l_type,
+            l_whence: self.l_whence,
+            l_start: self.l_start,
+            l_len: self.l_len,
+            l_pid: self.l_pid,
+        }
+    }
+
+    pub fn build(pid: ProcessIdentifier, ret: i32, lock: &flock) -> Message {
+        let message: RecordLockResponse = RecordLockResponse::new(ret, lock);
+        let message: LinuxDaemonMessage = LinuxDaemonMessage::new(
+            LinuxDaemonMessageHeader::RecordLockResponse,
+            message.into_bytes(),
+        );
+        let message: Message =
+            Message::new(crate::LINUXD, pid, MessageType::Ikc, None, message.into_bytes());
+
+        message
+    }
+}
@@ -7,9 +7,11 @@
 
 mod fadvise;
 mod fallocate;
+mod fcntl;
 mod openat;
 mod readlinkat;
 mod renameat;
+mod renameat2;
 mod symlinkat;
 mod unlinkat;
 
@@ -26,6 +28,10 @@ pub use self::{
         FileSpaceControlRequest,
         FileSpaceControlResponse,
     },
+    fcntl::{
+        RecordLockRequest,
+        RecordLockResponse,
+    },
     openat::{
         OpenAtRequest,
         OpenAtResponse,
@@ -38,6 +44,10 @@ pub use self::{
         RenameAtRequest,
         RenameAtResponse,
     },
+    renameat2::{
+        RenameAt2Request,
+        RenameAt2Response,
+    },
     symlinkat::{
         SymbolicLinkAtRequest,
         SymbolicLinkAtResponse,
@@ -6,8 +6,21 @@
 //==================================================================================================
 
 use crate::{
+    fcntl::{
+        O_APPEND,
+        O_CREAT,
+        O_EXCL,
+        O_RDONLY,
+        O_RDWR,
+        O_TRUNC,
+        O_WRONLY,
+    },
     limits,
     sys::types::mode_t,
+    wire::{
+        WireDecoder,
+        WireEncoder,
+    },
     LinuxDaemonMessage,
     LinuxDaemonMessageHeader,
 };
@@ -46,6 +59,14 @@ impl OpenAtRequest {
         - mem::size_of::<mode_t>()
         - limits::NAME_MAX;
 
+    /// Flags that requests may carry.
+    const VALID_FLAGS: ffi::c_int =
+        O_APPEND | O_CREAT | O_EXCL | O_TRUNC | O_RDONLY | O_WRONLY | O_RDWR;
+
+    /// Mode bits that requests may carry: permissions, along with the set-user-ID, set-group-ID
+    /// and sticky bits.
+    const VALID_MODE: mode_t = 0o7777;
+
     fn new(dirfd: i32, pathname: [u8; limits::NAME_MAX], flags: ffi::c_int, mode: mode_t) -> Self {
         Self {
             dirfd,
@@ -56,12 +77,62 @@ impl OpenAtRequest {
         }
     }
 
-    pub fn from_bytes(bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE]) -> Self {
-        unsafe { mem::transmute(bytes) }
+    ///
+    /// # Description
+    ///
+    /// Decodes a request that was received from an untrusted peer.
+    ///
+    /// # Parameters
+    ///
+    /// - `bytes`: Payload of the request.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the request is returned. Upon failure, an error is returned instead, if the
+    /// request carries unknown flags or mode bits, or bytes past the end of its pathname.
+    ///
+    pub fn try_from_bytes(bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE]) -> Result<Self, Error> {
+        let mut decoder: WireDecoder = WireDecoder::new(&bytes);
+        let request: Self = Self {
+            dirfd: decoder.get(),
+            flags: decoder.get(),
+            mode: decoder.get(),
+            pathname: decoder.get(),
+            _padding: [0; Self::PADDING_SIZE],
+        };
+
+        // Check if flags are valid.
+        if (request.flags & !Self::VALID_FLAGS) != 0 {
+            return Err(Error::new(ErrorCode::InvalidMessage, "invalid flags"));
+        }
+
+        // Check if mode is valid.
+        if (request.mode & !Self::VALID_MODE) != 0 {
+            return Err(Error::new(ErrorCode::InvalidMessage, "invalid mode"));
+        }
+
+        // Check if pathname is padded with zeros only, past its end.
+        let len: usize = request
+            .pathname
+            .iter()
+            .position(|b| *b == 0)
+            .unwrap_or(limits::NAME_MAX);
+        if request.pathname[len..].iter().any(|b| *b != 0) {
+            return Err(Error::new(ErrorCode::InvalidMessage, "invalid pathname length"));
+        }
+
+        Ok(request)
     }
 
     fn into_bytes(self) -> [u8; LinuxDaemonMessage::PAYLOAD_SIZE] {
-        unsafe { mem::transmute(self) }
+        let mut bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE] =
+            [0; LinuxDaemonMessage::PAYLOAD_SIZE];
+        let mut encoder: WireEncoder = WireEncoder::new(&mut bytes);
+        encoder.put(self.dirfd);
+        encoder.put(self.flags);
+        encoder.put(self.mode);
+        encoder.put(self.pathname);
+        bytes
     }
 
     pub fn build(
@@ -125,11 +196,19 @@ impl OpenAtResponse {
     }
 
     pub fn from_bytes(bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE]) -> Self {
-        unsafe { mem::transmute(bytes) }
+        let mut decoder: WireDecoder = WireDecoder::new(&bytes);
+        Self {
+            ret: decoder.get(),
+            _padding: [0; Self::PADDING_SIZE],
+        }
     }
 
     fn into_bytes(self) -> [u8; LinuxDaemonMessage::PAYLOAD_SIZE] {
-        unsafe { mem::transmute(self) }
+        let mut bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE] =
+            [0; LinuxDaemonMessage::PAYLOAD_SIZE];
+        let mut encoder: WireEncoder = WireEncoder::new(&mut bytes);
+        encoder.put(self.ret);
+        bytes
     }
 
     pub fn build(pid: ProcessIdentifier, ret: i32) -> Message {
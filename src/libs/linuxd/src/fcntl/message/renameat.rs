@@ -7,6 +7,10 @@
 
 use crate::{
     limits,
+    wire::{
+        WireDecoder,
+        WireEncoder,
+    },
     LinuxDaemonMessage,
     LinuxDaemonMessageHeader,
 };
@@ -63,11 +67,25 @@ impl RenameAtRequest {
     }
 
     pub fn from_bytes(bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE]) -> Self {
-        unsafe { mem::transmute(bytes) }
+        let mut decoder: WireDecoder = WireDecoder::new(&bytes);
+        Self {
+            olddirfd: decoder.get(),
+            oldpath: decoder.get(),
+            newdirfd: decoder.get(),
+            newpath: decoder.get(),
+            _padding: [0; Self::PADDING_SIZE],
+        }
     }
 
     fn into_bytes(self) -> [u8; LinuxDaemonMessage::PAYLOAD_SIZE] {
-        unsafe { mem::transmute(self) }
+        let mut bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE] =
+            [0; LinuxDaemonMessage::PAYLOAD_SIZE];
+        let mut encoder: WireEncoder = WireEncoder::new(&mut bytes);
+        encoder.put(self.olddirfd);
+        encoder.put(self.oldpath);
+        encoder.put(self.newdirfd);
+        encoder.put(self.newpath);
+        bytes
     }
 
     pub fn build(
@@ -115,9 +133,9 @@ impl fmt::Debug for RenameAtRequest {
             f,
             "{{ olddirfd: {}, oldpath: {:?}, newdirfd: {}, newpath: {:?} }}",
             olddirfd,
-            unsafe { core::str::from_utf8_unchecked(&self.oldpath) },
+            core::str::from_utf8(&self.oldpath).unwrap_or("<invalid>"),
             newdirfd,
-            unsafe { core::str::from_utf8_unchecked(&self.newpath) }
+            core::str::from_utf8(&self.newpath).unwrap_or("<invalid>")
         )
     }
 }
@@ -144,11 +162,19 @@ impl RenameAtResponse {
     }
 
     pub fn from_bytes(bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE]) -> Self {
-        unsafe { mem::transmute(bytes) }
+        let mut decoder: WireDecoder = WireDecoder::new(&bytes);
+        Self {
+            ret: decoder.get(),
+            _padding: [0; Self::PADDING_SIZE],
+        }
     }
 
     fn into_bytes(self) -> [u8; LinuxDaemonMessage::PAYLOAD_SIZE] {
-        unsafe { mem::transmute(self) }
+        let mut bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE] =
+            [0; LinuxDaemonMessage::PAYLOAD_SIZE];
+        let mut encoder: WireEncoder = WireEncoder::new(&mut bytes);
+        encoder.put(self.ret);
+        bytes
     }
 
     pub fn build(pid: ProcessIdentifier, ret: i32) -> Message {
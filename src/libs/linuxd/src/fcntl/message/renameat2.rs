@@ -0,0 +1,142 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::{
+    limits,
+    message::{
+        MessageDeserializer,
+        MessagePartitioner,
+        MessageSerializer,
+    },
+    wire::{
+        WireDecoder,
+        WireEncoder,
+    },
+    LinuxDaemonMessage,
+    LinuxDaemonMessageHeader,
+};
+use ::alloc::string::String;
+use ::core::mem;
+use ::nvx::{
+    ipc::{
+        Message,
+        MessageType,
+    },
+    pm::ProcessIdentifier,
+    sys::error::{
+        Error,
+        ErrorCode,
+    },
+};
+
+//==================================================================================================
+// RenameAt2Request
+//==================================================================================================
+
+///
+/// # Description
+///
+/// This struct represents the request message of the `renameat2()` system call.
+///
+#[derive(Debug, MessageSerializer, MessageDeserializer, MessagePartitioner)]
+#[message(request_part = RenameAt2RequestPart)]
+pub struct RenameAt2Request {
+    /// Directory file descriptor.
+    pub olddirfd: i32,
+    /// Old path.
+    #[message(max_len = limits::PATH_MAX)]
+    pub oldpath: String,
+    /// New directory file descriptor.
+    pub newdirfd: i32,
+    /// New path.
+    #[message(max_len = limits::PATH_MAX)]
+    pub newpath: String,
+    /// Flags (`RENAME_NOREPLACE` or `RENAME_EXCHANGE`).
+    pub flags: u32,
+}
+
+impl RenameAt2Request {
+    ///
+    /// # Description
+    ///
+    /// Creates a new request message for the `renameat2()` system call.
+    ///
+    pub fn new(
+        olddirfd: i32,
+        oldpath: String,
+        newdirfd: i32,
+        newpath: String,
+        flags: u32,
+    ) -> Result<Self, Error> {
+        // Check if the old path is too long.
+        if oldpath.len() > limits::PATH_MAX {
+            return Err(Error::new(ErrorCode::InvalidMessage, "old path too long"));
+        }
+
+        // Check if the new path is too long.
+        if newpath.len() > limits::PATH_MAX {
+            return Err(Error::new(ErrorCode::InvalidMessage, "new path too long"));
+        }
+
+        Ok(Self {
+            olddirfd,
+            oldpath,
+            newdirfd,
+            newpath,
+            flags,
+        })
+    }
+}
+
+//==================================================================================================
+// RenameAt2Response
+//==================================================================================================
+
+#[repr(C, packed)]
+pub struct RenameAt2Response {
+    pub ret: i32,
+    _padding: [u8; Self::PADDING_SIZE],
+}
+::nvx::sys::static_assert_size!(RenameAt2Response, LinuxDaemonMessage::PAYLOAD_SIZE);
+
+impl RenameAt2Response {
+    pub const PADDING_SIZE: usize = LinuxDaemonMessage::PAYLOAD_SIZE - mem::size_of::<i32>();
+
+    pub fn new(ret: i32) -> Self {
+        Self {
+            ret,
+            _padding: [0; Self::PADDING_SIZE],
+        }
+    }
+
+    pub fn from_bytes(bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE]) -> Self {
+        let mut decoder: WireDecoder = WireDecoder::new(&bytes);
+        Self {
+            ret: decoder.get(),
+            _padding: [0; Self::PADDING_SIZE],
+        }
+    }
+
+    fn into_bytes(self) -> [u8; LinuxDaemonMessage::PAYLOAD_SIZE] {
+        let mut bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE] =
+            [0; LinuxDaemonMessage::PAYLOAD_SIZE];
+        let mut encoder: WireEncoder = WireEncoder::new(&mut bytes);
+        encoder.put(self.ret);
+        bytes
+    }
+
+    pub fn build(pid: ProcessIdentifier, ret: i32) -> Message {
+        let message: RenameAt2Response = RenameAt2Response::new(ret);
+        let message: LinuxDaemonMessage = LinuxDaemonMessage::new(
+            LinuxDaemonMessageHeader::RenameAt2Response,
+            message.into_bytes(),
+        );
+        let message: Message =
+            Message::new(crate::LINUXD, pid, MessageType::Ikc, None, message.into_bytes());
+        message
+    }
+}
@@ -8,22 +8,19 @@
 use crate::{
     limits,
     message::{
-        LinuxDaemonMessagePart,
         MessageDeserializer,
         MessagePartitioner,
         MessageSerializer,
     },
+    wire::{
+        WireDecoder,
+        WireEncoder,
+    },
     LinuxDaemonMessage,
     LinuxDaemonMessageHeader,
 };
-use ::alloc::{
-    string::String,
-    vec::Vec,
-};
-use ::core::{
-    convert::TryInto,
-    mem,
-};
+use ::alloc::string::String;
+use ::core::mem;
 use ::nvx::{
     ipc::{
         Message,
@@ -45,39 +42,20 @@ use ::nvx::{
 ///
 /// This struct represents the request message of the `symlinkat()` system call.
 ///
-#[derive(Debug)]
+#[derive(Debug, MessageSerializer, MessageDeserializer, MessagePartitioner)]
+#[message(request_part = SymbolicLinkAtRequestPart)]
 pub struct SymbolicLinkAtRequest {
     /// Directory file descriptor.
     pub dirfd: i32,
     /// Path.
+    #[message(max_len = limits::PATH_MAX)]
     pub target: String,
     /// Symbolic link path.
+    #[message(max_len = limits::PATH_MAX)]
     pub linkpath: String,
 }
 
 impl SymbolicLinkAtRequest {
-    /// Sizes of 'directory file descriptor' field.
-    const SIZE_OF_DIRFD: usize = mem::size_of::<i32>();
-    /// Sizes of 'target length' field.
-    const SIZE_OF_TARGET: usize = mem::size_of::<u32>();
-    /// Sizes of 'link path length' field.
-    const SIZE_OF_LINKPATH: usize = mem::size_of::<u32>();
-    /// Offset of 'dirfd' field.
-    const OFFSET_DIRFD: usize = 0;
-    /// Offset of 'target length' field.
-    const OFFSET_TARGET_LENGTH: usize = Self::OFFSET_DIRFD + Self::SIZE_OF_DIRFD;
-    /// Offset of 'link path length' field.
-    const OFFSET_LINKPATH_LENGTH: usize = Self::OFFSET_TARGET_LENGTH + Self::SIZE_OF_TARGET;
-    /// Offset of 'target' field.
-    const OFFSET_TARGET: usize = Self::OFFSET_LINKPATH_LENGTH + Self::SIZE_OF_LINKPATH;
-
-    /// Maximum size of the message.
-    pub const MAX_SIZE: usize = Self::SIZE_OF_DIRFD
-        + Self::SIZE_OF_TARGET
-        + Self::SIZE_OF_LINKPATH
-        + limits::PATH_MAX
-        + limits::PATH_MAX;
-
     ///
     /// # Description
     ///
@@ -102,137 +80,6 @@ impl SymbolicLinkAtRequest {
     }
 }
 
-impl MessageSerializer for SymbolicLinkAtRequest {
-    ///
-    /// # Description
-    ///
-    /// Serializes the request message of the `symlinkat()` system call.
-    ///
-    fn to_bytes(&self) -> Vec<u8> {
-        let mut buffer: Vec<u8> = Vec::new();
-
-        // Serialize the 'directory file descriptor' field.
-        buffer.extend_from_slice(&self.dirfd.to_ne_bytes());
-        // Serialize the 'target length' field.
-        buffer.extend_from_slice(&(self.target.len() as u32).to_ne_bytes());
-        // Serialize the 'link path length' field.
-        buffer.extend_from_slice(&(self.linkpath.len() as u32).to_ne_bytes());
-        // Serialize the 'target' field.
-        buffer.extend_from_slice(self.target.as_bytes());
-        // Serialize the 'link path' field.
-        buffer.extend_from_slice(self.linkpath.as_bytes());
-
-        buffer
-    }
-}
-
-impl MessageDeserializer for SymbolicLinkAtRequest {
-    ///
-    /// # Description
-    ///
-    /// Deserializes the request message of the `symlinkat()` system call.
-    ///
-    /// # Parameters
-    ///
-    /// - `bytes`: Bytes to deserialize.
-    ///
-    /// # Returns
-    ///
-    /// Upon success, the deserialized message is returned. Upon failure, an error is returned.
-    ///
-    fn try_from_bytes(bytes: &[u8]) -> Result<Self, Error> {
-        // Check if message is too short.
-        if bytes.len() < SymbolicLinkAtRequest::OFFSET_TARGET {
-            return Err(Error::new(ErrorCode::InvalidMessage, "message too short"));
-        }
-
-        // Check if message is too long.
-        if bytes.len() > SymbolicLinkAtRequest::MAX_SIZE {
-            return Err(Error::new(ErrorCode::InvalidMessage, "message too long"));
-        }
-
-        // Deserialize the 'directory file descriptor' field.
-        let dirfd: i32 = i32::from_ne_bytes(
-            bytes[SymbolicLinkAtRequest::OFFSET_DIRFD..SymbolicLinkAtRequest::OFFSET_TARGET_LENGTH]
-                .try_into()
-                .map_err(|_| Error::new(ErrorCode::InvalidMessage, "invalid dirfd"))?,
-        );
-        // Deserialize the 'target length' field.
-        let target_length: usize = u32::from_ne_bytes(
-            bytes[SymbolicLinkAtRequest::OFFSET_TARGET_LENGTH
-                ..SymbolicLinkAtRequest::OFFSET_LINKPATH_LENGTH]
-                .try_into()
-                .map_err(|_| Error::new(ErrorCode::InvalidMessage, "invalid target length"))?,
-        ) as usize;
-        // Deserialize the 'link path length' field.
-        let linkpath_length: usize = u32::from_ne_bytes(
-            bytes[SymbolicLinkAtRequest::OFFSET_LINKPATH_LENGTH
-                ..SymbolicLinkAtRequest::OFFSET_TARGET]
-                .try_into()
-                .map_err(|_| Error::new(ErrorCode::InvalidMessage, "invalid link path length"))?,
-        ) as usize;
-
-        // Check if message is too short.
-        if bytes.len() < SymbolicLinkAtRequest::OFFSET_TARGET + target_length + linkpath_length {
-            return Err(Error::new(ErrorCode::InvalidMessage, "message too short"));
-        }
-
-        // Deserialize the 'target' field.
-        let target: String = String::from_utf8(
-            bytes[SymbolicLinkAtRequest::OFFSET_TARGET
-                ..SymbolicLinkAtRequest::OFFSET_TARGET + target_length]
-                .to_vec(),
-        )
-        .map_err(|_| Error::new(ErrorCode::InvalidMessage, "invalid target"))?;
-        // Deserialize the 'link path' field.
-        let linkpath: String = String::from_utf8(
-            bytes[SymbolicLinkAtRequest::OFFSET_TARGET + target_length
-                ..SymbolicLinkAtRequest::OFFSET_TARGET + target_length + linkpath_length]
-                .to_vec(),
-        )
-        .map_err(|_| Error::new(ErrorCode::InvalidMessage, "invalid link path"))?;
-
-        Ok(SymbolicLinkAtRequest {
-            dirfd,
-            target,
-            linkpath,
-        })
-    }
-}
-
-impl MessagePartitioner for SymbolicLinkAtRequest {
-    ///
-    /// # Description
-    ///
-    /// Creates a new message partition for the `symlinkat()` system call.
-    ///
-    /// # Parameters
-    ///
-    /// - `pid`: Process identifier.
-    /// - `part_number`: Partition number.
-    /// - `payload_size`: Payload size.
-    /// - `payload`: Payload.
-    ///
-    /// # Returns
-    ///
-    /// Upon success, the new message partition is returned. Upon failure, an error is returned.
-    ///
-    fn new_part(
-        pid: ProcessIdentifier,
-        part_number: u32,
-        payload_size: u8,
-        payload: [u8; LinuxDaemonMessagePart::PAYLOAD_SIZE],
-    ) -> Result<Message, Error> {
-        LinuxDaemonMessagePart::build_request(
-            pid,
-            LinuxDaemonMessageHeader::SymbolicLinkAtRequestPart,
-            part_number,
-            payload_size,
-            payload,
-        )
-    }
-}
-
 //==================================================================================================
 // SymbolicLinkAtResponse
 //==================================================================================================
@@ -255,11 +102,19 @@ impl SymbolicLinkAtResponse {
     }
 
     pub fn from_bytes(bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE]) -> Self {
-        unsafe { mem::transmute(bytes) }
+        let mut decoder: WireDecoder = WireDecoder::new(&bytes);
+        Self {
+            ret: decoder.get(),
+            _padding: [0; Self::PADDING_SIZE],
+        }
     }
 
     fn into_bytes(self) -> [u8; LinuxDaemonMessage::PAYLOAD_SIZE] {
-        unsafe { mem::transmute(self) }
+        let mut bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE] =
+            [0; LinuxDaemonMessage::PAYLOAD_SIZE];
+        let mut encoder: WireEncoder = WireEncoder::new(&mut bytes);
+        encoder.put(self.ret);
+        bytes
     }
 
     pub fn build(pid: ProcessIdentifier, ret: i32) -> Message {
@@ -7,6 +7,10 @@
 
 use crate::{
     limits,
+    wire::{
+        WireDecoder,
+        WireEncoder,
+    },
     LinuxDaemonMessage,
     LinuxDaemonMessageHeader,
 };
@@ -56,11 +60,23 @@ impl UnlinkAtRequest {
     }
 
     pub fn from_bytes(bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE]) -> Self {
-        unsafe { mem::transmute(bytes) }
+        let mut decoder: WireDecoder = WireDecoder::new(&bytes);
+        Self {
+            dirfd: decoder.get(),
+            pathname: decoder.get(),
+            flags: decoder.get(),
+            _padding: [0; Self::PADDING_SIZE],
+        }
     }
 
     fn into_bytes(self) -> [u8; LinuxDaemonMessage::PAYLOAD_SIZE] {
-        unsafe { mem::transmute(self) }
+        let mut bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE] =
+            [0; LinuxDaemonMessage::PAYLOAD_SIZE];
+        let mut encoder: WireEncoder = WireEncoder::new(&mut bytes);
+        encoder.put(self.dirfd);
+        encoder.put(self.pathname);
+        encoder.put(self.flags);
+        bytes
     }
 
     pub fn build(
@@ -92,7 +108,7 @@ impl UnlinkAtRequest {
 impl fmt::Debug for UnlinkAtRequest {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let dirfd: i32 = self.dirfd;
-        let pathname: &str = unsafe { core::str::from_utf8_unchecked(&self.pathname) };
+        let pathname: &str = core::str::from_utf8(&self.pathname).unwrap_or("<invalid>");
         let flags: ffi::c_int = self.flags;
         write!(f, "{{ dirfd: {}, pathname: {}, flags: {} }}", dirfd, pathname, flags)
     }
@@ -120,11 +136,19 @@ impl UnlinkAtResponse {
     }
 
     pub fn from_bytes(bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE]) -> Self {
-        unsafe { mem::transmute(bytes) }
+        let mut decoder: WireDecoder = WireDecoder::new(&bytes);
+        Self {
+            ret: decoder.get(),
+            _padding: [0; Self::PADDING_SIZE],
+        }
     }
 
     fn into_bytes(self) -> [u8; LinuxDaemonMessage::PAYLOAD_SIZE] {
-        unsafe { mem::transmute(self) }
+        let mut bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE] =
+            [0; LinuxDaemonMessage::PAYLOAD_SIZE];
+        let mut encoder: WireEncoder = WireEncoder::new(&mut bytes);
+        encoder.put(self.ret);
+        bytes
     }
 
     pub fn build(pid: ProcessIdentifier, ret: i32) -> Message {
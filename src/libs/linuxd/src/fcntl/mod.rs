@@ -11,7 +11,11 @@
 // Imports
 //==================================================================================================
 
-use crate::sys::types::mode_t;
+use crate::sys::types::{
+    mode_t,
+    off_t,
+    pid_t,
+};
 use core::ffi;
 
 //==================================================================================================
@@ -24,9 +28,11 @@ cfg_if::cfg_if! {
     if #[cfg(feature = "syscall")] {
         mod syscall;
         pub use self::syscall::{
+            fcntl,
             openat,
             unlinkat,
             renameat,
+            renameat2,
             posix_fallocate,
             posix_fadvise,
             symlinkat,
@@ -35,6 +41,32 @@ cfg_if::cfg_if! {
     }
 }
 
+//==================================================================================================
+// Structures
+//==================================================================================================
+
+///
+/// # Description
+///
+/// This structure describes an advisory record lock.
+///
+#[derive(Debug, Default, Clone, Copy)]
+#[repr(C)]
+pub struct flock {
+    /// Type of lock (`F_RDLCK`, `F_WRLCK` or `F_UNLCK`).
+    pub l_type: i16,
+    /// Flag for starting offset (`SEEK_SET`, `SEEK_CUR` or `SEEK_END`).
+    pub l_whence: i16,
+    /// Relative offset in bytes.
+    pub l_start: off_t,
+    /// Size of the locked region. If `0`, the lock extends until end-of-file.
+    pub l_len: off_t,
+    /// Process holding the lock. Only set by `F_GETLK`.
+    pub l_pid: pid_t,
+}
+
+//==================================================================================================
+// Constants
 //==================================================================================================
 
 pub const O_APPEND: ffi::c_int = 1 << 0;
@@ -73,3 +105,22 @@ pub const POSIX_FADV_WILLNEED: i32 = 3;
 pub const POSIX_FADV_DONTNEED: i32 = 4;
 /// The specified data will be accessed once and then will not be used again.
 pub const POSIX_FADV_NOREUSE: i32 = 5;
+
+/// Get record locking information.
+pub const F_GETLK: i32 = 5;
+/// Set or clear a record lock, without blocking.
+pub const F_SETLK: i32 = 6;
+/// Set or clear a record lock, waiting if blocked.
+pub const F_SETLKW: i32 = 7;
+
+/// Shared or read lock.
+pub const F_RDLCK: i16 = 0;
+/// Exclusive or write lock.
+pub const F_WRLCK: i16 = 1;
+/// Unlock.
+pub const F_UNLCK: i16 = 2;
+
+/// Do not overwrite the new path if it already exists.
+pub const RENAME_NOREPLACE: u32 = 1 << 0;
+/// Atomically exchange the old path and the new path.
+pub const RENAME_EXCHANGE: u32 = 1 << 1;
@@ -0,0 +1,102 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::{
+    fcntl::{
+        self,
+        flock,
+        message::{
+            RecordLockRequest,
+            RecordLockResponse,
+        },
+    },
+    LinuxDaemonMessage,
+    LinuxDaemonMessageHeader,
+};
+use ::nvx::{
+    ipc::Message,
+    pm::ProcessIdentifier,
+    sys::error::ErrorCode,
+};
+
+//==================================================================================================
+// Standalone Functions
+//==================================================================================================
+
+///
+/// # Description
+///
+/// The `fcntl()` system call performs record locking operations on an open file.
+///
+/// # Parameters
+///
+/// - `fd`: File descriptor of the file.
+/// - `cmd`: Command (`F_GETLK`, `F_SETLK` or `F_SETLKW`).
+/// - `lock`: Lock description. Updated with the conflicting lock when `cmd` is `F_GETLK`.
+///
+/// # Returns
+///
+/// Upon successful completion, `0` is returned. Upon failure, a negative error code is returned
+/// instead.
+///
+pub fn fcntl(fd: i32, cmd: i32, lock: &mut flock) -> i32 {
+    // Check if command is supported.
+    if cmd != fcntl::F_GETLK && cmd != fcntl::F_SETLK && cmd != fcntl::F_SETLKW {
+        return ErrorCode::InvalidArgument.into_errno();
+    }
+
+    let pid: ProcessIdentifier = match ::nvx::pm::getpid() {
+        Ok(pid) => pid,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    // Build request and send it.
+    let request: Message = RecordLockRequest::build(pid, fd, cmd, lock);
+    if let Err(e) = ::nvx::ipc::send(&request) {
+        return e.code.into_errno();
+    }
+
+    // Receive response. This may block until the lock is granted.
+    let response: Message = match ::nvx::ipc::recv() {
+        Ok(response) => response,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    // Check whether system call succeeded or not.
+    if response.status != 0 {
+        // System call failed, parse error code and return it.
+        match ErrorCode::try_from(response.status) {
+            Ok(e) => e.into_errno(),
+            Err(_) => ErrorCode::InvalidMessage.into_errno(),
+        }
+    } else {
+        // System call succeeded, parse response.
+        match LinuxDaemonMessage::try_from_bytes(response.payload) {
+            // Response was successfully parsed.
+            Ok(message) => match message.header {
+                // Response was successfully parsed.
+                LinuxDaemonMessageHeader::RecordLockResponse => {
+                    // Parse response.
+                    let response: RecordLockResponse =
+                        RecordLockResponse::from_bytes(message.payload);
+
+                    // Report conflicting lock, if requested.
+                    if cmd == fcntl::F_GETLK {
+                        *lock = response.lock();
+                    }
+
+                    // Return result.
+                    response.ret
+                },
+                // Response was not successfully parsed.
+                _ => ErrorCode::InvalidMessage.into_errno(),
+            },
+            // Response was not successfully parsed.
+            Err(_) => ErrorCode::InvalidMessage.into_errno(),
+        }
+    }
+}
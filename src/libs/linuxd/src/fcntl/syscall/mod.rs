@@ -7,9 +7,11 @@
 
 mod fadvise;
 mod fallocate;
+mod fcntl;
 mod openat;
 mod readlinkat;
 mod renameat;
+mod renameat2;
 mod symlinkat;
 mod unlinkat;
 
@@ -20,9 +22,11 @@ mod unlinkat;
 pub use self::{
     fadvise::posix_fadvise,
     fallocate::posix_fallocate,
+    fcntl::fcntl,
     openat::openat,
     readlinkat::readlinkat,
     renameat::renameat,
+    renameat2::renameat2,
     symlinkat::symlinkat,
     unlinkat::unlinkat,
 };
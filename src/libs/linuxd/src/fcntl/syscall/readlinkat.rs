@@ -13,6 +13,7 @@ use crate::{
     message::{
         LinuxDaemonLongMessage,
         LinuxDaemonMessagePart,
+        MessageDeserializer,
         MessagePartitioner,
     },
     LinuxDaemonMessage,
@@ -71,8 +72,7 @@ fn readlinkat_request(dirfd: i32, path: &str, buf: &[u8]) -> i32 {
 }
 
 fn readlinkat_response(buf: &mut [u8]) -> i32 {
-    let capacity: usize =
-        ReadLinkAtResponse::MAX_SIZE.div_ceil(LinuxDaemonMessagePart::PAYLOAD_SIZE);
+    let capacity: usize = LinuxDaemonLongMessage::capacity(ReadLinkAtResponse::MAX_SIZE);
 
     let mut assembler: LinuxDaemonLongMessage = match LinuxDaemonLongMessage::new(capacity) {
         Ok(assembler) => assembler,
@@ -98,7 +98,10 @@ fn readlinkat_response(buf: &mut [u8]) -> i32 {
                 Ok(message) => match message.header {
                     LinuxDaemonMessageHeader::ReadLinkAtResponsePart => {
                         let part: LinuxDaemonMessagePart =
-                            LinuxDaemonMessagePart::from_bytes(message.payload);
+                            match LinuxDaemonMessagePart::try_from_bytes(message.payload) {
+                                Ok(part) => part,
+                                Err(e) => break e.code.into_errno(),
+                            };
 
                         if let Err(e) = assembler.add_part(part) {
                             break e.code.into_errno();
@@ -0,0 +1,141 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::{
+    fcntl::message::{
+        RenameAt2Request,
+        RenameAt2Response,
+    },
+    message::MessagePartitioner,
+    LinuxDaemonMessage,
+    LinuxDaemonMessageHeader,
+};
+use ::alloc::{
+    string::ToString,
+    vec::Vec,
+};
+use ::nvx::{
+    ipc::Message,
+    pm::ProcessIdentifier,
+    sys::error::ErrorCode,
+};
+
+//==================================================================================================
+// Standalone Functions
+//==================================================================================================
+
+///
+/// # Description
+///
+/// The `renameat2()` system call renames a file. Unlike `renameat()`, it accepts flags that
+/// control how an existing target is handled.
+///
+/// # Parameters
+///
+/// - `olddirfd`: Directory file descriptor of the old path.
+/// - `oldpath`: Old path.
+/// - `newdirfd`: Directory file descriptor of the new path.
+/// - `newpath`: New path.
+/// - `flags`: Flags (`RENAME_NOREPLACE` or `RENAME_EXCHANGE`).
+///
+/// # Returns
+///
+/// Upon successful completion, `0` is returned. Upon failure, a negative error code is returned
+/// instead.
+///
+pub fn renameat2(olddirfd: i32, oldpath: &str, newdirfd: i32, newpath: &str, flags: u32) -> i32 {
+    // Send request.
+    let status: i32 = renameat2_request(olddirfd, oldpath, newdirfd, newpath, flags);
+    if status != 0 {
+        return status;
+    }
+
+    // Wait for response.
+    renameat2_response()
+}
+
+fn renameat2_request(
+    olddirfd: i32,
+    oldpath: &str,
+    newdirfd: i32,
+    newpath: &str,
+    flags: u32,
+) -> i32 {
+    let pid: ProcessIdentifier = match ::nvx::pm::getpid() {
+        Ok(pid) => pid,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    let request: RenameAt2Request = match RenameAt2Request::new(
+        olddirfd,
+        oldpath.to_string(),
+        newdirfd,
+        newpath.to_string(),
+        flags,
+    ) {
+        Ok(request) => request,
+        Err(e) => {
+            ::nvx::log!("failed to create message: {:?}", e);
+            return e.code.into_errno();
+        },
+    };
+
+    let requests: Vec<Message> = match request.into_parts(pid) {
+        Ok(requests) => requests,
+        Err(e) => {
+            ::nvx::log!("failed to partition message: {:?}", e);
+            return e.code.into_errno();
+        },
+    };
+
+    // Send request.
+    for request in requests {
+        match ::nvx::ipc::send(&request) {
+            Ok(_) => (),
+            Err(e) => return e.code.into_errno(),
+        }
+    }
+
+    0
+}
+
+fn renameat2_response() -> i32 {
+    // Receive response.
+    let response: Message = match ::nvx::ipc::recv() {
+        Ok(response) => response,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    // Check whether system call succeeded or not.
+    if response.status != 0 {
+        // System call failed, parse error code and return it.
+        match ErrorCode::try_from(response.status) {
+            Ok(e) => e.into_errno(),
+            Err(_) => ErrorCode::InvalidMessage.into_errno(),
+        }
+    } else {
+        // System call succeeded, parse response.
+        match LinuxDaemonMessage::try_from_bytes(response.payload) {
+            // Response was successfully parsed.
+            Ok(message) => match message.header {
+                // Response was successfully parsed.
+                LinuxDaemonMessageHeader::RenameAt2Response => {
+                    // Parse response.
+                    let response: RenameAt2Response =
+                        RenameAt2Response::from_bytes(message.payload);
+
+                    // Return result.
+                    response.ret
+                },
+                // Response was not successfully parsed.
+                _ => ErrorCode::InvalidMessage.into_errno(),
+            },
+            // Response was not successfully parsed.
+            Err(_) => ErrorCode::InvalidMessage.into_errno(),
+        }
+    }
+}
@@ -0,0 +1,236 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use ::hmac::{
+    Hmac,
+    Mac,
+};
+use ::nvx::sys::error::{
+    Error,
+    ErrorCode,
+};
+use ::sha2::Sha256;
+
+//==================================================================================================
+// Constants
+//==================================================================================================
+
+/// Magic number that opens a challenge.
+pub const CHALLENGE_MAGIC: [u8; 8] = *b"LINUXD\x00\x01";
+
+/// Size of a challenge nonce (in bytes).
+pub const NONCE_SIZE: usize = 32;
+
+/// Size of a challenge response (in bytes).
+pub const MAC_SIZE: usize = 32;
+
+/// Verdict sent once a connection is authenticated.
+pub const HANDSHAKE_ACCEPTED: u8 = 1;
+
+/// Verdict sent before a connection is closed for failing to authenticate.
+pub const HANDSHAKE_REFUSED: u8 = 0;
+
+//==================================================================================================
+// Traits
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Transport over which a client answers the challenge of the Linux Daemon, so that the same
+/// handshake runs on top of the socket of the host and on top of the one of the microVM.
+///
+pub trait HandshakeStream {
+    ///
+    /// # Description
+    ///
+    /// Reads exactly enough bytes to fill a buffer.
+    ///
+    /// # Parameters
+    ///
+    /// - `buf`: Buffer to fill.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, empty is returned. Upon failure, an error is returned instead.
+    ///
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), Error>;
+
+    ///
+    /// # Description
+    ///
+    /// Writes all bytes of a buffer.
+    ///
+    /// # Parameters
+    ///
+    /// - `buf`: Buffer to write.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, empty is returned. Upon failure, an error is returned instead.
+    ///
+    fn write_all(&mut self, buf: &[u8]) -> Result<(), Error>;
+}
+
+//==================================================================================================
+// Structures
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Challenge that the Linux Daemon sends when a connection is established.
+///
+/// The handshake goes as follows, before any other message is exchanged:
+///
+/// 1. The daemon sends a [`Challenge`] with a fresh random nonce.
+/// 2. The client answers with a [`ChallengeResponse`], which carries the HMAC-SHA256 of the
+///    challenge under a key that both sides share.
+/// 3. The daemon sends a single byte with its verdict, either [`HANDSHAKE_ACCEPTED`] or
+///    [`HANDSHAKE_REFUSED`]. In the latter case, the connection is closed.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Challenge {
+    /// Random nonce.
+    pub nonce: [u8; NONCE_SIZE],
+}
+
+///
+/// # Description
+///
+/// Response to a challenge.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChallengeResponse {
+    /// HMAC-SHA256 of the challenge.
+    pub mac: [u8; MAC_SIZE],
+}
+
+//==================================================================================================
+// Implementations
+//==================================================================================================
+
+impl Challenge {
+    /// Size of a challenge on the wire (in bytes).
+    pub const SIZE: usize = CHALLENGE_MAGIC.len() + NONCE_SIZE;
+
+    pub fn new(nonce: [u8; NONCE_SIZE]) -> Self {
+        Self { nonce }
+    }
+
+    pub fn from_bytes(bytes: [u8; Self::SIZE]) -> Result<Self, Error> {
+        let (magic, nonce): (&[u8], &[u8]) = bytes.split_at(CHALLENGE_MAGIC.len());
+        if magic != CHALLENGE_MAGIC {
+            return Err(Error::new(ErrorCode::InvalidMessage, "invalid challenge magic"));
+        }
+
+        let mut challenge: Self = Self::new([0; NONCE_SIZE]);
+        challenge.nonce.copy_from_slice(nonce);
+        Ok(challenge)
+    }
+
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut bytes: [u8; Self::SIZE] = [0; Self::SIZE];
+        bytes[..CHALLENGE_MAGIC.len()].copy_from_slice(&CHALLENGE_MAGIC);
+        bytes[CHALLENGE_MAGIC.len()..].copy_from_slice(&self.nonce);
+        bytes
+    }
+
+    fn mac(&self, key: &[u8]) -> Hmac<Sha256> {
+        let mut mac: Hmac<Sha256> =
+            Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any size");
+        mac.update(&self.to_bytes());
+        mac
+    }
+}
+
+impl ChallengeResponse {
+    /// Size of a challenge response on the wire (in bytes).
+    pub const SIZE: usize = MAC_SIZE;
+
+    ///
+    /// # Description
+    ///
+    /// Answers a challenge.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: Shared key.
+    /// - `challenge`: Challenge to answer.
+    ///
+    /// # Returns
+    ///
+    /// The response to the challenge.
+    ///
+    pub fn new(key: &[u8], challenge: &Challenge) -> Self {
+        Self {
+            mac: challenge.mac(key).finalize().into_bytes().into(),
+        }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Checks whether this is the right response to a challenge, in constant time.
+    ///
+    /// # Parameters
+    ///
+    /// - `key`: Shared key.
+    /// - `challenge`: Challenge that was sent.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the response is right, `false` otherwise.
+    ///
+    pub fn verify(&self, key: &[u8], challenge: &Challenge) -> bool {
+        challenge.mac(key).verify_slice(&self.mac).is_ok()
+    }
+
+    pub fn from_bytes(bytes: [u8; Self::SIZE]) -> Self {
+        Self { mac: bytes }
+    }
+
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        self.mac
+    }
+}
+
+//==================================================================================================
+// Standalone Functions
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Answers the challenge of the Linux Daemon, on the client side of a connection that was just
+/// established.
+///
+/// # Parameters
+///
+/// - `stream`: Connection to the daemon.
+/// - `key`: Key shared with the daemon.
+///
+/// # Returns
+///
+/// Upon success, empty is returned. Upon failure, an error is returned instead, either because the
+/// connection broke, the challenge is malformed, or the daemon refused the response.
+///
+pub fn respond<S: HandshakeStream>(stream: &mut S, key: &[u8]) -> Result<(), Error> {
+    let mut bytes: [u8; Challenge::SIZE] = [0; Challenge::SIZE];
+    stream.read_exact(&mut bytes)?;
+    let challenge: Challenge = Challenge::from_bytes(bytes)?;
+
+    stream.write_all(&ChallengeResponse::new(key, &challenge).to_bytes())?;
+
+    let mut verdict: [u8; 1] = [0; 1];
+    stream.read_exact(&mut verdict)?;
+    match verdict[0] {
+        HANDSHAKE_ACCEPTED => Ok(()),
+        HANDSHAKE_REFUSED => Err(Error::new(ErrorCode::InvalidArgument, "authentication refused")),
+        _ => Err(Error::new(ErrorCode::InvalidMessage, "invalid handshake verdict")),
+    }
+}
@@ -14,6 +14,9 @@
 
 extern crate alloc;
 
+// Code generated by the derive macros refers to this crate by name.
+extern crate self as linuxd;
+
 /// Time types.
 pub mod time;
 
@@ -29,9 +32,24 @@ pub mod limits;
 /// Messages.
 pub mod message;
 
+/// Wire format of messages.
+pub mod wire;
+
+/// Connection handshake.
+pub mod handshake;
+
+/// Protocol version and capability negotiation.
+pub mod protocol;
+
+/// Capture of the messages exchanged on a connection.
+pub mod capture;
+
 /// Standard symbolic constants and types.
 pub mod unistd;
 
+/// Standard library definitions.
+pub mod stdlib;
+
 /// System-specific headers.
 pub mod sys;
 
@@ -39,6 +57,10 @@ pub mod sys;
 // Imports
 //==================================================================================================
 
+use crate::wire::{
+    WireDecoder,
+    WireEncoder,
+};
 use ::core::{
     convert::TryFrom,
     mem,
@@ -57,55 +79,95 @@ use ::nvx::{
 // Structures
 //==================================================================================================
 
-#[derive(Debug, PartialEq, Eq, TryFromPrimitive)]
+///
+/// # Description
+///
+/// Type of a message exchanged with the Linux Daemon.
+///
+/// Discriminants are part of the wire protocol, so they must never change. New variants take the
+/// next free discriminant, and are announced through [`protocol::Capabilities`] so that peers
+/// built from different revisions can tell which messages the other side understands.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, TryFromPrimitive)]
 #[repr(u16)]
 pub enum LinuxDaemonMessageHeader {
-    GetClockResolutionRequest,
-    GetClockResolutionResponse,
-    GetClockTimeRequest,
-    GetClockTimeResponse,
-    JoinEnvRequest,
-    JoinEnvResponse,
-    LeaveEnvRequest,
-    LeaveEnvResponse,
-    OpenAtRequest,
-    OpenAtResponse,
-    UnlinkAtRequest,
-    UnlinkAtResponse,
-    CloseRequest,
-    CloseResponse,
-    RenameAtRequest,
-    RenameAtResponse,
-    FileStatAtRequestPart,
-    FileStatAtResponsePart,
-    FileDataSyncRequest,
-    FileDataSyncResponse,
-    FileSyncRequest,
-    FileSyncResponse,
-    SeekRequest,
-    SeekResponse,
-    FileSpaceControlRequest,
-    FileSpaceControlResponse,
-    FileTruncateRequest,
-    FileTruncateResponse,
-    FileAdvisoryInformationRequest,
-    FileAdvisoryInformationResponse,
-    FileStatRequest,
-    FileStatResponse,
-    WriteRequest,
-    WriteResponse,
-    ReadRequest,
-    ReadResponse,
-    PartialWriteRequest,
-    PartialWriteResponse,
-    PartialReadRequest,
-    PartialReadResponse,
-    SymbolicLinkAtRequestPart,
-    SymbolicLinkAtResponse,
-    LinkAtRequestPart,
-    LinkAtResponse,
-    ReadLinkAtRequestPart,
-    ReadLinkAtResponsePart,
+    GetClockResolutionRequest = 0,
+    GetClockResolutionResponse = 1,
+    GetClockTimeRequest = 2,
+    GetClockTimeResponse = 3,
+    JoinEnvRequest = 4,
+    JoinEnvResponse = 5,
+    LeaveEnvRequest = 6,
+    LeaveEnvResponse = 7,
+    OpenAtRequest = 8,
+    OpenAtResponse = 9,
+    UnlinkAtRequest = 10,
+    UnlinkAtResponse = 11,
+    CloseRequest = 12,
+    CloseResponse = 13,
+    RenameAtRequest = 14,
+    RenameAtResponse = 15,
+    FileStatAtRequestPart = 16,
+    FileStatAtResponsePart = 17,
+    FileDataSyncRequest = 18,
+    FileDataSyncResponse = 19,
+    FileSyncRequest = 20,
+    FileSyncResponse = 21,
+    SeekRequest = 22,
+    SeekResponse = 23,
+    FileSpaceControlRequest = 24,
+    FileSpaceControlResponse = 25,
+    FileTruncateRequest = 26,
+    FileTruncateResponse = 27,
+    FileAdvisoryInformationRequest = 28,
+    FileAdvisoryInformationResponse = 29,
+    FileStatRequest = 30,
+    FileStatResponse = 31,
+    WriteRequest = 32,
+    WriteResponse = 33,
+    ReadRequest = 34,
+    ReadResponse = 35,
+    PartialWriteRequest = 36,
+    PartialWriteResponse = 37,
+    PartialReadRequest = 38,
+    PartialReadResponse = 39,
+    SymbolicLinkAtRequestPart = 40,
+    SymbolicLinkAtResponse = 41,
+    LinkAtRequestPart = 42,
+    LinkAtResponse = 43,
+    ReadLinkAtRequestPart = 44,
+    ReadLinkAtResponsePart = 45,
+    FileLockRequest = 46,
+    FileLockResponse = 47,
+    RecordLockRequest = 48,
+    RecordLockResponse = 49,
+    CopyFileRangeRequest = 50,
+    CopyFileRangeResponse = 51,
+    SendFileRequest = 52,
+    SendFileResponse = 53,
+    GetXattrRequestPart = 54,
+    GetXattrResponsePart = 55,
+    SetXattrRequestPart = 56,
+    SetXattrResponse = 57,
+    ListXattrRequestPart = 58,
+    ListXattrResponsePart = 59,
+    RemoveXattrRequestPart = 60,
+    RemoveXattrResponse = 61,
+    TruncateRequestPart = 62,
+    TruncateResponse = 63,
+    RenameAt2RequestPart = 64,
+    RenameAt2Response = 65,
+    RealPathRequestPart = 66,
+    RealPathResponsePart = 67,
+    InotifyAddWatchRequestPart = 68,
+    InotifyAddWatchResponse = 69,
+    InotifyRemoveWatchRequest = 70,
+    InotifyRemoveWatchResponse = 71,
+    InotifyEventPart = 72,
+    GetResourceLimitRequest = 73,
+    GetResourceLimitResponse = 74,
+    GetResourceUsageRequest = 75,
+    GetResourceUsageResponse = 76,
 }
 
 #[repr(C, packed)]
@@ -132,6 +194,103 @@ pub const LINUXD: ProcessIdentifier = ProcessIdentifier::KERNEL;
 // Implementations
 //==================================================================================================
 
+impl LinuxDaemonMessageHeader {
+    ///
+    /// # Description
+    ///
+    /// Returns the name of the operation that a message belongs to.
+    ///
+    /// # Returns
+    ///
+    /// The name of the operation, or `None` if the message does not belong to any operation.
+    ///
+    pub fn operation(self) -> Option<&'static str> {
+        match self {
+            Self::GetClockResolutionRequest | Self::GetClockResolutionResponse => {
+                Some("clock_getres")
+            },
+            Self::GetClockTimeRequest | Self::GetClockTimeResponse => Some("clock_gettime"),
+            Self::JoinEnvRequest | Self::JoinEnvResponse => Some("joinenv"),
+            Self::LeaveEnvRequest | Self::LeaveEnvResponse => Some("leaveenv"),
+            Self::OpenAtRequest | Self::OpenAtResponse => Some("openat"),
+            Self::UnlinkAtRequest | Self::UnlinkAtResponse => Some("unlinkat"),
+            Self::CloseRequest | Self::CloseResponse => Some("close"),
+            Self::RenameAtRequest | Self::RenameAtResponse => Some("renameat"),
+            Self::FileStatAtRequestPart | Self::FileStatAtResponsePart => Some("fstatat"),
+            Self::FileDataSyncRequest | Self::FileDataSyncResponse => Some("fdatasync"),
+            Self::FileSyncRequest | Self::FileSyncResponse => Some("fsync"),
+            Self::SeekRequest | Self::SeekResponse => Some("lseek"),
+            Self::FileSpaceControlRequest | Self::FileSpaceControlResponse => Some("fallocate"),
+            Self::FileTruncateRequest | Self::FileTruncateResponse => Some("ftruncate"),
+            Self::FileAdvisoryInformationRequest | Self::FileAdvisoryInformationResponse => {
+                Some("posix_fadvise")
+            },
+            Self::FileStatRequest | Self::FileStatResponse => Some("fstat"),
+            Self::WriteRequest | Self::WriteResponse => Some("write"),
+            Self::ReadRequest | Self::ReadResponse => Some("read"),
+            Self::PartialWriteRequest | Self::PartialWriteResponse => Some("pwrite"),
+            Self::PartialReadRequest | Self::PartialReadResponse => Some("pread"),
+            Self::SymbolicLinkAtRequestPart | Self::SymbolicLinkAtResponse => Some("symlinkat"),
+            Self::LinkAtRequestPart | Self::LinkAtResponse => Some("linkat"),
+            Self::ReadLinkAtRequestPart | Self::ReadLinkAtResponsePart => Some("readlinkat"),
+            Self::FileLockRequest | Self::FileLockResponse => Some("flock"),
+            Self::RecordLockRequest | Self::RecordLockResponse => Some("fcntl"),
+            Self::CopyFileRangeRequest | Self::CopyFileRangeResponse => Some("copy_file_range"),
+            Self::SendFileRequest | Self::SendFileResponse => Some("sendfile"),
+            Self::GetXattrRequestPart | Self::GetXattrResponsePart => Some("getxattr"),
+            Self::SetXattrRequestPart | Self::SetXattrResponse => Some("setxattr"),
+            Self::ListXattrRequestPart | Self::ListXattrResponsePart => Some("listxattr"),
+            Self::RemoveXattrRequestPart | Self::RemoveXattrResponse => Some("removexattr"),
+            Self::TruncateRequestPart | Self::TruncateResponse => Some("truncate"),
+            Self::RenameAt2RequestPart | Self::RenameAt2Response => Some("renameat2"),
+            Self::RealPathRequestPart | Self::RealPathResponsePart => Some("realpath"),
+            Self::InotifyAddWatchRequestPart | Self::InotifyAddWatchResponse => {
+                Some("inotify_add_watch")
+            },
+            Self::InotifyRemoveWatchRequest | Self::InotifyRemoveWatchResponse => {
+                Some("inotify_rm_watch")
+            },
+            Self::GetResourceLimitRequest | Self::GetResourceLimitResponse => Some("getrlimit"),
+            Self::GetResourceUsageRequest | Self::GetResourceUsageResponse => Some("getrusage"),
+            // Events are pushed by the Linux Daemon, not requested.
+            Self::InotifyEventPart => None,
+        }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Checks whether a message is a part of a message that is split into multiple parts.
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if the message is a part. Otherwise, it returns `false`.
+    ///
+    pub fn is_part(self) -> bool {
+        matches!(
+            self,
+            Self::FileStatAtRequestPart
+                | Self::FileStatAtResponsePart
+                | Self::SymbolicLinkAtRequestPart
+                | Self::LinkAtRequestPart
+                | Self::ReadLinkAtRequestPart
+                | Self::ReadLinkAtResponsePart
+                | Self::GetXattrRequestPart
+                | Self::GetXattrResponsePart
+                | Self::SetXattrRequestPart
+                | Self::ListXattrRequestPart
+                | Self::ListXattrResponsePart
+                | Self::RemoveXattrRequestPart
+                | Self::TruncateRequestPart
+                | Self::RenameAt2RequestPart
+                | Self::RealPathRequestPart
+                | Self::RealPathResponsePart
+                | Self::InotifyAddWatchRequestPart
+                | Self::InotifyEventPart
+        )
+    }
+}
+
 impl LinuxDaemonMessage {
     pub const PAYLOAD_SIZE: usize =
         Message::PAYLOAD_SIZE - mem::size_of::<LinuxDaemonMessageHeader>();
@@ -141,17 +300,24 @@ impl LinuxDaemonMessage {
     }
 
     pub fn try_from_bytes(bytes: [u8; Message::PAYLOAD_SIZE]) -> Result<Self, Error> {
+        let mut decoder: WireDecoder = WireDecoder::new(&bytes);
+
         // Check if message header is valid.
-        let _header: LinuxDaemonMessageHeader =
-            LinuxDaemonMessageHeader::try_from(u16::from_ne_bytes([bytes[0], bytes[1]]))
+        let header: LinuxDaemonMessageHeader =
+            LinuxDaemonMessageHeader::try_from(decoder.get::<u16>())
                 .map_err(|_| Error::new(ErrorCode::InvalidMessage, "invalid message header"))?;
 
-        let message: LinuxDaemonMessage = unsafe { mem::transmute(bytes) };
-
-        Ok(message)
+        Ok(Self {
+            header,
+            payload: decoder.get(),
+        })
     }
 
     pub fn into_bytes(self) -> [u8; Message::PAYLOAD_SIZE] {
-        unsafe { mem::transmute(self) }
+        let mut bytes: [u8; Message::PAYLOAD_SIZE] = [0; Message::PAYLOAD_SIZE];
+        let mut encoder: WireEncoder = WireEncoder::new(&mut bytes);
+        encoder.put(self.header as u16);
+        encoder.put(self.payload);
+        bytes
     }
 }
@@ -14,3 +14,12 @@ pub const PATH_MAX: usize = 256;
 
 /// Maximum value for an object of type [`crate::sys::types::ssize_t`].
 pub const SSIZE_MAX: crate::sys::types::ssize_t = crate::sys::types::ssize_t::MAX;
+
+/// Maximum number of bytes in the name of an extended attribute.
+pub const XATTR_NAME_MAX: usize = 255;
+
+/// Maximum number of bytes in the value of an extended attribute.
+pub const XATTR_SIZE_MAX: usize = 4096;
+
+/// Maximum number of bytes in the list of extended attribute names of a file.
+pub const XATTR_LIST_MAX: usize = 4096;
@@ -0,0 +1,198 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::wire::{
+    WireDecoder,
+    WireEncoder,
+    WireFormat,
+};
+use ::alloc::{
+    string::String,
+    vec::Vec,
+};
+use ::nvx::sys::error::{
+    Error,
+    ErrorCode,
+};
+
+//==================================================================================================
+// Traits
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Field of a variable-length message. A field has a fixed-size head, which holds either its
+/// value or the length of its variable-length tail. Messages lay out the heads of all their fields
+/// first and then the tails of all their fields, so that every head sits at a fixed offset.
+///
+pub trait MessageField
+where
+    Self: Sized,
+{
+    /// Value encoded in the head of the field.
+    type Head;
+
+    /// Size of the head of the field (in bytes).
+    const HEAD_SIZE: usize;
+
+    /// Indicates whether the field has a variable-length tail.
+    const IS_VARIABLE: bool;
+
+    ///
+    /// # Description
+    ///
+    /// Returns the size of the tail of the field (in bytes).
+    ///
+    fn tail_size(&self) -> usize;
+
+    ///
+    /// # Description
+    ///
+    /// Encodes the head of the field.
+    ///
+    /// # Parameters
+    ///
+    /// - `encoder`: Target encoder.
+    ///
+    fn encode_head(&self, encoder: &mut WireEncoder);
+
+    ///
+    /// # Description
+    ///
+    /// Encodes the tail of the field.
+    ///
+    /// # Parameters
+    ///
+    /// - `encoder`: Target encoder.
+    ///
+    fn encode_tail(&self, encoder: &mut WireEncoder);
+
+    ///
+    /// # Description
+    ///
+    /// Decodes the head of the field. The caller ensures that the head is in bounds.
+    ///
+    /// # Parameters
+    ///
+    /// - `decoder`: Source decoder.
+    ///
+    /// # Returns
+    ///
+    /// The head of the field.
+    ///
+    fn decode_head(decoder: &mut WireDecoder) -> Self::Head;
+
+    ///
+    /// # Description
+    ///
+    /// Decodes the tail of the field.
+    ///
+    /// # Parameters
+    ///
+    /// - `head`: Head of the field.
+    /// - `decoder`: Source decoder.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the field is returned. Upon failure, an error is returned instead.
+    ///
+    fn decode_tail(head: Self::Head, decoder: &mut WireDecoder) -> Result<Self, Error>;
+}
+
+//==================================================================================================
+// Implementations
+//==================================================================================================
+
+impl<T: WireFormat + Copy> MessageField for T {
+    type Head = Self;
+
+    const HEAD_SIZE: usize = T::WIRE_SIZE;
+
+    const IS_VARIABLE: bool = false;
+
+    fn tail_size(&self) -> usize {
+        0
+    }
+
+    fn encode_head(&self, encoder: &mut WireEncoder) {
+        encoder.put(*self);
+    }
+
+    fn encode_tail(&self, _encoder: &mut WireEncoder) {}
+
+    fn decode_head(decoder: &mut WireDecoder) -> Self::Head {
+        decoder.get()
+    }
+
+    fn decode_tail(head: Self::Head, _decoder: &mut WireDecoder) -> Result<Self, Error> {
+        Ok(head)
+    }
+}
+
+impl MessageField for Vec<u8> {
+    type Head = u32;
+
+    const HEAD_SIZE: usize = u32::WIRE_SIZE;
+
+    const IS_VARIABLE: bool = true;
+
+    fn tail_size(&self) -> usize {
+        self.len()
+    }
+
+    fn encode_head(&self, encoder: &mut WireEncoder) {
+        encoder.put(self.len() as u32);
+    }
+
+    fn encode_tail(&self, encoder: &mut WireEncoder) {
+        encoder.put_bytes(self);
+    }
+
+    fn decode_head(decoder: &mut WireDecoder) -> Self::Head {
+        decoder.get()
+    }
+
+    fn decode_tail(head: Self::Head, decoder: &mut WireDecoder) -> Result<Self, Error> {
+        // Check if the message is too short.
+        if head as usize > decoder.remaining() {
+            return Err(Error::new(ErrorCode::InvalidMessage, "message too short"));
+        }
+
+        Ok(decoder.get_bytes(head as usize).to_vec())
+    }
+}
+
+impl MessageField for String {
+    type Head = u32;
+
+    const HEAD_SIZE: usize = u32::WIRE_SIZE;
+
+    const IS_VARIABLE: bool = true;
+
+    fn tail_size(&self) -> usize {
+        self.len()
+    }
+
+    fn encode_head(&self, encoder: &mut WireEncoder) {
+        encoder.put(self.len() as u32);
+    }
+
+    fn encode_tail(&self, encoder: &mut WireEncoder) {
+        encoder.put_bytes(self.as_bytes());
+    }
+
+    fn decode_head(decoder: &mut WireDecoder) -> Self::Head {
+        decoder.get()
+    }
+
+    fn decode_tail(head: Self::Head, decoder: &mut WireDecoder) -> Result<Self, Error> {
+        let bytes: Vec<u8> = Vec::<u8>::decode_tail(head, decoder)?;
+        String::from_utf8(bytes)
+            .map_err(|_| Error::new(ErrorCode::InvalidMessage, "invalid string"))
+    }
+}
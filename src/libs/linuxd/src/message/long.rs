@@ -2,8 +2,15 @@
 // Imports
 //==================================================================================================
 
-use crate::message::LinuxDaemonMessagePart;
+use crate::{
+    message::LinuxDaemonMessagePart,
+    wire::{
+        WireDecoder,
+        WireEncoder,
+    },
+};
 use ::alloc::vec::Vec;
+use ::core::mem;
 use ::nvx::sys::error::{
     Error,
     ErrorCode,
@@ -18,6 +25,9 @@ use ::nvx::sys::error::{
 ///
 /// This structure represents a long message that is split into multiple parts.
 ///
+/// The payload of the first part opens with a prologue that carries the total length and the
+/// checksum of the message. All parts of a message carry the same request identifier.
+///
 pub struct LinuxDaemonLongMessage {
     /// Indicates if the message contains all its parts.
     is_complete: bool,
@@ -32,6 +42,9 @@ pub struct LinuxDaemonLongMessage {
 //==================================================================================================
 
 impl LinuxDaemonLongMessage {
+    /// Size of the prologue that opens the first part of a message (in bytes).
+    pub const PROLOGUE_SIZE: usize = 2 * mem::size_of::<u32>();
+
     ///
     /// # Description
     ///
@@ -58,6 +71,25 @@ impl LinuxDaemonLongMessage {
         })
     }
 
+    ///
+    /// # Description
+    ///
+    /// Computes the number of parts that a message of a given size is split into.
+    ///
+    /// # Parameters
+    ///
+    /// - `size`: Size of the message (in bytes).
+    ///
+    /// # Returns
+    ///
+    /// The number of parts.
+    ///
+    pub const fn capacity(size: usize) -> usize {
+        Self::PROLOGUE_SIZE
+            .saturating_add(size)
+            .div_ceil(LinuxDaemonMessagePart::PAYLOAD_SIZE)
+    }
+
     ///
     /// # Description
     ///
@@ -82,6 +114,19 @@ impl LinuxDaemonLongMessage {
             return Err(Error::new(ErrorCode::InvalidMessage, "message is already complete"));
         }
 
+        match self.parts.first() {
+            Some(first) => {
+                // Check if the part belongs to another message.
+                if first.request_id != part.request_id {
+                    return Err(Error::new(
+                        ErrorCode::InvalidMessage,
+                        "interleaved message stream",
+                    ));
+                }
+            },
+            None => self.check_first_part(&part)?,
+        }
+
         let part_number: u32 = part.part_number;
         self.check_out_of_order(part_number)?;
         self.parts.push(part);
@@ -120,6 +165,104 @@ impl LinuxDaemonLongMessage {
         self.parts
     }
 
+    ///
+    /// # Description
+    ///
+    /// Prepends the prologue to a message.
+    ///
+    /// # Parameters
+    ///
+    /// - `bytes`: Message.
+    ///
+    /// # Returns
+    ///
+    /// The message, along with its prologue.
+    ///
+    pub fn frame(bytes: &[u8]) -> Vec<u8> {
+        let mut framed: Vec<u8> = Vec::with_capacity(Self::PROLOGUE_SIZE + bytes.len());
+        framed.resize(Self::PROLOGUE_SIZE, 0);
+
+        let mut encoder: WireEncoder = WireEncoder::new(&mut framed);
+        encoder.put(bytes.len() as u32);
+        encoder.put(crc32(bytes));
+
+        framed.extend_from_slice(bytes);
+        framed
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Strips the prologue from a message, checking its length and checksum.
+    ///
+    /// # Parameters
+    ///
+    /// - `framed`: Message, along with its prologue.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the message is returned. Upon failure, an error is returned instead.
+    ///
+    pub fn unframe(framed: &[u8]) -> Result<&[u8], Error> {
+        // Check if the prologue is truncated.
+        if framed.len() < Self::PROLOGUE_SIZE {
+            return Err(Error::new(ErrorCode::InvalidMessage, "truncated message stream"));
+        }
+
+        let (prologue, bytes): (&[u8], &[u8]) = framed.split_at(Self::PROLOGUE_SIZE);
+        let mut decoder: WireDecoder = WireDecoder::new(prologue);
+        let length: u32 = decoder.get();
+        let checksum: u32 = decoder.get();
+
+        // Check if the message is truncated.
+        if bytes.len() != length as usize {
+            return Err(Error::new(ErrorCode::InvalidMessage, "truncated message stream"));
+        }
+
+        // Check if the message is corrupted.
+        if crc32(bytes) != checksum {
+            return Err(Error::new(ErrorCode::InvalidMessage, "invalid checksum"));
+        }
+
+        Ok(bytes)
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Checks if the first part of a message announces as many parts as the message has.
+    ///
+    /// # Parameters
+    ///
+    /// - `part`: First part.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the function returns empty. Otherwise, it returns an error.
+    ///
+    fn check_first_part(&self, part: &LinuxDaemonMessagePart) -> Result<(), Error> {
+        // Check if the prologue is truncated.
+        if (part.payload_size as usize) < Self::PROLOGUE_SIZE {
+            return Err(Error::new(ErrorCode::InvalidMessage, "truncated message stream"));
+        }
+
+        let mut decoder: WireDecoder = WireDecoder::new(&part.payload[..Self::PROLOGUE_SIZE]);
+        let length: u32 = decoder.get();
+        let num_parts: usize = Self::capacity(length as usize);
+
+        // Check if leading parts are missing.
+        if part.part_number as usize != num_parts - 1 {
+            return Err(Error::new(ErrorCode::InvalidMessage, "truncated message stream"));
+        }
+
+        // Check if the message does not fit.
+        if num_parts > self.capacity {
+            return Err(Error::new(ErrorCode::MessageTooLong, "message too long"));
+        }
+
+        Ok(())
+    }
+
     ///
     /// # Description
     ///
@@ -143,3 +286,31 @@ impl LinuxDaemonLongMessage {
         Ok(())
     }
 }
+
+//==================================================================================================
+// Standalone Functions
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Computes the CRC-32 (IEEE 802.3) checksum of a byte array.
+///
+/// # Parameters
+///
+/// - `bytes`: Byte array.
+///
+/// # Returns
+///
+/// The checksum of the byte array.
+///
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc: u32 = !0;
+    for byte in bytes {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ (0xedb8_8320 & (crc & 1).wrapping_neg());
+        }
+    }
+    !crc
+}
@@ -5,6 +5,7 @@
 // Modules
 //==================================================================================================
 
+mod field;
 mod long;
 mod part;
 
@@ -12,7 +13,12 @@ mod part;
 // Imports
 //==================================================================================================
 
+use crate::LinuxDaemonMessageHeader;
 use ::alloc::vec::Vec;
+use ::core::sync::atomic::{
+    AtomicU32,
+    Ordering,
+};
 use ::nvx::{
     ipc::Message,
     pm::ProcessIdentifier,
@@ -23,9 +29,36 @@ use ::nvx::{
 // Exports
 //==================================================================================================
 
+pub use ::linuxd_derive::{
+    MessageDeserializer,
+    MessagePartitioner,
+    MessageSerializer,
+};
+pub use field::MessageField;
 pub use long::LinuxDaemonLongMessage;
 pub use part::LinuxDaemonMessagePart;
 
+/// Items that code generated by the derive macros refers to.
+#[doc(hidden)]
+pub mod __private {
+    pub use ::alloc::vec::Vec;
+    pub use ::nvx::{
+        ipc::Message,
+        pm::ProcessIdentifier,
+        sys::error::{
+            Error,
+            ErrorCode,
+        },
+    };
+}
+
+//==================================================================================================
+// Global Variables
+//==================================================================================================
+
+/// Identifier of the next message that is split into parts.
+static NEXT_REQUEST_ID: AtomicU32 = AtomicU32::new(0);
+
 //==================================================================================================
 // Traits
 //==================================================================================================
@@ -50,6 +83,9 @@ pub trait MessageDeserializer
 where
     Self: Sized,
 {
+    /// Maximum size of the serialized structure (in bytes).
+    const MAX_SIZE: usize;
+
     ///
     /// # Description
     ///
@@ -73,6 +109,9 @@ where
     Self: MessageSerializer,
     Self: MessageDeserializer,
 {
+    /// Header of the message parts.
+    const HEADER: LinuxDaemonMessageHeader;
+
     ///
     /// # Description
     ///
@@ -81,6 +120,7 @@ where
     /// # Parameters
     ///
     /// - `pid`: Process identifier.
+    /// - `request_id`: Identifier of the message that the part belongs to.
     /// - `part_number`: Part number.
     /// - `payload_size`: Payload size.
     /// - `payload`: Payload.
@@ -91,6 +131,7 @@ where
     ///
     fn new_part(
         pid: ProcessIdentifier,
+        request_id: u32,
         part_number: u32,
         payload_size: u8,
         payload: [u8; LinuxDaemonMessagePart::PAYLOAD_SIZE],
@@ -99,7 +140,7 @@ where
     ///
     /// # Description
     ///
-    /// Splits a message into parts.
+    /// Splits a message into parts. The first part opens with the prologue of the message.
     ///
     /// # Parameters
     ///
@@ -111,7 +152,8 @@ where
     /// returned instead.
     ///
     fn into_parts(self, pid: ProcessIdentifier) -> Result<Vec<Message>, Error> {
-        let bytes: Vec<u8> = self.to_bytes();
+        let bytes: Vec<u8> = LinuxDaemonLongMessage::frame(&self.to_bytes());
+        let request_id: u32 = NEXT_REQUEST_ID.fetch_add(1, Ordering::Relaxed);
         let num_parts: usize = bytes.len().div_ceil(LinuxDaemonMessagePart::PAYLOAD_SIZE);
         let mut parts: Vec<Message> = Vec::with_capacity(num_parts);
 
@@ -123,6 +165,7 @@ where
             payload[..chunk.len()].copy_from_slice(chunk);
             parts.push(Self::new_part(
                 pid,
+                request_id,
                 (num_parts - part_number - 1) as u32,
                 chunk.len() as u8,
                 payload,
@@ -155,6 +198,6 @@ where
             bytes.extend_from_slice(&part.payload[..part.payload_size as usize]);
         }
 
-        Self::try_from_bytes(&bytes)
+        Self::try_from_bytes(LinuxDaemonLongMessage::unframe(&bytes)?)
     }
 }
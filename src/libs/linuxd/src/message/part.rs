@@ -6,6 +6,10 @@
 //==================================================================================================
 
 use crate::{
+    wire::{
+        WireDecoder,
+        WireEncoder,
+    },
     LinuxDaemonMessage,
     LinuxDaemonMessageHeader,
 };
@@ -36,6 +40,8 @@ use ::nvx::{
 ///
 #[repr(C, packed)]
 pub struct LinuxDaemonMessagePart {
+    /// Identifier of the message that the part belongs to.
+    pub request_id: u32,
     /// Part number.
     pub part_number: u32,
     /// Payload size.
@@ -48,7 +54,7 @@ pub struct LinuxDaemonMessagePart {
 impl LinuxDaemonMessagePart {
     /// Maximum size of the payload.
     pub const PAYLOAD_SIZE: usize =
-        LinuxDaemonMessage::PAYLOAD_SIZE - mem::size_of::<u8>() - mem::size_of::<u32>();
+        LinuxDaemonMessage::PAYLOAD_SIZE - mem::size_of::<u8>() - 2 * mem::size_of::<u32>();
 
     ///
     /// # Description
@@ -59,6 +65,7 @@ impl LinuxDaemonMessagePart {
     ///
     /// - `pid`: Process identifier.
     /// - `header`: Message header.
+    /// - `request_id`: Identifier of the message that the part belongs to.
     /// - `part_number`: Part number.
     /// - `payload_size`: Payload size.
     /// - `payload`: Payload.
@@ -70,11 +77,12 @@ impl LinuxDaemonMessagePart {
     pub fn build_request(
         pid: ProcessIdentifier,
         header: LinuxDaemonMessageHeader,
+        request_id: u32,
         part_number: u32,
         payload_size: u8,
         payload: [u8; Self::PAYLOAD_SIZE],
     ) -> Result<Message, Error> {
-        Self::build(pid, header, part_number, payload_size, payload, false)
+        Self::build(pid, header, request_id, part_number, payload_size, payload, false)
     }
 
     ///
@@ -86,6 +94,7 @@ impl LinuxDaemonMessagePart {
     ///
     /// - `pid`: Process identifier.
     /// - `header`: Message header.
+    /// - `request_id`: Identifier of the message that the part belongs to.
     /// - `part_number`: Part number.
     /// - `payload_size`: Payload size.
     /// - `payload`: Payload.
@@ -97,11 +106,12 @@ impl LinuxDaemonMessagePart {
     pub fn build_response(
         pid: ProcessIdentifier,
         header: LinuxDaemonMessageHeader,
+        request_id: u32,
         part_number: u32,
         payload_size: u8,
         payload: [u8; Self::PAYLOAD_SIZE],
     ) -> Result<Message, Error> {
-        Self::build(pid, header, part_number, payload_size, payload, true)
+        Self::build(pid, header, request_id, part_number, payload_size, payload, true)
     }
 
     ///
@@ -115,10 +125,27 @@ impl LinuxDaemonMessagePart {
     ///
     /// # Returns
     ///
-    /// A Linux Daemon Message Part.
+    /// Upon success, a Linux Daemon Message Part is returned. Upon failure, an error is returned
+    /// instead, if the payload size is out of bounds.
     ///
-    pub fn from_bytes(bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE]) -> Self {
-        unsafe { mem::transmute(bytes) }
+    pub fn try_from_bytes(bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE]) -> Result<Self, Error> {
+        let mut decoder: WireDecoder = WireDecoder::new(&bytes);
+        let request_id: u32 = decoder.get();
+        let part_number: u32 = decoder.get();
+        let payload_size: u8 = decoder.get();
+        let payload: [u8; Self::PAYLOAD_SIZE] = decoder.get();
+
+        // Check if payload size is invalid.
+        if payload_size as usize > Self::PAYLOAD_SIZE {
+            return Err(Error::new(ErrorCode::InvalidMessage, "invalid payload size"));
+        }
+
+        Ok(Self {
+            request_id,
+            part_number,
+            payload_size,
+            payload,
+        })
     }
 
     ///
@@ -131,7 +158,14 @@ impl LinuxDaemonMessagePart {
     /// A byte array.
     ///
     fn into_bytes(self) -> [u8; LinuxDaemonMessage::PAYLOAD_SIZE] {
-        unsafe { mem::transmute(self) }
+        let mut bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE] =
+            [0; LinuxDaemonMessage::PAYLOAD_SIZE];
+        let mut encoder: WireEncoder = WireEncoder::new(&mut bytes);
+        encoder.put(self.request_id);
+        encoder.put(self.part_number);
+        encoder.put(self.payload_size);
+        encoder.put(self.payload);
+        bytes
     }
 
     ///
@@ -143,6 +177,7 @@ impl LinuxDaemonMessagePart {
     ///
     /// - `pid`: Process identifier.
     /// - `header`: Message header.
+    /// - `request_id`: Identifier of the message that the part belongs to.
     /// - `part_number`: Part number.
     /// - `payload_size`: Payload size.
     /// - `payload`: Payload.
@@ -153,12 +188,14 @@ impl LinuxDaemonMessagePart {
     fn build(
         pid: ProcessIdentifier,
         header: LinuxDaemonMessageHeader,
+        request_id: u32,
         part_number: u32,
         payload_size: u8,
         payload: [u8; Self::PAYLOAD_SIZE],
         is_response: bool,
     ) -> Result<Message, Error> {
-        let message: LinuxDaemonMessagePart = Self::new(part_number, payload_size, payload)?;
+        let message: LinuxDaemonMessagePart =
+            Self::new(request_id, part_number, payload_size, payload)?;
         let message: LinuxDaemonMessage = LinuxDaemonMessage::new(header, message.into_bytes());
         if is_response {
             Ok(Message::new(crate::LINUXD, pid, MessageType::Ikc, None, message.into_bytes()))
@@ -174,11 +211,13 @@ impl LinuxDaemonMessagePart {
     ///
     /// # Parameters
     ///
+    /// - `request_id`: Identifier of the message that the part belongs to.
     /// - `part_number`: Part number.
     /// - `payload_size`: Payload size.
     /// - `payload`: Payload.
     ///
     fn new(
+        request_id: u32,
         part_number: u32,
         payload_size: u8,
         payload: [u8; Self::PAYLOAD_SIZE],
@@ -189,6 +228,7 @@ impl LinuxDaemonMessagePart {
         }
 
         Ok(Self {
+            request_id,
             part_number,
             payload_size,
             payload,
@@ -200,7 +240,8 @@ impl Debug for LinuxDaemonMessagePart {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(
             f,
-            "LinuxDaemonMessagePart {{ part_number: {}, payload_size: {} }}",
+            "LinuxDaemonMessagePart {{ request_id: {}, part_number: {}, payload_size: {} }}",
+            { self.request_id },
             { self.part_number },
             { self.payload_size }
         )
@@ -0,0 +1,234 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::LinuxDaemonMessageHeader;
+use ::core::convert::TryFrom;
+use ::nvx::sys::error::{
+    Error,
+    ErrorCode,
+};
+
+//==================================================================================================
+// Constants
+//==================================================================================================
+
+/// Version of the protocol implemented by this revision.
+pub const PROTOCOL_VERSION: u16 = 2;
+
+/// First version of the protocol whose message parts carry a request identifier, and whose long
+/// messages carry their total length and checksum.
+pub const FRAMED_PARTS_PROTOCOL_VERSION: u16 = 2;
+
+/// Version assumed for peers that do not negotiate, which predate negotiation.
+pub const LEGACY_PROTOCOL_VERSION: u16 = 0;
+
+/// Oldest version of the protocol that is still supported.
+pub const MIN_PROTOCOL_VERSION: u16 = LEGACY_PROTOCOL_VERSION;
+
+/// Magic number that opens a hello.
+pub const HELLO_MAGIC: [u8; 8] = *b"LINUXD\x01\x00";
+
+/// Number of message types that capabilities can describe.
+const MAX_HEADERS: usize = 256;
+
+//==================================================================================================
+// Structures
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Set of message types that a peer understands.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    /// Bitmap indexed by message header.
+    bitmap: [u8; Self::SIZE],
+}
+
+///
+/// # Description
+///
+/// Hello that each side sends once a connection is established, right after authentication.
+///
+/// The client sends its hello first and the Linux Daemon answers with its own. Both sides then
+/// speak the lowest of the two versions and only send messages that the other side understands.
+/// Clients that start sending messages without a hello are taken to speak
+/// [`LEGACY_PROTOCOL_VERSION`].
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hello {
+    /// Protocol version.
+    pub version: u16,
+    /// Message types understood.
+    pub capabilities: Capabilities,
+}
+
+//==================================================================================================
+// Implementations
+//==================================================================================================
+
+impl Capabilities {
+    /// Size of capabilities on the wire (in bytes).
+    pub const SIZE: usize = MAX_HEADERS / 8;
+
+    /// No message types.
+    pub const NONE: Self = Self {
+        bitmap: [0; Self::SIZE],
+    };
+
+    ///
+    /// # Description
+    ///
+    /// Returns the message types understood by this revision.
+    ///
+    pub fn current() -> Self {
+        let mut capabilities: Self = Self::NONE;
+        for header in 0..MAX_HEADERS as u16 {
+            if let Ok(header) = LinuxDaemonMessageHeader::try_from(header) {
+                capabilities.insert(header);
+            }
+        }
+        capabilities
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Adds a message type.
+    ///
+    pub fn insert(&mut self, header: LinuxDaemonMessageHeader) {
+        let (index, mask): (usize, u8) = Self::locate(header);
+        self.bitmap[index] |= mask;
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Checks whether a message type is understood.
+    ///
+    pub fn supports(&self, header: LinuxDaemonMessageHeader) -> bool {
+        let (index, mask): (usize, u8) = Self::locate(header);
+        (self.bitmap[index] & mask) != 0
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the message types understood by both sides.
+    ///
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut capabilities: Self = Self::NONE;
+        for (i, byte) in capabilities.bitmap.iter_mut().enumerate() {
+            *byte = self.bitmap[i] & other.bitmap[i];
+        }
+        capabilities
+    }
+
+    pub fn from_bytes(bytes: [u8; Self::SIZE]) -> Self {
+        Self { bitmap: bytes }
+    }
+
+    pub fn to_bytes(self) -> [u8; Self::SIZE] {
+        self.bitmap
+    }
+
+    fn locate(header: LinuxDaemonMessageHeader) -> (usize, u8) {
+        let header: usize = header as usize;
+        (header / 8, 1 << (header % 8))
+    }
+}
+
+impl Hello {
+    /// Size of a hello on the wire (in bytes).
+    pub const SIZE: usize = HELLO_MAGIC.len() + 2 + Capabilities::SIZE;
+
+    ///
+    /// # Description
+    ///
+    /// Returns the hello of this revision.
+    ///
+    pub fn current() -> Self {
+        Self {
+            version: PROTOCOL_VERSION,
+            capabilities: Capabilities::current(),
+        }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the hello assumed for peers that do not negotiate. They understand all message
+    /// types that existed before negotiation was introduced.
+    ///
+    pub fn legacy() -> Self {
+        let mut capabilities: Capabilities = Capabilities::NONE;
+        for header in 0..=LinuxDaemonMessageHeader::GetResourceUsageResponse as u16 {
+            if let Ok(header) = LinuxDaemonMessageHeader::try_from(header) {
+                capabilities.insert(header);
+            }
+        }
+
+        Self {
+            version: LEGACY_PROTOCOL_VERSION,
+            capabilities,
+        }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Negotiates with the hello of the peer.
+    ///
+    /// # Parameters
+    ///
+    /// - `peer`: Hello of the peer.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the version and message types that both sides share are returned. Upon
+    /// failure, an error is returned instead, if the peer is too old.
+    ///
+    pub fn negotiate(&self, peer: &Self) -> Result<Self, Error> {
+        if !(MIN_PROTOCOL_VERSION..).contains(&peer.version) {
+            return Err(Error::new(
+                ErrorCode::OperationNotSupported,
+                "unsupported protocol version",
+            ));
+        }
+
+        Ok(Self {
+            version: self.version.min(peer.version),
+            capabilities: self.capabilities.intersection(&peer.capabilities),
+        })
+    }
+
+    pub fn from_bytes(bytes: [u8; Self::SIZE]) -> Result<Self, Error> {
+        let (magic, rest): (&[u8], &[u8]) = bytes.split_at(HELLO_MAGIC.len());
+        if magic != HELLO_MAGIC {
+            return Err(Error::new(ErrorCode::InvalidMessage, "invalid hello magic"));
+        }
+
+        let (version, bitmap): (&[u8], &[u8]) = rest.split_at(2);
+        let mut capabilities: [u8; Capabilities::SIZE] = [0; Capabilities::SIZE];
+        capabilities.copy_from_slice(bitmap);
+
+        Ok(Self {
+            version: u16::from_le_bytes([version[0], version[1]]),
+            capabilities: Capabilities::from_bytes(capabilities),
+        })
+    }
+
+    pub fn to_bytes(self) -> [u8; Self::SIZE] {
+        let mut bytes: [u8; Self::SIZE] = [0; Self::SIZE];
+        let (magic, rest): (&mut [u8], &mut [u8]) = bytes.split_at_mut(HELLO_MAGIC.len());
+        magic.copy_from_slice(&HELLO_MAGIC);
+        rest[..2].copy_from_slice(&self.version.to_le_bytes());
+        rest[2..].copy_from_slice(&self.capabilities.to_bytes());
+        bytes
+    }
+}
@@ -0,0 +1,116 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::{
+    limits,
+    message::{
+        MessageDeserializer,
+        MessagePartitioner,
+        MessageSerializer,
+    },
+};
+use ::alloc::string::String;
+use ::nvx::sys::error::{
+    Error,
+    ErrorCode,
+};
+
+//==================================================================================================
+// RealPathRequest
+//==================================================================================================
+
+///
+/// # Description
+///
+/// This struct represents the request message of the `realpath()` system call.
+///
+#[derive(Debug, MessageSerializer, MessageDeserializer, MessagePartitioner)]
+#[message(request_part = RealPathRequestPart)]
+pub struct RealPathRequest {
+    /// Path to resolve.
+    #[message(max_len = limits::PATH_MAX)]
+    pub path: String,
+}
+
+impl RealPathRequest {
+    ///
+    /// # Description
+    ///
+    /// Creates a request message of the `realpath()` system call.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: Path to resolve.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the request message of the `realpath()` system call is returned. Upon
+    /// failure, an error is returned instead.
+    ///
+    pub fn new(path: String) -> Result<Self, Error> {
+        // Check if the path is too long.
+        if path.len() > limits::PATH_MAX {
+            return Err(Error::new(ErrorCode::InvalidMessage, "path too long"));
+        }
+
+        Ok(Self { path })
+    }
+}
+
+//==================================================================================================
+// RealPathResponse
+//==================================================================================================
+
+///
+/// # Description
+///
+/// This struct represents the response message of the `realpath()` system call.
+///
+#[derive(Debug, MessageSerializer, MessageDeserializer, MessagePartitioner)]
+#[message(response_part = RealPathResponsePart, validate = Self::validate)]
+pub struct RealPathResponse {
+    /// Canonical path, relative to the root of the virtual environment.
+    #[message(max_len = limits::PATH_MAX)]
+    pub path: String,
+}
+
+impl RealPathResponse {
+    ///
+    /// # Description
+    ///
+    /// Creates a response message of the `realpath()` system call.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: Canonical path.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the response message of the `realpath()` system call is returned. Upon
+    /// failure, an error is returned instead.
+    ///
+    pub fn new(path: String) -> Result<Self, Error> {
+        // Check if the path is too long.
+        if path.len() > limits::PATH_MAX {
+            return Err(Error::new(ErrorCode::InvalidMessage, "path too long"));
+        }
+
+        let response: Self = Self { path };
+        response.validate()?;
+
+        Ok(response)
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        // Check if the path is not absolute.
+        if !self.path.starts_with('/') {
+            return Err(Error::new(ErrorCode::InvalidMessage, "path is not absolute"));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,154 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::{
+    message::{
+        LinuxDaemonLongMessage,
+        LinuxDaemonMessagePart,
+        MessageDeserializer,
+        MessagePartitioner,
+    },
+    stdlib::message::{
+        RealPathRequest,
+        RealPathResponse,
+    },
+    LinuxDaemonMessage,
+    LinuxDaemonMessageHeader,
+};
+use ::alloc::{
+    string::ToString,
+    vec::Vec,
+};
+use ::nvx::{
+    ipc::Message,
+    pm::ProcessIdentifier,
+    sys::error::ErrorCode,
+};
+
+//==================================================================================================
+// Standalone Functions
+//==================================================================================================
+
+///
+/// # Description
+///
+/// The `realpath()` system call resolves a path into a canonical absolute path. The resolved path
+/// is relative to the root of the virtual environment of the calling process.
+///
+/// # Parameters
+///
+/// - `path`: Path to resolve.
+/// - `resolved_path`: Buffer to store the resolved path.
+///
+/// # Returns
+///
+/// Upon successful completion, the length of the resolved path is returned. Upon failure, a
+/// negative error code is returned instead.
+///
+pub fn realpath(path: &str, resolved_path: &mut [u8]) -> i32 {
+    // Check if path is empty.
+    if path.is_empty() {
+        return ErrorCode::NoSuchEntry.into_errno();
+    }
+
+    // Send request.
+    let status: i32 = realpath_request(path);
+    if status != 0 {
+        return status;
+    }
+
+    // Wait for response.
+    realpath_response(resolved_path)
+}
+
+fn realpath_request(path: &str) -> i32 {
+    let pid: ProcessIdentifier = match ::nvx::pm::getpid() {
+        Ok(pid) => pid,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    let request: RealPathRequest = match RealPathRequest::new(path.to_string()) {
+        Ok(request) => request,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    let requests: Vec<Message> = match request.into_parts(pid) {
+        Ok(requests) => requests,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    for request in requests {
+        if let Err(e) = ::nvx::ipc::send(&request) {
+            return e.code.into_errno();
+        }
+    }
+
+    0
+}
+
+fn realpath_response(resolved_path: &mut [u8]) -> i32 {
+    let capacity: usize = LinuxDaemonLongMessage::capacity(RealPathResponse::MAX_SIZE);
+
+    let mut assembler: LinuxDaemonLongMessage = match LinuxDaemonLongMessage::new(capacity) {
+        Ok(assembler) => assembler,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    loop {
+        let response: Message = match ::nvx::ipc::recv() {
+            Ok(response) => response,
+            Err(e) => break e.code.into_errno(),
+        };
+
+        // Check whether system call succeeded or not.
+        if response.status != 0 {
+            // System call failed, parse error code and return it.
+            match ErrorCode::try_from(response.status) {
+                Ok(e) => break e.into_errno(),
+                Err(_) => break ErrorCode::InvalidMessage.into_errno(),
+            }
+        } else {
+            // System call succeeded, parse response.
+            match LinuxDaemonMessage::try_from_bytes(response.payload) {
+                Ok(message) => match message.header {
+                    LinuxDaemonMessageHeader::RealPathResponsePart => {
+                        let part: LinuxDaemonMessagePart =
+                            match LinuxDaemonMessagePart::try_from_bytes(message.payload) {
+                                Ok(part) => part,
+                                Err(e) => break e.code.into_errno(),
+                            };
+
+                        if let Err(e) = assembler.add_part(part) {
+                            break e.code.into_errno();
+                        }
+
+                        if !assembler.is_complete() {
+                            continue;
+                        }
+
+                        let parts: Vec<LinuxDaemonMessagePart> = assembler.take_parts();
+
+                        match RealPathResponse::from_parts(&parts) {
+                            Ok(response) => {
+                                // Check if resolved path does not fit in the buffer.
+                                if response.path.len() > resolved_path.len() {
+                                    break ErrorCode::InvalidArgument.into_errno();
+                                }
+                                resolved_path[..response.path.len()]
+                                    .copy_from_slice(response.path.as_bytes());
+                                break response.path.len() as i32;
+                            },
+                            Err(_) => break ErrorCode::InvalidMessage.into_errno(),
+                        }
+                    },
+                    _ => break ErrorCode::InvalidMessage.into_errno(),
+                },
+                Err(_) => break ErrorCode::InvalidMessage.into_errno(),
+            }
+        }
+    }
+}
@@ -0,0 +1,143 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::{
+    wire::{
+        WireDecoder,
+        WireEncoder,
+    },
+    LinuxDaemonMessage,
+    LinuxDaemonMessageHeader,
+};
+use ::core::mem;
+use ::nvx::{
+    ipc::{
+        Message,
+        MessageType,
+    },
+    pm::ProcessIdentifier,
+};
+
+//==================================================================================================
+// FileLockRequest
+//==================================================================================================
+
+///
+/// # Description
+///
+/// This struct represents the request message of the `flock()` system call.
+///
+#[derive(Debug)]
+#[repr(C, packed)]
+pub struct FileLockRequest {
+    /// File descriptor.
+    pub fd: i32,
+    /// Lock operation.
+    pub operation: i32,
+    _padding: [u8; Self::PADDING_SIZE],
+}
+::nvx::sys::static_assert_size!(FileLockRequest, LinuxDaemonMessage::PAYLOAD_SIZE);
+
+impl FileLockRequest {
+    pub const PADDING_SIZE: usize =
+        LinuxDaemonMessage::PAYLOAD_SIZE - mem::size_of::<i32>() - mem::size_of::<i32>();
+
+    fn new(fd: i32, operation: i32) -> Self {
+        Self {
+            fd,
+            operation,
+            _padding: [0; Self::PADDING_SIZE],
+        }
+    }
+
+    pub fn from_bytes(bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE]) -> Self {
+        let mut decoder: WireDecoder = WireDecoder::new(&bytes);
+        Self {
+            fd: decoder.get(),
+            operation: decoder.get(),
+            _padding: [0; Self::PADDING_SIZE],
+        }
+    }
+
+    fn into_bytes(self) -> [u8; LinuxDaemonMessage::PAYLOAD_SIZE] {
+        let mut bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE] =
+            [0; LinuxDaemonMessage::PAYLOAD_SIZE];
+        let mut encoder: WireEncoder = WireEncoder::new(&mut bytes);
+        encoder.put(self.fd);
+        encoder.put(self.operation);
+        bytes
+    }
+
+    pub fn build(pid: ProcessIdentifier, fd: i32, operation: i32) -> Message {
+        let message: FileLockRequest = FileLockRequest::new(fd, operation);
+        let message: LinuxDaemonMessage = LinuxDaemonMessage::new(
+            LinuxDaemonMessageHeader::FileLockRequest,
+            message.into_bytes(),
+        );
+        let message: Message =
+            Message::new(pid, crate::LINUXD, MessageType::Ikc, None, message.into_bytes());
+
+        message
+    }
+}
+
+//==================================================================================================
+// FileLockResponse
+//==================================================================================================
+
+///
+/// # Description
+///
+/// This struct represents the response message of the `flock()` system call.
+///
+#[derive(Debug)]
+#[repr(C, packed)]
+pub struct FileLockResponse {
+    /// Return value.
+    pub ret: i32,
+    _padding: [u8; Self::PADDING_SIZE],
+}
+::nvx::sys::static_assert_size!(FileLockResponse, LinuxDaemonMessage::PAYLOAD_SIZE);
+
+impl FileLockResponse {
+    pub const PADDING_SIZE: usize = LinuxDaemonMessage::PAYLOAD_SIZE - mem::size_of::<i32>();
+
+    fn new(ret: i32) -> Self {
+        Self {
+            ret,
+            _padding: [0; Self::PADDING_SIZE],
+        }
+    }
+
+    pub fn from_bytes(bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE]) -> Self {
+        let mut decoder: WireDecoder = WireDecoder::new(&bytes);
+        Self {
+            ret: decoder.get(),
+            _padding: [0; Self::PADDING_SIZE],
+        }
+    }
+
+    fn into_bytes(self) -> [u8; LinuxDaemonMessage::PAYLOAD_SIZE] {
+        let mut bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE] =
+            [0; LinuxDaemonMessage::PAYLOAD_SIZE];
+        let mut encoder: WireEncoder = WireEncoder::new(&mut bytes);
+        encoder.put(self.ret);
+        bytes
+    }
+
+    pub fn build(pid: ProcessIdentifier, ret: i32) -> Message {
+        let message: FileLockResponse = FileLockResponse::new(ret);
+        let message: LinuxDaemonMessage = LinuxDaemonMessage::new(
+            LinuxDaemonMessageHeader::FileLockResponse,
+            message.into_bytes(),
+        );
+        let message: Message =
+            Message::new(crate::LINUXD, pid, MessageType::Ikc, None, message.into_bytes());
+
+        message
+    }
+}
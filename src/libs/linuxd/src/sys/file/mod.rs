@@ -0,0 +1,26 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Modules
+//==================================================================================================
+
+pub mod message;
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "syscall")] {
+        mod syscall;
+        pub use self::syscall::flock;
+    }
+}
+
+//==================================================================================================
+
+/// Place a shared lock.
+pub const LOCK_SH: i32 = 1;
+/// Place an exclusive lock.
+pub const LOCK_EX: i32 = 2;
+/// Do not block when placing a lock.
+pub const LOCK_NB: i32 = 4;
+/// Remove an existing lock.
+pub const LOCK_UN: i32 = 8;
@@ -0,0 +1,87 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::{
+    sys::file::message::{
+        FileLockRequest,
+        FileLockResponse,
+    },
+    LinuxDaemonMessage,
+    LinuxDaemonMessageHeader,
+};
+use ::nvx::{
+    ipc::Message,
+    pm::ProcessIdentifier,
+    sys::error::ErrorCode,
+};
+
+//==================================================================================================
+// Standalone Functions
+//==================================================================================================
+
+///
+/// # Description
+///
+/// The `flock()` system call applies or removes an advisory lock on an open file.
+///
+/// # Parameters
+///
+/// - `fd`: File descriptor of the file.
+/// - `operation`: Lock operation (`LOCK_SH`, `LOCK_EX` or `LOCK_UN`, optionally or'ed with
+///   `LOCK_NB`).
+///
+/// # Returns
+///
+/// Upon successful completion, `0` is returned. Upon failure, a negative error code is returned
+/// instead.
+///
+pub fn flock(fd: i32, operation: i32) -> i32 {
+    let pid: ProcessIdentifier = match ::nvx::pm::getpid() {
+        Ok(pid) => pid,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    // Build request and send it.
+    let request: Message = FileLockRequest::build(pid, fd, operation);
+    if let Err(e) = ::nvx::ipc::send(&request) {
+        return e.code.into_errno();
+    }
+
+    // Receive response. This may block until the lock is granted.
+    let response: Message = match ::nvx::ipc::recv() {
+        Ok(response) => response,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    // Check whether system call succeeded or not.
+    if response.status != 0 {
+        // System call failed, parse error code and return it.
+        match ErrorCode::try_from(response.status) {
+            Ok(e) => e.into_errno(),
+            Err(_) => ErrorCode::InvalidMessage.into_errno(),
+        }
+    } else {
+        // System call succeeded, parse response.
+        match LinuxDaemonMessage::try_from_bytes(response.payload) {
+            // Response was successfully parsed.
+            Ok(message) => match message.header {
+                // Response was successfully parsed.
+                LinuxDaemonMessageHeader::FileLockResponse => {
+                    // Parse response.
+                    let response: FileLockResponse = FileLockResponse::from_bytes(message.payload);
+
+                    // Return result.
+                    response.ret
+                },
+                // Response was not successfully parsed.
+                _ => ErrorCode::InvalidMessage.into_errno(),
+            },
+            // Response was not successfully parsed.
+            Err(_) => ErrorCode::InvalidMessage.into_errno(),
+        }
+    }
+}
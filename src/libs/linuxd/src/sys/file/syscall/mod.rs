@@ -0,0 +1,14 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Modules
+//==================================================================================================
+
+mod flock;
+
+//==================================================================================================
+// Exports
+//==================================================================================================
+
+pub use self::flock::flock;
@@ -0,0 +1,78 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::{
+    limits,
+    message::{
+        MessageDeserializer,
+        MessagePartitioner,
+        MessageSerializer,
+    },
+};
+use ::alloc::string::String;
+use ::nvx::sys::error::{
+    Error,
+    ErrorCode,
+};
+
+//==================================================================================================
+// InotifyEvent
+//==================================================================================================
+
+///
+/// # Description
+///
+/// This struct represents a filesystem event that the Linux daemon pushes to a watching process.
+/// Unlike other messages, it is not sent in reply to a request.
+///
+#[derive(Debug, MessageSerializer, MessageDeserializer, MessagePartitioner)]
+#[message(response_part = InotifyEventPart)]
+pub struct InotifyEvent {
+    /// Watch descriptor.
+    pub wd: i32,
+    /// Event mask.
+    pub mask: u32,
+    /// Cookie that associates the two halves of a rename.
+    pub cookie: u32,
+    /// Name of the file, relative to the watched directory. Empty if the event refers to the
+    /// watched path itself.
+    #[message(max_len = limits::PATH_MAX)]
+    pub name: String,
+}
+
+impl InotifyEvent {
+    ///
+    /// # Description
+    ///
+    /// Creates a filesystem event message.
+    ///
+    /// # Parameters
+    ///
+    /// - `wd`: Watch descriptor.
+    /// - `mask`: Event mask.
+    /// - `cookie`: Cookie that associates the two halves of a rename.
+    /// - `name`: Name of the file, relative to the watched directory.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the filesystem event message is returned. Upon failure, an error is returned
+    /// instead.
+    ///
+    pub fn new(wd: i32, mask: u32, cookie: u32, name: String) -> Result<Self, Error> {
+        // Check if the name is too long.
+        if name.len() > limits::PATH_MAX {
+            return Err(Error::new(ErrorCode::InvalidMessage, "name too long"));
+        }
+
+        Ok(Self {
+            wd,
+            mask,
+            cookie,
+            name,
+        })
+    }
+}
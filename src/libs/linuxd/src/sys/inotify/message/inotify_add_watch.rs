@@ -0,0 +1,136 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::{
+    limits,
+    message::{
+        MessageDeserializer,
+        MessagePartitioner,
+        MessageSerializer,
+    },
+    wire::{
+        WireDecoder,
+        WireEncoder,
+    },
+    LinuxDaemonMessage,
+    LinuxDaemonMessageHeader,
+};
+use ::alloc::string::String;
+use ::core::mem;
+use ::nvx::{
+    ipc::{
+        Message,
+        MessageType,
+    },
+    pm::ProcessIdentifier,
+    sys::error::{
+        Error,
+        ErrorCode,
+    },
+};
+
+//==================================================================================================
+// InotifyAddWatchRequest
+//==================================================================================================
+
+///
+/// # Description
+///
+/// This struct represents the request message of the `inotify_add_watch()` system call.
+///
+#[derive(Debug, MessageSerializer, MessageDeserializer, MessagePartitioner)]
+#[message(request_part = InotifyAddWatchRequestPart)]
+pub struct InotifyAddWatchRequest {
+    /// Mask of events to watch.
+    pub mask: u32,
+    /// Path to watch.
+    #[message(max_len = limits::PATH_MAX)]
+    pub path: String,
+}
+
+impl InotifyAddWatchRequest {
+    ///
+    /// # Description
+    ///
+    /// Creates a request message of the `inotify_add_watch()` system call.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: Path to watch.
+    /// - `mask`: Mask of events to watch.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the request message of the `inotify_add_watch()` system call is returned.
+    /// Upon failure, an error is returned instead.
+    ///
+    pub fn new(path: String, mask: u32) -> Result<Self, Error> {
+        // Check if the path is too long.
+        if path.len() > limits::PATH_MAX {
+            return Err(Error::new(ErrorCode::InvalidMessage, "path too long"));
+        }
+
+        Ok(Self { path, mask })
+    }
+}
+
+//==================================================================================================
+// InotifyAddWatchResponse
+//==================================================================================================
+
+///
+/// # Description
+///
+/// This struct represents the response message of the `inotify_add_watch()` system call.
+///
+#[derive(Debug)]
+#[repr(C, packed)]
+pub struct InotifyAddWatchResponse {
+    /// Watch descriptor.
+    pub wd: i32,
+    _padding: [u8; Self::PADDING_SIZE],
+}
+::nvx::sys::static_assert_size!(InotifyAddWatchResponse, LinuxDaemonMessage::PAYLOAD_SIZE);
+
+impl InotifyAddWatchResponse {
+    pub const PADDING_SIZE: usize = LinuxDaemonMessage::PAYLOAD_SIZE - mem::size_of::<i32>();
+
+    fn new(wd: i32) -> Self {
+        Self {
+            wd,
+            _padding: [0; Self::PADDING_SIZE],
+        }
+    }
+
+    pub fn from_bytes(bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE]) -> Self {
+        let mut decoder: WireDecoder = WireDecoder::new(&bytes);
+        Self {
+            wd: decoder.get(),
+            _padding: [0; Self::PADDING_SIZE],
+        }
+    }
+
+    fn into_bytes(self) -> [u8; LinuxDaemonMessage::PAYLOAD_SIZE] {
+        let mut bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE] =
+            [0; LinuxDaemonMessage::PAYLOAD_SIZE];
+        let mut encoder: WireEncoder = WireEncoder::new(&mut bytes);
+        encoder.put(self.wd);
+        bytes
+    }
+
+    pub fn build(pid: ProcessIdentifier, wd: i32) -> Message {
+        let message: InotifyAddWatchResponse = InotifyAddWatchResponse::new(wd);
+        let message: LinuxDaemonMessage = LinuxDaemonMessage::new(
+            LinuxDaemonMessageHeader::InotifyAddWatchResponse,
+            message.into_bytes(),
+        );
+        let message: Message =
+            Message::new(crate::LINUXD, pid, MessageType::Ikc, None, message.into_bytes());
+
+        message
+    }
+}
@@ -0,0 +1,137 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::{
+    wire::{
+        WireDecoder,
+        WireEncoder,
+    },
+    LinuxDaemonMessage,
+    LinuxDaemonMessageHeader,
+};
+use ::core::mem;
+use ::nvx::{
+    ipc::{
+        Message,
+        MessageType,
+    },
+    pm::ProcessIdentifier,
+};
+
+//==================================================================================================
+// InotifyRemoveWatchRequest
+//==================================================================================================
+
+///
+/// # Description
+///
+/// This struct represents the request message of the `inotify_rm_watch()` system call.
+///
+#[derive(Debug)]
+#[repr(C, packed)]
+pub struct InotifyRemoveWatchRequest {
+    /// Watch descriptor.
+    pub wd: i32,
+    _padding: [u8; Self::PADDING_SIZE],
+}
+::nvx::sys::static_assert_size!(InotifyRemoveWatchRequest, LinuxDaemonMessage::PAYLOAD_SIZE);
+
+impl InotifyRemoveWatchRequest {
+    pub const PADDING_SIZE: usize = LinuxDaemonMessage::PAYLOAD_SIZE - mem::size_of::<i32>();
+
+    fn new(wd: i32) -> Self {
+        Self {
+            wd,
+            _padding: [0; Self::PADDING_SIZE],
+        }
+    }
+
+    pub fn from_bytes(bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE]) -> Self {
+        let mut decoder: WireDecoder = WireDecoder::new(&bytes);
+        Self {
+            wd: decoder.get(),
+            _padding: [0; Self::PADDING_SIZE],
+        }
+    }
+
+    fn into_bytes(self) -> [u8; LinuxDaemonMessage::PAYLOAD_SIZE] {
+        let mut bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE] =
+            [0; LinuxDaemonMessage::PAYLOAD_SIZE];
+        let mut encoder: WireEncoder = WireEncoder::new(&mut bytes);
+        encoder.put(self.wd);
+        bytes
+    }
+
+    pub fn build(pid: ProcessIdentifier, wd: i32) -> Message {
+        let message: InotifyRemoveWatchRequest = InotifyRemoveWatchRequest::new(wd);
+        let message: LinuxDaemonMessage = LinuxDaemonMessage::new(
+            LinuxDaemonMessageHeader::InotifyRemoveWatchRequest,
+            message.into_bytes(),
+        );
+        let message: Message =
+            Message::new(pid, crate::LINUXD, MessageType::Ikc, None, message.into_bytes());
+
+        message
+    }
+}
+
+//==================================================================================================
+// InotifyRemoveWatchResponse
+//==================================================================================================
+
+///
+/// # Description
+///
+/// This struct represents the response message of the `inotify_rm_watch()` system call.
+///
+#[derive(Debug)]
+#[repr(C, packed)]
+pub struct InotifyRemoveWatchResponse {
+    /// Return value.
+    pub ret: i32,
+    _padding: [u8; Self::PADDING_SIZE],
+}
+::nvx::sys::static_assert_size!(InotifyRemoveWatchResponse, LinuxDaemonMessage::PAYLOAD_SIZE);
+
+impl InotifyRemoveWatchResponse {
+    pub const PADDING_SIZE: usize = LinuxDaemonMessage::PAYLOAD_SIZE - mem::size_of::<i32>();
+
+    fn new(ret: i32) -> Self {
+        Self {
+            ret,
+            _padding: [0; Self::PADDING_SIZE],
+        }
+    }
+
+    pub fn from_bytes(bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE]) -> Self {
+        let mut decoder: WireDecoder = WireDecoder::new(&bytes);
+        Self {
+            ret: decoder.get(),
+            _padding: [0; Self::PADDING_SIZE],
+        }
+    }
+
+    fn into_bytes(self) -> [u8; LinuxDaemonMessage::PAYLOAD_SIZE] {
+        let mut bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE] =
+            [0; LinuxDaemonMessage::PAYLOAD_SIZE];
+        let mut encoder: WireEncoder = WireEncoder::new(&mut bytes);
+        encoder.put(self.ret);
+        bytes
+    }
+
+    pub fn build(pid: ProcessIdentifier, ret: i32) -> Message {
+        let message: InotifyRemoveWatchResponse = InotifyRemoveWatchResponse::new(ret);
+        let message: LinuxDaemonMessage = LinuxDaemonMessage::new(
+            LinuxDaemonMessageHeader::InotifyRemoveWatchResponse,
+            message.into_bytes(),
+        );
+        let message: Message =
+            Message::new(crate::LINUXD, pid, MessageType::Ikc, None, message.into_bytes());
+
+        message
+    }
+}
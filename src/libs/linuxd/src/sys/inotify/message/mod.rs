@@ -0,0 +1,26 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Modules
+//==================================================================================================
+
+mod event;
+mod inotify_add_watch;
+mod inotify_rm_watch;
+
+//==================================================================================================
+// Exports
+//==================================================================================================
+
+pub use self::{
+    event::InotifyEvent,
+    inotify_add_watch::{
+        InotifyAddWatchRequest,
+        InotifyAddWatchResponse,
+    },
+    inotify_rm_watch::{
+        InotifyRemoveWatchRequest,
+        InotifyRemoveWatchResponse,
+    },
+};
@@ -0,0 +1,76 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Configuration
+//==================================================================================================
+
+#![allow(non_camel_case_types)]
+
+//==================================================================================================
+// Modules
+//==================================================================================================
+
+pub mod message;
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "syscall")] {
+        mod syscall;
+        pub use self::syscall::{
+            inotify_add_watch,
+            inotify_read,
+            inotify_rm_watch,
+        };
+    }
+}
+
+//==================================================================================================
+// Structures
+//==================================================================================================
+
+///
+/// # Description
+///
+/// This structure describes a filesystem event on a watched path.
+///
+#[derive(Debug, Default, Clone, Copy)]
+#[repr(C)]
+pub struct inotify_event {
+    /// Watch descriptor.
+    pub wd: i32,
+    /// Event mask.
+    pub mask: u32,
+    /// Cookie that associates the two halves of a rename.
+    pub cookie: u32,
+    /// Length of the name of the file that the event refers to.
+    pub len: u32,
+}
+
+//==================================================================================================
+// Constants
+//==================================================================================================
+
+/// File was modified.
+pub const IN_MODIFY: u32 = 0x0000_0002;
+/// File was moved out of a watched directory.
+pub const IN_MOVED_FROM: u32 = 0x0000_0040;
+/// File was moved into a watched directory.
+pub const IN_MOVED_TO: u32 = 0x0000_0080;
+/// File was created in a watched directory.
+pub const IN_CREATE: u32 = 0x0000_0100;
+/// File was deleted from a watched directory.
+pub const IN_DELETE: u32 = 0x0000_0200;
+/// Watched path was deleted.
+pub const IN_DELETE_SELF: u32 = 0x0000_0400;
+/// Watched path was moved.
+pub const IN_MOVE_SELF: u32 = 0x0000_0800;
+/// Watch was removed, either explicitly or because the watched path is gone.
+pub const IN_IGNORED: u32 = 0x0000_8000;
+/// Event refers to a directory.
+pub const IN_ISDIR: u32 = 0x4000_0000;
+
+/// File was moved.
+pub const IN_MOVE: u32 = IN_MOVED_FROM | IN_MOVED_TO;
+/// All events that can be watched.
+pub const IN_ALL_EVENTS: u32 =
+    IN_MODIFY | IN_MOVED_FROM | IN_MOVED_TO | IN_CREATE | IN_DELETE | IN_DELETE_SELF | IN_MOVE_SELF;
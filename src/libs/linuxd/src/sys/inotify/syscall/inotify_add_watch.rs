@@ -0,0 +1,121 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::{
+    message::MessagePartitioner,
+    sys::inotify::message::{
+        InotifyAddWatchRequest,
+        InotifyAddWatchResponse,
+    },
+    LinuxDaemonMessage,
+    LinuxDaemonMessageHeader,
+};
+use ::alloc::{
+    string::ToString,
+    vec::Vec,
+};
+use ::nvx::{
+    ipc::Message,
+    pm::ProcessIdentifier,
+    sys::error::ErrorCode,
+};
+
+//==================================================================================================
+// Standalone Functions
+//==================================================================================================
+
+///
+/// # Description
+///
+/// The `inotify_add_watch()` system call adds a watch for filesystem events on a path. Once the
+/// watch is added, the Linux daemon pushes matching events to the calling process, which should
+/// retrieve them with `inotify_read()`. Paths outside of the virtual environment cannot be
+/// watched.
+///
+/// # Parameters
+///
+/// - `path`: Path to watch.
+/// - `mask`: Mask of events to watch.
+///
+/// # Returns
+///
+/// Upon successful completion, a watch descriptor is returned. Upon failure, a negative error code
+/// is returned instead.
+///
+pub fn inotify_add_watch(path: &str, mask: u32) -> i32 {
+    // Check if path is empty.
+    if path.is_empty() {
+        return ErrorCode::NoSuchEntry.into_errno();
+    }
+
+    // Send request.
+    let status: i32 = inotify_add_watch_request(path, mask);
+    if status != 0 {
+        return status;
+    }
+
+    // Wait for response.
+    inotify_add_watch_response()
+}
+
+fn inotify_add_watch_request(path: &str, mask: u32) -> i32 {
+    let pid: ProcessIdentifier = match ::nvx::pm::getpid() {
+        Ok(pid) => pid,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    let request: InotifyAddWatchRequest = match InotifyAddWatchRequest::new(path.to_string(), mask)
+    {
+        Ok(request) => request,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    let requests: Vec<Message> = match request.into_parts(pid) {
+        Ok(requests) => requests,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    for request in requests {
+        if let Err(e) = ::nvx::ipc::send(&request) {
+            return e.code.into_errno();
+        }
+    }
+
+    0
+}
+
+fn inotify_add_watch_response() -> i32 {
+    let response: Message = match ::nvx::ipc::recv() {
+        Ok(response) => response,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    // Check whether system call succeeded or not.
+    if response.status != 0 {
+        // System call failed, parse error code and return it.
+        match ErrorCode::try_from(response.status) {
+            Ok(e) => e.into_errno(),
+            Err(_) => ErrorCode::InvalidMessage.into_errno(),
+        }
+    } else {
+        // System call succeeded, parse response.
+        match LinuxDaemonMessage::try_from_bytes(response.payload) {
+            Ok(message) => match message.header {
+                LinuxDaemonMessageHeader::InotifyAddWatchResponse => {
+                    // Parse response.
+                    let response: InotifyAddWatchResponse =
+                        InotifyAddWatchResponse::from_bytes(message.payload);
+
+                    // Return watch descriptor.
+                    response.wd
+                },
+                _ => ErrorCode::InvalidMessage.into_errno(),
+            },
+            _ => ErrorCode::InvalidMessage.into_errno(),
+        }
+    }
+}
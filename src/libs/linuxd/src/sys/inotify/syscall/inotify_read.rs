@@ -0,0 +1,117 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::{
+    message::{
+        LinuxDaemonLongMessage,
+        LinuxDaemonMessagePart,
+        MessageDeserializer,
+        MessagePartitioner,
+    },
+    sys::inotify::{
+        inotify_event,
+        message::InotifyEvent,
+    },
+    LinuxDaemonMessage,
+    LinuxDaemonMessageHeader,
+};
+use ::alloc::vec::Vec;
+use ::nvx::{
+    ipc::Message,
+    sys::error::ErrorCode,
+};
+
+//==================================================================================================
+// Standalone Functions
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Waits for the next filesystem event that the Linux daemon pushes for a watch of the calling
+/// process.
+///
+/// # Parameters
+///
+/// - `event`: Storage location for the event.
+/// - `name`: Buffer to store the name of the file that the event refers to.
+///
+/// # Returns
+///
+/// Upon successful completion, `0` is returned and the length of the name is stored in
+/// `event.len`. Upon failure, a negative error code is returned instead.
+///
+/// # Notes
+///
+/// Events are delivered asynchronously, so this function should not be called while other system
+/// calls of the same process are in flight.
+///
+pub fn inotify_read(event: &mut inotify_event, name: &mut [u8]) -> i32 {
+    let capacity: usize = LinuxDaemonLongMessage::capacity(InotifyEvent::MAX_SIZE);
+
+    let mut assembler: LinuxDaemonLongMessage = match LinuxDaemonLongMessage::new(capacity) {
+        Ok(assembler) => assembler,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    loop {
+        let message: Message = match ::nvx::ipc::recv() {
+            Ok(message) => message,
+            Err(e) => break e.code.into_errno(),
+        };
+
+        // Check whether the daemon reported an error.
+        if message.status != 0 {
+            match ErrorCode::try_from(message.status) {
+                Ok(e) => break e.into_errno(),
+                Err(_) => break ErrorCode::InvalidMessage.into_errno(),
+            }
+        }
+
+        match LinuxDaemonMessage::try_from_bytes(message.payload) {
+            Ok(message) => match message.header {
+                LinuxDaemonMessageHeader::InotifyEventPart => {
+                    let part: LinuxDaemonMessagePart =
+                        match LinuxDaemonMessagePart::try_from_bytes(message.payload) {
+                            Ok(part) => part,
+                            Err(e) => break e.code.into_errno(),
+                        };
+
+                    if let Err(e) = assembler.add_part(part) {
+                        break e.code.into_errno();
+                    }
+
+                    if !assembler.is_complete() {
+                        continue;
+                    }
+
+                    let parts: Vec<LinuxDaemonMessagePart> = assembler.take_parts();
+
+                    match InotifyEvent::from_parts(&parts) {
+                        Ok(e) => {
+                            // Check if name does not fit in the buffer.
+                            if e.name.len() > name.len() {
+                                break ErrorCode::InvalidArgument.into_errno();
+                            }
+                            name[..e.name.len()].copy_from_slice(e.name.as_bytes());
+
+                            event.wd = e.wd;
+                            event.mask = e.mask;
+                            event.cookie = e.cookie;
+                            event.len = e.name.len() as u32;
+
+                            break 0;
+                        },
+                        Err(_) => break ErrorCode::InvalidMessage.into_errno(),
+                    }
+                },
+                _ => break ErrorCode::InvalidMessage.into_errno(),
+            },
+            Err(_) => break ErrorCode::InvalidMessage.into_errno(),
+        }
+    }
+}
@@ -0,0 +1,87 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::{
+    sys::inotify::message::{
+        InotifyRemoveWatchRequest,
+        InotifyRemoveWatchResponse,
+    },
+    LinuxDaemonMessage,
+    LinuxDaemonMessageHeader,
+};
+use ::nvx::{
+    ipc::Message,
+    pm::ProcessIdentifier,
+    sys::error::ErrorCode,
+};
+
+//==================================================================================================
+// Standalone Functions
+//==================================================================================================
+
+///
+/// # Description
+///
+/// The `inotify_rm_watch()` system call removes a watch that was added with
+/// `inotify_add_watch()`.
+///
+/// # Parameters
+///
+/// - `wd`: Watch descriptor.
+///
+/// # Returns
+///
+/// Upon successful completion, `0` is returned. Upon failure, a negative error code is returned
+/// instead.
+///
+pub fn inotify_rm_watch(wd: i32) -> i32 {
+    let pid: ProcessIdentifier = match ::nvx::pm::getpid() {
+        Ok(pid) => pid,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    // Build request and send it.
+    let request: Message = InotifyRemoveWatchRequest::build(pid, wd);
+    if let Err(e) = ::nvx::ipc::send(&request) {
+        return e.code.into_errno();
+    }
+
+    // Receive response.
+    let response: Message = match ::nvx::ipc::recv() {
+        Ok(response) => response,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    // Check whether system call succeeded or not.
+    if response.status != 0 {
+        // System call failed, parse error code and return it.
+        match ErrorCode::try_from(response.status) {
+            Ok(e) => e.into_errno(),
+            Err(_) => ErrorCode::InvalidMessage.into_errno(),
+        }
+    } else {
+        // System call succeeded, parse response.
+        match LinuxDaemonMessage::try_from_bytes(response.payload) {
+            // Response was successfully parsed.
+            Ok(message) => match message.header {
+                // Response was successfully parsed.
+                LinuxDaemonMessageHeader::InotifyRemoveWatchResponse => {
+                    // Parse response.
+                    let response: InotifyRemoveWatchResponse =
+                        InotifyRemoveWatchResponse::from_bytes(message.payload);
+
+                    // Return result.
+                    response.ret
+                },
+                // Response was not successfully parsed.
+                _ => ErrorCode::InvalidMessage.into_errno(),
+            },
+            // Response was not successfully parsed.
+            Err(_) => ErrorCode::InvalidMessage.into_errno(),
+        }
+    }
+}
@@ -0,0 +1,20 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Modules
+//==================================================================================================
+
+mod inotify_add_watch;
+mod inotify_read;
+mod inotify_rm_watch;
+
+//==================================================================================================
+// Exports
+//==================================================================================================
+
+pub use self::{
+    inotify_add_watch::inotify_add_watch,
+    inotify_read::inotify_read,
+    inotify_rm_watch::inotify_rm_watch,
+};
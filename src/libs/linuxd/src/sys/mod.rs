@@ -5,6 +5,18 @@
 // Modules
 //==================================================================================================
 
+/// File locking.
+pub mod file;
+
+/// Filesystem event notification.
+pub mod inotify;
+
+/// Resource limits and usage.
+pub mod resource;
+
+/// Data transfer between file descriptors.
+pub mod sendfile;
+
 /// File status.
 pub mod stat;
 
@@ -13,3 +25,6 @@ pub mod types;
 
 /// Definitions for vector I/O operations.
 pub mod uio;
+
+/// Extended attributes.
+pub mod xattr;
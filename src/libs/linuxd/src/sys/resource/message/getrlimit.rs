@@ -0,0 +1,138 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::{
+    sys::resource::rlimit,
+    wire::{
+        WireDecoder,
+        WireEncoder,
+    },
+    LinuxDaemonMessage,
+    LinuxDaemonMessageHeader,
+};
+use ::core::mem;
+use ::nvx::{
+    ipc::{
+        Message,
+        MessageType,
+    },
+    pm::ProcessIdentifier,
+};
+
+//==================================================================================================
+// GetResourceLimitRequest
+//==================================================================================================
+
+///
+/// # Description
+///
+/// This struct represents the request message of the `getrlimit()` system call.
+///
+#[derive(Debug)]
+#[repr(C, packed)]
+pub struct GetResourceLimitRequest {
+    /// Resource.
+    pub resource: i32,
+    _padding: [u8; Self::PADDING_SIZE],
+}
+::nvx::sys::static_assert_size!(GetResourceLimitRequest, LinuxDaemonMessage::PAYLOAD_SIZE);
+
+impl GetResourceLimitRequest {
+    pub const PADDING_SIZE: usize = LinuxDaemonMessage::PAYLOAD_SIZE - mem::size_of::<i32>();
+
+    fn new(resource: i32) -> Self {
+        Self {
+            resource,
+            _padding: [0; Self::PADDING_SIZE],
+        }
+    }
+
+    pub fn from_bytes(bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE]) -> Self {
+        let mut decoder: WireDecoder = WireDecoder::new(&bytes);
+        Self {
+            resource: decoder.get(),
+            _padding: [0; Self::PADDING_SIZE],
+        }
+    }
+
+    fn into_bytes(self) -> [u8; LinuxDaemonMessage::PAYLOAD_SIZE] {
+        let mut bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE] =
+            [0; LinuxDaemonMessage::PAYLOAD_SIZE];
+        let mut encoder: WireEncoder = WireEncoder::new(&mut bytes);
+        encoder.put(self.resource);
+        bytes
+    }
+
+    pub fn build(pid: ProcessIdentifier, resource: i32) -> Message {
+        let message: GetResourceLimitRequest = GetResourceLimitRequest::new(resource);
+        let message: LinuxDaemonMessage = LinuxDaemonMessage::new(
+            LinuxDaemonMessageHeader::GetResourceLimitRequest,
+            message.into_bytes(),
+        );
+        let message: Message =
+            Message::new(pid, crate::LINUXD, MessageType::Ikc, None, message.into_bytes());
+
+        message
+    }
+}
+
+//==================================================================================================
+// GetResourceLimitResponse
+//==================================================================================================
+
+///
+/// # Description
+///
+/// This struct represents the response message of the `getrlimit()` system call.
+///
+#[derive(Debug)]
+#[repr(C, packed)]
+pub struct GetResourceLimitResponse {
+    /// Resource limit.
+    pub rlim: rlimit,
+    _padding: [u8; Self::PADDING_SIZE],
+}
+::nvx::sys::static_assert_size!(GetResourceLimitResponse, LinuxDaemonMessage::PAYLOAD_SIZE);
+
+impl GetResourceLimitResponse {
+    pub const PADDING_SIZE: usize = LinuxDaemonMessage::PAYLOAD_SIZE - mem::size_of::<rlimit>();
+
+    fn new(rlim: rlimit) -> Self {
+        Self {
+            rlim,
+            _padding: [0; Self::PADDING_SIZE],
+        }
+    }
+
+    pub fn from_bytes(bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE]) -> Self {
+        let mut decoder: WireDecoder = WireDecoder::new(&bytes);
+        Self {
+            rlim: decoder.get(),
+            _padding: [0; Self::PADDING_SIZE],
+        }
+    }
+
+    fn into_bytes(self) -> [u8; LinuxDaemonMessage::PAYLOAD_SIZE] {
+        let mut bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE] =
+            [0; LinuxDaemonMessage::PAYLOAD_SIZE];
+        let mut encoder: WireEncoder = WireEncoder::new(&mut bytes);
+        encoder.put(self.rlim);
+        bytes
+    }
+
+    pub fn build(pid: ProcessIdentifier, rlim: rlimit) -> Message {
+        let message: GetResourceLimitResponse = GetResourceLimitResponse::new(rlim);
+        let message: LinuxDaemonMessage = LinuxDaemonMessage::new(
+            LinuxDaemonMessageHeader::GetResourceLimitResponse,
+            message.into_bytes(),
+        );
+        let message: Message =
+            Message::new(crate::LINUXD, pid, MessageType::Ikc, None, message.into_bytes());
+
+        message
+    }
+}
@@ -0,0 +1,138 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::{
+    sys::resource::rusage,
+    wire::{
+        WireDecoder,
+        WireEncoder,
+    },
+    LinuxDaemonMessage,
+    LinuxDaemonMessageHeader,
+};
+use ::core::mem;
+use ::nvx::{
+    ipc::{
+        Message,
+        MessageType,
+    },
+    pm::ProcessIdentifier,
+};
+
+//==================================================================================================
+// GetResourceUsageRequest
+//==================================================================================================
+
+///
+/// # Description
+///
+/// This struct represents the request message of the `getrusage()` system call.
+///
+#[derive(Debug)]
+#[repr(C, packed)]
+pub struct GetResourceUsageRequest {
+    /// Target of the query.
+    pub who: i32,
+    _padding: [u8; Self::PADDING_SIZE],
+}
+::nvx::sys::static_assert_size!(GetResourceUsageRequest, LinuxDaemonMessage::PAYLOAD_SIZE);
+
+impl GetResourceUsageRequest {
+    pub const PADDING_SIZE: usize = LinuxDaemonMessage::PAYLOAD_SIZE - mem::size_of::<i32>();
+
+    fn new(who: i32) -> Self {
+        Self {
+            who,
+            _padding: [0; Self::PADDING_SIZE],
+        }
+    }
+
+    pub fn from_bytes(bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE]) -> Self {
+        let mut decoder: WireDecoder = WireDecoder::new(&bytes);
+        Self {
+            who: decoder.get(),
+            _padding: [0; Self::PADDING_SIZE],
+        }
+    }
+
+    fn into_bytes(self) -> [u8; LinuxDaemonMessage::PAYLOAD_SIZE] {
+        let mut bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE] =
+            [0; LinuxDaemonMessage::PAYLOAD_SIZE];
+        let mut encoder: WireEncoder = WireEncoder::new(&mut bytes);
+        encoder.put(self.who);
+        bytes
+    }
+
+    pub fn build(pid: ProcessIdentifier, who: i32) -> Message {
+        let message: GetResourceUsageRequest = GetResourceUsageRequest::new(who);
+        let message: LinuxDaemonMessage = LinuxDaemonMessage::new(
+            LinuxDaemonMessageHeader::GetResourceUsageRequest,
+            message.into_bytes(),
+        );
+        let message: Message =
+            Message::new(pid, crate::LINUXD, MessageType::Ikc, None, message.into_bytes());
+
+        message
+    }
+}
+
+//==================================================================================================
+// GetResourceUsageResponse
+//==================================================================================================
+
+///
+/// # Description
+///
+/// This struct represents the response message of the `getrusage()` system call.
+///
+#[derive(Debug)]
+#[repr(C, packed)]
+pub struct GetResourceUsageResponse {
+    /// Resource usage.
+    pub usage: rusage,
+    _padding: [u8; Self::PADDING_SIZE],
+}
+::nvx::sys::static_assert_size!(GetResourceUsageResponse, LinuxDaemonMessage::PAYLOAD_SIZE);
+
+impl GetResourceUsageResponse {
+    pub const PADDING_SIZE: usize = LinuxDaemonMessage::PAYLOAD_SIZE - mem::size_of::<rusage>();
+
+    fn new(usage: rusage) -> Self {
+        Self {
+            usage,
+            _padding: [0; Self::PADDING_SIZE],
+        }
+    }
+
+    pub fn from_bytes(bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE]) -> Self {
+        let mut decoder: WireDecoder = WireDecoder::new(&bytes);
+        Self {
+            usage: decoder.get(),
+            _padding: [0; Self::PADDING_SIZE],
+        }
+    }
+
+    fn into_bytes(self) -> [u8; LinuxDaemonMessage::PAYLOAD_SIZE] {
+        let mut bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE] =
+            [0; LinuxDaemonMessage::PAYLOAD_SIZE];
+        let mut encoder: WireEncoder = WireEncoder::new(&mut bytes);
+        encoder.put(self.usage);
+        bytes
+    }
+
+    pub fn build(pid: ProcessIdentifier, usage: rusage) -> Message {
+        let message: GetResourceUsageResponse = GetResourceUsageResponse::new(usage);
+        let message: LinuxDaemonMessage = LinuxDaemonMessage::new(
+            LinuxDaemonMessageHeader::GetResourceUsageResponse,
+            message.into_bytes(),
+        );
+        let message: Message =
+            Message::new(crate::LINUXD, pid, MessageType::Ikc, None, message.into_bytes());
+
+        message
+    }
+}
@@ -0,0 +1,86 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Configuration
+//==================================================================================================
+
+#![allow(non_camel_case_types)]
+
+//==================================================================================================
+// Modules
+//==================================================================================================
+
+pub mod message;
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "syscall")] {
+        mod syscall;
+        pub use self::syscall::{
+            getrlimit,
+            getrusage,
+        };
+    }
+}
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::wire::WireFormat;
+
+//==================================================================================================
+// Types
+//==================================================================================================
+
+/// Used for resource limit values.
+pub type rlim_t = u64;
+
+//==================================================================================================
+// Constants
+//==================================================================================================
+
+/// Limit value that stands for no limit.
+pub const RLIM_INFINITY: rlim_t = rlim_t::MAX;
+
+/// Maximum size of a file, in bytes.
+pub const RLIMIT_FSIZE: i32 = 1;
+/// Maximum number of open file descriptors.
+pub const RLIMIT_NOFILE: i32 = 7;
+/// Maximum number of bytes written.
+pub const RLIMIT_WBYTES: i32 = 16;
+/// Maximum number of bytes of disk space in use.
+pub const RLIMIT_DBYTES: i32 = 17;
+/// Maximum number of requests per second.
+pub const RLIMIT_NREQS: i32 = 18;
+
+/// Resource usage of the virtual environment of the calling process.
+pub const RUSAGE_SELF: i32 = 0;
+
+//==================================================================================================
+// Structures
+//==================================================================================================
+
+/// Resource limit structure.
+#[derive(Default, Debug, Clone, Copy, WireFormat)]
+#[repr(C, packed)]
+pub struct rlimit {
+    /// Soft limit.
+    pub rlim_cur: rlim_t,
+    /// Hard limit.
+    pub rlim_max: rlim_t,
+}
+
+/// Resource usage structure.
+#[derive(Default, Debug, Clone, Copy, WireFormat)]
+#[repr(C, packed)]
+pub struct rusage {
+    /// Number of open file descriptors.
+    pub ru_nofile: u64,
+    /// Number of bytes written.
+    pub ru_wbytes: u64,
+    /// Number of bytes of disk space in use.
+    pub ru_dbytes: u64,
+    /// Number of requests in the current second.
+    pub ru_nreqs: u64,
+}
@@ -0,0 +1,94 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::{
+    sys::resource::{
+        message::{
+            GetResourceLimitRequest,
+            GetResourceLimitResponse,
+        },
+        rlimit,
+    },
+    LinuxDaemonMessage,
+    LinuxDaemonMessageHeader,
+};
+use ::nvx::{
+    ipc::Message,
+    pm::ProcessIdentifier,
+    sys::error::ErrorCode,
+};
+
+//==================================================================================================
+// Standalone Functions
+//==================================================================================================
+
+///
+/// # Description
+///
+/// The `getrlimit()` system call gets a resource limit of the virtual environment of the calling
+/// process.
+///
+/// # Parameters
+///
+/// - `resource`: Resource (`RLIMIT_FSIZE`, `RLIMIT_NOFILE`, `RLIMIT_WBYTES`, `RLIMIT_DBYTES` or
+///   `RLIMIT_NREQS`).
+/// - `rlim`: Buffer to store the resource limit.
+///
+/// # Returns
+///
+/// Upon successful completion, `0` is returned. Upon failure, a negative error code is returned
+/// instead.
+///
+pub fn getrlimit(resource: i32, rlim: &mut rlimit) -> i32 {
+    let pid: ProcessIdentifier = match ::nvx::pm::getpid() {
+        Ok(pid) => pid,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    // Build request and send it.
+    let request: Message = GetResourceLimitRequest::build(pid, resource);
+    if let Err(e) = ::nvx::ipc::send(&request) {
+        return e.code.into_errno();
+    }
+
+    // Receive response.
+    let response: Message = match ::nvx::ipc::recv() {
+        Ok(response) => response,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    // Check whether system call succeeded or not.
+    if response.status != 0 {
+        // System call failed, parse error code and return it.
+        match ErrorCode::try_from(response.status) {
+            Ok(e) => e.into_errno(),
+            Err(_) => ErrorCode::InvalidMessage.into_errno(),
+        }
+    } else {
+        // System call succeeded, parse response.
+        match LinuxDaemonMessage::try_from_bytes(response.payload) {
+            // Response was successfully parsed.
+            Ok(message) => match message.header {
+                // Response was successfully parsed.
+                LinuxDaemonMessageHeader::GetResourceLimitResponse => {
+                    // Parse response.
+                    let response: GetResourceLimitResponse =
+                        GetResourceLimitResponse::from_bytes(message.payload);
+
+                    // Copy resource limit.
+                    *rlim = response.rlim;
+
+                    0
+                },
+                // Response was not successfully parsed.
+                _ => ErrorCode::InvalidMessage.into_errno(),
+            },
+            // Response was not successfully parsed.
+            Err(_) => ErrorCode::InvalidMessage.into_errno(),
+        }
+    }
+}
@@ -0,0 +1,93 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::{
+    sys::resource::{
+        message::{
+            GetResourceUsageRequest,
+            GetResourceUsageResponse,
+        },
+        rusage,
+    },
+    LinuxDaemonMessage,
+    LinuxDaemonMessageHeader,
+};
+use ::nvx::{
+    ipc::Message,
+    pm::ProcessIdentifier,
+    sys::error::ErrorCode,
+};
+
+//==================================================================================================
+// Standalone Functions
+//==================================================================================================
+
+///
+/// # Description
+///
+/// The `getrusage()` system call gets the resource usage of the virtual environment of the calling
+/// process.
+///
+/// # Parameters
+///
+/// - `who`: Target of the query (`RUSAGE_SELF`).
+/// - `usage`: Buffer to store the resource usage.
+///
+/// # Returns
+///
+/// Upon successful completion, `0` is returned. Upon failure, a negative error code is returned
+/// instead.
+///
+pub fn getrusage(who: i32, usage: &mut rusage) -> i32 {
+    let pid: ProcessIdentifier = match ::nvx::pm::getpid() {
+        Ok(pid) => pid,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    // Build request and send it.
+    let request: Message = GetResourceUsageRequest::build(pid, who);
+    if let Err(e) = ::nvx::ipc::send(&request) {
+        return e.code.into_errno();
+    }
+
+    // Receive response.
+    let response: Message = match ::nvx::ipc::recv() {
+        Ok(response) => response,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    // Check whether system call succeeded or not.
+    if response.status != 0 {
+        // System call failed, parse error code and return it.
+        match ErrorCode::try_from(response.status) {
+            Ok(e) => e.into_errno(),
+            Err(_) => ErrorCode::InvalidMessage.into_errno(),
+        }
+    } else {
+        // System call succeeded, parse response.
+        match LinuxDaemonMessage::try_from_bytes(response.payload) {
+            // Response was successfully parsed.
+            Ok(message) => match message.header {
+                // Response was successfully parsed.
+                LinuxDaemonMessageHeader::GetResourceUsageResponse => {
+                    // Parse response.
+                    let response: GetResourceUsageResponse =
+                        GetResourceUsageResponse::from_bytes(message.payload);
+
+                    // Copy resource usage.
+                    *usage = response.usage;
+
+                    0
+                },
+                // Response was not successfully parsed.
+                _ => ErrorCode::InvalidMessage.into_errno(),
+            },
+            // Response was not successfully parsed.
+            Err(_) => ErrorCode::InvalidMessage.into_errno(),
+        }
+    }
+}
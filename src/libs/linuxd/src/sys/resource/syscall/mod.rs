@@ -0,0 +1,18 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Modules
+//==================================================================================================
+
+mod getrlimit;
+mod getrusage;
+
+//==================================================================================================
+// Exports
+//==================================================================================================
+
+pub use self::{
+    getrlimit::getrlimit,
+    getrusage::getrusage,
+};
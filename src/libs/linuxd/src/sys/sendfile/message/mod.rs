@@ -0,0 +1,17 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Modules
+//==================================================================================================
+
+mod sendfile;
+
+//==================================================================================================
+// Exports
+//==================================================================================================
+
+pub use self::sendfile::{
+    SendFileRequest,
+    SendFileResponse,
+};
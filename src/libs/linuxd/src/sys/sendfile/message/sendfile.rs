@@ -0,0 +1,192 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::{
+    sys::types::{
+        off_t,
+        size_t,
+        ssize_t,
+    },
+    wire::{
+        WireDecoder,
+        WireEncoder,
+    },
+    LinuxDaemonMessage,
+    LinuxDaemonMessageHeader,
+};
+use ::core::mem;
+use ::nvx::{
+    ipc::{
+        Message,
+        MessageType,
+    },
+    pm::ProcessIdentifier,
+};
+
+//==================================================================================================
+// Constants
+//==================================================================================================
+
+/// Offset value that instructs the daemon to use (and update) the file offset of the descriptor.
+const NO_OFFSET: off_t = -1;
+
+//==================================================================================================
+// SendFileRequest
+//==================================================================================================
+
+///
+/// # Description
+///
+/// This struct represents the request message of the `sendfile()` system call.
+///
+#[derive(Debug)]
+#[repr(C, packed)]
+pub struct SendFileRequest {
+    /// Target file descriptor.
+    pub out_fd: i32,
+    /// Source file descriptor.
+    pub in_fd: i32,
+    /// Offset in source file, or `NO_OFFSET` to use the file offset of `in_fd`.
+    offset: off_t,
+    /// Number of bytes to transfer.
+    pub count: size_t,
+    _padding: [u8; Self::PADDING_SIZE],
+}
+::nvx::sys::static_assert_size!(SendFileRequest, LinuxDaemonMessage::PAYLOAD_SIZE);
+
+impl SendFileRequest {
+    pub const PADDING_SIZE: usize = LinuxDaemonMessage::PAYLOAD_SIZE
+        - mem::size_of::<i32>()
+        - mem::size_of::<i32>()
+        - mem::size_of::<off_t>()
+        - mem::size_of::<size_t>();
+
+    fn new(out_fd: i32, in_fd: i32, offset: Option<off_t>, count: size_t) -> Self {
+        Self {
+            out_fd,
+            in_fd,
+            offset: offset.unwrap_or(NO_OFFSET),
+            count,
+            _padding: [0; Self::PADDING_SIZE],
+        }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the offset in the source file, if any.
+    ///
+    pub fn offset(&self) -> Option<off_t> {
+        match self.offset {
+            NO_OFFSET => None,
+            offset => Some(offset),
+        }
+    }
+
+    pub fn from_bytes(bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE]) -> Self {
+        let mut decoder: WireDecoder = WireDecoder::new(&bytes);
+        Self {
+            out_fd: decoder.get(),
+            in_fd: decoder.get(),
+            offset: decoder.get(),
+            count: decoder.get(),
+            _padding: [0; Self::PADDING_SIZE],
+        }
+    }
+
+    fn into_bytes(self) -> [u8; LinuxDaemonMessage::PAYLOAD_SIZE] {
+        let mut bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE] =
+            [0; LinuxDaemonMessage::PAYLOAD_SIZE];
+        let mut encoder: WireEncoder = WireEncoder::new(&mut bytes);
+        encoder.put(self.out_fd);
+        encoder.put(self.in_fd);
+        encoder.put(self.offset);
+        encoder.put(self.count);
+        bytes
+    }
+
+    pub fn build(
+        pid: ProcessIdentifier,
+        out_fd: i32,
+        in_fd: i32,
+        offset: Option<off_t>,
+        count: size_t,
+    ) -> Message {
+        let message: SendFileRequest = SendFileRequest::new(out_fd, in_fd, offset, count);
+        let message: LinuxDaemonMessage = LinuxDaemonMessage::new(
+            LinuxDaemonMessageHeader::SendFileRequest,
+            message.into_bytes(),
+        );
+        let message: Message =
+            Message::new(pid, crate::LINUXD, MessageType::Ikc, None, message.into_bytes());
+
+        message
+    }
+}
+
+//==================================================================================================
+// SendFileResponse
+//==================================================================================================
+
+///
+/// # Description
+///
+/// This struct represents the response message of the `sendfile()` system call.
+///
+#[derive(Debug)]
+#[repr(C, packed)]
+pub struct SendFileResponse {
+    /// Number of bytes transferred.
+    pub count: ssize_t,
+    /// Updated offset in source file. Meaningless if no offset was supplied in the request.
+    pub offset: off_t,
+    _padding: [u8; Self::PADDING_SIZE],
+}
+::nvx::sys::static_assert_size!(SendFileResponse, LinuxDaemonMessage::PAYLOAD_SIZE);
+
+impl SendFileResponse {
+    pub const PADDING_SIZE: usize =
+        LinuxDaemonMessage::PAYLOAD_SIZE - mem::size_of::<ssize_t>() - mem::size_of::<off_t>();
+
+    fn new(count: ssize_t, offset: off_t) -> Self {
+        Self {
+            count,
+            offset,
+            _padding: [0; Self::PADDING_SIZE],
+        }
+    }
+
+    pub fn from_bytes(bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE]) -> Self {
+        let mut decoder: WireDecoder = WireDecoder::new(&bytes);
+        Self {
+            count: decoder.get(),
+            offset: decoder.get(),
+            _padding: [0; Self::PADDING_SIZE],
+        }
+    }
+
+    fn into_bytes(self) -> [u8; LinuxDaemonMessage::PAYLOAD_SIZE] {
+        let mut bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE] =
+            [0; LinuxDaemonMessage::PAYLOAD_SIZE];
+        let mut encoder: WireEncoder = WireEncoder::new(&mut bytes);
+        encoder.put(self.count);
+        encoder.put(self.offset);
+        bytes
+    }
+
+    pub fn build(pid: ProcessIdentifier, count: ssize_t, offset: off_t) -> Message {
+        let message: SendFileResponse = SendFileResponse::new(count, offset);
+        let message: LinuxDaemonMessage = LinuxDaemonMessage::new(
+            LinuxDaemonMessageHeader::SendFileResponse,
+            message.into_bytes(),
+        );
+        let message: Message =
+            Message::new(crate::LINUXD, pid, MessageType::Ikc, None, message.into_bytes());
+
+        message
+    }
+}
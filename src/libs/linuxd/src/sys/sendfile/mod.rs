@@ -0,0 +1,15 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Modules
+//==================================================================================================
+
+pub mod message;
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "syscall")] {
+        mod syscall;
+        pub use self::syscall::sendfile;
+    }
+}
@@ -0,0 +1,14 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Modules
+//==================================================================================================
+
+mod sendfile;
+
+//==================================================================================================
+// Exports
+//==================================================================================================
+
+pub use self::sendfile::sendfile;
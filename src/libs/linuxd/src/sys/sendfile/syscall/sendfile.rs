@@ -0,0 +1,108 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::{
+    sys::{
+        sendfile::message::{
+            SendFileRequest,
+            SendFileResponse,
+        },
+        types::{
+            off_t,
+            size_t,
+            ssize_t,
+        },
+    },
+    LinuxDaemonMessage,
+    LinuxDaemonMessageHeader,
+};
+use ::nvx::{
+    ipc::Message,
+    pm::ProcessIdentifier,
+    sys::error::ErrorCode,
+};
+
+//==================================================================================================
+// Standalone Functions
+//==================================================================================================
+
+///
+/// # Description
+///
+/// The `sendfile()` system call transfers data between two file descriptors. The transfer is
+/// carried out entirely on the host, and only the number of transferred bytes is sent back.
+///
+/// # Parameters
+///
+/// - `out_fd`: Target file descriptor.
+/// - `in_fd`: Source file descriptor.
+/// - `offset`: Offset in the source file. If `None`, the file offset of `in_fd` is used and
+///   updated. Otherwise, the file offset is left untouched and `offset` is updated instead.
+/// - `count`: Number of bytes to transfer.
+///
+/// # Returns
+///
+/// Upon successful completion, the number of bytes transferred is returned. Upon failure, a
+/// negative error code is returned instead.
+///
+pub fn sendfile(out_fd: i32, in_fd: i32, offset: Option<&mut off_t>, count: size_t) -> ssize_t {
+    // Check if offset is invalid.
+    if offset.as_deref().is_some_and(|off| *off < 0) {
+        return ErrorCode::InvalidArgument.into_errno();
+    }
+
+    let pid: ProcessIdentifier = match ::nvx::pm::getpid() {
+        Ok(pid) => pid,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    // Build request and send it.
+    let request: Message =
+        SendFileRequest::build(pid, out_fd, in_fd, offset.as_deref().copied(), count);
+    if let Err(e) = ::nvx::ipc::send(&request) {
+        return e.code.into_errno();
+    }
+
+    // Receive response.
+    let response: Message = match ::nvx::ipc::recv() {
+        Ok(response) => response,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    // Check whether system call succeeded or not.
+    if response.status != 0 {
+        // System call failed, parse error code and return it.
+        match ErrorCode::try_from(response.status) {
+            Ok(e) => e.into_errno(),
+            Err(_) => ErrorCode::InvalidMessage.into_errno(),
+        }
+    } else {
+        // System call succeeded, parse response.
+        match LinuxDaemonMessage::try_from_bytes(response.payload) {
+            // Response was successfully parsed.
+            Ok(message) => match message.header {
+                // Response was successfully parsed.
+                LinuxDaemonMessageHeader::SendFileResponse => {
+                    // Parse response.
+                    let response: SendFileResponse = SendFileResponse::from_bytes(message.payload);
+
+                    // Update offset.
+                    if let Some(offset) = offset {
+                        *offset = response.offset;
+                    }
+
+                    // Return number of bytes transferred.
+                    response.count
+                },
+                // Response was not successfully parsed.
+                _ => ErrorCode::InvalidMessage.into_errno(),
+            },
+            // Response was not successfully parsed.
+            Err(_) => ErrorCode::InvalidMessage.into_errno(),
+        }
+    }
+}
@@ -6,6 +6,10 @@
 //==================================================================================================
 
 use crate::{
+    wire::{
+        WireDecoder,
+        WireEncoder,
+    },
     LinuxDaemonMessage,
     LinuxDaemonMessageHeader,
 };
@@ -50,12 +54,20 @@ impl FileStatRequest {
 
     /// Creates a new request message from a byte array.
     pub fn from_bytes(bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE]) -> Self {
-        unsafe { mem::transmute(bytes) }
+        let mut decoder: WireDecoder = WireDecoder::new(&bytes);
+        Self {
+            fd: decoder.get(),
+            padding: [0; Self::PADDING_SIZE],
+        }
     }
 
     /// Converts the request message to a byte array.
     fn into_bytes(self) -> [u8; LinuxDaemonMessage::PAYLOAD_SIZE] {
-        unsafe { mem::transmute(self) }
+        let mut bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE] =
+            [0; LinuxDaemonMessage::PAYLOAD_SIZE];
+        let mut encoder: WireEncoder = WireEncoder::new(&mut bytes);
+        encoder.put(self.fd);
+        bytes
     }
 
     pub fn build(pid: ProcessIdentifier, fd: i32) -> Message {
@@ -17,6 +17,7 @@ use crate::{
     message::{
         LinuxDaemonLongMessage,
         LinuxDaemonMessagePart,
+        MessageDeserializer,
         MessagePartitioner,
     },
     sys::{
@@ -59,7 +60,7 @@ pub use stat::stat;
 /// instead.
 ///
 fn fstatat_response(buf: &mut sys::stat::stat) -> i32 {
-    let capacity: usize = sys::stat::stat::SIZE.div_ceil(LinuxDaemonMessagePart::PAYLOAD_SIZE);
+    let capacity: usize = LinuxDaemonLongMessage::capacity(FileStatAtResponse::MAX_SIZE);
 
     let mut assembler: LinuxDaemonLongMessage = match LinuxDaemonLongMessage::new(capacity) {
         Ok(assembler) => assembler,
@@ -85,7 +86,10 @@ fn fstatat_response(buf: &mut sys::stat::stat) -> i32 {
                 Ok(message) => match message.header {
                     LinuxDaemonMessageHeader::FileStatAtResponsePart => {
                         let part: LinuxDaemonMessagePart =
-                            LinuxDaemonMessagePart::from_bytes(message.payload);
+                            match LinuxDaemonMessagePart::try_from_bytes(message.payload) {
+                                Ok(part) => part,
+                                Err(e) => break e.code.into_errno(),
+                            };
 
                         if let Err(e) = assembler.add_part(part) {
                             break e.code.into_errno();
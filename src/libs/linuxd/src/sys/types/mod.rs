@@ -30,6 +30,9 @@ pub type ino_t = u64;
 /// Used for link counts.
 pub type nlink_t = u64;
 
+/// Used for process IDs.
+pub type pid_t = i32;
+
 /// Used for file sizes.
 pub type off_t = i64;
 
@@ -0,0 +1,138 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::{
+    limits,
+    message::{
+        MessageDeserializer,
+        MessagePartitioner,
+        MessageSerializer,
+    },
+};
+use ::alloc::{
+    string::String,
+    vec::Vec,
+};
+use ::nvx::sys::error::{
+    Error,
+    ErrorCode,
+};
+
+//==================================================================================================
+// GetXattrRequest
+//==================================================================================================
+
+///
+/// # Description
+///
+/// This struct represents the request message of the `getxattr()` and `fgetxattr()` system calls.
+///
+#[derive(Debug, MessageSerializer, MessageDeserializer, MessagePartitioner)]
+#[message(request_part = GetXattrRequestPart)]
+pub struct GetXattrRequest {
+    /// File descriptor. Only meaningful if `path` is empty.
+    pub fd: i32,
+    /// Path. If empty, the request targets `fd`.
+    #[message(max_len = limits::PATH_MAX)]
+    pub path: String,
+    /// Name of the extended attribute.
+    #[message(min_len = 1, max_len = limits::XATTR_NAME_MAX)]
+    pub name: String,
+    /// Size of the value buffer. If zero, only the size of the value is queried.
+    pub size: usize,
+}
+
+impl GetXattrRequest {
+    ///
+    /// # Description
+    ///
+    /// Creates a request message of the `getxattr()` system call.
+    ///
+    /// # Parameters
+    ///
+    /// - `fd`: File descriptor.
+    /// - `path`: Path.
+    /// - `name`: Name of the extended attribute.
+    /// - `size`: Size of the value buffer.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the request message of the `getxattr()` system call is returned. Upon
+    /// failure, an error is returned instead.
+    ///
+    pub fn new(fd: i32, path: String, name: String, size: usize) -> Result<Self, Error> {
+        // Check if the path is too long.
+        if path.len() > limits::PATH_MAX {
+            return Err(Error::new(ErrorCode::InvalidMessage, "path too long"));
+        }
+
+        // Check if the name is invalid.
+        if name.is_empty() || name.len() > limits::XATTR_NAME_MAX {
+            return Err(Error::new(ErrorCode::InvalidMessage, "invalid attribute name"));
+        }
+
+        Ok(Self {
+            fd,
+            path,
+            name,
+            size,
+        })
+    }
+}
+
+//==================================================================================================
+// GetXattrResponse
+//==================================================================================================
+
+///
+/// # Description
+///
+/// This struct represents the response message of the `getxattr()` and `fgetxattr()` system
+/// calls.
+///
+#[derive(Debug, MessageSerializer, MessageDeserializer, MessagePartitioner)]
+#[message(response_part = GetXattrResponsePart, validate = Self::validate)]
+pub struct GetXattrResponse {
+    /// Size of the value of the extended attribute.
+    pub size: usize,
+    /// Value of the extended attribute. Empty if only the size was queried.
+    #[message(max_len = limits::XATTR_SIZE_MAX)]
+    pub value: Vec<u8>,
+}
+
+impl GetXattrResponse {
+    ///
+    /// # Description
+    ///
+    /// Creates a response message of the `getxattr()` system call.
+    ///
+    /// # Parameters
+    ///
+    /// - `size`: Size of the value of the extended attribute.
+    /// - `value`: Value of the extended attribute.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the response message of the `getxattr()` system call is returned. Upon
+    /// failure, an error is returned instead.
+    ///
+    pub fn new(size: usize, value: Vec<u8>) -> Result<Self, Error> {
+        let response: Self = Self { size, value };
+        response.validate()?;
+
+        Ok(response)
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        // Check if the value is too long.
+        if self.size > limits::XATTR_SIZE_MAX || self.value.len() > self.size {
+            return Err(Error::new(ErrorCode::InvalidMessage, "value too long"));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,125 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::{
+    limits,
+    message::{
+        MessageDeserializer,
+        MessagePartitioner,
+        MessageSerializer,
+    },
+};
+use ::alloc::{
+    string::String,
+    vec::Vec,
+};
+use ::nvx::sys::error::{
+    Error,
+    ErrorCode,
+};
+
+//==================================================================================================
+// ListXattrRequest
+//==================================================================================================
+
+///
+/// # Description
+///
+/// This struct represents the request message of the `listxattr()` and `flistxattr()` system
+/// calls.
+///
+#[derive(Debug, MessageSerializer, MessageDeserializer, MessagePartitioner)]
+#[message(request_part = ListXattrRequestPart)]
+pub struct ListXattrRequest {
+    /// File descriptor. Only meaningful if `path` is empty.
+    pub fd: i32,
+    /// Path. If empty, the request targets `fd`.
+    #[message(max_len = limits::PATH_MAX)]
+    pub path: String,
+    /// Size of the list buffer. If zero, only the size of the list is queried.
+    pub size: usize,
+}
+
+impl ListXattrRequest {
+    ///
+    /// # Description
+    ///
+    /// Creates a request message of the `listxattr()` system call.
+    ///
+    /// # Parameters
+    ///
+    /// - `fd`: File descriptor.
+    /// - `path`: Path.
+    /// - `size`: Size of the list buffer.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the request message of the `listxattr()` system call is returned. Upon
+    /// failure, an error is returned instead.
+    ///
+    pub fn new(fd: i32, path: String, size: usize) -> Result<Self, Error> {
+        // Check if the path is too long.
+        if path.len() > limits::PATH_MAX {
+            return Err(Error::new(ErrorCode::InvalidMessage, "path too long"));
+        }
+
+        Ok(Self { fd, path, size })
+    }
+}
+
+//==================================================================================================
+// ListXattrResponse
+//==================================================================================================
+
+///
+/// # Description
+///
+/// This struct represents the response message of the `listxattr()` and `flistxattr()` system
+/// calls.
+///
+#[derive(Debug, MessageSerializer, MessageDeserializer, MessagePartitioner)]
+#[message(response_part = ListXattrResponsePart, validate = Self::validate)]
+pub struct ListXattrResponse {
+    /// Size of the list of names.
+    pub size: usize,
+    /// Null-terminated names of the extended attributes. Empty if only the size was queried.
+    #[message(max_len = limits::XATTR_LIST_MAX)]
+    pub list: Vec<u8>,
+}
+
+impl ListXattrResponse {
+    ///
+    /// # Description
+    ///
+    /// Creates a response message of the `listxattr()` system call.
+    ///
+    /// # Parameters
+    ///
+    /// - `size`: Size of the list of names.
+    /// - `list`: Null-terminated names of the extended attributes.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the response message of the `listxattr()` system call is returned. Upon
+    /// failure, an error is returned instead.
+    ///
+    pub fn new(size: usize, list: Vec<u8>) -> Result<Self, Error> {
+        let response: Self = Self { size, list };
+        response.validate()?;
+
+        Ok(response)
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        // Check if the list is too long.
+        if self.size > limits::XATTR_LIST_MAX || self.list.len() > self.size {
+            return Err(Error::new(ErrorCode::InvalidMessage, "list too long"));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,147 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::{
+    limits,
+    message::{
+        MessageDeserializer,
+        MessagePartitioner,
+        MessageSerializer,
+    },
+    wire::{
+        WireDecoder,
+        WireEncoder,
+    },
+    LinuxDaemonMessage,
+    LinuxDaemonMessageHeader,
+};
+use ::alloc::string::String;
+use ::core::mem;
+use ::nvx::{
+    ipc::{
+        Message,
+        MessageType,
+    },
+    pm::ProcessIdentifier,
+    sys::error::{
+        Error,
+        ErrorCode,
+    },
+};
+
+//==================================================================================================
+// RemoveXattrRequest
+//==================================================================================================
+
+///
+/// # Description
+///
+/// This struct represents the request message of the `removexattr()` and `fremovexattr()` system
+/// calls.
+///
+#[derive(Debug, MessageSerializer, MessageDeserializer, MessagePartitioner)]
+#[message(request_part = RemoveXattrRequestPart)]
+pub struct RemoveXattrRequest {
+    /// File descriptor. Only meaningful if `path` is empty.
+    pub fd: i32,
+    /// Path. If empty, the request targets `fd`.
+    #[message(max_len = limits::PATH_MAX)]
+    pub path: String,
+    /// Name of the extended attribute.
+    #[message(min_len = 1, max_len = limits::XATTR_NAME_MAX)]
+    pub name: String,
+}
+
+impl RemoveXattrRequest {
+    ///
+    /// # Description
+    ///
+    /// Creates a request message of the `removexattr()` system call.
+    ///
+    /// # Parameters
+    ///
+    /// - `fd`: File descriptor.
+    /// - `path`: Path.
+    /// - `name`: Name of the extended attribute.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the request message of the `removexattr()` system call is returned. Upon
+    /// failure, an error is returned instead.
+    ///
+    pub fn new(fd: i32, path: String, name: String) -> Result<Self, Error> {
+        // Check if the path is too long.
+        if path.len() > limits::PATH_MAX {
+            return Err(Error::new(ErrorCode::InvalidMessage, "path too long"));
+        }
+
+        // Check if the name is invalid.
+        if name.is_empty() || name.len() > limits::XATTR_NAME_MAX {
+            return Err(Error::new(ErrorCode::InvalidMessage, "invalid attribute name"));
+        }
+
+        Ok(Self { fd, path, name })
+    }
+}
+
+//==================================================================================================
+// RemoveXattrResponse
+//==================================================================================================
+
+///
+/// # Description
+///
+/// This struct represents the response message of the `removexattr()` and `fremovexattr()`
+/// system calls.
+///
+#[derive(Debug)]
+#[repr(C, packed)]
+pub struct RemoveXattrResponse {
+    /// Return value.
+    pub ret: i32,
+    _padding: [u8; Self::PADDING_SIZE],
+}
+::nvx::sys::static_assert_size!(RemoveXattrResponse, LinuxDaemonMessage::PAYLOAD_SIZE);
+
+impl RemoveXattrResponse {
+    pub const PADDING_SIZE: usize = LinuxDaemonMessage::PAYLOAD_SIZE - mem::size_of::<i32>();
+
+    fn new(ret: i32) -> Self {
+        Self {
+            ret,
+            _padding: [0; Self::PADDING_SIZE],
+        }
+    }
+
+    pub fn from_bytes(bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE]) -> Self {
+        let mut decoder: WireDecoder = WireDecoder::new(&bytes);
+        Self {
+            ret: decoder.get(),
+            _padding: [0; Self::PADDING_SIZE],
+        }
+    }
+
+    fn into_bytes(self) -> [u8; LinuxDaemonMessage::PAYLOAD_SIZE] {
+        let mut bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE] =
+            [0; LinuxDaemonMessage::PAYLOAD_SIZE];
+        let mut encoder: WireEncoder = WireEncoder::new(&mut bytes);
+        encoder.put(self.ret);
+        bytes
+    }
+
+    pub fn build(pid: ProcessIdentifier, ret: i32) -> Message {
+        let message: RemoveXattrResponse = RemoveXattrResponse::new(ret);
+        let message: LinuxDaemonMessage = LinuxDaemonMessage::new(
+            LinuxDaemonMessageHeader::RemoveXattrResponse,
+            message.into_bytes(),
+        );
+        let message: Message =
+            Message::new(crate::LINUXD, pid, MessageType::Ikc, None, message.into_bytes());
+
+        message
+    }
+}
@@ -0,0 +1,31 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Modules
+//==================================================================================================
+
+pub mod message;
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "syscall")] {
+        mod syscall;
+        pub use self::syscall::{
+            fgetxattr,
+            flistxattr,
+            fremovexattr,
+            fsetxattr,
+            getxattr,
+            listxattr,
+            removexattr,
+            setxattr,
+        };
+    }
+}
+
+//==================================================================================================
+
+/// Fail if the extended attribute already exists.
+pub const XATTR_CREATE: i32 = 1;
+/// Fail if the extended attribute does not exist.
+pub const XATTR_REPLACE: i32 = 2;
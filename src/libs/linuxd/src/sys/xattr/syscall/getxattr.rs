@@ -0,0 +1,183 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::{
+    message::{
+        LinuxDaemonLongMessage,
+        LinuxDaemonMessagePart,
+        MessageDeserializer,
+        MessagePartitioner,
+    },
+    sys::{
+        types::ssize_t,
+        xattr::message::{
+            GetXattrRequest,
+            GetXattrResponse,
+        },
+    },
+    LinuxDaemonMessage,
+    LinuxDaemonMessageHeader,
+};
+use ::alloc::{
+    string::{
+        String,
+        ToString,
+    },
+    vec::Vec,
+};
+use ::nvx::{
+    ipc::Message,
+    pm::ProcessIdentifier,
+    sys::error::ErrorCode,
+};
+
+//==================================================================================================
+// Standalone Functions
+//==================================================================================================
+
+///
+/// # Description
+///
+/// The `getxattr()` system call retrieves the value of an extended attribute of a file.
+///
+/// # Parameters
+///
+/// - `path`: Path to the file.
+/// - `name`: Name of the extended attribute.
+/// - `value`: Buffer to store the value. If empty, only the size of the value is queried.
+///
+/// # Returns
+///
+/// Upon successful completion, the size of the value is returned. Upon failure, a negative error
+/// code is returned instead.
+///
+pub fn getxattr(path: &str, name: &str, value: &mut [u8]) -> ssize_t {
+    // Check if path is empty.
+    if path.is_empty() {
+        return ErrorCode::NoSuchEntry.into_errno();
+    }
+
+    do_getxattr(-1, path.to_string(), name, value)
+}
+
+///
+/// # Description
+///
+/// The `fgetxattr()` system call retrieves the value of an extended attribute of an open file.
+///
+/// # Parameters
+///
+/// - `fd`: File descriptor of the file.
+/// - `name`: Name of the extended attribute.
+/// - `value`: Buffer to store the value. If empty, only the size of the value is queried.
+///
+/// # Returns
+///
+/// Upon successful completion, the size of the value is returned. Upon failure, a negative error
+/// code is returned instead.
+///
+pub fn fgetxattr(fd: i32, name: &str, value: &mut [u8]) -> ssize_t {
+    do_getxattr(fd, String::new(), name, value)
+}
+
+fn do_getxattr(fd: i32, path: String, name: &str, value: &mut [u8]) -> ssize_t {
+    // Send request.
+    let status: i32 = getxattr_request(fd, path, name, value.len());
+    if status != 0 {
+        return status;
+    }
+
+    // Wait for response.
+    getxattr_response(value)
+}
+
+fn getxattr_request(fd: i32, path: String, name: &str, size: usize) -> i32 {
+    let pid: ProcessIdentifier = match ::nvx::pm::getpid() {
+        Ok(pid) => pid,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    let request: GetXattrRequest = match GetXattrRequest::new(fd, path, name.to_string(), size) {
+        Ok(request) => request,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    let requests: Vec<Message> = match request.into_parts(pid) {
+        Ok(requests) => requests,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    for request in requests {
+        if let Err(e) = ::nvx::ipc::send(&request) {
+            return e.code.into_errno();
+        }
+    }
+
+    0
+}
+
+fn getxattr_response(value: &mut [u8]) -> ssize_t {
+    let capacity: usize = LinuxDaemonLongMessage::capacity(GetXattrResponse::MAX_SIZE);
+
+    let mut assembler: LinuxDaemonLongMessage = match LinuxDaemonLongMessage::new(capacity) {
+        Ok(assembler) => assembler,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    loop {
+        let response: Message = match ::nvx::ipc::recv() {
+            Ok(response) => response,
+            Err(e) => break e.code.into_errno(),
+        };
+
+        // Check whether system call succeeded or not.
+        if response.status != 0 {
+            // System call failed, parse error code and return it.
+            match ErrorCode::try_from(response.status) {
+                Ok(e) => break e.into_errno(),
+                Err(_) => break ErrorCode::InvalidMessage.into_errno(),
+            }
+        } else {
+            // System call succeeded, parse response.
+            match LinuxDaemonMessage::try_from_bytes(response.payload) {
+                Ok(message) => match message.header {
+                    LinuxDaemonMessageHeader::GetXattrResponsePart => {
+                        let part: LinuxDaemonMessagePart =
+                            match LinuxDaemonMessagePart::try_from_bytes(message.payload) {
+                                Ok(part) => part,
+                                Err(e) => break e.code.into_errno(),
+                            };
+
+                        if let Err(e) = assembler.add_part(part) {
+                            break e.code.into_errno();
+                        }
+
+                        if !assembler.is_complete() {
+                            continue;
+                        }
+
+                        let parts: Vec<LinuxDaemonMessagePart> = assembler.take_parts();
+
+                        match GetXattrResponse::from_parts(&parts) {
+                            Ok(response) => {
+                                // Check if value does not fit in the buffer.
+                                if response.value.len() > value.len() {
+                                    break ErrorCode::InvalidMessage.into_errno();
+                                }
+                                value[..response.value.len()].copy_from_slice(&response.value);
+                                break response.size as ssize_t;
+                            },
+                            Err(_) => break ErrorCode::InvalidMessage.into_errno(),
+                        }
+                    },
+                    _ => break ErrorCode::InvalidMessage.into_errno(),
+                },
+                Err(_) => break ErrorCode::InvalidMessage.into_errno(),
+            }
+        }
+    }
+}
@@ -0,0 +1,183 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::{
+    message::{
+        LinuxDaemonLongMessage,
+        LinuxDaemonMessagePart,
+        MessageDeserializer,
+        MessagePartitioner,
+    },
+    sys::{
+        types::ssize_t,
+        xattr::message::{
+            ListXattrRequest,
+            ListXattrResponse,
+        },
+    },
+    LinuxDaemonMessage,
+    LinuxDaemonMessageHeader,
+};
+use ::alloc::{
+    string::{
+        String,
+        ToString,
+    },
+    vec::Vec,
+};
+use ::nvx::{
+    ipc::Message,
+    pm::ProcessIdentifier,
+    sys::error::ErrorCode,
+};
+
+//==================================================================================================
+// Standalone Functions
+//==================================================================================================
+
+///
+/// # Description
+///
+/// The `listxattr()` system call lists the names of the extended attributes of a file.
+///
+/// # Parameters
+///
+/// - `path`: Path to the file.
+/// - `list`: Buffer to store the null-terminated names. If empty, only the size of the list is
+///   queried.
+///
+/// # Returns
+///
+/// Upon successful completion, the size of the list is returned. Upon failure, a negative error
+/// code is returned instead.
+///
+pub fn listxattr(path: &str, list: &mut [u8]) -> ssize_t {
+    // Check if path is empty.
+    if path.is_empty() {
+        return ErrorCode::NoSuchEntry.into_errno();
+    }
+
+    do_listxattr(-1, path.to_string(), list)
+}
+
+///
+/// # Description
+///
+/// The `flistxattr()` system call lists the names of the extended attributes of an open file.
+///
+/// # Parameters
+///
+/// - `fd`: File descriptor of the file.
+/// - `list`: Buffer to store the null-terminated names. If empty, only the size of the list is
+///   queried.
+///
+/// # Returns
+///
+/// Upon successful completion, the size of the list is returned. Upon failure, a negative error
+/// code is returned instead.
+///
+pub fn flistxattr(fd: i32, list: &mut [u8]) -> ssize_t {
+    do_listxattr(fd, String::new(), list)
+}
+
+fn do_listxattr(fd: i32, path: String, list: &mut [u8]) -> ssize_t {
+    // Send request.
+    let status: i32 = listxattr_request(fd, path, list.len());
+    if status != 0 {
+        return status;
+    }
+
+    // Wait for response.
+    listxattr_response(list)
+}
+
+fn listxattr_request(fd: i32, path: String, size: usize) -> i32 {
+    let pid: ProcessIdentifier = match ::nvx::pm::getpid() {
+        Ok(pid) => pid,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    let request: ListXattrRequest = match ListXattrRequest::new(fd, path, size) {
+        Ok(request) => request,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    let requests: Vec<Message> = match request.into_parts(pid) {
+        Ok(requests) => requests,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    for request in requests {
+        if let Err(e) = ::nvx::ipc::send(&request) {
+            return e.code.into_errno();
+        }
+    }
+
+    0
+}
+
+fn listxattr_response(list: &mut [u8]) -> ssize_t {
+    let capacity: usize = LinuxDaemonLongMessage::capacity(ListXattrResponse::MAX_SIZE);
+
+    let mut assembler: LinuxDaemonLongMessage = match LinuxDaemonLongMessage::new(capacity) {
+        Ok(assembler) => assembler,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    loop {
+        let response: Message = match ::nvx::ipc::recv() {
+            Ok(response) => response,
+            Err(e) => break e.code.into_errno(),
+        };
+
+        // Check whether system call succeeded or not.
+        if response.status != 0 {
+            // System call failed, parse error code and return it.
+            match ErrorCode::try_from(response.status) {
+                Ok(e) => break e.into_errno(),
+                Err(_) => break ErrorCode::InvalidMessage.into_errno(),
+            }
+        } else {
+            // System call succeeded, parse response.
+            match LinuxDaemonMessage::try_from_bytes(response.payload) {
+                Ok(message) => match message.header {
+                    LinuxDaemonMessageHeader::ListXattrResponsePart => {
+                        let part: LinuxDaemonMessagePart =
+                            match LinuxDaemonMessagePart::try_from_bytes(message.payload) {
+                                Ok(part) => part,
+                                Err(e) => break e.code.into_errno(),
+                            };
+
+                        if let Err(e) = assembler.add_part(part) {
+                            break e.code.into_errno();
+                        }
+
+                        if !assembler.is_complete() {
+                            continue;
+                        }
+
+                        let parts: Vec<LinuxDaemonMessagePart> = assembler.take_parts();
+
+                        match ListXattrResponse::from_parts(&parts) {
+                            Ok(response) => {
+                                // Check if list does not fit in the buffer.
+                                if response.list.len() > list.len() {
+                                    break ErrorCode::InvalidMessage.into_errno();
+                                }
+                                list[..response.list.len()].copy_from_slice(&response.list);
+                                break response.size as ssize_t;
+                            },
+                            Err(_) => break ErrorCode::InvalidMessage.into_errno(),
+                        }
+                    },
+                    _ => break ErrorCode::InvalidMessage.into_errno(),
+                },
+                Err(_) => break ErrorCode::InvalidMessage.into_errno(),
+            }
+        }
+    }
+}
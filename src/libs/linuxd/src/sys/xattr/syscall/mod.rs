@@ -0,0 +1,34 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Modules
+//==================================================================================================
+
+mod getxattr;
+mod listxattr;
+mod removexattr;
+mod setxattr;
+
+//==================================================================================================
+// Exports
+//==================================================================================================
+
+pub use self::{
+    getxattr::{
+        fgetxattr,
+        getxattr,
+    },
+    listxattr::{
+        flistxattr,
+        listxattr,
+    },
+    removexattr::{
+        fremovexattr,
+        removexattr,
+    },
+    setxattr::{
+        fsetxattr,
+        setxattr,
+    },
+};
@@ -0,0 +1,147 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::{
+    message::MessagePartitioner,
+    sys::xattr::message::{
+        RemoveXattrRequest,
+        RemoveXattrResponse,
+    },
+    LinuxDaemonMessage,
+    LinuxDaemonMessageHeader,
+};
+use ::alloc::{
+    string::{
+        String,
+        ToString,
+    },
+    vec::Vec,
+};
+use ::nvx::{
+    ipc::Message,
+    pm::ProcessIdentifier,
+    sys::error::ErrorCode,
+};
+
+//==================================================================================================
+// Standalone Functions
+//==================================================================================================
+
+///
+/// # Description
+///
+/// The `removexattr()` system call removes an extended attribute of a file.
+///
+/// # Parameters
+///
+/// - `path`: Path to the file.
+/// - `name`: Name of the extended attribute.
+///
+/// # Returns
+///
+/// Upon successful completion, `0` is returned. Upon failure, a negative error code is returned
+/// instead.
+///
+pub fn removexattr(path: &str, name: &str) -> i32 {
+    // Check if path is empty.
+    if path.is_empty() {
+        return ErrorCode::NoSuchEntry.into_errno();
+    }
+
+    do_removexattr(-1, path.to_string(), name)
+}
+
+///
+/// # Description
+///
+/// The `fremovexattr()` system call removes an extended attribute of an open file.
+///
+/// # Parameters
+///
+/// - `fd`: File descriptor of the file.
+/// - `name`: Name of the extended attribute.
+///
+/// # Returns
+///
+/// Upon successful completion, `0` is returned. Upon failure, a negative error code is returned
+/// instead.
+///
+pub fn fremovexattr(fd: i32, name: &str) -> i32 {
+    do_removexattr(fd, String::new(), name)
+}
+
+fn do_removexattr(fd: i32, path: String, name: &str) -> i32 {
+    // Send request.
+    let status: i32 = removexattr_request(fd, path, name);
+    if status != 0 {
+        return status;
+    }
+
+    // Wait for response.
+    removexattr_response()
+}
+
+fn removexattr_request(fd: i32, path: String, name: &str) -> i32 {
+    let pid: ProcessIdentifier = match ::nvx::pm::getpid() {
+        Ok(pid) => pid,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    let request: RemoveXattrRequest = match RemoveXattrRequest::new(fd, path, name.to_string()) {
+        Ok(request) => request,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    let requests: Vec<Message> = match request.into_parts(pid) {
+        Ok(requests) => requests,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    for request in requests {
+        if let Err(e) = ::nvx::ipc::send(&request) {
+            return e.code.into_errno();
+        }
+    }
+
+    0
+}
+
+fn removexattr_response() -> i32 {
+    let response: Message = match ::nvx::ipc::recv() {
+        Ok(response) => response,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    // Check whether system call succeeded or not.
+    if response.status != 0 {
+        // System call failed, parse error code and return it.
+        match ErrorCode::try_from(response.status) {
+            Ok(e) => e.into_errno(),
+            Err(_) => ErrorCode::InvalidMessage.into_errno(),
+        }
+    } else {
+        // System call succeeded, parse response.
+        match LinuxDaemonMessage::try_from_bytes(response.payload) {
+            // Response was successfully parsed.
+            Ok(message) => match message.header {
+                // Response was successfully parsed.
+                LinuxDaemonMessageHeader::RemoveXattrResponse => {
+                    // Parse response.
+                    let response: RemoveXattrResponse =
+                        RemoveXattrResponse::from_bytes(message.payload);
+
+                    // Return result.
+                    response.ret
+                },
+                // Response was not successfully parsed.
+                _ => ErrorCode::InvalidMessage.into_errno(),
+            },
+            // Response was not successfully parsed.
+            Err(_) => ErrorCode::InvalidMessage.into_errno(),
+        }
+    }
+}
@@ -0,0 +1,151 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::{
+    message::MessagePartitioner,
+    sys::xattr::message::{
+        SetXattrRequest,
+        SetXattrResponse,
+    },
+    LinuxDaemonMessage,
+    LinuxDaemonMessageHeader,
+};
+use ::alloc::{
+    string::{
+        String,
+        ToString,
+    },
+    vec::Vec,
+};
+use ::nvx::{
+    ipc::Message,
+    pm::ProcessIdentifier,
+    sys::error::ErrorCode,
+};
+
+//==================================================================================================
+// Standalone Functions
+//==================================================================================================
+
+///
+/// # Description
+///
+/// The `setxattr()` system call sets the value of an extended attribute of a file.
+///
+/// # Parameters
+///
+/// - `path`: Path to the file.
+/// - `name`: Name of the extended attribute.
+/// - `value`: Value of the extended attribute.
+/// - `flags`: Flags (`XATTR_CREATE` or `XATTR_REPLACE`).
+///
+/// # Returns
+///
+/// Upon successful completion, `0` is returned. Upon failure, a negative error code is returned
+/// instead.
+///
+pub fn setxattr(path: &str, name: &str, value: &[u8], flags: i32) -> i32 {
+    // Check if path is empty.
+    if path.is_empty() {
+        return ErrorCode::NoSuchEntry.into_errno();
+    }
+
+    do_setxattr(-1, path.to_string(), name, value, flags)
+}
+
+///
+/// # Description
+///
+/// The `fsetxattr()` system call sets the value of an extended attribute of an open file.
+///
+/// # Parameters
+///
+/// - `fd`: File descriptor of the file.
+/// - `name`: Name of the extended attribute.
+/// - `value`: Value of the extended attribute.
+/// - `flags`: Flags (`XATTR_CREATE` or `XATTR_REPLACE`).
+///
+/// # Returns
+///
+/// Upon successful completion, `0` is returned. Upon failure, a negative error code is returned
+/// instead.
+///
+pub fn fsetxattr(fd: i32, name: &str, value: &[u8], flags: i32) -> i32 {
+    do_setxattr(fd, String::new(), name, value, flags)
+}
+
+fn do_setxattr(fd: i32, path: String, name: &str, value: &[u8], flags: i32) -> i32 {
+    // Send request.
+    let status: i32 = setxattr_request(fd, path, name, value, flags);
+    if status != 0 {
+        return status;
+    }
+
+    // Wait for response.
+    setxattr_response()
+}
+
+fn setxattr_request(fd: i32, path: String, name: &str, value: &[u8], flags: i32) -> i32 {
+    let pid: ProcessIdentifier = match ::nvx::pm::getpid() {
+        Ok(pid) => pid,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    let request: SetXattrRequest =
+        match SetXattrRequest::new(fd, path, name.to_string(), value.to_vec(), flags) {
+            Ok(request) => request,
+            Err(e) => return e.code.into_errno(),
+        };
+
+    let requests: Vec<Message> = match request.into_parts(pid) {
+        Ok(requests) => requests,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    for request in requests {
+        if let Err(e) = ::nvx::ipc::send(&request) {
+            return e.code.into_errno();
+        }
+    }
+
+    0
+}
+
+fn setxattr_response() -> i32 {
+    let response: Message = match ::nvx::ipc::recv() {
+        Ok(response) => response,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    // Check whether system call succeeded or not.
+    if response.status != 0 {
+        // System call failed, parse error code and return it.
+        match ErrorCode::try_from(response.status) {
+            Ok(e) => e.into_errno(),
+            Err(_) => ErrorCode::InvalidMessage.into_errno(),
+        }
+    } else {
+        // System call succeeded, parse response.
+        match LinuxDaemonMessage::try_from_bytes(response.payload) {
+            // Response was successfully parsed.
+            Ok(message) => match message.header {
+                // Response was successfully parsed.
+                LinuxDaemonMessageHeader::SetXattrResponse => {
+                    // Parse response.
+                    let response: SetXattrResponse = SetXattrResponse::from_bytes(message.payload);
+
+                    // Return result.
+                    response.ret
+                },
+                // Response was not successfully parsed.
+                _ => ErrorCode::InvalidMessage.into_errno(),
+            },
+            // Response was not successfully parsed.
+            Err(_) => ErrorCode::InvalidMessage.into_errno(),
+        }
+    }
+}
@@ -0,0 +1,233 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::{
+    sys::types::{
+        off_t,
+        size_t,
+        ssize_t,
+    },
+    wire::{
+        WireDecoder,
+        WireEncoder,
+    },
+    LinuxDaemonMessage,
+    LinuxDaemonMessageHeader,
+};
+use ::core::mem;
+use ::nvx::{
+    ipc::{
+        Message,
+        MessageType,
+    },
+    pm::ProcessIdentifier,
+};
+
+//==================================================================================================
+// Constants
+//==================================================================================================
+
+/// Offset value that instructs the daemon to use (and update) the file offset of the descriptor.
+const NO_OFFSET: off_t = -1;
+
+//==================================================================================================
+// CopyFileRangeRequest
+//==================================================================================================
+
+///
+/// # Description
+///
+/// This struct represents the request message of the `copy_file_range()` system call.
+///
+#[derive(Debug)]
+#[repr(C, packed)]
+pub struct CopyFileRangeRequest {
+    /// Source file descriptor.
+    pub fd_in: i32,
+    /// Offset in source file, or `NO_OFFSET` to use the file offset of `fd_in`.
+    off_in: off_t,
+    /// Target file descriptor.
+    pub fd_out: i32,
+    /// Offset in target file, or `NO_OFFSET` to use the file offset of `fd_out`.
+    off_out: off_t,
+    /// Number of bytes to copy.
+    pub len: size_t,
+    /// Flags.
+    pub flags: u32,
+    _padding: [u8; Self::PADDING_SIZE],
+}
+::nvx::sys::static_assert_size!(CopyFileRangeRequest, LinuxDaemonMessage::PAYLOAD_SIZE);
+
+impl CopyFileRangeRequest {
+    pub const PADDING_SIZE: usize = LinuxDaemonMessage::PAYLOAD_SIZE
+        - mem::size_of::<i32>()
+        - mem::size_of::<off_t>()
+        - mem::size_of::<i32>()
+        - mem::size_of::<off_t>()
+        - mem::size_of::<size_t>()
+        - mem::size_of::<u32>();
+
+    fn new(
+        fd_in: i32,
+        off_in: Option<off_t>,
+        fd_out: i32,
+        off_out: Option<off_t>,
+        len: size_t,
+        flags: u32,
+    ) -> Self {
+        Self {
+            fd_in,
+            off_in: off_in.unwrap_or(NO_OFFSET),
+            fd_out,
+            off_out: off_out.unwrap_or(NO_OFFSET),
+            len,
+            flags,
+            _padding: [0; Self::PADDING_SIZE],
+        }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the offset in the source file, if any.
+    ///
+    pub fn off_in(&self) -> Option<off_t> {
+        match self.off_in {
+            NO_OFFSET => None,
+            off_in => Some(off_in),
+        }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the offset in the target file, if any.
+    ///
+    pub fn off_out(&self) -> Option<off_t> {
+        match self.off_out {
+            NO_OFFSET => None,
+            off_out => Some(off_out),
+        }
+    }
+
+    pub fn from_bytes(bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE]) -> Self {
+        let mut decoder: WireDecoder = WireDecoder::new(&bytes);
+        Self {
+            fd_in: decoder.get(),
+            off_in: decoder.get(),
+            fd_out: decoder.get(),
+            off_out: decoder.get(),
+            len: decoder.get(),
+            flags: decoder.get(),
+            _padding: [0; Self::PADDING_SIZE],
+        }
+    }
+
+    fn into_bytes(self) -> [u8; LinuxDaemonMessage::PAYLOAD_SIZE] {
+        let mut bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE] =
+            [0; LinuxDaemonMessage::PAYLOAD_SIZE];
+        let mut encoder: WireEncoder = WireEncoder::new(&mut bytes);
+        encoder.put(self.fd_in);
+        encoder.put(self.off_in);
+        encoder.put(self.fd_out);
+        encoder.put(self.off_out);
+        encoder.put(self.len);
+        encoder.put(self.flags);
+        bytes
+    }
+
+    pub fn build(
+        pid: ProcessIdentifier,
+        fd_in: i32,
+        off_in: Option<off_t>,
+        fd_out: i32,
+        off_out: Option<off_t>,
+        len: size_t,
+        flags: u32,
+    ) -> Message {
+        let message: CopyFileRangeRequest =
+            CopyFileRangeRequest::new(fd_in, off_in, fd_out, off_out, len, flags);
+        let message: LinuxDaemonMessage = LinuxDaemonMessage::new(
+            LinuxDaemonMessageHeader::CopyFileRangeRequest,
+            message.into_bytes(),
+        );
+        let message: Message =
+            Message::new(pid, crate::LINUXD, MessageType::Ikc, None, message.into_bytes());
+
+        message
+    }
+}
+
+//==================================================================================================
+// CopyFileRangeResponse
+//==================================================================================================
+
+///
+/// # Description
+///
+/// This struct represents the response message of the `copy_file_range()` system call.
+///
+#[derive(Debug)]
+#[repr(C, packed)]
+pub struct CopyFileRangeResponse {
+    /// Number of bytes copied.
+    pub count: ssize_t,
+    /// Updated offset in source file. Meaningless if no offset was supplied in the request.
+    pub off_in: off_t,
+    /// Updated offset in target file. Meaningless if no offset was supplied in the request.
+    pub off_out: off_t,
+    _padding: [u8; Self::PADDING_SIZE],
+}
+::nvx::sys::static_assert_size!(CopyFileRangeResponse, LinuxDaemonMessage::PAYLOAD_SIZE);
+
+impl CopyFileRangeResponse {
+    pub const PADDING_SIZE: usize = LinuxDaemonMessage::PAYLOAD_SIZE
+        - mem::size_of::<ssize_t>()
+        - mem::size_of::<off_t>()
+        - mem::size_of::<off_t>();
+
+    fn new(count: ssize_t, off_in: off_t, off_out: off_t) -> Self {
+        Self {
+            count,
+            off_in,
+            off_out,
+            _padding: [0; Self::PADDING_SIZE],
+        }
+    }
+
+    pub fn from_bytes(bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE]) -> Self {
+        let mut decoder: WireDecoder = WireDecoder::new(&bytes);
+        Self {
+            count: decoder.get(),
+            off_in: decoder.get(),
+            off_out: decoder.get(),
+            _padding: [0; Self::PADDING_SIZE],
+        }
+    }
+
+    fn into_bytes(self) -> [u8; LinuxDaemonMessage::PAYLOAD_SIZE] {
+        let mut bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE] =
+            [0; LinuxDaemonMessage::PAYLOAD_SIZE];
+        let mut encoder: WireEncoder = WireEncoder::new(&mut bytes);
+        encoder.put(self.count);
+        encoder.put(self.off_in);
+        encoder.put(self.off_out);
+        bytes
+    }
+
+    pub fn build(pid: ProcessIdentifier, count: ssize_t, off_in: off_t, off_out: off_t) -> Message {
+        let message: CopyFileRangeResponse = CopyFileRangeResponse::new(count, off_in, off_out);
+        let message: LinuxDaemonMessage = LinuxDaemonMessage::new(
+            LinuxDaemonMessageHeader::CopyFileRangeResponse,
+            message.into_bytes(),
+        );
+        let message: Message =
+            Message::new(crate::LINUXD, pid, MessageType::Ikc, None, message.into_bytes());
+
+        message
+    }
+}
@@ -6,6 +6,7 @@
 //==================================================================================================
 
 mod close;
+mod copy_file_range;
 mod fdatasync;
 mod fsync;
 mod ftruncate;
@@ -14,6 +15,7 @@ mod lseek;
 mod pread;
 mod pwrite;
 mod read;
+mod truncate;
 mod write;
 
 //==================================================================================================
@@ -25,6 +27,10 @@ pub use self::{
         CloseRequest,
         CloseResponse,
     },
+    copy_file_range::{
+        CopyFileRangeRequest,
+        CopyFileRangeResponse,
+    },
     fdatasync::{
         FileDataSyncRequest,
         FileDataSyncResponse,
@@ -57,6 +63,10 @@ pub use self::{
         ReadRequest,
         ReadResponse,
     },
+    truncate::{
+        TruncateRequest,
+        TruncateResponse,
+    },
     write::{
         WriteRequest,
         WriteResponse,
@@ -0,0 +1,137 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::{
+    limits,
+    message::{
+        MessageDeserializer,
+        MessagePartitioner,
+        MessageSerializer,
+    },
+    sys::types::off_t,
+    wire::{
+        WireDecoder,
+        WireEncoder,
+    },
+    LinuxDaemonMessage,
+    LinuxDaemonMessageHeader,
+};
+use ::alloc::string::String;
+use ::core::mem;
+use ::nvx::{
+    ipc::{
+        Message,
+        MessageType,
+    },
+    pm::ProcessIdentifier,
+    sys::error::{
+        Error,
+        ErrorCode,
+    },
+};
+
+//==================================================================================================
+// TruncateRequest
+//==================================================================================================
+
+///
+/// # Description
+///
+/// This struct represents the request message of the `truncate()` system call.
+///
+#[derive(Debug, MessageSerializer, MessageDeserializer, MessagePartitioner)]
+#[message(request_part = TruncateRequestPart)]
+pub struct TruncateRequest {
+    /// Length to truncate the file to.
+    pub length: off_t,
+    /// Path to the file.
+    #[message(max_len = limits::PATH_MAX)]
+    pub path: String,
+}
+
+impl TruncateRequest {
+    ///
+    /// # Description
+    ///
+    /// Creates a request message of the `truncate()` system call.
+    ///
+    /// # Parameters
+    ///
+    /// - `path`: Path to the file.
+    /// - `length`: Length to truncate the file to.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the request message of the `truncate()` system call is returned. Upon
+    /// failure, an error is returned instead.
+    ///
+    pub fn new(path: String, length: off_t) -> Result<Self, Error> {
+        // Check if the path is too long.
+        if path.len() > limits::PATH_MAX {
+            return Err(Error::new(ErrorCode::InvalidMessage, "path too long"));
+        }
+
+        Ok(Self { path, length })
+    }
+}
+
+//==================================================================================================
+// TruncateResponse
+//==================================================================================================
+
+///
+/// # Description
+///
+/// This struct represents the response message of the `truncate()` system call.
+///
+#[derive(Debug)]
+#[repr(C, packed)]
+pub struct TruncateResponse {
+    /// Return value.
+    pub ret: i32,
+    _padding: [u8; Self::PADDING_SIZE],
+}
+::nvx::sys::static_assert_size!(TruncateResponse, LinuxDaemonMessage::PAYLOAD_SIZE);
+
+impl TruncateResponse {
+    pub const PADDING_SIZE: usize = LinuxDaemonMessage::PAYLOAD_SIZE - mem::size_of::<i32>();
+
+    fn new(ret: i32) -> Self {
+        Self {
+            ret,
+            _padding: [0; Self::PADDING_SIZE],
+        }
+    }
+
+    pub fn from_bytes(bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE]) -> Self {
+        let mut decoder: WireDecoder = WireDecoder::new(&bytes);
+        Self {
+            ret: decoder.get(),
+            _padding: [0; Self::PADDING_SIZE],
+        }
+    }
+
+    fn into_bytes(self) -> [u8; LinuxDaemonMessage::PAYLOAD_SIZE] {
+        let mut bytes: [u8; LinuxDaemonMessage::PAYLOAD_SIZE] =
+            [0; LinuxDaemonMessage::PAYLOAD_SIZE];
+        let mut encoder: WireEncoder = WireEncoder::new(&mut bytes);
+        encoder.put(self.ret);
+        bytes
+    }
+
+    pub fn build(pid: ProcessIdentifier, ret: i32) -> Message {
+        let message: TruncateResponse = TruncateResponse::new(ret);
+        let message: LinuxDaemonMessage = LinuxDaemonMessage::new(
+            LinuxDaemonMessageHeader::TruncateResponse,
+            message.into_bytes(),
+        );
+        let message: Message =
+            Message::new(crate::LINUXD, pid, MessageType::Ikc, None, message.into_bytes());
+
+        message
+    }
+}
@@ -31,10 +31,12 @@ cfg_if::cfg_if! {
         mod syscall;
         pub use self::syscall::{
             close,
+            copy_file_range,
             fdatasync,
             fsync,
             lseek,
             ftruncate,
+            truncate,
             write,
             read,
             pwrite,
@@ -0,0 +1,128 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::{
+    sys::types::{
+        off_t,
+        size_t,
+        ssize_t,
+    },
+    unistd::message::{
+        CopyFileRangeRequest,
+        CopyFileRangeResponse,
+    },
+    LinuxDaemonMessage,
+    LinuxDaemonMessageHeader,
+};
+use ::nvx::{
+    ipc::Message,
+    pm::ProcessIdentifier,
+    sys::error::ErrorCode,
+};
+
+//==================================================================================================
+// Standalone Functions
+//==================================================================================================
+
+///
+/// # Description
+///
+/// The `copy_file_range()` system call copies a range of data from one file to another. The copy
+/// is carried out entirely on the host, and only the number of copied bytes is transferred back.
+///
+/// # Parameters
+///
+/// - `fd_in`: Source file descriptor.
+/// - `off_in`: Offset in the source file. If `None`, the file offset of `fd_in` is used and
+///   updated. Otherwise, the file offset is left untouched and `off_in` is updated instead.
+/// - `fd_out`: Target file descriptor.
+/// - `off_out`: Offset in the target file. Same semantics as `off_in`.
+/// - `len`: Number of bytes to copy.
+/// - `flags`: Flags. Must be zero.
+///
+/// # Returns
+///
+/// Upon successful completion, the number of bytes copied is returned. Upon failure, a negative
+/// error code is returned instead.
+///
+pub fn copy_file_range(
+    fd_in: i32,
+    off_in: Option<&mut off_t>,
+    fd_out: i32,
+    off_out: Option<&mut off_t>,
+    len: size_t,
+    flags: u32,
+) -> ssize_t {
+    // Check if offsets are invalid.
+    if off_in.as_deref().is_some_and(|off| *off < 0)
+        || off_out.as_deref().is_some_and(|off| *off < 0)
+    {
+        return ErrorCode::InvalidArgument.into_errno();
+    }
+
+    let pid: ProcessIdentifier = match ::nvx::pm::getpid() {
+        Ok(pid) => pid,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    // Build request and send it.
+    let request: Message = CopyFileRangeRequest::build(
+        pid,
+        fd_in,
+        off_in.as_deref().copied(),
+        fd_out,
+        off_out.as_deref().copied(),
+        len,
+        flags,
+    );
+    if let Err(e) = ::nvx::ipc::send(&request) {
+        return e.code.into_errno();
+    }
+
+    // Receive response.
+    let response: Message = match ::nvx::ipc::recv() {
+        Ok(response) => response,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    // Check whether system call succeeded or not.
+    if response.status != 0 {
+        // System call failed, parse error code and return it.
+        match ErrorCode::try_from(response.status) {
+            Ok(e) => e.into_errno(),
+            Err(_) => ErrorCode::InvalidMessage.into_errno(),
+        }
+    } else {
+        // System call succeeded, parse response.
+        match LinuxDaemonMessage::try_from_bytes(response.payload) {
+            // Response was successfully parsed.
+            Ok(message) => match message.header {
+                // Response was successfully parsed.
+                LinuxDaemonMessageHeader::CopyFileRangeResponse => {
+                    // Parse response.
+                    let response: CopyFileRangeResponse =
+                        CopyFileRangeResponse::from_bytes(message.payload);
+
+                    // Update offsets.
+                    if let Some(off_in) = off_in {
+                        *off_in = response.off_in;
+                    }
+                    if let Some(off_out) = off_out {
+                        *off_out = response.off_out;
+                    }
+
+                    // Return number of bytes copied.
+                    response.count
+                },
+                // Response was not successfully parsed.
+                _ => ErrorCode::InvalidMessage.into_errno(),
+            },
+            // Response was not successfully parsed.
+            Err(_) => ErrorCode::InvalidMessage.into_errno(),
+        }
+    }
+}
@@ -6,6 +6,7 @@
 //==================================================================================================
 
 mod close;
+mod copy_file_range;
 mod fdatasync;
 mod fsync;
 mod ftruncate;
@@ -14,6 +15,7 @@ mod lseek;
 mod pread;
 mod pwrite;
 mod read;
+mod truncate;
 mod write;
 
 //==================================================================================================
@@ -22,6 +24,7 @@ mod write;
 
 pub use self::{
     close::close,
+    copy_file_range::copy_file_range,
     fdatasync::fdatasync,
     fsync::fsync,
     ftruncate::ftruncate,
@@ -30,5 +33,6 @@ pub use self::{
     pread::pread,
     pwrite::pwrite,
     read::read,
+    truncate::truncate,
     write::write,
 };
@@ -0,0 +1,117 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::{
+    message::MessagePartitioner,
+    sys::types::off_t,
+    unistd::message::{
+        TruncateRequest,
+        TruncateResponse,
+    },
+    LinuxDaemonMessage,
+    LinuxDaemonMessageHeader,
+};
+use ::alloc::{
+    string::ToString,
+    vec::Vec,
+};
+use ::nvx::{
+    ipc::Message,
+    pm::ProcessIdentifier,
+    sys::error::ErrorCode,
+};
+
+//==================================================================================================
+// Standalone Functions
+//==================================================================================================
+
+///
+/// # Description
+///
+/// The `truncate()` system call truncates a file, given its path, to a specified length.
+///
+/// # Parameters
+///
+/// - `path`: Path to the file.
+/// - `length`: Length to truncate the file to.
+///
+/// # Returns
+///
+/// Upon successful completion, `0` is returned. Upon failure, a negative error code is returned
+/// instead.
+///
+pub fn truncate(path: &str, length: off_t) -> i32 {
+    // Check if path is empty.
+    if path.is_empty() {
+        return ErrorCode::NoSuchEntry.into_errno();
+    }
+
+    // Send request.
+    let status: i32 = truncate_request(path, length);
+    if status != 0 {
+        return status;
+    }
+
+    // Wait for response.
+    truncate_response()
+}
+
+fn truncate_request(path: &str, length: off_t) -> i32 {
+    let pid: ProcessIdentifier = match ::nvx::pm::getpid() {
+        Ok(pid) => pid,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    let request: TruncateRequest = match TruncateRequest::new(path.to_string(), length) {
+        Ok(request) => request,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    let requests: Vec<Message> = match request.into_parts(pid) {
+        Ok(requests) => requests,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    for request in requests {
+        if let Err(e) = ::nvx::ipc::send(&request) {
+            return e.code.into_errno();
+        }
+    }
+
+    0
+}
+
+fn truncate_response() -> i32 {
+    let response: Message = match ::nvx::ipc::recv() {
+        Ok(response) => response,
+        Err(e) => return e.code.into_errno(),
+    };
+
+    // Check whether system call succeeded or not.
+    if response.status != 0 {
+        // System call failed, parse error code and return it.
+        match ErrorCode::try_from(response.status) {
+            Ok(e) => e.into_errno(),
+            Err(_) => ErrorCode::InvalidMessage.into_errno(),
+        }
+    } else {
+        // System call succeeded, parse response.
+        match LinuxDaemonMessage::try_from_bytes(response.payload) {
+            Ok(message) => match message.header {
+                LinuxDaemonMessageHeader::TruncateResponse => {
+                    // Parse response.
+                    let response: TruncateResponse = TruncateResponse::from_bytes(message.payload);
+
+                    // Return result.
+                    response.ret
+                },
+                _ => ErrorCode::InvalidMessage.into_errno(),
+            },
+            _ => ErrorCode::InvalidMessage.into_errno(),
+        }
+    }
+}
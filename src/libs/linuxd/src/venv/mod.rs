@@ -21,6 +21,11 @@ cfg_if::cfg_if! {
 // Imports
 //==================================================================================================
 
+use crate::wire::{
+    WireDecoder,
+    WireEncoder,
+    WireFormat,
+};
 use core::fmt::Debug;
 
 //==================================================================================================
@@ -71,3 +76,15 @@ impl Default for VirtualEnvironmentIdentifier {
         Self { id: 1 }
     }
 }
+
+impl WireFormat for VirtualEnvironmentIdentifier {
+    const WIRE_SIZE: usize = u32::WIRE_SIZE;
+
+    fn encode(self, encoder: &mut WireEncoder) {
+        encoder.put(self.id);
+    }
+
+    fn decode(decoder: &mut WireDecoder) -> Self {
+        Self::new(decoder.get())
+    }
+}
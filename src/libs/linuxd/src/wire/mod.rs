@@ -0,0 +1,267 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use ::core::mem;
+
+//==================================================================================================
+// Exports
+//==================================================================================================
+
+pub use ::linuxd_derive::WireFormat;
+
+//==================================================================================================
+// Traits
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Type with a fixed encoding on the wire, which is the same regardless of the endianness, word
+/// size and struct layout of either side. Third-party clients only need this description to talk
+/// to the Linux Daemon.
+///
+/// # Primitive Types
+///
+/// - Integers are encoded in little-endian byte order. Signed integers use two's complement.
+/// - Fields are laid out back to back in declaration order, with no padding nor alignment.
+/// - Byte arrays, such as file names and data buffers, are copied as is. File names are padded
+///   with zeros up to their fixed size.
+/// - Types have the same width on every platform: `size_t` and `ssize_t` are 32 bits wide, while
+///   `off_t`, `time_t`, `dev_t`, `ino_t`, `nlink_t` and `rlim_t` are 64 bits wide.
+/// - Native sizes (`usize`), such as the sizes of buffers that multipart messages ask for, are 64
+///   bits wide, so that no value of the host is truncated. Peers whose `usize` is narrower decode
+///   values that do not fit as `usize::MAX`.
+///
+/// # Messages
+///
+/// Messages travel in the payload of an IKC message, which is laid out as follows:
+///
+/// | Offset | Size | Field                                                  |
+/// |--------|------|--------------------------------------------------------|
+/// | 0      | 2    | Message type, as a [`crate::LinuxDaemonMessageHeader`] |
+/// | 2      | rest | Fields of the message, as defined by its structure     |
+///
+/// Bytes past the last field of a message are zero. Multipart messages are carried by
+/// [`crate::message::LinuxDaemonMessagePart`]s: a 32-bit part number that counts down to zero
+/// on the last part, an 8-bit payload size and the payload. The payloads of all parts are
+/// concatenated before being decoded as a single structure.
+///
+/// # Variable-Length Messages
+///
+/// Multipart messages may have variable-length fields, such as paths and buffers. Every field
+/// has a fixed-size head, as described by [`crate::message::MessageField`]: fixed-size fields
+/// are encoded in their head, while variable-length fields encode a 32-bit length in their head
+/// and their contents in a tail. The heads of all fields come first, in declaration order, and
+/// the tails of all fields follow, in declaration order.
+///
+pub trait WireFormat
+where
+    Self: Sized,
+{
+    /// Size of the type on the wire (in bytes).
+    const WIRE_SIZE: usize;
+
+    ///
+    /// # Description
+    ///
+    /// Encodes a value.
+    ///
+    /// # Parameters
+    ///
+    /// - `encoder`: Target encoder.
+    ///
+    fn encode(self, encoder: &mut WireEncoder);
+
+    ///
+    /// # Description
+    ///
+    /// Decodes a value.
+    ///
+    /// # Parameters
+    ///
+    /// - `decoder`: Source decoder.
+    ///
+    /// # Returns
+    ///
+    /// The decoded value.
+    ///
+    fn decode(decoder: &mut WireDecoder) -> Self;
+}
+
+//==================================================================================================
+// Structures
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Writes values to a buffer, one after the other.
+///
+/// # Notes
+///
+/// Buffers are sized after the structures that they hold, so running past the end of one is a
+/// bug in the structure definition and panics.
+///
+pub struct WireEncoder<'a> {
+    /// Target buffer.
+    bytes: &'a mut [u8],
+    /// Offset of the next value.
+    offset: usize,
+}
+
+///
+/// # Description
+///
+/// Reads values from a buffer, one after the other.
+///
+/// # Notes
+///
+/// Buffers are sized after the structures that they hold, so running past the end of one is a
+/// bug in the structure definition and panics.
+///
+pub struct WireDecoder<'a> {
+    /// Source buffer.
+    bytes: &'a [u8],
+    /// Offset of the next value.
+    offset: usize,
+}
+
+//==================================================================================================
+// Implementations
+//==================================================================================================
+
+impl<'a> WireEncoder<'a> {
+    pub fn new(bytes: &'a mut [u8]) -> Self {
+        Self { bytes, offset: 0 }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Writes a value.
+    ///
+    pub fn put<T: WireFormat>(&mut self, value: T) {
+        value.encode(self);
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Writes raw bytes.
+    ///
+    pub fn put_bytes(&mut self, bytes: &[u8]) {
+        self.take(bytes.len()).copy_from_slice(bytes);
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the number of bytes written so far.
+    ///
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    fn take(&mut self, size: usize) -> &mut [u8] {
+        let start: usize = self.offset;
+        self.offset += size;
+        &mut self.bytes[start..self.offset]
+    }
+}
+
+impl<'a> WireDecoder<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, offset: 0 }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Reads a value.
+    ///
+    pub fn get<T: WireFormat>(&mut self) -> T {
+        T::decode(self)
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Reads raw bytes.
+    ///
+    pub fn get_bytes(&mut self, size: usize) -> &'a [u8] {
+        let start: usize = self.offset;
+        self.offset += size;
+        &self.bytes[start..self.offset]
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the number of bytes read so far.
+    ///
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the number of bytes left to read.
+    ///
+    pub fn remaining(&self) -> usize {
+        self.bytes.len() - self.offset
+    }
+}
+
+macro_rules! impl_wire_format_integer {
+    ($($t:ty),*) => {
+        $(
+            impl WireFormat for $t {
+                const WIRE_SIZE: usize = mem::size_of::<$t>();
+
+                fn encode(self, encoder: &mut WireEncoder) {
+                    encoder.put_bytes(&self.to_le_bytes());
+                }
+
+                fn decode(decoder: &mut WireDecoder) -> Self {
+                    let mut bytes: [u8; Self::WIRE_SIZE] = [0; Self::WIRE_SIZE];
+                    bytes.copy_from_slice(decoder.get_bytes(Self::WIRE_SIZE));
+                    Self::from_le_bytes(bytes)
+                }
+            }
+        )*
+    };
+}
+
+impl_wire_format_integer!(u8, i8, u16, i16, u32, i32, u64, i64);
+
+impl WireFormat for usize {
+    const WIRE_SIZE: usize = u64::WIRE_SIZE;
+
+    fn encode(self, encoder: &mut WireEncoder) {
+        encoder.put(self as u64);
+    }
+
+    fn decode(decoder: &mut WireDecoder) -> Self {
+        // Sizes that do not fit are as large as they get on this side.
+        usize::try_from(decoder.get::<u64>()).unwrap_or(usize::MAX)
+    }
+}
+
+impl<const N: usize> WireFormat for [u8; N] {
+    const WIRE_SIZE: usize = N;
+
+    fn encode(self, encoder: &mut WireEncoder) {
+        encoder.put_bytes(&self);
+    }
+
+    fn decode(decoder: &mut WireDecoder) -> Self {
+        let mut bytes: [u8; N] = [0; N];
+        bytes.copy_from_slice(decoder.get_bytes(N));
+        bytes
+    }
+}
@@ -0,0 +1,74 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Configuration
+//==================================================================================================
+
+#![no_main]
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use ::libfuzzer_sys::fuzz_target;
+use ::nvx::ipc::{
+    Message,
+    SystemMessage,
+    SystemMessageHeader,
+};
+use ::procd::{
+    LookupMessage,
+    LookupResponseMessage,
+    ProcessManagementMessage,
+    ProcessManagementMessageHeader,
+    ShutdownMessage,
+    SignupMessage,
+    SignupResponseMessage,
+};
+
+//==================================================================================================
+// Fuzz Target
+//==================================================================================================
+
+// Decodes a system message the way the process manager daemon and its clients do.
+fuzz_target!(|data: &[u8]| {
+    let mut bytes: [u8; Message::PAYLOAD_SIZE] = [0; Message::PAYLOAD_SIZE];
+    let len: usize = data.len().min(bytes.len());
+    bytes[..len].copy_from_slice(&data[..len]);
+
+    let message: SystemMessage = match SystemMessage::from_bytes(bytes) {
+        Ok(message) => message,
+        Err(_) => return,
+    };
+
+    if !matches!(message.header, SystemMessageHeader::ProcessManagement) {
+        return;
+    }
+
+    let message: ProcessManagementMessage =
+        match ProcessManagementMessage::from_bytes(message.payload) {
+            Ok(message) => message,
+            Err(_) => return,
+        };
+
+    match message.header {
+        ProcessManagementMessageHeader::Shutdown => {
+            let _ = ShutdownMessage::from_bytes(message.payload).code;
+        },
+        ProcessManagementMessageHeader::Signup => {
+            let message: SignupMessage = SignupMessage::from_bytes(message.payload);
+            let _ = message.pid();
+            let _ = message.name();
+        },
+        ProcessManagementMessageHeader::SignupResponse => {
+            let _ = SignupResponseMessage::from_bytes(message.payload).status;
+        },
+        ProcessManagementMessageHeader::Lookup => {
+            let _ = LookupMessage::from_bytes(message.payload).name();
+        },
+        ProcessManagementMessageHeader::LookupResponse => {
+            let _ = LookupResponseMessage::from_bytes(message.payload).status;
+        },
+    }
+});
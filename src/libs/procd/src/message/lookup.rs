@@ -110,7 +110,9 @@ impl<'a> LookupMessage {
     /// A lookup message.
     ///
     pub fn from_bytes(bytes: [u8; ProcessManagementMessage::PAYLOAD_SIZE]) -> Self {
-        unsafe { mem::transmute(bytes) }
+        let mut name: [u8; Self::NAME_SIZE] = [0; Self::NAME_SIZE];
+        name.copy_from_slice(&bytes[..Self::NAME_SIZE]);
+        Self::new(name)
     }
 
     ///
@@ -123,7 +125,10 @@ impl<'a> LookupMessage {
     /// The corresponding byte array.
     ///
     pub fn as_bytes(self) -> [u8; ProcessManagementMessage::PAYLOAD_SIZE] {
-        unsafe { mem::transmute(self) }
+        let mut bytes: [u8; ProcessManagementMessage::PAYLOAD_SIZE] =
+            [0; ProcessManagementMessage::PAYLOAD_SIZE];
+        bytes[..Self::NAME_SIZE].copy_from_slice(&self.name);
+        bytes
     }
 
     ///
@@ -186,7 +191,10 @@ impl LookupResponseMessage {
     /// A lookup response message.
     ///
     pub fn from_bytes(bytes: [u8; ProcessManagementMessage::PAYLOAD_SIZE]) -> Self {
-        unsafe { mem::transmute(bytes) }
+        let (pid, status): (&[u8], &[u8]) = bytes.split_at(mem::size_of::<u32>());
+        let pid: u32 = u32::from_le_bytes([pid[0], pid[1], pid[2], pid[3]]);
+        let status: i32 = i32::from_le_bytes([status[0], status[1], status[2], status[3]]);
+        Self::new(ProcessIdentifier::from(pid), status)
     }
 
     ///
@@ -199,7 +207,12 @@ impl LookupResponseMessage {
     /// The corresponding byte array.
     ///
     pub fn into_bytes(self) -> [u8; ProcessManagementMessage::PAYLOAD_SIZE] {
-        unsafe { mem::transmute(self) }
+        let mut bytes: [u8; ProcessManagementMessage::PAYLOAD_SIZE] =
+            [0; ProcessManagementMessage::PAYLOAD_SIZE];
+        let (pid, status): (&mut [u8], &mut [u8]) = bytes.split_at_mut(mem::size_of::<u32>());
+        pid.copy_from_slice(&u32::from(self.pid).to_le_bytes());
+        status[..mem::size_of::<i32>()].copy_from_slice(&{ self.status }.to_le_bytes());
+        bytes
     }
 }
 
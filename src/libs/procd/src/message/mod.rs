@@ -64,7 +64,7 @@ impl TryFrom<u8> for ProcessManagementMessageHeader {
             3 => Ok(ProcessManagementMessageHeader::SignupResponse),
             4 => Ok(ProcessManagementMessageHeader::Lookup),
             5 => Ok(ProcessManagementMessageHeader::LookupResponse),
-            _ => Err(Error::new(ErrorCode::InvalidArgument, "invalid process management message")),
+            _ => Err(Error::new(ErrorCode::InvalidMessage, "invalid process management message")),
         }
     }
 }
@@ -135,14 +135,19 @@ impl ProcessManagementMessage {
     ///
     /// # Returns
     ///
-    /// A process management message.
+    /// Upon success, a process management message is returned. Upon failure, an error is returned
+    /// instead, if the message header is invalid.
     ///
     pub fn from_bytes(bytes: [u8; SystemMessage::PAYLOAD_SIZE]) -> Result<Self, Error> {
+        let (header, payload): (&[u8], &[u8]) =
+            bytes.split_at(mem::size_of::<ProcessManagementMessageHeader>());
+
         // Check if message header is valid.
-        let _header: ProcessManagementMessageHeader =
-            ProcessManagementMessageHeader::try_from(bytes[0])?;
+        let header: ProcessManagementMessageHeader =
+            ProcessManagementMessageHeader::try_from(header[0])?;
 
-        let message: ProcessManagementMessage = unsafe { mem::transmute(bytes) };
+        let mut message: ProcessManagementMessage = Self::new(header, [0; Self::PAYLOAD_SIZE]);
+        message.payload.copy_from_slice(payload);
 
         Ok(message)
     }
@@ -157,6 +162,11 @@ impl ProcessManagementMessage {
     /// The corresponding byte array.
     ///
     pub fn into_bytes(self) -> [u8; SystemMessage::PAYLOAD_SIZE] {
-        unsafe { mem::transmute(self) }
+        let mut bytes: [u8; SystemMessage::PAYLOAD_SIZE] = [0; SystemMessage::PAYLOAD_SIZE];
+        let (header, payload): (&mut [u8], &mut [u8]) =
+            bytes.split_at_mut(mem::size_of::<ProcessManagementMessageHeader>());
+        header[0] = u8::from(&{ self.header });
+        payload.copy_from_slice(&self.payload);
+        bytes
     }
 }
@@ -81,7 +81,7 @@ impl ShutdownMessage {
     /// The corresponding shutdown message.
     ///
     pub fn from_bytes(bytes: [u8; ProcessManagementMessage::PAYLOAD_SIZE]) -> Self {
-        unsafe { mem::transmute(bytes) }
+        Self::new(bytes[0])
     }
 
     ///
@@ -94,7 +94,10 @@ impl ShutdownMessage {
     /// The corresponding byte array.
     ///
     pub fn into_bytes(self) -> [u8; ProcessManagementMessage::PAYLOAD_SIZE] {
-        unsafe { mem::transmute(self) }
+        let mut bytes: [u8; ProcessManagementMessage::PAYLOAD_SIZE] =
+            [0; ProcessManagementMessage::PAYLOAD_SIZE];
+        bytes[0] = self.code;
+        bytes
     }
 }
 
@@ -111,7 +111,11 @@ impl<'a> SignupMessage {
     /// A signup message.
     ///
     pub fn from_bytes(bytes: [u8; ProcessManagementMessage::PAYLOAD_SIZE]) -> Self {
-        unsafe { mem::transmute(bytes) }
+        let (pid, name): (&[u8], &[u8]) = bytes.split_at(mem::size_of::<u32>());
+        let pid: u32 = u32::from_le_bytes([pid[0], pid[1], pid[2], pid[3]]);
+        let mut buffer: [u8; Self::NAME_SIZE] = [0; Self::NAME_SIZE];
+        buffer.copy_from_slice(&name[..Self::NAME_SIZE]);
+        Self::new(ProcessIdentifier::from(pid), buffer)
     }
 
     ///
@@ -124,7 +128,12 @@ impl<'a> SignupMessage {
     /// The corresponding byte array.
     ///
     pub fn into_bytes(self) -> [u8; ProcessManagementMessage::PAYLOAD_SIZE] {
-        unsafe { mem::transmute(self) }
+        let mut bytes: [u8; ProcessManagementMessage::PAYLOAD_SIZE] =
+            [0; ProcessManagementMessage::PAYLOAD_SIZE];
+        let (pid, name): (&mut [u8], &mut [u8]) = bytes.split_at_mut(mem::size_of::<u32>());
+        pid.copy_from_slice(&u32::from(self.pid).to_le_bytes());
+        name[..Self::NAME_SIZE].copy_from_slice(&self.name);
+        bytes
     }
 
     ///
@@ -197,7 +206,10 @@ impl SignupResponseMessage {
     /// A signup response message.
     ///
     pub fn from_bytes(bytes: [u8; ProcessManagementMessage::PAYLOAD_SIZE]) -> Self {
-        unsafe { mem::transmute(bytes) }
+        let (pid, status): (&[u8], &[u8]) = bytes.split_at(mem::size_of::<u32>());
+        let pid: u32 = u32::from_le_bytes([pid[0], pid[1], pid[2], pid[3]]);
+        let status: i32 = i32::from_le_bytes([status[0], status[1], status[2], status[3]]);
+        Self::new(ProcessIdentifier::from(pid), status)
     }
 
     ///
@@ -210,7 +222,12 @@ impl SignupResponseMessage {
     /// The corresponding byte array.
     ///
     pub fn into_bytes(self) -> [u8; ProcessManagementMessage::PAYLOAD_SIZE] {
-        unsafe { mem::transmute(self) }
+        let mut bytes: [u8; ProcessManagementMessage::PAYLOAD_SIZE] =
+            [0; ProcessManagementMessage::PAYLOAD_SIZE];
+        let (pid, status): (&mut [u8], &mut [u8]) = bytes.split_at_mut(mem::size_of::<u32>());
+        pid.copy_from_slice(&u32::from(self.pid).to_le_bytes());
+        status[..mem::size_of::<i32>()].copy_from_slice(&{ self.status }.to_le_bytes());
+        bytes
     }
 }
 
@@ -256,13 +273,8 @@ pub fn signup_request(pid: ProcessIdentifier, name: &str) -> Result<Message, Err
         SystemMessage::new(SystemMessageHeader::ProcessManagement, pm_message.into_bytes());
 
     // Construct an IPC  message.
-    let ipc_message: Message = Message::new(
-        pid,
-        crate::PROCD,
-        MessageType::Ipc,
-        None,
-        system_message.into_bytes(),
-    );
+    let ipc_message: Message =
+        Message::new(pid, crate::PROCD, MessageType::Ipc, None, system_message.into_bytes());
 
     Ok(ipc_message)
 }
@@ -42,7 +42,7 @@ impl TryFrom<u8> for SystemMessageHeader {
             1 => Ok(SystemMessageHeader::ProcessManagement),
             2 => Ok(SystemMessageHeader::MemoryManagement),
             3 => Ok(SystemMessageHeader::FilesystemManagement),
-            _ => Err(Error::new(ErrorCode::InvalidArgument, "invalid system message")),
+            _ => Err(Error::new(ErrorCode::InvalidMessage, "invalid system message")),
         }
     }
 }
@@ -109,13 +109,18 @@ impl SystemMessage {
     ///
     /// # Returns
     ///
-    /// A system message.
+    /// Upon success, a system message is returned. Upon failure, an error is returned instead, if
+    /// the message header is invalid.
     ///
     pub fn from_bytes(bytes: [u8; Message::PAYLOAD_SIZE]) -> Result<Self, Error> {
+        let (header, payload): (&[u8], &[u8]) =
+            bytes.split_at(mem::size_of::<SystemMessageHeader>());
+
         // Check if message header is valid.
-        let _header: SystemMessageHeader = SystemMessageHeader::try_from(bytes[0])?;
+        let header: SystemMessageHeader = SystemMessageHeader::try_from(header[0])?;
 
-        let message: SystemMessage = unsafe { mem::transmute(bytes) };
+        let mut message: SystemMessage = Self::new(header, [0; Self::PAYLOAD_SIZE]);
+        message.payload.copy_from_slice(payload);
 
         Ok(message)
     }
@@ -130,6 +135,11 @@ impl SystemMessage {
     /// The corresponding byte array.
     ///
     pub fn into_bytes(self) -> [u8; Message::PAYLOAD_SIZE] {
-        unsafe { mem::transmute(self) }
+        let mut bytes: [u8; Message::PAYLOAD_SIZE] = [0; Message::PAYLOAD_SIZE];
+        let (header, payload): (&mut [u8], &mut [u8]) =
+            bytes.split_at_mut(mem::size_of::<SystemMessageHeader>());
+        header[0] = u8::from(&{ self.header });
+        payload.copy_from_slice(&self.payload);
+        bytes
     }
 }
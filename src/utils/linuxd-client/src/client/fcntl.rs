@@ -0,0 +1,178 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::client::Client;
+use ::anyhow::Result;
+use ::linuxd::{
+    fcntl::message::{
+        OpenAtRequest,
+        OpenAtResponse,
+        ReadLinkAtRequest,
+        ReadLinkAtResponse,
+        RenameAtRequest,
+        RenameAtResponse,
+        SymbolicLinkAtRequest,
+        SymbolicLinkAtResponse,
+        UnlinkAtRequest,
+        UnlinkAtResponse,
+    },
+    message::MessagePartitioner,
+    sys::types::mode_t,
+    LinuxDaemonMessageHeader,
+};
+use ::nvx::ipc::Message;
+use ::std::ffi;
+
+//==================================================================================================
+// Implementations
+//==================================================================================================
+
+impl Client {
+    ///
+    /// # Description
+    ///
+    /// The `openat()` system call opens a file.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the new file descriptor is returned. Upon failure, a negative error code is
+    /// returned instead.
+    ///
+    pub fn openat(
+        &mut self,
+        dirfd: i32,
+        pathname: &str,
+        flags: ffi::c_int,
+        mode: mode_t,
+    ) -> Result<i32> {
+        let request: Message = match OpenAtRequest::build(self.pid, dirfd, pathname, flags, mode) {
+            Ok(request) => request,
+            Err(e) => return Ok(e.code.into_errno()),
+        };
+
+        Ok(match self.call(request, LinuxDaemonMessageHeader::OpenAtResponse)? {
+            Ok(payload) => OpenAtResponse::from_bytes(payload).ret,
+            Err(code) => code.into_errno(),
+        })
+    }
+
+    ///
+    /// # Description
+    ///
+    /// The `unlinkat()` system call removes a directory entry.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, `0` is returned. Upon failure, a negative error code is returned instead.
+    ///
+    pub fn unlinkat(&mut self, dirfd: i32, pathname: &str, flags: ffi::c_int) -> Result<i32> {
+        let request: Message = match UnlinkAtRequest::build(self.pid, dirfd, pathname, flags) {
+            Ok(request) => request,
+            Err(e) => return Ok(e.code.into_errno()),
+        };
+
+        Ok(match self.call(request, LinuxDaemonMessageHeader::UnlinkAtResponse)? {
+            Ok(payload) => UnlinkAtResponse::from_bytes(payload).ret,
+            Err(code) => code.into_errno(),
+        })
+    }
+
+    ///
+    /// # Description
+    ///
+    /// The `renameat()` system call renames a file.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, `0` is returned. Upon failure, a negative error code is returned instead.
+    ///
+    pub fn renameat(
+        &mut self,
+        olddirfd: i32,
+        oldpath: &str,
+        newdirfd: i32,
+        newpath: &str,
+    ) -> Result<i32> {
+        let request: Message =
+            match RenameAtRequest::build(self.pid, olddirfd, oldpath, newdirfd, newpath) {
+                Ok(request) => request,
+                Err(e) => return Ok(e.code.into_errno()),
+            };
+
+        Ok(match self.call(request, LinuxDaemonMessageHeader::RenameAtResponse)? {
+            Ok(payload) => RenameAtResponse::from_bytes(payload).ret,
+            Err(code) => code.into_errno(),
+        })
+    }
+
+    ///
+    /// # Description
+    ///
+    /// The `symlinkat()` system call creates a symbolic link.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, `0` is returned. Upon failure, a negative error code is returned instead.
+    ///
+    pub fn symlinkat(&mut self, target: &str, dirfd: i32, linkpath: &str) -> Result<i32> {
+        let request: SymbolicLinkAtRequest =
+            match SymbolicLinkAtRequest::new(target.to_string(), dirfd, linkpath.to_string()) {
+                Ok(request) => request,
+                Err(e) => return Ok(e.code.into_errno()),
+            };
+        let requests: Vec<Message> = match request.into_parts(self.pid) {
+            Ok(requests) => requests,
+            Err(e) => return Ok(e.code.into_errno()),
+        };
+
+        for request in requests {
+            self.send(request)?;
+        }
+
+        Ok(match self.response(LinuxDaemonMessageHeader::SymbolicLinkAtResponse)? {
+            Ok(payload) => SymbolicLinkAtResponse::from_bytes(payload).ret,
+            Err(code) => code.into_errno(),
+        })
+    }
+
+    ///
+    /// # Description
+    ///
+    /// The `readlinkat()` system call reads the target of a symbolic link into `buf`.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the number of bytes placed in `buf` is returned. Upon failure, a negative
+    /// error code is returned instead.
+    ///
+    pub fn readlinkat(&mut self, dirfd: i32, path: &str, buf: &mut [u8]) -> Result<i32> {
+        let request: ReadLinkAtRequest =
+            match ReadLinkAtRequest::new(dirfd, path.to_string(), buf.len()) {
+                Ok(request) => request,
+                Err(e) => return Ok(e.code.into_errno()),
+            };
+        let requests: Vec<Message> = match request.into_parts(self.pid) {
+            Ok(requests) => requests,
+            Err(e) => return Ok(e.code.into_errno()),
+        };
+
+        match self.call_parts::<ReadLinkAtResponse>(
+            requests,
+            LinuxDaemonMessageHeader::ReadLinkAtResponsePart,
+        )? {
+            Ok(response) => {
+                // Check if the daemon returned more than was asked for.
+                if response.buffer.len() > buf.len() {
+                    anyhow::bail!("readlinkat() response does not fit (len={:?})", buf.len());
+                }
+                buf[..response.buffer.len()].copy_from_slice(&response.buffer);
+                Ok(response.buffer.len() as i32)
+            },
+            Err(code) => Ok(code.into_errno()),
+        }
+    }
+}
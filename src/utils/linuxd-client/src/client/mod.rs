@@ -0,0 +1,383 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Modules
+//==================================================================================================
+
+mod fcntl;
+mod stat;
+mod time;
+mod unistd;
+mod venv;
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use ::anyhow::Result;
+use ::linuxd::{
+    handshake::{
+        self,
+        HandshakeStream,
+    },
+    message::{
+        LinuxDaemonLongMessage,
+        LinuxDaemonMessagePart,
+        MessagePartitioner,
+    },
+    protocol::{
+        Hello,
+        FRAMED_PARTS_PROTOCOL_VERSION,
+    },
+    LinuxDaemonMessage,
+    LinuxDaemonMessageHeader,
+};
+use ::nvx::{
+    ipc::{
+        Message,
+        MessageType,
+    },
+    pm::ProcessIdentifier,
+    sys::{
+        config,
+        error::{
+            Error,
+            ErrorCode,
+        },
+    },
+};
+use ::std::{
+    collections::VecDeque,
+    io::{
+        Read,
+        Write,
+    },
+    net::{
+        TcpStream,
+        ToSocketAddrs,
+    },
+};
+
+//==================================================================================================
+// Types
+//==================================================================================================
+
+/// Outcome of a system call, either what the daemon answered or the error that it reported.
+type Reply<T> = ::core::result::Result<T, ErrorCode>;
+
+//==================================================================================================
+// Structures
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Connection to the Linux Daemon, through which system calls are issued on behalf of guest
+/// processes.
+///
+/// System calls mirror those of `linuxd-lib`, and return what a guest would see, including
+/// negative error numbers. Errors are reserved for failures of the connection itself and for
+/// responses that break the protocol.
+///
+pub struct Client {
+    /// Connection to the daemon.
+    stream: TcpStream,
+    /// Process on behalf of which system calls are issued.
+    pid: ProcessIdentifier,
+    /// Version and capabilities shared with the daemon.
+    peer: Hello,
+    /// Messages that arrived while waiting for a response, such as events.
+    backlog: VecDeque<Message>,
+}
+
+///
+/// # Description
+///
+/// Connection to the daemon while it is being authenticated.
+///
+struct Handshake<'a>(&'a mut TcpStream);
+
+//==================================================================================================
+// Implementations
+//==================================================================================================
+
+impl Client {
+    ///
+    /// # Description
+    ///
+    /// Connects to the Linux Daemon.
+    ///
+    /// # Parameters
+    ///
+    /// - `sockaddr`: Socket address of the daemon.
+    /// - `pid`: Process on behalf of which system calls are issued.
+    /// - `key`: Key shared with the daemon, if connections must authenticate.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the connection is returned. Upon failure, an error is returned instead.
+    ///
+    pub fn connect<A: ToSocketAddrs>(
+        sockaddr: A,
+        pid: ProcessIdentifier,
+        key: Option<&[u8]>,
+    ) -> Result<Self> {
+        let mut stream: TcpStream = TcpStream::connect(sockaddr)?;
+        stream.set_nodelay(true)?;
+
+        if let Some(key) = key {
+            Self::authenticate(&mut stream, key)?;
+        }
+        let peer: Hello = Self::negotiate(&mut stream)?;
+
+        Ok(Self {
+            stream,
+            pid,
+            peer,
+            backlog: VecDeque::new(),
+        })
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the process on behalf of which system calls are issued.
+    ///
+    pub fn pid(&self) -> ProcessIdentifier {
+        self.pid
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Changes the process on behalf of which system calls are issued. All processes of a guest
+    /// share a single connection to the daemon.
+    ///
+    pub fn set_pid(&mut self, pid: ProcessIdentifier) {
+        self.pid = pid;
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the version and capabilities shared with the daemon.
+    ///
+    pub fn peer(&self) -> &Hello {
+        &self.peer
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Sends a message to the daemon.
+    ///
+    /// # Parameters
+    ///
+    /// - `message`: Message to send.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, empty is returned. Upon failure, an error is returned instead.
+    ///
+    pub fn send(&mut self, message: Message) -> Result<()> {
+        self.stream
+            .write_all(&message.to_bytes())
+            .map_err(|e| anyhow::anyhow!("failed to write message (error={:?})", e))
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Receives the next response addressed to the current process. Messages addressed to other
+    /// processes, as well as events, are kept until they are asked for.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the response is returned. Upon failure, an error is returned instead.
+    ///
+    pub fn recv(&mut self) -> Result<Message> {
+        let pid: ProcessIdentifier = self.pid;
+
+        // Messages that arrived earlier come first.
+        if let Some(index) = self.backlog.iter().position(|m| is_response(pid, m)) {
+            if let Some(message) = self.backlog.remove(index) {
+                return Ok(message);
+            }
+        }
+
+        loop {
+            let message: Message = self.recv_message()?;
+            if is_response(pid, &message) {
+                return Ok(message);
+            }
+            self.backlog.push_back(message);
+        }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Takes the event parts that were received for the current process so far.
+    ///
+    /// # Returns
+    ///
+    /// The event parts, in the order in which they were received.
+    ///
+    pub fn take_events(&mut self) -> Vec<Message> {
+        let pid: ProcessIdentifier = self.pid;
+        let (events, others): (VecDeque<Message>, VecDeque<Message>) = self
+            .backlog
+            .drain(..)
+            .partition(|m| m.destination == pid && is_event(m));
+        self.backlog = others;
+        events.into()
+    }
+
+    // Answer the challenge of the daemon.
+    fn authenticate(stream: &mut TcpStream, key: &[u8]) -> Result<()> {
+        handshake::respond(&mut Handshake(stream), key)
+            .map_err(|e| anyhow::anyhow!("failed to authenticate: {:?}", e))
+    }
+
+    // Negotiate the protocol version and capabilities with the daemon.
+    fn negotiate(stream: &mut TcpStream) -> Result<Hello> {
+        let ours: Hello = Hello::current();
+        stream.write_all(&ours.to_bytes())?;
+
+        let mut bytes: [u8; Hello::SIZE] = [0; Hello::SIZE];
+        stream.read_exact(&mut bytes)?;
+        let theirs: Hello =
+            Hello::from_bytes(bytes).map_err(|e| anyhow::anyhow!("invalid hello: {:?}", e))?;
+
+        let shared: Hello = ours
+            .negotiate(&theirs)
+            .map_err(|e| anyhow::anyhow!("unsupported daemon: {:?}", e))?;
+
+        // Long messages are split into framed parts only.
+        if shared.version < FRAMED_PARTS_PROTOCOL_VERSION {
+            anyhow::bail!("unsupported daemon (version={:?})", theirs.version);
+        }
+
+        Ok(shared)
+    }
+
+    // Read a message from the TCP stream.
+    fn recv_message(&mut self) -> Result<Message> {
+        let mut buf = [0u8; config::kernel::IPC_MESSAGE_SIZE];
+        self.stream
+            .read_exact(&mut buf)
+            .map_err(|e| anyhow::anyhow!("failed to read message (error={:?})", e))?;
+
+        Message::try_from_bytes(buf).map_err(|e| anyhow::anyhow!("malformed message: {:?}", e))
+    }
+
+    // Send a request and wait for its response.
+    fn call(
+        &mut self,
+        request: Message,
+        header: LinuxDaemonMessageHeader,
+    ) -> Result<Reply<[u8; LinuxDaemonMessage::PAYLOAD_SIZE]>> {
+        self.send(request)?;
+        self.response(header)
+    }
+
+    // Send the parts of a request and wait for the parts of its response.
+    fn call_parts<T: MessagePartitioner>(
+        &mut self,
+        requests: Vec<Message>,
+        header: LinuxDaemonMessageHeader,
+    ) -> Result<Reply<T>> {
+        for request in requests {
+            self.send(request)?;
+        }
+
+        let capacity: usize = LinuxDaemonLongMessage::capacity(T::MAX_SIZE);
+        let mut assembler: LinuxDaemonLongMessage = LinuxDaemonLongMessage::new(capacity)
+            .map_err(|e| anyhow::anyhow!("failed to create assembler: {:?}", e))?;
+
+        loop {
+            let payload: [u8; LinuxDaemonMessage::PAYLOAD_SIZE] = match self.response(header)? {
+                Ok(payload) => payload,
+                Err(code) => return Ok(Err(code)),
+            };
+
+            let part: LinuxDaemonMessagePart = LinuxDaemonMessagePart::try_from_bytes(payload)
+                .map_err(|e| anyhow::anyhow!("malformed message part: {:?}", e))?;
+            assembler
+                .add_part(part)
+                .map_err(|e| anyhow::anyhow!("invalid message part: {:?}", e))?;
+
+            if assembler.is_complete() {
+                let parts: Vec<LinuxDaemonMessagePart> = assembler.take_parts();
+                return T::from_parts(&parts)
+                    .map(Ok)
+                    .map_err(|e| anyhow::anyhow!("malformed response: {:?}", e));
+            }
+        }
+    }
+
+    // Wait for a response, checking that it has the expected type.
+    fn response(
+        &mut self,
+        header: LinuxDaemonMessageHeader,
+    ) -> Result<Reply<[u8; LinuxDaemonMessage::PAYLOAD_SIZE]>> {
+        let response: Message = self.recv()?;
+
+        // Check whether system call failed.
+        if response.status != 0 {
+            return match ErrorCode::try_from(response.status) {
+                Ok(code) => Ok(Err(code)),
+                Err(_) => Err(anyhow::anyhow!("invalid status (status={:?})", response.status)),
+            };
+        }
+
+        let message: LinuxDaemonMessage = LinuxDaemonMessage::try_from_bytes(response.payload)
+            .map_err(|e| anyhow::anyhow!("malformed response: {:?}", e))?;
+        let actual: LinuxDaemonMessageHeader = message.header;
+        if actual != header {
+            anyhow::bail!("unexpected response (header={:?}, expected={:?})", actual, header);
+        }
+
+        Ok(Ok(message.payload))
+    }
+}
+
+impl HandshakeStream for Handshake<'_> {
+    fn read_exact(&mut self, buf: &mut [u8]) -> ::core::result::Result<(), Error> {
+        self.0
+            .read_exact(buf)
+            .map_err(|_| Error::new(ErrorCode::InvalidMessage, "failed to read handshake"))
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> ::core::result::Result<(), Error> {
+        self.0
+            .write_all(buf)
+            .map_err(|_| Error::new(ErrorCode::InvalidMessage, "failed to write handshake"))
+    }
+}
+
+//==================================================================================================
+// Standalone Functions
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Checks whether a message is an event, which the daemon sends without being asked.
+///
+fn is_event(message: &Message) -> bool {
+    matches!(message.message_type, MessageType::Ikc)
+        && message.status == 0
+        && LinuxDaemonMessage::try_from_bytes(message.payload)
+            .is_ok_and(|m| matches!({ m.header }, LinuxDaemonMessageHeader::InotifyEventPart))
+}
+
+///
+/// # Description
+///
+/// Checks whether a message is a response addressed to a process.
+///
+fn is_response(pid: ProcessIdentifier, message: &Message) -> bool {
+    message.destination == pid && !is_event(message)
+}
@@ -0,0 +1,79 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::client::Client;
+use ::anyhow::Result;
+use ::linuxd::{
+    message::MessagePartitioner,
+    sys::stat::{
+        message::{
+            FileStatAtRequest,
+            FileStatAtResponse,
+            FileStatRequest,
+        },
+        stat,
+    },
+    LinuxDaemonMessageHeader,
+};
+use ::nvx::ipc::Message;
+
+//==================================================================================================
+// Implementations
+//==================================================================================================
+
+impl Client {
+    ///
+    /// # Description
+    ///
+    /// The `fstat()` system call obtains information about an open file.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, `0` is returned. Upon failure, a negative error code is returned instead.
+    ///
+    pub fn fstat(&mut self, fd: i32, buf: &mut stat) -> Result<i32> {
+        let request: Message = FileStatRequest::build(self.pid, fd);
+        self.stat_response(vec![request], buf)
+    }
+
+    ///
+    /// # Description
+    ///
+    /// The `fstatat()` system call obtains information about a file.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, `0` is returned. Upon failure, a negative error code is returned instead.
+    ///
+    pub fn fstatat(&mut self, dirfd: i32, path: &str, buf: &mut stat, flag: i32) -> Result<i32> {
+        let request: FileStatAtRequest = match FileStatAtRequest::new(dirfd, path.to_string(), flag)
+        {
+            Ok(request) => request,
+            Err(e) => return Ok(e.code.into_errno()),
+        };
+        let requests: Vec<Message> = match request.into_parts(self.pid) {
+            Ok(requests) => requests,
+            Err(e) => return Ok(e.code.into_errno()),
+        };
+
+        self.stat_response(requests, buf)
+    }
+
+    // Send a request and wait for the file information in its response.
+    fn stat_response(&mut self, requests: Vec<Message>, buf: &mut stat) -> Result<i32> {
+        match self.call_parts::<FileStatAtResponse>(
+            requests,
+            LinuxDaemonMessageHeader::FileStatAtResponsePart,
+        )? {
+            Ok(response) => {
+                *buf = response.stat;
+                Ok(0)
+            },
+            Err(code) => Ok(code.into_errno()),
+        }
+    }
+}
@@ -0,0 +1,71 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::client::Client;
+use ::anyhow::Result;
+use ::linuxd::{
+    sys::types::clockid_t,
+    time::{
+        message::{
+            ClockGetResolutionResponse,
+            ClockResolutionRequest,
+            GetClockTimeRequest,
+            GetClockTimeResponse,
+        },
+        timespec,
+    },
+    LinuxDaemonMessageHeader,
+};
+use ::nvx::ipc::Message;
+
+//==================================================================================================
+// Implementations
+//==================================================================================================
+
+impl Client {
+    ///
+    /// # Description
+    ///
+    /// The `clock_getres()` system call obtains the resolution of a clock.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, `0` is returned. Upon failure, a negative error code is returned instead.
+    ///
+    pub fn clock_getres(&mut self, clock_id: clockid_t, res: &mut timespec) -> Result<i32> {
+        let request: Message = ClockResolutionRequest::build(self.pid, clock_id);
+
+        match self.call(request, LinuxDaemonMessageHeader::GetClockResolutionResponse)? {
+            Ok(payload) => {
+                *res = ClockGetResolutionResponse::from_bytes(payload).res;
+                Ok(0)
+            },
+            Err(code) => Ok(code.into_errno()),
+        }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// The `clock_gettime()` system call obtains the time of a clock.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, `0` is returned. Upon failure, a negative error code is returned instead.
+    ///
+    pub fn clock_gettime(&mut self, clock_id: clockid_t, tp: &mut timespec) -> Result<i32> {
+        let request: Message = GetClockTimeRequest::build(self.pid, clock_id);
+
+        match self.call(request, LinuxDaemonMessageHeader::GetClockTimeResponse)? {
+            Ok(payload) => {
+                *tp = GetClockTimeResponse::from_bytes(payload).tp;
+                Ok(0)
+            },
+            Err(code) => Ok(code.into_errno()),
+        }
+    }
+}
@@ -0,0 +1,342 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::client::Client;
+use ::anyhow::Result;
+use ::linuxd::{
+    message::MessagePartitioner,
+    sys::types::{
+        off_t,
+        size_t,
+        ssize_t,
+    },
+    unistd::message::{
+        CloseRequest,
+        CloseResponse,
+        FileSyncRequest,
+        FileSyncResponse,
+        FileTruncateRequest,
+        FileTruncateResponse,
+        LinkAtRequest,
+        LinkAtResponse,
+        PartialReadRequest,
+        PartialReadResponse,
+        PartialWriteRequest,
+        PartialWriteResponse,
+        ReadRequest,
+        ReadResponse,
+        SeekRequest,
+        SeekResponse,
+        WriteRequest,
+        WriteResponse,
+    },
+    LinuxDaemonMessageHeader,
+};
+use ::nvx::ipc::Message;
+use ::std::cmp;
+
+//==================================================================================================
+// Implementations
+//==================================================================================================
+
+impl Client {
+    ///
+    /// # Description
+    ///
+    /// The `close()` system call closes a file descriptor.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, `0` is returned. Upon failure, a negative error code is returned instead.
+    ///
+    pub fn close(&mut self, fd: i32) -> Result<i32> {
+        let request: Message = CloseRequest::build(self.pid, fd);
+
+        Ok(match self.call(request, LinuxDaemonMessageHeader::CloseResponse)? {
+            Ok(payload) => CloseResponse::from_bytes(payload).ret,
+            Err(code) => code.into_errno(),
+        })
+    }
+
+    ///
+    /// # Description
+    ///
+    /// The `read()` system call reads from a file into `buffer`, one message at a time. Reading
+    /// stops short at the end of the file.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the number of bytes read is returned. Upon failure, a negative error code is
+    /// returned instead, unless some bytes were read already.
+    ///
+    pub fn read(&mut self, fd: i32, buffer: &mut [u8]) -> Result<ssize_t> {
+        let mut offset: usize = 0;
+
+        while offset < buffer.len() {
+            let chunk_size: usize = cmp::min(ReadResponse::BUFFER_SIZE, buffer.len() - offset);
+            let request: Message = ReadRequest::build(self.pid, fd, chunk_size as size_t);
+
+            let response: ReadResponse =
+                match self.call(request, LinuxDaemonMessageHeader::ReadResponse)? {
+                    Ok(payload) => ReadResponse::from_bytes(payload),
+                    Err(_) if offset > 0 => break,
+                    Err(code) => return Ok(code.into_errno()),
+                };
+
+            let count: usize = checked_count(response.count, chunk_size)?;
+            buffer[offset..offset + count].copy_from_slice(&response.buffer[..count]);
+            offset += count;
+
+            // Check if reached the end of the file.
+            if count < chunk_size {
+                break;
+            }
+        }
+
+        Ok(offset as ssize_t)
+    }
+
+    ///
+    /// # Description
+    ///
+    /// The `write()` system call writes `buffer` to a file, one message at a time.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the number of bytes written is returned. Upon failure, a negative error code
+    /// is returned instead, unless some bytes were written already.
+    ///
+    pub fn write(&mut self, fd: i32, buffer: &[u8]) -> Result<ssize_t> {
+        let mut offset: usize = 0;
+
+        while offset < buffer.len() {
+            let chunk_size: usize = cmp::min(WriteRequest::BUFFER_SIZE, buffer.len() - offset);
+            let mut chunk: [u8; WriteRequest::BUFFER_SIZE] = [0; WriteRequest::BUFFER_SIZE];
+            chunk[..chunk_size].copy_from_slice(&buffer[offset..offset + chunk_size]);
+            let request: Message = WriteRequest::build(self.pid, fd, chunk_size as size_t, chunk);
+
+            let response: WriteResponse =
+                match self.call(request, LinuxDaemonMessageHeader::WriteResponse)? {
+                    Ok(payload) => WriteResponse::from_bytes(payload),
+                    Err(_) if offset > 0 => break,
+                    Err(code) => return Ok(code.into_errno()),
+                };
+
+            let count: usize = checked_count(response.count, chunk_size)?;
+            offset += count;
+
+            // Check if the file cannot take more.
+            if count < chunk_size {
+                break;
+            }
+        }
+
+        Ok(offset as ssize_t)
+    }
+
+    ///
+    /// # Description
+    ///
+    /// The `pread()` system call reads from a file at a given offset into `buffer`, one message at
+    /// a time. Reading stops short at the end of the file.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the number of bytes read is returned. Upon failure, a negative error code is
+    /// returned instead, unless some bytes were read already.
+    ///
+    pub fn pread(&mut self, fd: i32, buffer: &mut [u8], offset: off_t) -> Result<ssize_t> {
+        let mut done: usize = 0;
+
+        while done < buffer.len() {
+            let chunk_size: usize = cmp::min(PartialReadResponse::BUFFER_SIZE, buffer.len() - done);
+            let request: Message = PartialReadRequest::build(
+                self.pid,
+                fd,
+                chunk_size as size_t,
+                offset + done as off_t,
+            );
+
+            let response: PartialReadResponse =
+                match self.call(request, LinuxDaemonMessageHeader::PartialReadResponse)? {
+                    Ok(payload) => PartialReadResponse::from_bytes(payload),
+                    Err(_) if done > 0 => break,
+                    Err(code) => return Ok(code.into_errno()),
+                };
+
+            let count: usize = checked_count(response.count, chunk_size)?;
+            buffer[done..done + count].copy_from_slice(&response.buffer[..count]);
+            done += count;
+
+            // Check if reached the end of the file.
+            if count < chunk_size {
+                break;
+            }
+        }
+
+        Ok(done as ssize_t)
+    }
+
+    ///
+    /// # Description
+    ///
+    /// The `pwrite()` system call writes `buffer` to a file at a given offset, one message at a
+    /// time.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the number of bytes written is returned. Upon failure, a negative error code
+    /// is returned instead, unless some bytes were written already.
+    ///
+    pub fn pwrite(&mut self, fd: i32, buffer: &[u8], offset: off_t) -> Result<ssize_t> {
+        let mut done: usize = 0;
+
+        while done < buffer.len() {
+            let chunk_size: usize = cmp::min(PartialWriteRequest::BUFFER_SIZE, buffer.len() - done);
+            let mut chunk: [u8; PartialWriteRequest::BUFFER_SIZE] =
+                [0; PartialWriteRequest::BUFFER_SIZE];
+            chunk[..chunk_size].copy_from_slice(&buffer[done..done + chunk_size]);
+            let request: Message = PartialWriteRequest::build(
+                self.pid,
+                fd,
+                chunk_size as size_t,
+                offset + done as off_t,
+                chunk,
+            );
+
+            let response: PartialWriteResponse =
+                match self.call(request, LinuxDaemonMessageHeader::PartialWriteResponse)? {
+                    Ok(payload) => PartialWriteResponse::from_bytes(payload),
+                    Err(_) if done > 0 => break,
+                    Err(code) => return Ok(code.into_errno()),
+                };
+
+            let count: usize = checked_count(response.count, chunk_size)?;
+            done += count;
+
+            // Check if the file cannot take more.
+            if count < chunk_size {
+                break;
+            }
+        }
+
+        Ok(done as ssize_t)
+    }
+
+    ///
+    /// # Description
+    ///
+    /// The `lseek()` system call repositions the offset of a file descriptor.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the new offset is returned. Upon failure, a negative error code is returned
+    /// instead.
+    ///
+    pub fn lseek(&mut self, fd: i32, offset: i64, whence: i32) -> Result<i64> {
+        let request: Message = SeekRequest::build(self.pid, fd, offset, whence);
+
+        Ok(match self.call(request, LinuxDaemonMessageHeader::SeekResponse)? {
+            Ok(payload) => SeekResponse::from_bytes(payload).offset,
+            Err(code) => code.into_errno() as i64,
+        })
+    }
+
+    ///
+    /// # Description
+    ///
+    /// The `fsync()` system call flushes a file to its storage device.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, `0` is returned. Upon failure, a negative error code is returned instead.
+    ///
+    pub fn fsync(&mut self, fd: i32) -> Result<i32> {
+        let request: Message = FileSyncRequest::build(self.pid, fd);
+
+        Ok(match self.call(request, LinuxDaemonMessageHeader::FileSyncResponse)? {
+            Ok(payload) => FileSyncResponse::from_bytes(payload).ret,
+            Err(code) => code.into_errno(),
+        })
+    }
+
+    ///
+    /// # Description
+    ///
+    /// The `ftruncate()` system call truncates a file to a given length.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, `0` is returned. Upon failure, a negative error code is returned instead.
+    ///
+    pub fn ftruncate(&mut self, fd: i32, length: off_t) -> Result<i32> {
+        let request: Message = FileTruncateRequest::build(self.pid, fd, length);
+
+        Ok(match self.call(request, LinuxDaemonMessageHeader::FileTruncateResponse)? {
+            Ok(payload) => FileTruncateResponse::from_bytes(payload).ret,
+            Err(code) => code.into_errno(),
+        })
+    }
+
+    ///
+    /// # Description
+    ///
+    /// The `linkat()` system call creates a hard link.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, `0` is returned. Upon failure, a negative error code is returned instead.
+    ///
+    pub fn linkat(
+        &mut self,
+        olddirfd: i32,
+        oldpath: &str,
+        newdirfd: i32,
+        newpath: &str,
+        flags: i32,
+    ) -> Result<i32> {
+        let request: LinkAtRequest = match LinkAtRequest::new(
+            olddirfd,
+            oldpath.to_string(),
+            newdirfd,
+            newpath.to_string(),
+            flags,
+        ) {
+            Ok(request) => request,
+            Err(e) => return Ok(e.code.into_errno()),
+        };
+        let requests: Vec<Message> = match request.into_parts(self.pid) {
+            Ok(requests) => requests,
+            Err(e) => return Ok(e.code.into_errno()),
+        };
+
+        for request in requests {
+            self.send(request)?;
+        }
+
+        Ok(match self.response(LinuxDaemonMessageHeader::LinkAtResponse)? {
+            Ok(payload) => LinkAtResponse::from_bytes(payload).ret,
+            Err(code) => code.into_errno(),
+        })
+    }
+}
+
+//==================================================================================================
+// Standalone Functions
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Checks the number of bytes that the daemon reports to have transferred in a chunk.
+///
+fn checked_count(count: ssize_t, chunk_size: usize) -> Result<usize> {
+    match usize::try_from(count) {
+        Ok(count) if count <= chunk_size => Ok(count),
+        _ => Err(anyhow::anyhow!("invalid transfer count (count={:?})", count)),
+    }
+}
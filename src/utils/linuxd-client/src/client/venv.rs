@@ -0,0 +1,69 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::client::Client;
+use ::anyhow::Result;
+use ::linuxd::{
+    venv::{
+        message::{
+            JoinEnvRequest,
+            JoinEnvResponse,
+            LeaveEnvRequest,
+        },
+        VirtualEnvironmentIdentifier,
+    },
+    LinuxDaemonMessageHeader,
+};
+use ::nvx::{
+    ipc::Message,
+    sys::error::ErrorCode,
+};
+
+//==================================================================================================
+// Implementations
+//==================================================================================================
+
+impl Client {
+    ///
+    /// # Description
+    ///
+    /// Joins a virtual environment. [`VirtualEnvironmentIdentifier::NEW`] asks for a new one.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the environment that was joined is returned. Upon failure, the error that the
+    /// daemon reported is returned instead.
+    ///
+    pub fn join(
+        &mut self,
+        env: VirtualEnvironmentIdentifier,
+    ) -> Result<Result<VirtualEnvironmentIdentifier, ErrorCode>> {
+        let request: Message = JoinEnvRequest::build(self.pid, env);
+
+        Ok(self
+            .call(request, LinuxDaemonMessageHeader::JoinEnvResponse)?
+            .map(|payload| JoinEnvResponse::from_bytes(payload).env))
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Leaves a virtual environment.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, empty is returned. Upon failure, the error that the daemon reported is
+    /// returned instead.
+    ///
+    pub fn leave(&mut self, env: VirtualEnvironmentIdentifier) -> Result<Result<(), ErrorCode>> {
+        let request: Message = LeaveEnvRequest::build(self.pid, env);
+
+        Ok(self
+            .call(request, LinuxDaemonMessageHeader::LeaveEnvResponse)?
+            .map(|_| ()))
+    }
+}
@@ -0,0 +1,167 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::client::Client;
+use ::anyhow::Result;
+use ::nvx::pm::ProcessIdentifier;
+use ::std::{
+    fs,
+    net::{
+        Ipv4Addr,
+        SocketAddr,
+    },
+    path::{
+        Path,
+        PathBuf,
+    },
+    process::{
+        Child,
+        Command,
+    },
+    thread,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+use ::tempfile::TempDir;
+
+//==================================================================================================
+// Constants
+//==================================================================================================
+
+/// Time that the daemon has to start accepting connections.
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Time between two looks at the port file of the daemon.
+const STARTUP_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+//==================================================================================================
+// Structures
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Linux Daemon that runs in a child process and listens on an ephemeral port of the loopback
+/// interface. The daemon is killed when this structure is dropped.
+///
+pub struct Daemon {
+    /// Child process.
+    child: Child,
+    /// Socket address on which the daemon accepts connections.
+    sockaddr: SocketAddr,
+    /// Directory that holds the port file of the daemon.
+    _rundir: TempDir,
+}
+
+//==================================================================================================
+// Implementations
+//==================================================================================================
+
+impl Daemon {
+    ///
+    /// # Description
+    ///
+    /// Spawns the Linux Daemon and waits until it accepts connections.
+    ///
+    /// # Parameters
+    ///
+    /// - `program`: Path to the daemon executable.
+    /// - `root`: Directory that paths of guests are resolved against.
+    /// - `args`: Additional command-line arguments.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the running daemon is returned. Upon failure, an error is returned instead.
+    ///
+    pub fn spawn(program: &Path, root: &Path, args: &[&str]) -> Result<Self> {
+        let rundir: TempDir = TempDir::new()?;
+        let port_file: PathBuf = rundir.path().join("port");
+
+        let mut child: Child = Command::new(program)
+            .arg("-server")
+            .arg("127.0.0.1:0")
+            .arg("-port-file")
+            .arg(&port_file)
+            .args(args)
+            .current_dir(root)
+            .spawn()
+            .map_err(|e| anyhow::anyhow!("failed to spawn daemon {:?}: {}", program, e))?;
+
+        match Self::wait_listening(&mut child, &port_file) {
+            Ok(port) => Ok(Self {
+                child,
+                sockaddr: SocketAddr::from((Ipv4Addr::LOCALHOST, port)),
+                _rundir: rundir,
+            }),
+            Err(e) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                Err(e)
+            },
+        }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the socket address on which the daemon accepts connections.
+    ///
+    pub fn sockaddr(&self) -> SocketAddr {
+        self.sockaddr
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Connects to the daemon.
+    ///
+    /// # Parameters
+    ///
+    /// - `pid`: Process on behalf of which system calls are issued.
+    /// - `key`: Key shared with the daemon, if connections must authenticate.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the connection is returned. Upon failure, an error is returned instead.
+    ///
+    pub fn connect(&self, pid: ProcessIdentifier, key: Option<&[u8]>) -> Result<Client> {
+        Client::connect(self.sockaddr, pid, key)
+    }
+
+    // Wait for the daemon to write the port on which it accepts connections.
+    fn wait_listening(child: &mut Child, port_file: &Path) -> Result<u16> {
+        let deadline: Instant = Instant::now() + STARTUP_TIMEOUT;
+        loop {
+            // The daemon renames the file into place once it is complete.
+            if let Ok(contents) = fs::read_to_string(port_file) {
+                return contents
+                    .trim_end()
+                    .parse::<u16>()
+                    .map_err(|_| anyhow::anyhow!("invalid port file: {:?}", contents));
+            }
+
+            if let Some(status) = child.try_wait()? {
+                anyhow::bail!("daemon exited before accepting connections ({})", status);
+            }
+
+            if Instant::now() >= deadline {
+                anyhow::bail!("daemon did not accept connections in time");
+            }
+
+            thread::sleep(STARTUP_POLL_INTERVAL);
+        }
+    }
+}
+
+impl Drop for Daemon {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
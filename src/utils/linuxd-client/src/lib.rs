@@ -0,0 +1,27 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Configuration
+//==================================================================================================
+
+#![deny(clippy::all)]
+
+//==================================================================================================
+// Modules
+//==================================================================================================
+
+/// Connections to the Linux Daemon.
+mod client;
+
+/// Linux Daemon processes.
+mod daemon;
+
+//==================================================================================================
+// Exports
+//==================================================================================================
+
+pub use self::{
+    client::Client,
+    daemon::Daemon,
+};
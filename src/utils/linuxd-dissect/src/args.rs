@@ -0,0 +1,268 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::dissect::Protocol;
+use ::anyhow::Result;
+
+//==================================================================================================
+// Structures
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Source of the frames to dissect.
+///
+pub enum Input {
+    /// File that holds raw frames or a capture. `-` stands for the standard input.
+    File(String),
+    /// Single frame, written in hexadecimal.
+    Hex(String),
+}
+
+///
+/// # Description
+///
+/// This structure packs the command-line arguments that were passed to the program.
+///
+pub struct Args {
+    /// Source of the frames.
+    input: Input,
+    /// Protocol that IKC messages are decoded with.
+    protocol: Protocol,
+    /// Process to show messages of, if any.
+    pid: Option<u32>,
+    /// Type of messages to show, if any.
+    message_type: Option<String>,
+    /// Operation to show messages of, if any.
+    operation: Option<String>,
+    /// Print a JSON object per message?
+    json: bool,
+    /// Print the payload of messages?
+    payload: bool,
+}
+
+//==================================================================================================
+// Implementations
+//==================================================================================================
+
+impl Args {
+    /// Command-line option for printing the help message.
+    const OPT_HELP: &'static str = "-help";
+    /// Command-line option for selecting the input file.
+    const OPT_INPUT: &'static str = "-input";
+    /// Command-line option for passing a single frame in hexadecimal.
+    const OPT_HEX: &'static str = "-hex";
+    /// Command-line option for selecting the protocol of IKC messages.
+    const OPT_PROTOCOL: &'static str = "-protocol";
+    /// Command-line option for filtering by process.
+    const OPT_PID: &'static str = "-pid";
+    /// Command-line option for filtering by message type.
+    const OPT_TYPE: &'static str = "-type";
+    /// Command-line option for filtering by operation.
+    const OPT_OP: &'static str = "-op";
+    /// Command-line option for printing JSON.
+    const OPT_JSON: &'static str = "-json";
+    /// Command-line option for printing payloads.
+    const OPT_PAYLOAD: &'static str = "-payload";
+
+    ///
+    /// # Description
+    ///
+    /// Parses the command-line arguments that were passed to the program.
+    ///
+    /// # Parameters
+    ///
+    /// - `args`: Command-line arguments.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the function returns the parsed command-line arguments that were passed to the
+    /// program. Upon failure, the function returns an error.
+    ///
+    pub fn parse(args: Vec<String>) -> Result<Self> {
+        trace!("parse(): parsing command-line arguments...");
+
+        let mut input: Option<Input> = None;
+        let mut protocol: Protocol = Protocol::Auto;
+        let mut pid: Option<u32> = None;
+        let mut message_type: Option<String> = None;
+        let mut operation: Option<String> = None;
+        let mut json: bool = false;
+        let mut payload: bool = false;
+
+        let mut i: usize = 1;
+        while i < args.len() {
+            match args[i].as_str() {
+                Self::OPT_HELP => {
+                    Self::usage(args[0].as_str());
+                    return Err(anyhow::anyhow!("help message"));
+                },
+                Self::OPT_INPUT => {
+                    i += 1;
+                    let path: &str = args
+                        .get(i)
+                        .ok_or_else(|| anyhow::anyhow!("missing input file"))?;
+                    input = Some(Input::File(path.to_string()));
+                },
+                Self::OPT_HEX => {
+                    i += 1;
+                    let hex: &str = args
+                        .get(i)
+                        .ok_or_else(|| anyhow::anyhow!("missing frame"))?;
+                    input = Some(Input::Hex(hex.to_string()));
+                },
+                Self::OPT_PROTOCOL => {
+                    i += 1;
+                    let name: &str = args
+                        .get(i)
+                        .ok_or_else(|| anyhow::anyhow!("missing protocol"))?;
+                    protocol = match name {
+                        "auto" => Protocol::Auto,
+                        "linuxd" => Protocol::Linuxd,
+                        "wasmd" => Protocol::Wasmd,
+                        _ => return Err(anyhow::anyhow!("invalid protocol: {}", name)),
+                    };
+                },
+                Self::OPT_PID => {
+                    i += 1;
+                    let value: &str = args.get(i).ok_or_else(|| anyhow::anyhow!("missing pid"))?;
+                    pid = Some(
+                        value
+                            .parse::<u32>()
+                            .map_err(|_| anyhow::anyhow!("invalid pid: {}", value))?,
+                    );
+                },
+                Self::OPT_TYPE => {
+                    i += 1;
+                    let value: &str = args
+                        .get(i)
+                        .ok_or_else(|| anyhow::anyhow!("missing message type"))?;
+                    message_type = Some(value.to_string());
+                },
+                Self::OPT_OP => {
+                    i += 1;
+                    let value: &str = args
+                        .get(i)
+                        .ok_or_else(|| anyhow::anyhow!("missing operation"))?;
+                    operation = Some(value.to_string());
+                },
+                Self::OPT_JSON => {
+                    json = true;
+                },
+                Self::OPT_PAYLOAD => {
+                    payload = true;
+                },
+                _ => {
+                    return Err(anyhow::anyhow!("invalid argument"));
+                },
+            }
+
+            i += 1;
+        }
+
+        Ok(Self {
+            input: input.ok_or_else(|| {
+                anyhow::anyhow!("missing {} or {}", Self::OPT_INPUT, Self::OPT_HEX)
+            })?,
+            protocol,
+            pid,
+            message_type,
+            operation,
+            json,
+            payload,
+        })
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Prints program usage.
+    ///
+    /// # Parameters
+    ///
+    /// - `program_name`: Name of the program.
+    ///
+    pub fn usage(program_name: &str) {
+        println!(
+            "Usage: {} {{{} <file> | {} <frame>}} [{} auto|linuxd|wasmd] [{} <pid>] [{} <type>] \
+             [{} <operation>] [{}] [{}]",
+            program_name,
+            Self::OPT_INPUT,
+            Self::OPT_HEX,
+            Self::OPT_PROTOCOL,
+            Self::OPT_PID,
+            Self::OPT_TYPE,
+            Self::OPT_OP,
+            Self::OPT_JSON,
+            Self::OPT_PAYLOAD,
+        );
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the source of the frames.
+    ///
+    pub fn input(&self) -> &Input {
+        &self.input
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the protocol that IKC messages are decoded with.
+    ///
+    pub fn protocol(&self) -> Protocol {
+        self.protocol
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the process to show messages of, if any.
+    ///
+    pub fn pid(&self) -> Option<u32> {
+        self.pid
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the type of messages to show, if any.
+    ///
+    pub fn message_type(&self) -> Option<&str> {
+        self.message_type.as_deref()
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the operation to show messages of, if any.
+    ///
+    pub fn operation(&self) -> Option<&str> {
+        self.operation.as_deref()
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Checks whether a JSON object should be printed per message.
+    ///
+    pub fn json(&self) -> bool {
+        self.json
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Checks whether the payload of messages should be printed.
+    ///
+    pub fn payload(&self) -> bool {
+        self.payload
+    }
+}
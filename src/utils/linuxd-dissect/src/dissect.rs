@@ -0,0 +1,621 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use ::linuxd::{
+    fcntl::message::{
+        ReadLinkAtRequest,
+        ReadLinkAtResponse,
+        RenameAt2Request,
+        SymbolicLinkAtRequest,
+    },
+    message::{
+        LinuxDaemonLongMessage,
+        LinuxDaemonMessagePart,
+        MessagePartitioner,
+    },
+    stdlib::message::{
+        RealPathRequest,
+        RealPathResponse,
+    },
+    sys::{
+        inotify::message::{
+            InotifyAddWatchRequest,
+            InotifyEvent,
+        },
+        stat::message::{
+            FileStatAtRequest,
+            FileStatAtResponse,
+        },
+        xattr::message::{
+            GetXattrRequest,
+            GetXattrResponse,
+            ListXattrRequest,
+            ListXattrResponse,
+            RemoveXattrRequest,
+            SetXattrRequest,
+        },
+    },
+    unistd::message::{
+        LinkAtRequest,
+        TruncateRequest,
+    },
+    LinuxDaemonMessage,
+    LinuxDaemonMessageHeader,
+};
+use ::nvx::{
+    ipc::{
+        Message,
+        MessageType,
+        SystemMessage,
+        SystemMessageHeader,
+    },
+    sys::{
+        config,
+        error::{
+            Error,
+            ErrorCode,
+        },
+    },
+};
+use ::procd::{
+    LookupMessage,
+    LookupResponseMessage,
+    ProcessManagementMessage,
+    ProcessManagementMessageHeader,
+    ShutdownMessage,
+    SignupMessage,
+    SignupResponseMessage,
+};
+use ::serde::Serialize;
+use ::std::{
+    collections::{
+        hash_map::Entry,
+        HashMap,
+    },
+    fmt,
+    fmt::Debug,
+};
+use ::wasmd::{
+    LoadMessage,
+    WasmdMessage,
+    WasmdMessageHeader,
+};
+
+//==================================================================================================
+// Constants
+//==================================================================================================
+
+/// Largest message that is reassembled from parts (in bytes).
+const MESSAGE_SIZE_MAX: usize = 64 * 1024;
+
+//==================================================================================================
+// Structures
+//==================================================================================================
+
+/// Raw frame of a message.
+pub type Frame = [u8; config::kernel::IPC_MESSAGE_SIZE];
+
+/// Key of a message that is split into parts: source, destination and request identifier.
+type StreamKey = (u32, u32, u32);
+
+///
+/// # Description
+///
+/// Protocol that IKC messages are decoded with.
+///
+#[derive(Debug, Clone, Copy)]
+pub enum Protocol {
+    /// Linux Daemon messages if the Linux Daemon is an endpoint, WASM Daemon messages otherwise.
+    Auto,
+    /// Linux Daemon messages.
+    Linuxd,
+    /// WASM Daemon messages.
+    Wasmd,
+}
+
+///
+/// # Description
+///
+/// Part of a message that is split into multiple parts.
+///
+#[derive(Debug, Serialize)]
+pub struct Part {
+    /// Identifier of the message that the part belongs to.
+    pub request_id: u32,
+    /// Number of parts that follow this one.
+    pub part_number: u32,
+    /// Size of the payload of the part (in bytes).
+    pub payload_size: u8,
+}
+
+///
+/// # Description
+///
+/// Human-readable decoding of a frame. Layers that do not apply to the frame are left out.
+///
+#[derive(Debug, Default, Serialize)]
+pub struct Dissection {
+    /// Position of the frame in the input.
+    pub index: usize,
+    /// Direction in which the frame travelled, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub direction: Option<&'static str>,
+    /// Microseconds since the connection was established, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp_us: Option<u64>,
+    /// Source process.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<u32>,
+    /// Destination process.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub destination: Option<u32>,
+    /// Type of the message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_type: Option<String>,
+    /// Status of the message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<i32>,
+    /// Header of the system message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system: Option<String>,
+    /// Header of the process management message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub process_management: Option<String>,
+    /// Header of the Linux Daemon message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub linuxd: Option<String>,
+    /// Header of the WASM Daemon message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wasmd: Option<&'static str>,
+    /// Operation that the message belongs to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operation: Option<&'static str>,
+    /// Part of a multipart message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part: Option<Part>,
+    /// Number of parts that were reassembled, on the last part of a multipart message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reassembled: Option<usize>,
+    /// Decoded body of the message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    /// Payload of the message, in hexadecimal.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payload: Option<String>,
+    /// Reason why the frame could not be fully decoded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+///
+/// # Description
+///
+/// Criteria that dissections must meet to be shown.
+///
+#[derive(Debug, Default)]
+pub struct Filter {
+    /// Process that is either the source or the destination.
+    pub pid: Option<u32>,
+    /// Type of the message.
+    pub message_type: Option<String>,
+    /// Operation, or header at any layer.
+    pub operation: Option<String>,
+}
+
+///
+/// # Description
+///
+/// Decoder of a stream of frames. Parts of multipart messages are reassembled as they arrive, so
+/// frames must be dissected in the order in which they were exchanged.
+///
+pub struct Dissector {
+    /// Protocol that IKC messages are decoded with.
+    protocol: Protocol,
+    /// Dump the payload of messages?
+    payload: bool,
+    /// Multipart messages whose last part has not arrived yet.
+    streams: HashMap<StreamKey, LinuxDaemonLongMessage>,
+}
+
+//==================================================================================================
+// Implementations
+//==================================================================================================
+
+impl Dissector {
+    ///
+    /// # Description
+    ///
+    /// Creates a dissector.
+    ///
+    /// # Parameters
+    ///
+    /// - `protocol`: Protocol that IKC messages are decoded with.
+    /// - `payload`: Dump the payload of messages?
+    ///
+    pub fn new(protocol: Protocol, payload: bool) -> Self {
+        Self {
+            protocol,
+            payload,
+            streams: HashMap::new(),
+        }
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Dissects a frame.
+    ///
+    /// # Parameters
+    ///
+    /// - `index`: Position of the frame in the input.
+    /// - `frame`: Frame.
+    ///
+    /// # Returns
+    ///
+    /// The dissection of the frame. Frames that cannot be fully decoded are dissected as far as
+    /// possible, and carry the reason why decoding stopped.
+    ///
+    pub fn dissect(&mut self, index: usize, frame: &Frame) -> Dissection {
+        let mut dissection: Dissection = Dissection {
+            index,
+            ..Default::default()
+        };
+
+        let message: Message = match Message::try_from_bytes(*frame) {
+            Ok(message) => message,
+            Err(e) => {
+                dissection.error = Some(format!("{:?}", e));
+                return dissection;
+            },
+        };
+
+        let source: u32 = u32::from(message.source);
+        let destination: u32 = u32::from(message.destination);
+        let status: i32 = message.status;
+        dissection.source = Some(source);
+        dissection.destination = Some(destination);
+        dissection.message_type = Some(format!("{:?}", message.message_type));
+        dissection.status = Some(status);
+        if self.payload {
+            dissection.payload = Some(hex(&message.payload));
+        }
+
+        // Failed requests carry no payload.
+        if status != 0 {
+            return dissection;
+        }
+
+        let result: Result<(), Error> = match message.message_type {
+            MessageType::Ipc => Self::dissect_system(&mut dissection, message.payload),
+            MessageType::Ikc => match self.protocol {
+                Protocol::Linuxd => {
+                    self.dissect_linuxd(&mut dissection, source, destination, message.payload)
+                },
+                Protocol::Wasmd => Self::dissect_wasmd(&mut dissection, message.payload),
+                Protocol::Auto => {
+                    let linuxd: u32 = u32::from(::linuxd::LINUXD);
+                    if source == linuxd || destination == linuxd {
+                        self.dissect_linuxd(&mut dissection, source, destination, message.payload)
+                    } else {
+                        Self::dissect_wasmd(&mut dissection, message.payload)
+                    }
+                },
+            },
+            _ => Ok(()),
+        };
+
+        if let Err(e) = result {
+            dissection.error = Some(format!("{:?}", e));
+        }
+
+        dissection
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the number of multipart messages whose last part has not been dissected yet.
+    ///
+    pub fn incomplete(&self) -> usize {
+        self.streams.len()
+    }
+
+    // Dissect a system message.
+    fn dissect_system(
+        dissection: &mut Dissection,
+        payload: [u8; Message::PAYLOAD_SIZE],
+    ) -> Result<(), Error> {
+        let message: SystemMessage = SystemMessage::from_bytes(payload)?;
+        dissection.system = Some(format!("{:?}", message.header));
+
+        if let SystemMessageHeader::ProcessManagement = message.header {
+            let message: ProcessManagementMessage =
+                ProcessManagementMessage::from_bytes(message.payload)?;
+            let header: ProcessManagementMessageHeader = { message.header };
+            dissection.process_management = Some(format!("{:?}", header));
+
+            let body: String = match header {
+                ProcessManagementMessageHeader::Shutdown => {
+                    let message: ShutdownMessage = ShutdownMessage::from_bytes(message.payload);
+                    format!("code={}", { message.code })
+                },
+                ProcessManagementMessageHeader::Signup => {
+                    let message: SignupMessage = SignupMessage::from_bytes(message.payload);
+                    format!("pid={}, name={:?}", u32::from(message.pid()), message.name()?)
+                },
+                ProcessManagementMessageHeader::SignupResponse => {
+                    let message: SignupResponseMessage =
+                        SignupResponseMessage::from_bytes(message.payload);
+                    format!("pid={}, status={}", u32::from(message.pid), { message.status })
+                },
+                ProcessManagementMessageHeader::Lookup => {
+                    let message: LookupMessage = LookupMessage::from_bytes(message.payload);
+                    format!("name={:?}", message.name()?)
+                },
+                ProcessManagementMessageHeader::LookupResponse => {
+                    let message: LookupResponseMessage =
+                        LookupResponseMessage::from_bytes(message.payload);
+                    format!("pid={}, status={}", u32::from(message.pid), { message.status })
+                },
+            };
+            dissection.body = Some(body);
+        }
+
+        Ok(())
+    }
+
+    // Dissect a Linux Daemon message, reassembling multipart messages.
+    fn dissect_linuxd(
+        &mut self,
+        dissection: &mut Dissection,
+        source: u32,
+        destination: u32,
+        payload: [u8; Message::PAYLOAD_SIZE],
+    ) -> Result<(), Error> {
+        let message: LinuxDaemonMessage = LinuxDaemonMessage::try_from_bytes(payload)?;
+        let header: LinuxDaemonMessageHeader = { message.header };
+        dissection.linuxd = Some(format!("{:?}", header));
+        dissection.operation = header.operation();
+
+        if !header.is_part() {
+            return Ok(());
+        }
+
+        let part: LinuxDaemonMessagePart = LinuxDaemonMessagePart::try_from_bytes(message.payload)?;
+        dissection.part = Some(Part {
+            request_id: part.request_id,
+            part_number: part.part_number,
+            payload_size: part.payload_size,
+        });
+
+        let key: StreamKey = (source, destination, part.request_id);
+        let stream: &mut LinuxDaemonLongMessage = match self.streams.entry(key) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                // The first part announces how many parts the message has.
+                let num_parts: usize = part.part_number as usize + 1;
+                if num_parts > LinuxDaemonLongMessage::capacity(MESSAGE_SIZE_MAX) {
+                    return Err(Error::new(ErrorCode::MessageTooLong, "message too long"));
+                }
+                entry.insert(LinuxDaemonLongMessage::new(num_parts)?)
+            },
+        };
+
+        if let Err(e) = stream.add_part(part) {
+            self.streams.remove(&key);
+            return Err(e);
+        }
+
+        if stream.is_complete() {
+            let parts: Vec<LinuxDaemonMessagePart> = self
+                .streams
+                .remove(&key)
+                .map(LinuxDaemonLongMessage::take_parts)
+                .unwrap_or_default();
+            dissection.reassembled = Some(parts.len());
+            dissection.body = Some(reassemble(header, parts)?);
+        }
+
+        Ok(())
+    }
+
+    // Dissect a WASM Daemon message.
+    fn dissect_wasmd(
+        dissection: &mut Dissection,
+        payload: [u8; Message::PAYLOAD_SIZE],
+    ) -> Result<(), Error> {
+        let message: WasmdMessage = WasmdMessage::from_bytes(payload)?;
+        match message.header {
+            WasmdMessageHeader::Wasm => {
+                dissection.wasmd = Some("Wasm");
+                let message: LoadMessage = LoadMessage::try_from_bytes(message.payload)?;
+                dissection.body = Some(format!("len={}", { message.len }));
+            },
+        }
+
+        Ok(())
+    }
+}
+
+impl Filter {
+    ///
+    /// # Description
+    ///
+    /// Checks whether a dissection meets the criteria.
+    ///
+    /// # Parameters
+    ///
+    /// - `dissection`: Dissection.
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if the dissection should be shown. Otherwise, it returns `false`.
+    ///
+    pub fn accepts(&self, dissection: &Dissection) -> bool {
+        if let Some(pid) = self.pid {
+            if dissection.source != Some(pid) && dissection.destination != Some(pid) {
+                return false;
+            }
+        }
+
+        if let Some(message_type) = self.message_type.as_deref() {
+            match dissection.message_type.as_deref() {
+                Some(actual) if normalize(actual) == normalize(message_type) => {},
+                _ => return false,
+            }
+        }
+
+        if let Some(operation) = self.operation.as_deref() {
+            let names: [Option<&str>; 5] = [
+                dissection.operation,
+                dissection.linuxd.as_deref(),
+                dissection.process_management.as_deref(),
+                dissection.system.as_deref(),
+                dissection.wasmd,
+            ];
+            if !names
+                .iter()
+                .flatten()
+                .any(|name| name.eq_ignore_ascii_case(operation))
+            {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+impl fmt::Display for Dissection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.index)?;
+        if let Some(direction) = self.direction {
+            write!(f, " {}", direction)?;
+        }
+        if let Some(timestamp_us) = self.timestamp_us {
+            write!(f, " +{}us", timestamp_us)?;
+        }
+        if let (Some(source), Some(destination)) = (self.source, self.destination) {
+            write!(f, " {} -> {}", source, destination)?;
+        }
+        if let Some(message_type) = self.message_type.as_deref() {
+            write!(f, " {}", message_type)?;
+        }
+        if let Some(status) = self.status.filter(|status| *status != 0) {
+            write!(f, " status={}", status)?;
+        }
+        if let Some(system) = self.system.as_deref() {
+            write!(f, " system={}", system)?;
+        }
+        if let Some(header) = self.process_management.as_deref() {
+            write!(f, " procd={}", header)?;
+        }
+        if let Some(header) = self.linuxd.as_deref() {
+            write!(f, " linuxd={}", header)?;
+        }
+        if let Some(header) = self.wasmd {
+            write!(f, " wasmd={}", header)?;
+        }
+        if let Some(operation) = self.operation {
+            write!(f, " op={}", operation)?;
+        }
+        if let Some(part) = self.part.as_ref() {
+            write!(
+                f,
+                " part=(request_id={}, part_number={}, size={})",
+                part.request_id, part.part_number, part.payload_size
+            )?;
+        }
+        if let Some(reassembled) = self.reassembled {
+            write!(f, " reassembled={}", reassembled)?;
+        }
+        if let Some(body) = self.body.as_deref() {
+            write!(f, " {}", body)?;
+        }
+        if let Some(error) = self.error.as_deref() {
+            write!(f, " error={}", error)?;
+        }
+        if let Some(payload) = self.payload.as_deref() {
+            write!(f, "\n    {}", payload)?;
+        }
+
+        Ok(())
+    }
+}
+
+//==================================================================================================
+// Standalone Functions
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Decodes a message that was reassembled from its parts.
+///
+/// # Parameters
+///
+/// - `header`: Header of the parts.
+/// - `parts`: Parts of the message.
+///
+/// # Returns
+///
+/// Upon success, the decoded message is returned. Upon failure, an error is returned instead.
+///
+fn reassemble(
+    header: LinuxDaemonMessageHeader,
+    parts: Vec<LinuxDaemonMessagePart>,
+) -> Result<String, Error> {
+    match header {
+        LinuxDaemonMessageHeader::FileStatAtRequestPart => debug::<FileStatAtRequest>(parts),
+        LinuxDaemonMessageHeader::FileStatAtResponsePart => debug::<FileStatAtResponse>(parts),
+        LinuxDaemonMessageHeader::SymbolicLinkAtRequestPart => {
+            debug::<SymbolicLinkAtRequest>(parts)
+        },
+        LinuxDaemonMessageHeader::LinkAtRequestPart => debug::<LinkAtRequest>(parts),
+        LinuxDaemonMessageHeader::ReadLinkAtRequestPart => debug::<ReadLinkAtRequest>(parts),
+        LinuxDaemonMessageHeader::ReadLinkAtResponsePart => debug::<ReadLinkAtResponse>(parts),
+        LinuxDaemonMessageHeader::GetXattrRequestPart => debug::<GetXattrRequest>(parts),
+        LinuxDaemonMessageHeader::GetXattrResponsePart => debug::<GetXattrResponse>(parts),
+        LinuxDaemonMessageHeader::SetXattrRequestPart => debug::<SetXattrRequest>(parts),
+        LinuxDaemonMessageHeader::ListXattrRequestPart => debug::<ListXattrRequest>(parts),
+        LinuxDaemonMessageHeader::ListXattrResponsePart => debug::<ListXattrResponse>(parts),
+        LinuxDaemonMessageHeader::RemoveXattrRequestPart => debug::<RemoveXattrRequest>(parts),
+        LinuxDaemonMessageHeader::TruncateRequestPart => debug::<TruncateRequest>(parts),
+        LinuxDaemonMessageHeader::RenameAt2RequestPart => debug::<RenameAt2Request>(parts),
+        LinuxDaemonMessageHeader::RealPathRequestPart => debug::<RealPathRequest>(parts),
+        LinuxDaemonMessageHeader::RealPathResponsePart => debug::<RealPathResponse>(parts),
+        LinuxDaemonMessageHeader::InotifyAddWatchRequestPart => {
+            debug::<InotifyAddWatchRequest>(parts)
+        },
+        LinuxDaemonMessageHeader::InotifyEventPart => debug::<InotifyEvent>(parts),
+        _ => Err(Error::new(ErrorCode::InvalidMessage, "not a message part")),
+    }
+}
+
+// Reassemble a message of a given type and format it.
+fn debug<T: MessagePartitioner + Debug>(
+    parts: Vec<LinuxDaemonMessagePart>,
+) -> Result<String, Error> {
+    T::from_parts(&parts).map(|message| format!("{:?}", message))
+}
+
+// Format bytes in hexadecimal.
+fn hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+// Normalize the name of a message type, so that `scheduling-event` matches `SchedulingEvent`.
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| *c != '-' && *c != '_')
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
@@ -0,0 +1,161 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use crate::{
+    args::Input,
+    dissect::Frame,
+};
+use ::anyhow::Result;
+use ::linuxd::capture::{
+    CaptureHeader,
+    CaptureRecord,
+    Direction,
+    CAPTURE_MAGIC,
+};
+use ::nvx::sys::config;
+use ::std::{
+    fs,
+    io::{
+        self,
+        Read,
+    },
+};
+
+//==================================================================================================
+// Structures
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Frame to dissect, along with what a capture recorded about it.
+///
+pub struct Record {
+    /// Direction in which the frame travelled, if known.
+    pub direction: Option<Direction>,
+    /// Microseconds since the connection was established, if known.
+    pub timestamp_us: Option<u64>,
+    /// Frame.
+    pub frame: Frame,
+}
+
+//==================================================================================================
+// Standalone Functions
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Loads the frames to dissect. Files that open with the magic number of captures are loaded as
+/// captures, and as a sequence of raw frames otherwise.
+///
+/// # Parameters
+///
+/// - `input`: Source of the frames.
+///
+/// # Returns
+///
+/// Upon success, the frames are returned in order. Upon failure, an error is returned instead.
+///
+pub fn load(input: &Input) -> Result<Vec<Record>> {
+    match input {
+        Input::File(path) => {
+            let bytes: Vec<u8> = if path == "-" {
+                let mut bytes: Vec<u8> = Vec::new();
+                io::stdin().read_to_end(&mut bytes)?;
+                bytes
+            } else {
+                fs::read(path).map_err(|e| anyhow::anyhow!("failed to read {:?}: {}", path, e))?
+            };
+
+            if bytes.starts_with(&CAPTURE_MAGIC) {
+                load_capture(&bytes)
+            } else {
+                load_frames(&bytes)
+            }
+        },
+        Input::Hex(hex) => Ok(vec![Record {
+            direction: None,
+            timestamp_us: None,
+            frame: parse_hex(hex)?,
+        }]),
+    }
+}
+
+// Load a capture.
+fn load_capture(bytes: &[u8]) -> Result<Vec<Record>> {
+    if bytes.len() < CaptureHeader::SIZE {
+        anyhow::bail!("capture is too short (len={:?})", bytes.len());
+    }
+
+    let (header, rest): (&[u8], &[u8]) = bytes.split_at(CaptureHeader::SIZE);
+    let header: CaptureHeader = CaptureHeader::from_bytes(header.try_into()?)
+        .map_err(|e| anyhow::anyhow!("invalid capture header: {:?}", e))?;
+    info!("loading capture (version={:?})", header.hello.version);
+
+    let chunks = rest.chunks_exact(CaptureRecord::SIZE);
+    // The daemon may have died while writing the last record.
+    if !chunks.remainder().is_empty() {
+        warn!("ignoring truncated record (len={:?})", chunks.remainder().len());
+    }
+
+    let mut records: Vec<Record> = Vec::new();
+    for chunk in chunks {
+        let record: CaptureRecord = CaptureRecord::from_bytes(chunk.try_into()?)
+            .map_err(|e| anyhow::anyhow!("invalid capture record: {:?}", e))?;
+        records.push(Record {
+            direction: Some(record.direction),
+            timestamp_us: Some(record.timestamp_us),
+            frame: record.frame,
+        });
+    }
+
+    Ok(records)
+}
+
+// Load a sequence of raw frames.
+fn load_frames(bytes: &[u8]) -> Result<Vec<Record>> {
+    let chunks = bytes.chunks_exact(config::kernel::IPC_MESSAGE_SIZE);
+    if !chunks.remainder().is_empty() {
+        warn!("ignoring truncated frame (len={:?})", chunks.remainder().len());
+    }
+
+    let mut records: Vec<Record> = Vec::new();
+    for chunk in chunks {
+        records.push(Record {
+            direction: None,
+            timestamp_us: None,
+            frame: chunk.try_into()?,
+        });
+    }
+
+    Ok(records)
+}
+
+// Parse a frame written in hexadecimal, as printed by `xxd -p` or `hexdump`.
+fn parse_hex(hex: &str) -> Result<Frame> {
+    let digits: Vec<u8> = hex
+        .bytes()
+        .filter(|c| !c.is_ascii_whitespace() && *c != b':' && *c != b',')
+        .collect();
+    if digits.len() != 2 * config::kernel::IPC_MESSAGE_SIZE {
+        anyhow::bail!(
+            "frame must be {} bytes long (len={})",
+            config::kernel::IPC_MESSAGE_SIZE,
+            digits.len() / 2
+        );
+    }
+
+    let mut frame: Frame = [0; config::kernel::IPC_MESSAGE_SIZE];
+    for (byte, pair) in frame.iter_mut().zip(digits.chunks_exact(2)) {
+        let pair: &str = std::str::from_utf8(pair)?;
+        *byte = u8::from_str_radix(pair, 16)
+            .map_err(|_| anyhow::anyhow!("invalid hexadecimal digits: {:?}", pair))?;
+    }
+
+    Ok(frame)
+}
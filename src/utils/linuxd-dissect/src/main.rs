@@ -0,0 +1,105 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Configuration
+//==================================================================================================
+
+#![deny(clippy::all)]
+
+//==================================================================================================
+// Modules
+//==================================================================================================
+
+mod args;
+mod dissect;
+mod input;
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+// Must come first.
+#[macro_use]
+extern crate log;
+
+use self::{
+    args::Args,
+    dissect::{
+        Dissection,
+        Dissector,
+        Filter,
+    },
+    input::Record,
+};
+use ::anyhow::Result;
+use ::flexi_logger::Logger;
+use ::linuxd::capture::Direction;
+use ::std::{
+    env,
+    sync::Once,
+};
+
+//==================================================================================================
+// Standalone Functions
+//==================================================================================================
+
+fn main() -> Result<()> {
+    initialize();
+
+    // Parse and retrieve command-line arguments.
+    let args: Args = Args::parse(env::args().collect())?;
+
+    let records: Vec<Record> = input::load(args.input())?;
+    let filter: Filter = Filter {
+        pid: args.pid(),
+        message_type: args.message_type().map(str::to_string),
+        operation: args.operation().map(str::to_string),
+    };
+
+    // Every frame is dissected, so that multipart messages are reassembled regardless of filters.
+    let mut dissector: Dissector = Dissector::new(args.protocol(), args.payload());
+    for (index, record) in records.iter().enumerate() {
+        let mut dissection: Dissection = dissector.dissect(index, &record.frame);
+        dissection.direction = record.direction.map(|direction| match direction {
+            Direction::Inbound => "inbound",
+            Direction::Outbound => "outbound",
+        });
+        dissection.timestamp_us = record.timestamp_us;
+
+        if !filter.accepts(&dissection) {
+            continue;
+        }
+
+        if args.json() {
+            println!("{}", serde_json::to_string(&dissection)?);
+        } else {
+            println!("{}", dissection);
+        }
+    }
+
+    if dissector.incomplete() > 0 {
+        warn!("input ends in the middle of {} multipart messages", dissector.incomplete());
+    }
+
+    Ok(())
+}
+
+///
+/// # Description
+///
+/// Initializes the logger.
+///
+/// # Note
+///
+/// If the logger cannot be initialized, the function will panic.
+///
+pub fn initialize() {
+    static INIT_LOG: Once = Once::new();
+    INIT_LOG.call_once(|| {
+        Logger::try_with_env()
+            .expect("malformed RUST_LOG environment variable")
+            .start()
+            .expect("failed to initialize logger");
+    });
+}
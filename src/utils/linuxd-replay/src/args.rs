@@ -0,0 +1,229 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use ::anyhow::Result;
+use ::std::{
+    path::{
+        Path,
+        PathBuf,
+    },
+    time::Duration,
+};
+
+//==================================================================================================
+// Structures
+//==================================================================================================
+
+///
+/// # Description
+///
+/// This structure packs the command-line arguments that were passed to the program.
+///
+pub struct Args {
+    /// Path to the daemon executable.
+    daemon: PathBuf,
+    /// Capture file.
+    capture: PathBuf,
+    /// Directory that paths of guests are resolved against.
+    root: Option<PathBuf>,
+    /// Use the in-memory filesystem backend?
+    memory: bool,
+    /// Compare only the addressee, status and type of responses?
+    headers_only: bool,
+    /// Time to wait for each response.
+    timeout: Duration,
+}
+
+//==================================================================================================
+// Implementations
+//==================================================================================================
+
+impl Args {
+    /// Command-line option for printing the help message.
+    const OPT_HELP: &'static str = "-help";
+    /// Command-line option for selecting the daemon executable.
+    const OPT_DAEMON: &'static str = "-daemon";
+    /// Command-line option for selecting the capture file.
+    const OPT_CAPTURE: &'static str = "-capture";
+    /// Command-line option for selecting the root directory.
+    const OPT_ROOT: &'static str = "-root";
+    /// Command-line option for using the in-memory filesystem backend.
+    const OPT_MEMORY: &'static str = "-memory";
+    /// Command-line option for comparing only the addressee, status and type of responses.
+    const OPT_HEADERS_ONLY: &'static str = "-headers-only";
+    /// Command-line option for the time to wait for each response, in milliseconds.
+    const OPT_TIMEOUT: &'static str = "-timeout";
+
+    /// Default time to wait for each response.
+    const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+    ///
+    /// # Description
+    ///
+    /// Parses the command-line arguments that were passed to the program.
+    ///
+    /// # Parameters
+    ///
+    /// - `args`: Command-line arguments.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the function returns the parsed command-line arguments that were passed to the
+    /// program. Upon failure, the function returns an error.
+    ///
+    pub fn parse(args: Vec<String>) -> Result<Self> {
+        trace!("parse(): parsing command-line arguments...");
+
+        let mut daemon: Option<PathBuf> = None;
+        let mut capture: Option<PathBuf> = None;
+        let mut root: Option<PathBuf> = None;
+        let mut memory: bool = false;
+        let mut headers_only: bool = false;
+        let mut timeout: Duration = Self::DEFAULT_TIMEOUT;
+
+        let mut i: usize = 1;
+        while i < args.len() {
+            match args[i].as_str() {
+                Self::OPT_HELP => {
+                    Self::usage(args[0].as_str());
+                    return Err(anyhow::anyhow!("help message"));
+                },
+                Self::OPT_DAEMON => {
+                    i += 1;
+                    let path: &str = args
+                        .get(i)
+                        .ok_or_else(|| anyhow::anyhow!("missing daemon executable"))?;
+                    daemon = Some(PathBuf::from(path));
+                },
+                Self::OPT_CAPTURE => {
+                    i += 1;
+                    let path: &str = args
+                        .get(i)
+                        .ok_or_else(|| anyhow::anyhow!("missing capture file"))?;
+                    capture = Some(PathBuf::from(path));
+                },
+                Self::OPT_ROOT => {
+                    i += 1;
+                    let path: &str = args
+                        .get(i)
+                        .ok_or_else(|| anyhow::anyhow!("missing root directory"))?;
+                    root = Some(PathBuf::from(path));
+                },
+                Self::OPT_MEMORY => {
+                    memory = true;
+                },
+                Self::OPT_HEADERS_ONLY => {
+                    headers_only = true;
+                },
+                Self::OPT_TIMEOUT => {
+                    i += 1;
+                    let millis: &str = args
+                        .get(i)
+                        .ok_or_else(|| anyhow::anyhow!("missing timeout"))?;
+                    timeout = match millis.parse::<u64>() {
+                        Ok(millis) if millis > 0 => Duration::from_millis(millis),
+                        _ => return Err(anyhow::anyhow!("invalid timeout: {}", millis)),
+                    };
+                },
+                _ => {
+                    return Err(anyhow::anyhow!("invalid argument"));
+                },
+            }
+
+            i += 1;
+        }
+
+        Ok(Self {
+            daemon: daemon.ok_or_else(|| anyhow::anyhow!("missing {}", Self::OPT_DAEMON))?,
+            capture: capture.ok_or_else(|| anyhow::anyhow!("missing {}", Self::OPT_CAPTURE))?,
+            root,
+            memory,
+            headers_only,
+            timeout,
+        })
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Prints program usage.
+    ///
+    /// # Parameters
+    ///
+    /// - `program_name`: Name of the program.
+    ///
+    pub fn usage(program_name: &str) {
+        println!(
+            "Usage: {} {} <linuxd> {} <capture> [{} <dir>] [{}] [{}] [{} <milliseconds>]",
+            program_name,
+            Self::OPT_DAEMON,
+            Self::OPT_CAPTURE,
+            Self::OPT_ROOT,
+            Self::OPT_MEMORY,
+            Self::OPT_HEADERS_ONLY,
+            Self::OPT_TIMEOUT,
+        );
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the path to the daemon executable.
+    ///
+    pub fn daemon(&self) -> &Path {
+        &self.daemon
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the capture file.
+    ///
+    pub fn capture(&self) -> &Path {
+        &self.capture
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the directory that paths of guests are resolved against.
+    ///
+    /// # Returns
+    ///
+    /// The root directory, or `None` if a fresh temporary directory should be used.
+    ///
+    pub fn root(&self) -> Option<&Path> {
+        self.root.as_deref()
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Checks whether the in-memory filesystem backend should be used.
+    ///
+    pub fn memory(&self) -> bool {
+        self.memory
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Checks whether only the addressee, status and type of responses should be compared.
+    ///
+    pub fn headers_only(&self) -> bool {
+        self.headers_only
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Returns the time to wait for each response.
+    ///
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+}
@@ -0,0 +1,154 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Configuration
+//==================================================================================================
+
+#![deny(clippy::all)]
+
+//==================================================================================================
+// Modules
+//==================================================================================================
+
+mod args;
+mod replay;
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+// Must come first.
+#[macro_use]
+extern crate log;
+
+use self::{
+    args::Args,
+    replay::{
+        Replay,
+        Report,
+    },
+};
+use ::anyhow::Result;
+use ::flexi_logger::Logger;
+use ::linuxd::capture::{
+    CaptureHeader,
+    CaptureRecord,
+};
+use ::linuxd_client::Daemon;
+use ::std::{
+    env,
+    fs,
+    path::Path,
+    sync::Once,
+};
+use ::tempfile::TempDir;
+
+//==================================================================================================
+// Standalone Functions
+//==================================================================================================
+
+fn main() -> Result<()> {
+    initialize();
+
+    // Parse and retrieve command-line arguments.
+    let args: Args = Args::parse(env::args().collect())?;
+
+    let (header, records): (CaptureHeader, Vec<CaptureRecord>) = load(args.capture())?;
+    info!("loaded capture (records={:?}, version={:?})", records.len(), header.hello.version);
+
+    // Captures start from an empty directory, unless told otherwise.
+    let tmpdir: TempDir = TempDir::new()?;
+    let root: &Path = args.root().unwrap_or(tmpdir.path());
+
+    // The daemon only listens on the loopback interface, for the replay alone.
+    let daemon_args: &[&str] = if args.memory() {
+        &["-no-auth", "-backend", "memory"]
+    } else {
+        &["-no-auth"]
+    };
+    let daemon: Daemon = Daemon::spawn(args.daemon(), root, daemon_args)?;
+
+    let mut replay: Replay =
+        Replay::connect(daemon.sockaddr(), header.hello, args.timeout(), args.headers_only())?;
+    let report: Report = replay.run(&records)?;
+
+    println!(
+        "sent {} frames, compared {} responses: {} differ, {} unexpected{}",
+        report.sent,
+        report.compared,
+        report.mismatches,
+        report.unexpected,
+        if report.stalled {
+            ", daemon stalled"
+        } else {
+            ""
+        },
+    );
+
+    if report.mismatches > 0 || report.unexpected > 0 || report.stalled {
+        anyhow::bail!("replay diverged from the capture");
+    }
+
+    Ok(())
+}
+
+///
+/// # Description
+///
+/// Loads a capture file.
+///
+/// # Parameters
+///
+/// - `path`: Path to the capture file.
+///
+/// # Returns
+///
+/// Upon success, the header and records of the capture are returned. Upon failure, an error is
+/// returned instead.
+///
+fn load(path: &Path) -> Result<(CaptureHeader, Vec<CaptureRecord>)> {
+    let bytes: Vec<u8> =
+        fs::read(path).map_err(|e| anyhow::anyhow!("failed to read capture {:?}: {}", path, e))?;
+    if bytes.len() < CaptureHeader::SIZE {
+        anyhow::bail!("capture is too short (len={:?})", bytes.len());
+    }
+
+    let (header, rest): (&[u8], &[u8]) = bytes.split_at(CaptureHeader::SIZE);
+    let header: CaptureHeader = CaptureHeader::from_bytes(header.try_into()?)
+        .map_err(|e| anyhow::anyhow!("invalid capture header: {:?}", e))?;
+
+    let chunks = rest.chunks_exact(CaptureRecord::SIZE);
+    // The daemon may have died while writing the last record.
+    if !chunks.remainder().is_empty() {
+        warn!("ignoring truncated record (len={:?})", chunks.remainder().len());
+    }
+
+    let mut records: Vec<CaptureRecord> = Vec::new();
+    for chunk in chunks {
+        let record: CaptureRecord = CaptureRecord::from_bytes(chunk.try_into()?)
+            .map_err(|e| anyhow::anyhow!("invalid capture record: {:?}", e))?;
+        records.push(record);
+    }
+
+    Ok((header, records))
+}
+
+///
+/// # Description
+///
+/// Initializes the logger.
+///
+/// # Note
+///
+/// If the logger cannot be initialized, the function will panic.
+///
+pub fn initialize() {
+    static INIT_LOG: Once = Once::new();
+    INIT_LOG.call_once(|| {
+        Logger::try_with_env()
+            .expect("malformed RUST_LOG environment variable")
+            .start()
+            .expect("failed to initialize logger");
+    });
+}
@@ -0,0 +1,265 @@
+// Copyright(c) The Maintainers of Nanvix.
+// Licensed under the MIT License.
+
+//==================================================================================================
+// Imports
+//==================================================================================================
+
+use ::anyhow::Result;
+use ::linuxd::{
+    capture::{
+        CaptureRecord,
+        Direction,
+    },
+    protocol::Hello,
+    LinuxDaemonMessage,
+};
+use ::nvx::{
+    ipc::Message,
+    sys::config,
+};
+use ::std::{
+    io::{
+        ErrorKind,
+        Read,
+        Write,
+    },
+    net::{
+        SocketAddr,
+        TcpStream,
+    },
+    time::Duration,
+};
+
+//==================================================================================================
+// Constants
+//==================================================================================================
+
+/// Time to wait for responses that were not recorded, once the capture is exhausted.
+const TRAILING_TIMEOUT: Duration = Duration::from_millis(200);
+
+//==================================================================================================
+// Structures
+//==================================================================================================
+
+/// Raw frame of a message.
+type Frame = [u8; config::kernel::IPC_MESSAGE_SIZE];
+
+///
+/// # Description
+///
+/// Outcome of replaying a capture.
+///
+#[derive(Debug, Default)]
+pub struct Report {
+    /// Number of frames sent to the daemon.
+    pub sent: usize,
+    /// Number of responses compared against the recording.
+    pub compared: usize,
+    /// Number of responses that differ from the recording.
+    pub mismatches: usize,
+    /// Whether the daemon stopped answering before the capture was exhausted.
+    pub stalled: bool,
+    /// Number of responses that the recording does not have.
+    pub unexpected: usize,
+}
+
+///
+/// # Description
+///
+/// Session that replays a capture against a daemon.
+///
+pub struct Replay {
+    /// Connection to the daemon.
+    stream: TcpStream,
+    /// Time to wait for each response.
+    timeout: Duration,
+    /// Compare only the addressee, status and type of responses?
+    headers_only: bool,
+}
+
+//==================================================================================================
+// Implementations
+//==================================================================================================
+
+impl Replay {
+    ///
+    /// # Description
+    ///
+    /// Connects to a daemon, negotiating what was negotiated on the captured connection.
+    ///
+    /// # Parameters
+    ///
+    /// - `sockaddr`: Socket address of the daemon.
+    /// - `hello`: Version and capabilities that were negotiated on the captured connection.
+    /// - `timeout`: Time to wait for each response.
+    /// - `headers_only`: Compare only the addressee, status and type of responses?
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the session is returned. Upon failure, an error is returned instead.
+    ///
+    pub fn connect(
+        sockaddr: SocketAddr,
+        hello: Hello,
+        timeout: Duration,
+        headers_only: bool,
+    ) -> Result<Self> {
+        let mut stream: TcpStream = TcpStream::connect(sockaddr)?;
+        stream.set_nodelay(true)?;
+
+        stream.write_all(&hello.to_bytes())?;
+        let mut bytes: [u8; Hello::SIZE] = [0; Hello::SIZE];
+        stream.read_exact(&mut bytes)?;
+        let theirs: Hello =
+            Hello::from_bytes(bytes).map_err(|e| anyhow::anyhow!("invalid hello: {:?}", e))?;
+
+        // A daemon that speaks a different version answers differently.
+        if theirs.version < hello.version {
+            anyhow::bail!(
+                "daemon is older than the capture (version={:?}, expected={:?})",
+                theirs.version,
+                hello.version
+            );
+        }
+
+        stream.set_read_timeout(Some(timeout))?;
+
+        Ok(Self {
+            stream,
+            timeout,
+            headers_only,
+        })
+    }
+
+    ///
+    /// # Description
+    ///
+    /// Sends the frames that the client sent and compares the responses of the daemon against
+    /// those that were recorded, in order. Differences are printed as they are found.
+    ///
+    /// # Parameters
+    ///
+    /// - `records`: Records of the capture.
+    ///
+    /// # Returns
+    ///
+    /// Upon success, the outcome is returned. Upon failure, an error is returned instead, if the
+    /// connection breaks.
+    ///
+    pub fn run(&mut self, records: &[CaptureRecord]) -> Result<Report> {
+        let mut report: Report = Report::default();
+
+        for (index, record) in records.iter().enumerate() {
+            match record.direction {
+                Direction::Inbound => {
+                    self.stream.write_all(&record.frame)?;
+                    report.sent += 1;
+                },
+                Direction::Outbound => {
+                    let actual: Frame = match self.recv()? {
+                        Some(frame) => frame,
+                        None => {
+                            println!(
+                                "record {} (+{}us): no response within {:?}, expected {}",
+                                index,
+                                record.timestamp_us,
+                                self.timeout,
+                                describe(&record.frame)
+                            );
+                            report.stalled = true;
+                            return Ok(report);
+                        },
+                    };
+
+                    report.compared += 1;
+                    if !self.matches(&record.frame, &actual) {
+                        report.mismatches += 1;
+                        println!(
+                            "record {} (+{}us): expected {}, got {}{}",
+                            index,
+                            record.timestamp_us,
+                            describe(&record.frame),
+                            describe(&actual),
+                            first_difference(&record.frame, &actual)
+                                .map(|offset| format!(", first difference at byte {}", offset))
+                                .unwrap_or_default(),
+                        );
+                    }
+                },
+            }
+        }
+
+        // Responses that were not recorded arrive shortly, if at all.
+        self.stream.set_read_timeout(Some(TRAILING_TIMEOUT))?;
+        while let Some(frame) = self.recv()? {
+            report.unexpected += 1;
+            println!("unexpected response: {}", describe(&frame));
+        }
+
+        Ok(report)
+    }
+
+    // Read a frame, or `None` if none arrives in time. Fails if the connection is broken.
+    fn recv(&mut self) -> Result<Option<Frame>> {
+        let mut frame: Frame = [0; config::kernel::IPC_MESSAGE_SIZE];
+        match self.stream.read_exact(&mut frame) {
+            Ok(()) => Ok(Some(frame)),
+            Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => Ok(None),
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => {
+                anyhow::bail!("daemon closed the connection")
+            },
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    // Check whether a response matches the recording.
+    fn matches(&self, expected: &Frame, actual: &Frame) -> bool {
+        if self.headers_only {
+            describe(expected) == describe(actual)
+        } else {
+            expected == actual
+        }
+    }
+}
+
+//==================================================================================================
+// Standalone Functions
+//==================================================================================================
+
+///
+/// # Description
+///
+/// Describes the addressee, status and type of a response.
+///
+fn describe(frame: &Frame) -> String {
+    let message: Message = match Message::try_from_bytes(*frame) {
+        Ok(message) => message,
+        Err(e) => return format!("{{ malformed: {:?} }}", e),
+    };
+
+    let header: String = if message.status != 0 {
+        "-".to_string()
+    } else {
+        match LinuxDaemonMessage::try_from_bytes(message.payload) {
+            Ok(response) => format!("{:?}", { response.header }),
+            Err(_) => "malformed".to_string(),
+        }
+    };
+
+    format!(
+        "{{ destination: {:?}, status: {:?}, header: {} }}",
+        u32::from(message.destination),
+        { message.status },
+        header
+    )
+}
+
+///
+/// # Description
+///
+/// Returns the offset of the first byte at which two frames differ, if any.
+///
+fn first_difference(expected: &Frame, actual: &Frame) -> Option<usize> {
+    expected.iter().zip(actual.iter()).position(|(a, b)| a != b)
+}